@@ -0,0 +1,98 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Compares the time taken to generate noise by hashing the integer
+//! coordinates on the fly and by hashing them through a `PermTable`.
+//!
+//! Run with `cargo bench --bench perm_table`.
+
+extern crate noise;
+
+use noise::module::{Module, Perlin, Voronoi};
+use noise::noise_map::NoiseMap;
+use noise::noisegen::{gradient_coherent_noise3d, gradient_coherent_noise3d_table, value_noise3d,
+                      value_noise3d_table, NoiseQuality, PermTable};
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 512;
+const RUNS: u32 = 5;
+
+/// Returns the shortest time taken to run `f` over several runs.
+fn time_best<F: Fn() -> f64>(f: F) -> Duration {
+    (0..RUNS).map(|_| {
+        let start = Instant::now();
+        let sum = f();
+        let elapsed = start.elapsed();
+        // Make sure the result is not optimised away.
+        assert!(!sum.is_nan());
+        elapsed
+    }).min().unwrap()
+}
+
+/// Sums a function over every point of a `SIZE`x`SIZE` grid.
+fn sum_grid<F: Fn(usize, usize) -> f64>(f: F) -> f64 {
+    let mut sum = 0.0;
+    for j in 0..SIZE {
+        for i in 0..SIZE {
+            sum += f(i, j);
+        }
+    }
+    sum
+}
+
+fn fill<M: Module>(module: &M) -> f64 {
+    let step = 4.0 / SIZE as f64;
+    let map = NoiseMap::from_module(module, (0.0, 0.0), (step, step), SIZE, SIZE);
+    map.values().iter().sum()
+}
+
+fn report(name: &str, hashed: Duration, table: Duration) {
+    println!("{:<16} hashed {:>8.2} ms   table {:>8.2} ms   ratio {:.2}x", name,
+             hashed.as_secs_f64() * 1000.0, table.as_secs_f64() * 1000.0,
+             hashed.as_secs_f64() / table.as_secs_f64());
+}
+
+fn main() {
+    let table = PermTable::new(0);
+    println!("{}x{} values, best of {} runs (ratio > 1 means the table is faster):",
+             SIZE, SIZE, RUNS);
+
+    report("value noise",
+           time_best(|| sum_grid(|i, j| value_noise3d(i as i32, j as i32, 7, 0))),
+           time_best(|| sum_grid(|i, j| value_noise3d_table(&table, i as i32, j as i32, 7, 0))));
+
+    let step = 4.0 / SIZE as f64;
+    report("gradient noise",
+           time_best(|| sum_grid(|i, j| {
+               gradient_coherent_noise3d(i as f64 * step, j as f64 * step, 0.5, 0,
+                                         NoiseQuality::Standard)
+           })),
+           time_best(|| sum_grid(|i, j| {
+               gradient_coherent_noise3d_table(i as f64 * step, j as f64 * step, 0.5, &table, 0,
+                                               NoiseQuality::Standard)
+           })));
+
+    let perlin = Perlin::new();
+    let mut perlin_table = Perlin::new();
+    perlin_table.set_perm_table(Some(table.clone()));
+    report("Perlin", time_best(|| fill(&perlin)), time_best(|| fill(&perlin_table)));
+
+    let voronoi = Voronoi::new();
+    let mut voronoi_table = Voronoi::new();
+    voronoi_table.set_perm_table(Some(table.clone()));
+    report("Voronoi", time_best(|| fill(&voronoi)), time_best(|| fill(&voronoi_table)));
+}
@@ -0,0 +1,70 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Compares the time taken to fill a noise map with each noise quality.
+//!
+//! Run with `cargo bench --bench quality`.
+
+extern crate noise;
+
+use noise::module::{Billow, Module, Perlin, RidgedMulti};
+use noise::noise_map::NoiseMap;
+use noise::noisegen::NoiseQuality;
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 512;
+const RUNS: u32 = 5;
+
+/// Returns the shortest time taken to fill a noise map over several runs.
+fn time_fill<M: Module>(module: &M) -> Duration {
+    let step = 4.0 / SIZE as f64;
+    (0..RUNS).map(|_| {
+        let start = Instant::now();
+        let map = NoiseMap::from_module(module, (0.0, 0.0), (step, step), SIZE, SIZE);
+        let elapsed = start.elapsed();
+        // Make sure the map is not optimised away.
+        assert!(map.values().iter().all(|value| !value.is_nan()));
+        elapsed
+    }).min().unwrap()
+}
+
+fn report<M: Module, F: Fn(NoiseQuality) -> M>(name: &str, make_module: F) {
+    for &quality in &[NoiseQuality::Fast, NoiseQuality::Standard, NoiseQuality::Best] {
+        let elapsed = time_fill(&make_module(quality));
+        println!("{:<12} {:<10} {:>8.2} ms", name, format!("{:?}", quality),
+                 elapsed.as_secs_f64() * 1000.0);
+    }
+}
+
+fn main() {
+    println!("Filling a {}x{} noise map, best of {} runs:", SIZE, SIZE, RUNS);
+    report("Perlin", |quality| {
+        let mut perlin = Perlin::new();
+        perlin.set_quality(quality);
+        perlin
+    });
+    report("Billow", |quality| {
+        let mut billow = Billow::new();
+        billow.set_quality(quality);
+        billow
+    });
+    report("RidgedMulti", |quality| {
+        let mut ridged_multi = RidgedMulti::new();
+        ridged_multi.set_quality(quality);
+        ridged_multi
+    });
+}
@@ -0,0 +1,103 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Compares the time taken to generate gradient coherent noise one value at a
+//! time and four values at a time, and to fill a noise map with `Perlin` noise,
+//! which generates four octaves at a time with the `simd` feature.
+//!
+//! Run with `cargo bench --bench simd --features simd`.  To compare the time
+//! taken to fill the noise map without the `simd` feature, run
+//! `cargo bench --bench dimensions` with and without it.
+
+extern crate noise;
+
+use noise::module::Perlin;
+use noise::noise_map::NoiseMap;
+use noise::noisegen::{gradient_coherent_noise3d, gradient_coherent_noise3d_x4, NoiseQuality};
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 512;
+const RUNS: u32 = 5;
+
+/// Returns the shortest time taken to run `f` over several runs.
+fn time_best<F: Fn() -> f64>(f: F) -> Duration {
+    (0..RUNS).map(|_| {
+        let start = Instant::now();
+        let sum = f();
+        let elapsed = start.elapsed();
+        // Make sure the result is not optimised away.
+        assert!(!sum.is_nan());
+        elapsed
+    }).min().unwrap()
+}
+
+/// Returns the input values of one row of the noise map, for each of four
+/// octaves with different seeds.
+fn octave_inputs(j: usize) -> ([f64; 4], [f64; 4], [f64; 4], [i32; 4]) {
+    let y = j as f64 * 4.0 / SIZE as f64;
+    ([0.0, 0.5, 1.25, 3.5], [y, y * 2.0, y * 4.0, y * 8.0], [0.25, 0.5, 1.0, 2.0], [0, 1, 2, 3])
+}
+
+fn scalar(quality: NoiseQuality) -> f64 {
+    let mut sum = 0.0;
+    for j in 0..SIZE {
+        let (x, y, z, seed) = octave_inputs(j);
+        for i in 0..SIZE {
+            let dx = i as f64 * 4.0 / SIZE as f64;
+            for lane in 0..4 {
+                sum += gradient_coherent_noise3d(x[lane] + dx, y[lane], z[lane], seed[lane],
+                                                 quality);
+            }
+        }
+    }
+    sum
+}
+
+fn vectorised(quality: NoiseQuality) -> f64 {
+    let mut sum = 0.0;
+    for j in 0..SIZE {
+        let (x, y, z, seed) = octave_inputs(j);
+        for i in 0..SIZE {
+            let dx = i as f64 * 4.0 / SIZE as f64;
+            let x = [x[0] + dx, x[1] + dx, x[2] + dx, x[3] + dx];
+            sum += gradient_coherent_noise3d_x4(x, y, z, seed, quality).iter().sum::<f64>();
+        }
+    }
+    sum
+}
+
+fn main() {
+    println!("Generating {}x{}x4 gradient coherent noise values, best of {} runs:",
+             SIZE, SIZE, RUNS);
+    for &quality in &[NoiseQuality::Fast, NoiseQuality::Standard, NoiseQuality::Best] {
+        let scalar = time_best(|| scalar(quality));
+        let vectorised = time_best(|| vectorised(quality));
+        println!("{:<10} scalar {:>8.2} ms   x4 {:>8.2} ms   speedup {:.2}x",
+                 format!("{:?}", quality), scalar.as_secs_f64() * 1000.0,
+                 vectorised.as_secs_f64() * 1000.0,
+                 scalar.as_secs_f64() / vectorised.as_secs_f64());
+    }
+
+    let step = 4.0 / SIZE as f64;
+    let perlin = Perlin::new();
+    let elapsed = time_best(|| {
+        let map = NoiseMap::from_module(&perlin, (0.0, 0.0), (step, step), SIZE, SIZE);
+        map.values().iter().sum()
+    });
+    println!("Filling a {}x{} noise map with Perlin noise: {:.2} ms",
+             SIZE, SIZE, elapsed.as_secs_f64() * 1000.0);
+}
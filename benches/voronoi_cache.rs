@@ -0,0 +1,79 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Compares the time taken to generate Voronoi noise over a grid by
+//! calculating the seed points for each input value and by looking them up in
+//! a `VoronoiCache`.
+//!
+//! Run with `cargo bench --bench voronoi_cache`.
+
+extern crate noise;
+
+use noise::module::{Module, Voronoi, VoronoiCache};
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 512;
+const RUNS: u32 = 5;
+/// Extent of the grid along the `x` and `y` axes.
+const EXTENT: f64 = 16.0;
+
+/// Returns the shortest time taken to run `f` over several runs.
+fn time_best<F: Fn() -> f64>(f: F) -> Duration {
+    (0..RUNS).map(|_| {
+        let start = Instant::now();
+        let sum = f();
+        let elapsed = start.elapsed();
+        // Make sure the result is not optimised away.
+        assert!(!sum.is_nan());
+        elapsed
+    }).min().unwrap()
+}
+
+/// Sums the output values at every point of a `SIZE`x`SIZE` grid.
+fn sum_grid<M: Module>(module: &M) -> f64 {
+    let step = EXTENT / SIZE as f64;
+    let mut sum = 0.0;
+    for j in 0..SIZE {
+        for i in 0..SIZE {
+            sum += module.get_value(i as f64 * step, j as f64 * step, 0.5);
+        }
+    }
+    sum
+}
+
+fn make_cache(voronoi: &Voronoi) -> VoronoiCache {
+    VoronoiCache::new(voronoi.clone(), (0.0, 0.0, 0.5), (EXTENT, EXTENT, 0.5))
+}
+
+fn report(name: &str, uncached: Duration, cached: Duration, build: Duration) {
+    println!("{:<10} uncached {:>8.2} ms   cached {:>8.2} ms (+ {:.2} ms to build)   \
+              speedup {:.2}x",
+             name, uncached.as_secs_f64() * 1000.0, cached.as_secs_f64() * 1000.0,
+             build.as_secs_f64() * 1000.0, uncached.as_secs_f64() / cached.as_secs_f64());
+}
+
+fn main() {
+    println!("{}x{} values, best of {} runs:", SIZE, SIZE, RUNS);
+
+    let mut voronoi = Voronoi::new();
+    for &(name, distance) in &[("cells", false), ("distance", true)] {
+        voronoi.enable_distance(distance);
+        let cache = make_cache(&voronoi);
+        let build = time_best(|| make_cache(&voronoi).get_value(0.0, 0.0, 0.5));
+        report(name, time_best(|| sum_grid(&voronoi)), time_best(|| sum_grid(&cache)), build);
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Compares the time taken to generate Voronoi noise with the default seed
+//! point search and with the faster, approximate search.
+//!
+//! Run with `cargo bench --bench voronoi_fast`.
+
+extern crate noise;
+
+use noise::module::{Module, Voronoi};
+use noise::noise_map::NoiseMap;
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 512;
+const RUNS: u32 = 5;
+
+/// Returns the shortest time taken to run `f` over several runs.
+fn time_best<F: Fn() -> f64>(f: F) -> Duration {
+    (0..RUNS).map(|_| {
+        let start = Instant::now();
+        let sum = f();
+        let elapsed = start.elapsed();
+        // Make sure the result is not optimised away.
+        assert!(!sum.is_nan());
+        elapsed
+    }).min().unwrap()
+}
+
+/// Sums the output values at every point of a `SIZE`x`SIZE` grid, calling
+/// `get_value()` for each point.
+fn sum_points<M: Module>(module: &M) -> f64 {
+    let step = 16.0 / SIZE as f64;
+    let mut sum = 0.0;
+    for j in 0..SIZE {
+        for i in 0..SIZE {
+            sum += module.get_value(i as f64 * step, j as f64 * step, 0.5);
+        }
+    }
+    sum
+}
+
+/// Sums the output values of a `SIZE`x`SIZE` noise map, which is filled a row
+/// at a time.
+fn sum_map<M: Module>(module: &M) -> f64 {
+    let step = 16.0 / SIZE as f64;
+    let map = NoiseMap::from_module(module, (0.0, 0.0), (step, step), SIZE, SIZE);
+    map.values().iter().sum()
+}
+
+fn report(name: &str, default: Duration, fast: Duration) {
+    println!("{:<20} default {:>8.2} ms   fast {:>8.2} ms   speedup {:.2}x", name,
+             default.as_secs_f64() * 1000.0, fast.as_secs_f64() * 1000.0,
+             default.as_secs_f64() / fast.as_secs_f64());
+}
+
+fn main() {
+    println!("{}x{} values, best of {} runs:", SIZE, SIZE, RUNS);
+
+    let mut voronoi = Voronoi::new();
+    let mut fast = Voronoi::new();
+    fast.set_fast(true);
+    report("get_value", time_best(|| sum_points(&voronoi)), time_best(|| sum_points(&fast)));
+    report("noise map", time_best(|| sum_map(&voronoi)), time_best(|| sum_map(&fast)));
+
+    voronoi.enable_distance(true);
+    fast.enable_distance(true);
+    report("get_value, distance",
+           time_best(|| sum_points(&voronoi)), time_best(|| sum_points(&fast)));
+}
@@ -0,0 +1,202 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Diagnostics for the output of noise modules.
+
+use module::Module;
+use std::f64;
+use std::f64::consts::PI;
+
+/// Returns the power spectrum of the output values from a noise module along a
+/// line.
+///
+///   * `module` - The noise module to sample.
+///   * `start` - The input value of the first sample point.
+///   * `step` - The offset between adjacent sample points.
+///   * `n` - The number of sample points, which must be a power of two.
+///
+/// The noise module is sampled at the input values `start + i * step`, for `i`
+/// from 0 to `n - 1`, and the samples are transformed with a radix-2 fast
+/// Fourier transform.  Element `k` of the returned vector is the squared
+/// magnitude of the `k`th frequency bin, |X<sub>k</sub>|<sup>2</sup>, for `k`
+/// from 0 to `n / 2` inclusive; the bins above `n / 2` mirror these for real
+/// samples, so they are not returned.  Bin `k` corresponds to `k` cycles over
+/// the length of the line, `n` times the length of `step`.
+///
+/// This is a diagnostic for checking the spectral slope of fractal noise.
+/// For example, fractal noise whose amplitude halves as its frequency doubles
+/// has a power spectrum falling roughly with the square of the frequency,
+/// which shows as a slope of -2 on a log-log plot.  No window function is
+/// applied, so the power of frequencies that do not complete a whole number
+/// of cycles over the line leaks into nearby bins.
+///
+/// # Panics
+///
+/// Panics if `n` is not a power of two.
+///
+/// # Examples
+///
+/// ```
+/// use noise::analysis::power_spectrum_1d;
+/// use noise::module::Perlin;
+///
+/// let step = (1.0 / 64.0, 0.0, 0.0);
+/// let spectrum = power_spectrum_1d(&Perlin::new(), (0.0, 0.0, 0.0), step, 1024);
+/// assert_eq!(spectrum.len(), 513);
+/// ```
+pub fn power_spectrum_1d<M: Module>(module: &M, start: (f64, f64, f64), step: (f64, f64, f64),
+                                    n: usize) -> Vec<f64> {
+    if !n.is_power_of_two() {
+        panic!("`n` must be a power of two!");
+    }
+
+    let mut re: Vec<f64> = (0..n).map(|i| {
+        let i = i as f64;
+        module.get_value(start.0 + i * step.0, start.1 + i * step.1, start.2 + i * step.2)
+    }).collect();
+    let mut im = vec![0.0; n];
+    fft(&mut re, &mut im);
+
+    (0..(n / 2 + 1)).map(|k| re[k] * re[k] + im[k] * im[k]).collect()
+}
+
+/// Returns a histogram of the output values from a noise module over a region
+/// of the `z = 0` plane.
+///
+///   * `module` - The noise module to sample.
+///   * `bounds` - The (`x`, `y`) coordinates of the lower and upper corners
+///     of the region.
+///   * `samples` - The number of sample points along the `x` and `y` axes.
+///   * `bins` - The number of bins.
+///   * `range` - The range of output values covered by the bins, or `None`
+///     to use the range of the sampled output values.
+///
+/// The region is divided into `samples.0` by `samples.1` cells, and the noise
+/// module is sampled at the center of each.  The range is divided into `bins`
+/// bins of equal width, and element `i` of the returned vector is the number
+/// of output values that fall into bin `i`, counting from the lower end of the
+/// range.  An output value equal to the upper end of the range falls into the
+/// last bin.  Output values outside of a given range, and NaN output values,
+/// are not counted.  If `range` is `None` and every output value is the same,
+/// they all fall into the first bin.
+///
+/// This is useful for placing the control points of a
+/// [`Curve`](../module/struct.Curve.html) or
+/// [`Terrace`](../module/struct.Terrace.html) noise module where the output
+/// values from its source module actually lie; for example, the output values
+/// from [`Perlin`](../module/struct.Perlin.html) cluster around zero.
+///
+/// # Panics
+///
+/// Panics if `bins` is zero, or if the lower end of a given range is not
+/// smaller than its upper end.
+///
+/// # Examples
+///
+/// ```
+/// use noise::analysis::histogram;
+/// use noise::module::Perlin;
+///
+/// let bounds = ((0.0, 0.0), (16.0, 16.0));
+/// let counts = histogram(&Perlin::new(), bounds, (64, 64), 8, Some((-1.0, 1.0)));
+/// assert_eq!(counts.len(), 8);
+///
+/// // The output values cluster around zero.
+/// assert!(counts[3] + counts[4] > counts[0] + counts[7]);
+/// ```
+pub fn histogram<M: Module>(module: &M, bounds: ((f64, f64), (f64, f64)), samples: (usize, usize),
+                            bins: usize, range: Option<(f64, f64)>) -> Vec<u64> {
+    if bins == 0 {
+        panic!("`bins` must be greater than zero!");
+    }
+    if let Some((lower, upper)) = range {
+        if lower.is_nan() || upper.is_nan() || lower >= upper {
+            panic!("Lower bound is not smaller than upper bound!");
+        }
+    }
+
+    let (lower, upper) = bounds;
+    let step = ((upper.0 - lower.0) / samples.0 as f64, (upper.1 - lower.1) / samples.1 as f64);
+    let mut values = Vec::with_capacity(samples.0 * samples.1);
+    for y in 0..samples.1 {
+        let y_cur = lower.1 + (y as f64 + 0.5) * step.1;
+        for x in 0..samples.0 {
+            let x_cur = lower.0 + (x as f64 + 0.5) * step.0;
+            values.push(module.get_value(x_cur, y_cur, 0.0));
+        }
+    }
+
+    let (min, max) = range.unwrap_or_else(|| {
+        values.iter().filter(|value| !value.is_nan())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                (f64::min(min, value), f64::max(max, value))
+            })
+    });
+    let mut counts = vec![0; bins];
+    for value in values {
+        if value.is_nan() || value < min || value > max {
+            continue;
+        }
+        let bin = if max > min {
+            ((value - min) / (max - min) * bins as f64) as usize
+        } else {
+            0
+        };
+        counts[::std::cmp::min(bin, bins - 1)] += 1;
+    }
+    counts
+}
+
+/// Transforms the given complex values in place with an iterative radix-2
+/// fast Fourier transform.  The number of values must be a power of two.
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+
+    // Reorder the values by bit-reversed index.
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    // Combine pairs of transforms of doubling length.
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..(len / 2) {
+                let (w_im, w_re) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
@@ -0,0 +1,891 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Reading and writing noise module graphs in a compact binary format.
+//!
+//! A graph is written as the four magic bytes `NMG1`, followed by its root
+//! noise module.  Each noise module is written as a one-byte *type tag*
+//! identifying its type, followed by its parameters, followed by each of its
+//! source modules in the order returned by
+//! [`Module::sources()`](../module/trait.Module.html#method.sources), written
+//! the same way.  The type tag determines the parameters and the number of
+//! source modules, so no other framing is needed.  Parameters are written
+//! little-endian, whatever the byte order of the platform: `f64` and `u64`
+//! values take eight bytes, `i32` and `u32` values four, and `bool` values and
+//! enumerations one.
+//!
+//! The following noise modules are supported:
+//!
+//!   * Generators: [`Constant`](../module/struct.Constant.html),
+//!     [`Perlin`](../module/struct.Perlin.html),
+//!     [`Billow`](../module/struct.Billow.html),
+//!     [`RidgedMulti`](../module/struct.RidgedMulti.html),
+//!     [`Voronoi`](../module/struct.Voronoi.html),
+//!     [`Checkerboard`](../module/struct.Checkerboard.html),
+//!     [`Cylinders`](../module/struct.Cylinders.html) and
+//!     [`Spheres`](../module/struct.Spheres.html).
+//!   * Modifiers: [`Abs`](../module/struct.Abs.html),
+//!     [`Invert`](../module/struct.Invert.html),
+//!     [`Clamp`](../module/struct.Clamp.html),
+//!     [`Exponent`](../module/struct.Exponent.html),
+//!     [`ScaleBias`](../module/struct.ScaleBias.html),
+//!     [`Curve`](../module/struct.Curve.html) and
+//!     [`Terrace`](../module/struct.Terrace.html).
+//!   * Transformers: [`ScalePoint`](../module/struct.ScalePoint.html),
+//!     [`TranslatePoint`](../module/struct.TranslatePoint.html),
+//!     [`RotatePoint`](../module/struct.RotatePoint.html),
+//!     [`Turbulence`](../module/struct.Turbulence.html) and
+//!     [`Displace`](../module/struct.Displace.html).
+//!   * Combiners: [`Add`](../module/struct.Add.html),
+//!     [`Multiply`](../module/struct.Multiply.html),
+//!     [`Min`](../module/struct.Min.html),
+//!     [`Max`](../module/struct.Max.html) and
+//!     [`Power`](../module/struct.Power.html).
+//!   * Selectors: [`Blend`](../module/struct.Blend.html) and
+//!     [`Select`](../module/struct.Select.html).
+//!
+//! Noise modules with source modules are only recognised when their source
+//! modules are [`BoxedModule`](../module/type.BoxedModule.html)s, which is
+//! also how [`read_binary()`](fn.read_binary.html) builds them.
+
+use module::{Abs, Add, Billow, Blend, BoxedModule, Checkerboard, Clamp, Constant, Curve,
+             Cylinders, Displace, Exponent, Falloff, Interp, Invert, Max, Min, Module, Multiply,
+             Perlin, Power, RidgedMulti, RotatePoint, ScaleBias, ScalePoint, Select, Spheres,
+             Terrace, TranslatePoint, Turbulence, Voronoi, BILLOW_MAX_OCTAVE, PERLIN_MAX_OCTAVE,
+             RIDGED_MAX_OCTAVE};
+use noisegen::{Dimensions, GradientTable, NoiseQuality, PermTable};
+use std::io::{self, Read, Write};
+
+/// Magic bytes at the start of a binary noise module graph.
+const BINARY_MAGIC: [u8; 4] = *b"NMG1";
+
+/// Deepest nesting of noise modules accepted by
+/// [`read_binary()`](fn.read_binary.html), so that corrupt data cannot
+/// overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+// The registry of type tags.  Tags are grouped by the category of noise
+// module, leaving room to add more noise modules to each category; a tag must
+// never be reused for a different noise module once released.
+const TAG_CONSTANT: u8 = 0x00;
+const TAG_PERLIN: u8 = 0x01;
+const TAG_BILLOW: u8 = 0x02;
+const TAG_RIDGED_MULTI: u8 = 0x03;
+const TAG_VORONOI: u8 = 0x04;
+const TAG_CHECKERBOARD: u8 = 0x05;
+const TAG_CYLINDERS: u8 = 0x06;
+const TAG_SPHERES: u8 = 0x07;
+const TAG_ABS: u8 = 0x20;
+const TAG_INVERT: u8 = 0x21;
+const TAG_CLAMP: u8 = 0x22;
+const TAG_EXPONENT: u8 = 0x23;
+const TAG_SCALE_BIAS: u8 = 0x24;
+const TAG_CURVE: u8 = 0x25;
+const TAG_TERRACE: u8 = 0x26;
+const TAG_SCALE_POINT: u8 = 0x40;
+const TAG_TRANSLATE_POINT: u8 = 0x41;
+const TAG_ROTATE_POINT: u8 = 0x42;
+const TAG_TURBULENCE: u8 = 0x43;
+const TAG_DISPLACE: u8 = 0x44;
+const TAG_ADD: u8 = 0x60;
+const TAG_MULTIPLY: u8 = 0x61;
+const TAG_MIN: u8 = 0x62;
+const TAG_MAX: u8 = 0x63;
+const TAG_POWER: u8 = 0x64;
+const TAG_BLEND: u8 = 0x80;
+const TAG_SELECT: u8 = 0x81;
+
+/// Writes a noise module graph in the compact binary format described in the
+/// [module documentation](index.html).
+///
+/// The graph can be read back with [`read_binary()`](fn.read_binary.html),
+/// which rebuilds it from [`BoxedModule`](../module/type.BoxedModule.html)s
+/// with the same parameters, and so the same output values.  Nothing is
+/// written unless the whole graph is supported.
+///
+/// Returns an error with the kind `InvalidInput` if the graph contains a noise
+/// module that is not supported by the format.
+///
+/// # Examples
+///
+/// ```
+/// use noise::binary::{read_binary, write_binary};
+/// use noise::module::{Abs, BoxedModule, Module, Perlin};
+///
+/// let graph: BoxedModule = Box::new(Abs::new(Box::new(Perlin::new()) as BoxedModule));
+/// let mut bytes = Vec::new();
+/// write_binary(&*graph, &mut bytes).unwrap();
+///
+/// let read = read_binary(&mut &bytes[..]).unwrap();
+/// assert_eq!(read.config_hash(), graph.config_hash());
+/// ```
+pub fn write_binary<W: Write>(module: &(dyn Module + 'static), writer: &mut W) -> io::Result<()> {
+    let mut bytes = BINARY_MAGIC.to_vec();
+    write_module(module, &mut bytes)?;
+    writer.write_all(&bytes)
+}
+
+/// Reads a noise module graph written by
+/// [`write_binary()`](fn.write_binary.html).
+///
+/// Returns an error with the kind `InvalidData` if the magic bytes do not
+/// match, the data contains an unknown type tag or an invalid parameter, or
+/// the graph is nested more than 128 noise modules deep, or `UnexpectedEof` if
+/// the data ends early.
+pub fn read_binary<R: Read>(reader: &mut R) -> io::Result<BoxedModule> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != BINARY_MAGIC {
+        return Err(invalid_data("not a binary noise module graph"));
+    }
+    read_module(reader, 0)
+}
+
+/// Writes a noise module and its source modules.
+fn write_module(module: &(dyn Module + 'static), bytes: &mut Vec<u8>) -> io::Result<()> {
+    let any = module.as_any();
+
+    // Generators.
+    if let Some(m) = any.downcast_ref::<Constant>() {
+        bytes.push(TAG_CONSTANT);
+        put_f64(bytes, m.const_value());
+        return Ok(());
+    }
+    if let Some(m) = any.downcast_ref::<Perlin>() {
+        bytes.push(TAG_PERLIN);
+        put_perlin(bytes, m);
+        return Ok(());
+    }
+    if let Some(m) = any.downcast_ref::<Billow>() {
+        bytes.push(TAG_BILLOW);
+        put_f64(bytes, m.frequency());
+        put_f64(bytes, m.lacunarity());
+        put_quality(bytes, m.quality());
+        put_i32(bytes, m.octave_count());
+        put_f64(bytes, m.persistence());
+        put_i32(bytes, m.seed());
+        put_bool(bytes, m.is_normalized());
+        put_f64(bytes, m.bias());
+        put_dimensions(bytes, m.dimensions());
+        return Ok(());
+    }
+    if let Some(m) = any.downcast_ref::<RidgedMulti>() {
+        bytes.push(TAG_RIDGED_MULTI);
+        put_f64(bytes, m.frequency());
+        put_f64(bytes, m.lacunarity());
+        put_quality(bytes, m.quality());
+        put_i32(bytes, m.octave_count());
+        put_i32(bytes, m.seed());
+        put_bool(bytes, m.is_normalized());
+        put_dimensions(bytes, m.dimensions());
+        return Ok(());
+    }
+    if let Some(m) = any.downcast_ref::<Voronoi>() {
+        bytes.push(TAG_VORONOI);
+        put_f64(bytes, m.displacement());
+        put_bool(bytes, m.is_distance_enabled());
+        bytes.push(match m.distance_falloff() {
+            Falloff::Linear => 0,
+            Falloff::Quadratic => 1,
+            Falloff::Exponential => 2,
+            Falloff::Smoothstep => 3,
+        });
+        put_bool(bytes, m.is_fast());
+        put_f64(bytes, m.frequency());
+        put_f64(bytes, m.jitter());
+        put_i32(bytes, m.seed());
+        put_perm_table(bytes, m.perm_table());
+        put_bool(bytes, m.is_smooth());
+        put_f64(bytes, m.smoothness());
+        put_f64(bytes, m.smooth_k());
+        return Ok(());
+    }
+    if any.is::<Checkerboard>() {
+        bytes.push(TAG_CHECKERBOARD);
+        return Ok(());
+    }
+    if let Some(m) = any.downcast_ref::<Cylinders>() {
+        bytes.push(TAG_CYLINDERS);
+        put_f64(bytes, m.frequency());
+        put_f64(bytes, m.offset());
+        put_bool(bytes, m.is_inverted());
+        put_bool(bytes, m.is_signed_distance());
+        return Ok(());
+    }
+    if let Some(m) = any.downcast_ref::<Spheres>() {
+        bytes.push(TAG_SPHERES);
+        put_f64(bytes, m.frequency());
+        put_f64(bytes, m.offset());
+        put_bool(bytes, m.is_inverted());
+        put_bool(bytes, m.is_signed_distance());
+        return Ok(());
+    }
+
+    // Modifiers.
+    if let Some(m) = any.downcast_ref::<Abs<BoxedModule>>() {
+        bytes.push(TAG_ABS);
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Invert<BoxedModule>>() {
+        bytes.push(TAG_INVERT);
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Clamp<BoxedModule>>() {
+        bytes.push(TAG_CLAMP);
+        put_f64(bytes, m.lower_bound());
+        put_f64(bytes, m.upper_bound());
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Exponent<BoxedModule>>() {
+        bytes.push(TAG_EXPONENT);
+        put_f64(bytes, m.exponent());
+        put_bool(bytes, m.is_input_clamped());
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<ScaleBias<BoxedModule>>() {
+        bytes.push(TAG_SCALE_BIAS);
+        put_f64(bytes, m.scale());
+        put_f64(bytes, m.bias());
+        let output_clamp = m.output_clamp();
+        put_bool(bytes, output_clamp.is_some());
+        let (lower_bound, upper_bound) = output_clamp.unwrap_or((0.0, 0.0));
+        put_f64(bytes, lower_bound);
+        put_f64(bytes, upper_bound);
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Curve<BoxedModule>>() {
+        bytes.push(TAG_CURVE);
+        put_len(bytes, m.control_points().len())?;
+        for control_point in m.control_points() {
+            put_f64(bytes, control_point.input_value);
+            put_f64(bytes, control_point.output_value);
+        }
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Terrace<BoxedModule>>() {
+        bytes.push(TAG_TERRACE);
+        put_bool(bytes, m.invert_terraces());
+        put_len(bytes, m.control_points().len())?;
+        for &value in m.control_points() {
+            put_f64(bytes, value);
+        }
+        return write_module(&**m.module(), bytes);
+    }
+
+    // Transformers.
+    if let Some(m) = any.downcast_ref::<ScalePoint<BoxedModule>>() {
+        bytes.push(TAG_SCALE_POINT);
+        put_f64(bytes, m.x_scale());
+        put_f64(bytes, m.y_scale());
+        put_f64(bytes, m.z_scale());
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<TranslatePoint<BoxedModule>>() {
+        bytes.push(TAG_TRANSLATE_POINT);
+        put_f64(bytes, m.x_trans());
+        put_f64(bytes, m.y_trans());
+        put_f64(bytes, m.z_trans());
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<RotatePoint<BoxedModule>>() {
+        bytes.push(TAG_ROTATE_POINT);
+        // The angles are NaN if the matrix was set directly, in which case the
+        // matrix is restored instead.
+        put_f64(bytes, m.x_angle());
+        put_f64(bytes, m.y_angle());
+        put_f64(bytes, m.z_angle());
+        for row in &m.matrix() {
+            for &element in row {
+                put_f64(bytes, element);
+            }
+        }
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Turbulence<BoxedModule>>() {
+        bytes.push(TAG_TURBULENCE);
+        put_f64(bytes, m.power());
+        put_bool(bytes, m.is_auto_power());
+        put_perlin(bytes, m.x_distort());
+        put_perlin(bytes, m.y_distort());
+        put_perlin(bytes, m.z_distort());
+        return write_module(&**m.module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Displace<BoxedModule, BoxedModule, BoxedModule,
+                                                 BoxedModule>>() {
+        bytes.push(TAG_DISPLACE);
+        write_module(&**m.module(), bytes)?;
+        write_module(&**m.x_displace_module(), bytes)?;
+        write_module(&**m.y_displace_module(), bytes)?;
+        return write_module(&**m.z_displace_module(), bytes);
+    }
+
+    // Combiners.
+    if let Some(m) = any.downcast_ref::<Add<BoxedModule, BoxedModule>>() {
+        bytes.push(TAG_ADD);
+        write_module(&**m.module1(), bytes)?;
+        return write_module(&**m.module2(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Multiply<BoxedModule, BoxedModule>>() {
+        bytes.push(TAG_MULTIPLY);
+        write_module(&**m.module1(), bytes)?;
+        return write_module(&**m.module2(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Min<BoxedModule, BoxedModule>>() {
+        bytes.push(TAG_MIN);
+        write_module(&**m.module1(), bytes)?;
+        return write_module(&**m.module2(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Max<BoxedModule, BoxedModule>>() {
+        bytes.push(TAG_MAX);
+        write_module(&**m.module1(), bytes)?;
+        return write_module(&**m.module2(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Power<BoxedModule, BoxedModule>>() {
+        bytes.push(TAG_POWER);
+        write_module(&**m.module1(), bytes)?;
+        return write_module(&**m.module2(), bytes);
+    }
+
+    // Selectors.
+    if let Some(m) = any.downcast_ref::<Blend<BoxedModule, BoxedModule, BoxedModule>>() {
+        bytes.push(TAG_BLEND);
+        put_f64(bytes, m.control_lower_bound());
+        put_f64(bytes, m.control_upper_bound());
+        put_interp(bytes, m.interp());
+        write_module(&**m.module1(), bytes)?;
+        write_module(&**m.module2(), bytes)?;
+        return write_module(&**m.control_module(), bytes);
+    }
+    if let Some(m) = any.downcast_ref::<Select<BoxedModule, BoxedModule, BoxedModule>>() {
+        bytes.push(TAG_SELECT);
+        put_f64(bytes, m.edge_falloff());
+        put_f64(bytes, m.lower_bound());
+        put_f64(bytes, m.upper_bound());
+        put_interp(bytes, m.interp());
+        write_module(&**m.module1(), bytes)?;
+        write_module(&**m.module2(), bytes)?;
+        return write_module(&**m.control_module(), bytes);
+    }
+
+    Err(io::Error::new(io::ErrorKind::InvalidInput,
+                       "noise module type not supported by the binary format"))
+}
+
+/// Reads a noise module and its source modules, `depth` levels below the root
+/// noise module.
+fn read_module<R: Read>(reader: &mut R, depth: usize) -> io::Result<BoxedModule> {
+    if depth >= MAX_DEPTH {
+        return Err(invalid_data("noise module graph is nested too deeply"));
+    }
+    let depth = depth + 1;
+
+    let tag = get_u8(reader)?;
+    if let Some(module) = read_generator(tag, reader)? {
+        return Ok(module);
+    }
+    let module: BoxedModule = match tag {
+        TAG_ABS => Box::new(Abs::new(read_module(reader, depth)?)),
+        TAG_INVERT => Box::new(Invert::new(read_module(reader, depth)?)),
+        TAG_CLAMP => {
+            let (lower_bound, upper_bound) = (get_f64(reader)?, get_f64(reader)?);
+            if lower_bound > upper_bound {
+                return Err(invalid_data("Clamp lower bound is larger than upper bound"));
+            }
+            let mut m = Clamp::new(read_module(reader, depth)?);
+            m.set_bounds(lower_bound, upper_bound);
+            Box::new(m)
+        },
+        TAG_EXPONENT => {
+            let (exponent, clamp_input) = (get_f64(reader)?, get_bool(reader)?);
+            let mut m = Exponent::new(read_module(reader, depth)?);
+            m.set_exponent(exponent);
+            m.set_clamp_input(clamp_input);
+            Box::new(m)
+        },
+        TAG_SCALE_BIAS => {
+            let (scale, bias) = (get_f64(reader)?, get_f64(reader)?);
+            let has_output_clamp = get_bool(reader)?;
+            let (lower_bound, upper_bound) = (get_f64(reader)?, get_f64(reader)?);
+            if lower_bound > upper_bound {
+                return Err(invalid_data("ScaleBias lower bound is larger than upper bound"));
+            }
+            let mut m = ScaleBias::new(read_module(reader, depth)?);
+            m.set_scale(scale);
+            m.set_bias(bias);
+            if has_output_clamp {
+                m.set_output_clamp(Some((lower_bound, upper_bound)));
+            }
+            Box::new(m)
+        },
+        TAG_CURVE => {
+            let count = get_u32(reader)?;
+            let mut control_points = Vec::new();
+            for _ in 0..count {
+                let (input_value, output_value) = (get_f64(reader)?, get_f64(reader)?);
+                if output_value.is_nan() {
+                    return Err(invalid_data("invalid Curve control point"));
+                }
+                control_points.push((input_value, output_value));
+            }
+            check_increasing(control_points.iter().map(|p| p.0), "Curve")?;
+            let mut m = Curve::new(read_module(reader, depth)?);
+            for (input_value, output_value) in control_points {
+                m.add_control_point(input_value, output_value);
+            }
+            Box::new(m)
+        },
+        TAG_TERRACE => {
+            let invert_terraces = get_bool(reader)?;
+            let count = get_u32(reader)?;
+            let mut control_points = Vec::new();
+            for _ in 0..count {
+                control_points.push(get_f64(reader)?);
+            }
+            check_increasing(control_points.iter().cloned(), "Terrace")?;
+            let mut m = Terrace::new(read_module(reader, depth)?);
+            m.set_invert_terraces(invert_terraces);
+            for value in control_points {
+                m.add_control_point(value);
+            }
+            Box::new(m)
+        },
+
+        TAG_SCALE_POINT => {
+            let (x, y, z) = (get_f64(reader)?, get_f64(reader)?, get_f64(reader)?);
+            let mut m = ScalePoint::new(read_module(reader, depth)?);
+            m.set_xyz_scale(x, y, z);
+            Box::new(m)
+        },
+        TAG_TRANSLATE_POINT => {
+            let (x, y, z) = (get_f64(reader)?, get_f64(reader)?, get_f64(reader)?);
+            let mut m = TranslatePoint::new(read_module(reader, depth)?);
+            m.set_xyz_trans(x, y, z);
+            Box::new(m)
+        },
+        TAG_ROTATE_POINT => {
+            let (x, y, z) = (get_f64(reader)?, get_f64(reader)?, get_f64(reader)?);
+            let mut matrix = [[0.0; 3]; 3];
+            for row in &mut matrix {
+                for element in row.iter_mut() {
+                    *element = get_f64(reader)?;
+                }
+            }
+            let mut m = RotatePoint::new(read_module(reader, depth)?);
+            if x.is_nan() || y.is_nan() || z.is_nan() {
+                if matrix.iter().flat_map(|row| row.iter()).any(|e| !e.is_finite()) {
+                    return Err(invalid_data("invalid RotatePoint matrix"));
+                }
+                m.set_matrix(matrix);
+            } else {
+                m.set_angles(x, y, z);
+            }
+            Box::new(m)
+        },
+        TAG_TURBULENCE => read_turbulence(reader, depth)?,
+        TAG_DISPLACE => {
+            let source = read_module(reader, depth)?;
+            let x_displace = read_module(reader, depth)?;
+            let y_displace = read_module(reader, depth)?;
+            let z_displace = read_module(reader, depth)?;
+            Box::new(Displace::new(source, x_displace, y_displace, z_displace))
+        },
+
+        TAG_ADD => {
+            let module1 = read_module(reader, depth)?;
+            Box::new(Add::new(module1, read_module(reader, depth)?))
+        },
+        TAG_MULTIPLY => {
+            let module1 = read_module(reader, depth)?;
+            Box::new(Multiply::new(module1, read_module(reader, depth)?))
+        },
+        TAG_MIN => {
+            let module1 = read_module(reader, depth)?;
+            Box::new(Min::new(module1, read_module(reader, depth)?))
+        },
+        TAG_MAX => {
+            let module1 = read_module(reader, depth)?;
+            Box::new(Max::new(module1, read_module(reader, depth)?))
+        },
+        TAG_POWER => {
+            let module1 = read_module(reader, depth)?;
+            Box::new(Power::new(module1, read_module(reader, depth)?))
+        },
+
+        TAG_BLEND => {
+            let (lower_bound, upper_bound) = (get_f64(reader)?, get_f64(reader)?);
+            if !lower_bound.is_finite() || !upper_bound.is_finite() {
+                return Err(invalid_data("Blend control range bounds are not finite"));
+            }
+            if lower_bound >= upper_bound {
+                return Err(invalid_data("Blend lower bound is not smaller than upper bound"));
+            }
+            let interp = get_interp(reader)?;
+            let module1 = read_module(reader, depth)?;
+            let module2 = read_module(reader, depth)?;
+            let mut m = Blend::new(module1, module2, read_module(reader, depth)?);
+            m.set_control_range(lower_bound, upper_bound);
+            m.set_interp(interp);
+            Box::new(m)
+        },
+        TAG_SELECT => {
+            let edge_falloff = get_f64(reader)?;
+            let (lower_bound, upper_bound) = (get_f64(reader)?, get_f64(reader)?);
+            if lower_bound > upper_bound {
+                return Err(invalid_data("Select lower bound is larger than upper bound"));
+            }
+            let interp = get_interp(reader)?;
+            let module1 = read_module(reader, depth)?;
+            let module2 = read_module(reader, depth)?;
+            let mut m = Select::new(module1, module2, read_module(reader, depth)?);
+            // The edge falloff is limited by the bounds, so set it after.
+            m.set_bounds(lower_bound, upper_bound);
+            m.set_edge_falloff(edge_falloff);
+            m.set_interp(interp);
+            Box::new(m)
+        },
+
+        _ => return Err(invalid_data("unknown noise module type tag")),
+    };
+    Ok(module)
+}
+
+/// Reads a generator noise module, which has no source modules, returning
+/// `None` if `tag` is not the type tag of a generator.
+///
+/// This is kept out of `read_module()` so that the large generator noise
+/// modules do not take up space in every level of its recursion.
+#[inline(never)]
+fn read_generator<R: Read>(tag: u8, reader: &mut R) -> io::Result<Option<BoxedModule>> {
+    let module: BoxedModule = match tag {
+        TAG_CONSTANT => {
+            let mut m = Constant::new();
+            m.set_const_value(get_f64(reader)?);
+            Box::new(m)
+        },
+        TAG_PERLIN => Box::new(get_perlin(reader)?),
+        TAG_BILLOW => {
+            let mut m = Billow::new();
+            m.set_frequency(get_f64(reader)?);
+            m.set_lacunarity(get_f64(reader)?);
+            m.set_quality(get_quality(reader)?);
+            m.set_octave_count(get_octave_count(reader, BILLOW_MAX_OCTAVE)?);
+            m.set_persistence(get_f64(reader)?);
+            m.set_seed(get_i32(reader)?);
+            m.set_normalize(get_bool(reader)?);
+            m.set_bias(get_f64(reader)?);
+            m.set_dimensions(get_dimensions(reader)?);
+            Box::new(m)
+        },
+        TAG_RIDGED_MULTI => {
+            let mut m = RidgedMulti::new();
+            m.set_frequency(get_f64(reader)?);
+            m.set_lacunarity(get_f64(reader)?);
+            m.set_quality(get_quality(reader)?);
+            m.set_octave_count(get_octave_count(reader, RIDGED_MAX_OCTAVE)?);
+            m.set_seed(get_i32(reader)?);
+            m.set_normalize(get_bool(reader)?);
+            m.set_dimensions(get_dimensions(reader)?);
+            Box::new(m)
+        },
+        TAG_VORONOI => {
+            let mut m = Voronoi::new();
+            m.set_displacement(get_f64(reader)?);
+            m.enable_distance(get_bool(reader)?);
+            m.set_distance_falloff(match get_u8(reader)? {
+                0 => Falloff::Linear,
+                1 => Falloff::Quadratic,
+                2 => Falloff::Exponential,
+                3 => Falloff::Smoothstep,
+                _ => return Err(invalid_data("unknown distance falloff")),
+            });
+            m.set_fast(get_bool(reader)?);
+            m.set_frequency(get_f64(reader)?);
+            let jitter = get_f64(reader)?;
+            if !(0.0..=1.0).contains(&jitter) {
+                return Err(invalid_data("Voronoi jitter out of range"));
+            }
+            m.set_jitter(jitter);
+            m.set_seed(get_i32(reader)?);
+            m.set_perm_table(get_perm_table(reader)?);
+            m.set_smooth(get_bool(reader)?);
+            let smoothness = get_f64(reader)?;
+            if !smoothness.is_finite() || smoothness <= 0.0 {
+                return Err(invalid_data("Voronoi smoothness out of range"));
+            }
+            m.set_smoothness(smoothness);
+            let smooth_k = get_f64(reader)?;
+            if !smooth_k.is_finite() || smooth_k < 0.0 {
+                return Err(invalid_data("Voronoi smooth_k out of range"));
+            }
+            m.set_smooth_k(smooth_k);
+            Box::new(m)
+        },
+        TAG_CHECKERBOARD => Box::new(Checkerboard),
+        TAG_CYLINDERS => {
+            let mut m = Cylinders::new();
+            m.set_frequency(get_f64(reader)?);
+            m.set_offset(get_f64(reader)?);
+            m.set_invert(get_bool(reader)?);
+            m.set_signed_distance(get_bool(reader)?);
+            Box::new(m)
+        },
+        TAG_SPHERES => {
+            let mut m = Spheres::new();
+            m.set_frequency(get_f64(reader)?);
+            m.set_offset(get_f64(reader)?);
+            m.set_invert(get_bool(reader)?);
+            m.set_signed_distance(get_bool(reader)?);
+            Box::new(m)
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(module))
+}
+
+/// Reads a `Turbulence` noise module and its source module.
+///
+/// Like `read_generator()`, this is kept out of `read_module()` because of the
+/// size of the internal noise modules.
+#[inline(never)]
+fn read_turbulence<R: Read>(reader: &mut R, depth: usize) -> io::Result<BoxedModule> {
+    let (power, auto_power) = (get_f64(reader)?, get_bool(reader)?);
+    let x_distort = get_perlin(reader)?;
+    let y_distort = get_perlin(reader)?;
+    let z_distort = get_perlin(reader)?;
+    let mut m = Turbulence::new(read_module(reader, depth)?);
+    *m.x_distort_mut() = x_distort;
+    *m.y_distort_mut() = y_distort;
+    *m.z_distort_mut() = z_distort;
+    // Enabling automatic power overwrites the power, so set it after.
+    m.set_auto_power(auto_power);
+    m.set_power(power);
+    Ok(Box::new(m))
+}
+
+/// Writes the parameters of a `Perlin` noise module.
+fn put_perlin(bytes: &mut Vec<u8>, m: &Perlin) {
+    put_f64(bytes, m.frequency());
+    put_f64(bytes, m.lacunarity());
+    put_quality(bytes, m.quality());
+    put_f64(bytes, m.octave_count_f());
+    put_f64(bytes, m.persistence());
+    put_i32(bytes, m.seed());
+    put_bool(bytes, m.seed_u64().is_some());
+    put_u64(bytes, m.seed_u64().unwrap_or(0));
+    put_perm_table(bytes, m.perm_table());
+    put_bool(bytes, m.gradient_table().is_some());
+    if let Some(gradients) = m.gradient_table() {
+        for index in 0..256 {
+            for &component in &gradients.vector(index as u8) {
+                put_f64(bytes, component);
+            }
+        }
+    }
+    put_bool(bytes, m.is_normalized());
+    put_bool(bytes, m.is_octave_rotation());
+    put_bool(bytes, m.is_octave_offset());
+    put_dimensions(bytes, m.dimensions());
+}
+
+/// Reads the parameters of a `Perlin` noise module.
+fn get_perlin<R: Read>(reader: &mut R) -> io::Result<Perlin> {
+    let mut m = Perlin::new();
+    m.set_frequency(get_f64(reader)?);
+    m.set_lacunarity(get_f64(reader)?);
+    m.set_quality(get_quality(reader)?);
+    let octave_count = get_f64(reader)?;
+    if octave_count.is_nan() || octave_count < 1.0 || octave_count > PERLIN_MAX_OCTAVE as f64 {
+        return Err(invalid_data("octave count out of range"));
+    }
+    m.set_octave_count_f(octave_count);
+    m.set_persistence(get_f64(reader)?);
+    let seed = get_i32(reader)?;
+    let (has_seed_u64, seed_u64) = (get_bool(reader)?, get_u64(reader)?);
+    if has_seed_u64 {
+        m.set_seed_u64(seed_u64);
+    } else {
+        m.set_seed(seed);
+    }
+    m.set_perm_table(get_perm_table(reader)?);
+    if get_bool(reader)? {
+        let mut gradients = Vec::with_capacity(256);
+        for _ in 0..256 {
+            let gradient = [get_f64(reader)?, get_f64(reader)?, get_f64(reader)?];
+            if gradient.iter().any(|c| !c.is_finite()) {
+                return Err(invalid_data("gradient vector is not finite"));
+            }
+            gradients.push(gradient);
+        }
+        m.set_gradient_table(Some(GradientTable::new(&gradients)));
+    }
+    m.set_normalize(get_bool(reader)?);
+    m.set_octave_rotation(get_bool(reader)?);
+    m.set_octave_offset(get_bool(reader)?);
+    m.set_dimensions(get_dimensions(reader)?);
+    Ok(m)
+}
+
+/// Reads an integer octave count, checking it is between 1 and `max_octave`.
+fn get_octave_count<R: Read>(reader: &mut R, max_octave: i32) -> io::Result<i32> {
+    let octave_count = get_i32(reader)?;
+    if !(1..=max_octave).contains(&octave_count) {
+        return Err(invalid_data("octave count out of range"));
+    }
+    Ok(octave_count)
+}
+
+/// Checks that control point values are strictly increasing, as they are
+/// stored by the noise module, so that adding them cannot panic.
+fn check_increasing<I: Iterator<Item=f64>>(values: I, name: &str) -> io::Result<()> {
+    let mut prev = None;
+    for value in values {
+        if value.is_nan() || prev.is_some_and(|prev| prev >= value) {
+            return Err(invalid_data(&format!("invalid {} control points", name)));
+        }
+        prev = Some(value);
+    }
+    Ok(())
+}
+
+/// Writes an optional permutation table as a flag followed by its seed, which
+/// determines the whole table.
+fn put_perm_table(bytes: &mut Vec<u8>, perm_table: Option<&PermTable>) {
+    put_bool(bytes, perm_table.is_some());
+    put_i32(bytes, perm_table.map_or(0, |perm_table| perm_table.seed()));
+}
+
+fn get_perm_table<R: Read>(reader: &mut R) -> io::Result<Option<PermTable>> {
+    let (has_perm_table, seed) = (get_bool(reader)?, get_i32(reader)?);
+    Ok(if has_perm_table { Some(PermTable::new(seed)) } else { None })
+}
+
+fn put_quality(bytes: &mut Vec<u8>, quality: NoiseQuality) {
+    bytes.push(match quality {
+        NoiseQuality::Fast => 0,
+        NoiseQuality::Standard => 1,
+        NoiseQuality::Best => 2,
+    });
+}
+
+fn get_quality<R: Read>(reader: &mut R) -> io::Result<NoiseQuality> {
+    match get_u8(reader)? {
+        0 => Ok(NoiseQuality::Fast),
+        1 => Ok(NoiseQuality::Standard),
+        2 => Ok(NoiseQuality::Best),
+        _ => Err(invalid_data("unknown noise quality")),
+    }
+}
+
+fn put_dimensions(bytes: &mut Vec<u8>, dimensions: Dimensions) {
+    bytes.push(match dimensions {
+        Dimensions::Dim2 => 2,
+        Dimensions::Dim3 => 3,
+    });
+}
+
+fn get_dimensions<R: Read>(reader: &mut R) -> io::Result<Dimensions> {
+    match get_u8(reader)? {
+        2 => Ok(Dimensions::Dim2),
+        3 => Ok(Dimensions::Dim3),
+        _ => Err(invalid_data("unknown number of dimensions")),
+    }
+}
+
+fn put_interp(bytes: &mut Vec<u8>, interp: Interp) {
+    bytes.push(match interp {
+        Interp::Linear => 0,
+        Interp::Cubic => 1,
+        Interp::Quintic => 2,
+    });
+}
+
+fn get_interp<R: Read>(reader: &mut R) -> io::Result<Interp> {
+    match get_u8(reader)? {
+        0 => Ok(Interp::Linear),
+        1 => Ok(Interp::Cubic),
+        2 => Ok(Interp::Quintic),
+        _ => Err(invalid_data("unknown easing curve")),
+    }
+}
+
+/// Writes the length of a list of parameters.
+fn put_len(bytes: &mut Vec<u8>, len: usize) -> io::Result<()> {
+    if len > u32::MAX as usize {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                  "too many control points for the binary format"));
+    }
+    bytes.extend_from_slice(&(len as u32).to_le_bytes());
+    Ok(())
+}
+
+fn put_bool(bytes: &mut Vec<u8>, value: bool) {
+    bytes.push(value as u8);
+}
+
+fn put_i32(bytes: &mut Vec<u8>, value: i32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u64(bytes: &mut Vec<u8>, value: u64) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_f64(bytes: &mut Vec<u8>, value: f64) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn get_u8<R: Read>(reader: &mut R) -> io::Result<u8> {
+    let mut bytes = [0; 1];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+fn get_bool<R: Read>(reader: &mut R) -> io::Result<bool> {
+    match get_u8(reader)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(invalid_data("invalid boolean")),
+    }
+}
+
+fn get_i32<R: Read>(reader: &mut R) -> io::Result<i32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(i32::from_le_bytes(bytes))
+}
+
+fn get_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn get_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn get_f64<R: Read>(reader: &mut R) -> io::Result<f64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(f64::from_le_bytes(bytes))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
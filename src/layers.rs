@@ -0,0 +1,186 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Composition of noise module graphs from an ordered list of layers.
+//!
+//! A [`LayerStack`](struct.LayerStack.html) describes a graph the way an image
+//! editor describes a picture: as a stack of layers, each blended onto the
+//! layers beneath it with a [`BlendMode`](enum.BlendMode.html) and a weight.
+//! This suits terrain that is defined by data rather than code.
+//!
+//! # Examples
+//!
+//! ```
+//! use noise::layers::{BlendMode, LayerStack};
+//! use noise::module::{Module, Perlin, Voronoi};
+//!
+//! let mut stack = LayerStack::new();
+//! stack.add_layer(Box::new(Perlin::new()), BlendMode::Add, 1.0);
+//! stack.add_layer(Box::new(Voronoi::new()), BlendMode::Add, 1.0);
+//! let terrain = stack.build();
+//!
+//! let expected = Perlin::new().get_value(0.3, 0.5, 0.7) + Voronoi::new().get_value(0.3, 0.5, 0.7);
+//! assert_eq!(terrain.get_value(0.3, 0.5, 0.7), expected);
+//! ```
+
+use module::{Add, BoxedModule, Combine2, Constant, Max, Min, Multiply, Overlay};
+use std::fmt;
+use util::{linear_interp, overlay_blend};
+
+/// Enumerates the ways a layer is blended onto the layers beneath it.
+///
+/// In the descriptions below, `base` is the output value of the layers
+/// beneath and `layer` is the output value of the layer being blended.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BlendMode {
+    /// Outputs `base + layer`.
+    Add,
+    /// Outputs `base * layer`.
+    Multiply,
+    /// Outputs the standard overlay blend of the two values, which multiplies
+    /// the low values of `base` and screens the high values, increasing
+    /// contrast.  See the [`Overlay`](../module/struct.Overlay.html) noise
+    /// module for the formula.
+    Overlay,
+    /// Outputs the larger of `base` and `layer`.
+    Max,
+    /// Outputs the smaller of `base` and `layer`.
+    Min,
+}
+
+impl BlendMode {
+    /// Blends the output value of a layer onto the output value of the layers
+    /// beneath it.
+    pub fn blend(self, base: f64, layer: f64) -> f64 {
+        match self {
+            BlendMode::Add => base + layer,
+            BlendMode::Multiply => base * layer,
+            BlendMode::Overlay => overlay_blend(base, layer),
+            BlendMode::Max => f64::max(base, layer),
+            BlendMode::Min => f64::min(base, layer),
+        }
+    }
+}
+
+/// A layer of a [`LayerStack`](struct.LayerStack.html).
+struct Layer {
+    module: BoxedModule,
+    mode: BlendMode,
+    weight: f64,
+}
+
+impl fmt::Debug for Layer {
+    // The boxed module cannot be printed, so only the blend mode and weight
+    // are.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Layer")
+            .field("mode", &self.mode)
+            .field("weight", &self.weight)
+            .finish()
+    }
+}
+
+/// An ordered stack of layers that is composed into a single noise module
+/// graph.
+///
+/// Layers are added from the bottom up with the
+/// [`add_layer()`](struct.LayerStack.html#method.add_layer) method.  When the
+/// stack is built, each layer is blended onto the result of the layers beneath
+/// it with its blend mode, then mixed with that result by its weight, like the
+/// opacity of a layer in an image editor: a weight of 1.0 applies the blend
+/// fully, a weight of 0.0 leaves the layers beneath unchanged, and weights in
+/// between interpolate linearly.  The blend mode and weight of the bottom layer
+/// are ignored, as there is nothing beneath it.
+///
+/// Layers with a weight of 1.0 are built from the matching combiner noise
+/// module, such as [`Add`](../module/struct.Add.html) or
+/// [`Overlay`](../module/struct.Overlay.html), so that the built graph can be
+/// inspected and simplified like a graph built by hand.  Other layers are
+/// built from a [`Combine2`](../module/struct.Combine2.html) noise module.
+#[derive(Default)]
+pub struct LayerStack {
+    layers: Vec<Layer>,
+}
+
+impl LayerStack {
+    /// Create a new `LayerStack` with no layers.
+    pub fn new() -> LayerStack {
+        Default::default()
+    }
+
+    /// Returns the number of layers in the stack.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Determines if the stack has no layers.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Adds a layer on top of the stack, blended onto the layers beneath it
+    /// with the given blend mode and weight.
+    pub fn add_layer(&mut self, module: BoxedModule, mode: BlendMode, weight: f64) {
+        self.layers.push(Layer {
+            module: module,
+            mode: mode,
+            weight: weight,
+        });
+    }
+
+    /// Composes the layers into a single noise module graph.
+    ///
+    /// An empty stack builds a [`Constant`](../module/struct.Constant.html)
+    /// noise module that outputs 0.0.
+    pub fn build(self) -> BoxedModule {
+        let mut layers = self.layers.into_iter();
+        let mut graph = match layers.next() {
+            Some(layer) => layer.module,
+            None => return Box::new(Constant::new()),
+        };
+        for layer in layers {
+            graph = blend_layer(graph, layer);
+        }
+        graph
+    }
+}
+
+impl fmt::Debug for LayerStack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LayerStack")
+            .field("layer_count", &self.layers.len())
+            .field("layers", &self.layers)
+            .finish()
+    }
+}
+
+/// Blends a layer onto the graph of the layers beneath it.
+fn blend_layer(base: BoxedModule, layer: Layer) -> BoxedModule {
+    if layer.weight == 1.0 {
+        match layer.mode {
+            BlendMode::Add => return Box::new(Add::new(base, layer.module)),
+            BlendMode::Multiply => return Box::new(Multiply::new(base, layer.module)),
+            BlendMode::Overlay => return Box::new(Overlay::new(base, layer.module)),
+            BlendMode::Max => return Box::new(Max::new(base, layer.module)),
+            BlendMode::Min => return Box::new(Min::new(base, layer.module)),
+        }
+    }
+    let (mode, weight) = (layer.mode, layer.weight);
+    Box::new(Combine2::new(base, layer.module, move |base, value| {
+        linear_interp(base, mode.blend(base, value), weight)
+    }))
+}
@@ -1,4 +1,17 @@
+#[cfg(feature = "simd")]
+extern crate wide;
+
+pub mod analysis;
+pub mod binary;
 mod consts;
+pub mod layers;
 pub mod module;
+pub mod noise_map;
 pub mod noisegen;
+pub mod presets;
+pub mod render;
+pub mod sampling;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod util;
+pub mod writer;
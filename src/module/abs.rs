@@ -14,10 +14,11 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Noise module that outputs the absolute value of the output value from a
 /// source module.
+#[derive(Debug, PartialEq)]
 pub struct Abs<M: Module> {
     module: M,
 }
@@ -50,6 +51,31 @@ impl<M: Module> Module for Abs<M> {
     fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
         self.module.get_value(x, y, z).abs()
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Abs");
+        hasher.write_u64(self.module.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
 }
 
 impl<M: Module + Clone> Clone for Abs<M> {
@@ -14,12 +14,14 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{BoxedModule, ConfigHasher, Module};
+use std::fmt;
 
 /// Noise module that outputs the sum of the two output values from two
 /// source modules.
 ///
 /// This noise module requires two source modules.
+#[derive(Debug, PartialEq)]
 pub struct Add<M1: Module, M2: Module> {
     module1: M1,
     module2: M2,
@@ -69,6 +71,34 @@ impl<M1: Module, M2: Module> Module for Add<M1, M2> {
     fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
         self.module1.get_value(x, y, z) + self.module2.get_value(x, y, z)
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Add");
+        hasher.write_u64(self.module1.config_hash());
+        hasher.write_u64(self.module2.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        2
+    }
+
+    /// Returns the first and second source modules, in that order.
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module1 as &dyn Module, &self.module2]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module1),
+            1 => Some(&self.module2),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module1 as &mut dyn Module, &mut self.module2]
+    }
 }
 
 impl<M1: Module + Clone, M2: Module + Clone> Clone for Add<M1, M2> {
@@ -79,3 +109,113 @@ impl<M1: Module + Clone, M2: Module + Clone> Clone for Add<M1, M2> {
         }
     }
 }
+
+/// Noise module that outputs the sum of the output values from any number of
+/// source modules.
+///
+/// This is equivalent to a chain of nested [`Add`](struct.Add.html) noise
+/// modules, but is easier to build and avoids the deep nesting.  The source
+/// modules are added with the [`push()`](struct.Sum.html#method.push)
+/// method, and their output values are added together in the order they were
+/// added.  With no source modules, the output value is 0.0.
+///
+/// This noise module accepts any number of source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Add, Module, Perlin, Sum};
+///
+/// let mut perlins = Vec::new();
+/// for seed in 0..3 {
+///     let mut perlin = Perlin::new();
+///     perlin.set_seed(seed);
+///     perlins.push(perlin);
+/// }
+///
+/// let nested = Add::new(Add::new(perlins[0].clone(), perlins[1].clone()), perlins[2].clone());
+/// let mut flat = Sum::new();
+/// for perlin in perlins {
+///     flat.push(Box::new(perlin));
+/// }
+/// assert_eq!(flat.get_value(0.3, 0.5, 0.7), nested.get_value(0.3, 0.5, 0.7));
+/// ```
+pub struct Sum {
+    modules: Vec<BoxedModule>,
+}
+
+impl Default for Sum {
+    /// Create a new `Sum` noise module with no source modules.
+    fn default() -> Sum {
+        Sum {
+            modules: Vec::new(),
+        }
+    }
+}
+
+impl Sum {
+    /// Create a new `Sum` noise module with no source modules.
+    pub fn new() -> Sum {
+        Default::default()
+    }
+
+    /// Returns the source modules, in the order they were added.
+    pub fn modules(&self) -> &[BoxedModule] {
+        &self.modules
+    }
+
+    /// Returns a mutable reference to the source modules.
+    pub fn modules_mut(&mut self) -> &mut Vec<BoxedModule> {
+        &mut self.modules
+    }
+
+    /// Adds a source module.
+    pub fn push(&mut self, module: BoxedModule) {
+        self.modules.push(module);
+    }
+}
+
+impl Module for Sum {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let mut value = 0.0;
+        for module in &self.modules {
+            value += module.get_value(x, y, z);
+        }
+        value
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Sum");
+        hasher.write_u64(self.modules.len() as u64);
+        for module in &self.modules {
+            hasher.write_u64(module.config_hash());
+        }
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Returns the source modules, in the order they were added.
+    fn sources(&self) -> Vec<&dyn Module> {
+        self.modules.iter().map(|module| &**module).collect()
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        self.modules.get(index).map(|module| &**module as &dyn Module)
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        self.modules.iter_mut().map(|module| &mut **module as &mut dyn Module).collect()
+    }
+}
+
+impl fmt::Debug for Sum {
+    // The boxed source modules cannot be printed, so only their number is.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sum")
+            .field("module_count", &self.modules.len())
+            .finish()
+    }
+}
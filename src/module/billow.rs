@@ -14,8 +14,10 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
-use noisegen::{gradient_coherent_noise3d, make_i32_range, NoiseQuality};
+use module::{ConfigHasher, Module, Seeded};
+use noisegen::{gradient_coherent_noise2d, gradient_coherent_noise3d, make_i32_range, Dimensions,
+               NoiseQuality};
+use util::amplitude_sum;
 
 /// Default frequency for the [`Billow`](struct.Billow.html) noise module.
 pub const DEFAULT_BILLOW_FREQUENCY: f64 = 1.0;
@@ -38,6 +40,13 @@ pub const DEFAULT_BILLOW_QUALITY: NoiseQuality = NoiseQuality::Standard;
 /// Default noise seed for the the [`Billow`](struct.Billow.html) noise module.
 pub const DEFAULT_BILLOW_SEED: i32 = 0;
 
+/// Default number of dimensions of the coherent noise for the
+/// [`Billow`](struct.Billow.html) noise module.
+pub const DEFAULT_BILLOW_DIMENSIONS: Dimensions = Dimensions::Dim3;
+
+/// Default bias for the the [`Billow`](struct.Billow.html) noise module.
+pub const DEFAULT_BILLOW_BIAS: f64 = 0.5;
+
 /// Maximum number of octaves for the the [`Billow`](struct.Billow.html) noise
 /// module.
 pub const BILLOW_MAX_OCTAVE: i32 = 30;
@@ -51,7 +60,10 @@ pub const BILLOW_MAX_OCTAVE: i32 = 30;
 /// [`Perlin`](../perlin/struct.Perlin.html) except this noise module modifies
 /// each octave with an absolute-value function.  See the documentation of
 /// `Perlin` for more information.
-#[derive(Clone)]
+///
+/// To guarantee that the output value is within the range -1.0 to +1.0, call
+/// the [`set_normalize()`](struct.Billow.html#method.set_normalize) method.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Billow {
     frequency: f64,
     lacunarity: f64,
@@ -59,6 +71,9 @@ pub struct Billow {
     octave_count: i32,
     persistence: f64,
     seed: i32,
+    bias: f64,
+    normalize: bool,
+    dimensions: Dimensions,
 }
 
 impl Default for Billow {
@@ -71,6 +86,9 @@ impl Default for Billow {
             octave_count: DEFAULT_BILLOW_OCTAVE_COUNT,
             persistence: DEFAULT_BILLOW_PERSISTENCE,
             seed: DEFAULT_BILLOW_SEED,
+            bias: DEFAULT_BILLOW_BIAS,
+            normalize: false,
+            dimensions: DEFAULT_BILLOW_DIMENSIONS,
         }
     }
 }
@@ -120,6 +138,22 @@ impl Billow {
         self.seed
     }
 
+    /// Returns the bias added to the sum of the octaves.
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
+    /// Determines if the output value is normalized to the range -1.0 to
+    /// +1.0.
+    pub fn is_normalized(&self) -> bool {
+        self.normalize
+    }
+
+    /// Returns the number of dimensions of the coherent noise.
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
     /// Sets the frequency of the first octave.
     pub fn set_frequency(&mut self, frequency: f64) {
         self.frequency = frequency;
@@ -171,9 +205,80 @@ impl Billow {
     }
 
     /// Sets the seed value used by the billowy-noise function.
+    ///
+    /// Every `i32` is a valid seed.  Each octave uses the seed value plus the
+    /// index of the octave, wrapping around on overflow, with the sign bit
+    /// cleared, as in [`RidgedMulti`](../ridged_multi/struct.RidgedMulti.html).
+    /// Seeds that differ only in their sign bit therefore generate the same
+    /// noise.
     pub fn set_seed(&mut self, seed: i32) {
         self.seed = seed;
     }
+
+    /// Sets the bias added to the sum of the octaves.
+    ///
+    /// Each octave of billowy noise is an absolute value mapped back to the
+    /// range -1.0 to +1.0, so its mean is below zero.  The bias shifts the sum
+    /// of the octaves to compensate.  It is added once, after the octaves are
+    /// summed, so it is not scaled by the persistence: the same bias centers
+    /// the output less well as the persistence or the number of octaves grows
+    /// and the amplitude of the sum grows with them.  When normalization is
+    /// enabled the bias is added after dividing by the sum of the amplitudes,
+    /// so it shifts the normalized output by the same amount.
+    ///
+    /// By default, the bias is
+    /// [`DEFAULT_BILLOW_BIAS`](constant.DEFAULT_BILLOW_BIAS.html), matching
+    /// the original libnoise.
+    pub fn set_bias(&mut self, bias: f64) {
+        self.bias = bias;
+    }
+
+    /// Enables or disables normalizing the output value to the range -1.0 to
+    /// +1.0.
+    ///
+    /// When enabled, the sum of the octaves is divided by the sum of the
+    /// amplitudes of the octaves, which is
+    /// `(1 - persistence^octave_count) / (1 - persistence)`, then the
+    /// [bias](struct.Billow.html#method.set_bias) is added and the result is
+    /// clamped to the range -1.0 to +1.0, which guarantees it.  The clamp is
+    /// needed because the coherent noise of a single octave can slightly
+    /// exceed that range, and the bias shifts it.
+    ///
+    /// The octaves rarely all reach their extremes at the same input value, so
+    /// normalized output values use less of the range than it allows, reducing
+    /// the contrast compared with the default output.
+    ///
+    /// By default, normalization is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Billow, Module};
+    ///
+    /// let mut billow = Billow::new();
+    /// billow.set_persistence(0.9);
+    /// billow.set_normalize(true);
+    /// for i in 0..10000 {
+    ///     let value = billow.get_value(i as f64 * 0.0173, i as f64 * 0.0071, 0.5);
+    ///     assert!(value >= -1.0 && value <= 1.0);
+    /// }
+    /// ```
+    pub fn set_normalize(&mut self, normalize: bool) {
+        self.normalize = normalize;
+    }
+
+    /// Sets the number of dimensions of the coherent noise.
+    ///
+    /// With [`Dimensions::Dim2`](../noisegen/enum.Dimensions.html), the `z`
+    /// coordinate of the input value is ignored, and each octave is
+    /// two-dimensional coherent noise, which is faster to calculate.  This
+    /// changes the output values, not just the speed; see
+    /// [`Dimensions`](../noisegen/enum.Dimensions.html).
+    ///
+    /// By default, the coherent noise is three-dimensional.
+    pub fn set_dimensions(&mut self, dimensions: Dimensions) {
+        self.dimensions = dimensions;
+    }
 }
 
 impl Module for Billow {
@@ -194,8 +299,11 @@ impl Module for Billow {
 
             // Get the coherent-noise value from the input value and add it to
             // the final result.
-            let seed = self.seed + cur_octave;
-            let signal = gradient_coherent_noise3d(nx, ny, nz, seed, self.quality);
+            let seed = self.seed.wrapping_add(cur_octave) & 0x7fffffff;
+            let signal = match self.dimensions {
+                Dimensions::Dim2 => gradient_coherent_noise2d(nx, ny, seed, self.quality),
+                Dimensions::Dim3 => gradient_coherent_noise3d(nx, ny, nz, seed, self.quality),
+            };
             let signal = 2.0 * signal.abs() - 1.0;
             value += signal * cur_persistence;
 
@@ -205,8 +313,40 @@ impl Module for Billow {
             z *= self.lacunarity;
             cur_persistence *= self.persistence;
         }
-        value += 0.5;
 
-        value
+        if self.normalize {
+            let amplitude_sum = amplitude_sum(self.persistence, self.octave_count, 0.0);
+            (value / amplitude_sum + self.bias).clamp(-1.0, 1.0)
+        } else {
+            value + self.bias
+        }
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Billow");
+        hasher.write_f64(self.frequency);
+        hasher.write_f64(self.lacunarity);
+        hasher.write_u64(self.quality as u64);
+        hasher.write_i32(self.octave_count);
+        hasher.write_f64(self.persistence);
+        hasher.write_i32(self.seed);
+        hasher.write_f64(self.bias);
+        hasher.write_bool(self.normalize);
+        hasher.write_u64(self.dimensions as u64);
+        hasher.finish()
+    }
+
+    fn as_seeded_mut(&mut self) -> Option<&mut dyn Seeded> {
+        Some(self)
+    }
+}
+
+impl Seeded for Billow {
+    fn seed(&self) -> i32 {
+        Billow::seed(self)
+    }
+
+    fn set_seed(&mut self, seed: i32) {
+        Billow::set_seed(self, seed)
     }
 }
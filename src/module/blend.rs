@@ -14,20 +14,45 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Interp, Module};
 use util::linear_interp;
 
+/// Default lower bound of the control range for the
+/// [`Blend`](struct.Blend.html) noise module.
+pub const DEFAULT_BLEND_CONTROL_LOWER_BOUND: f64 = -1.0;
+
+/// Default upper bound of the control range for the
+/// [`Blend`](struct.Blend.html) noise module.
+pub const DEFAULT_BLEND_CONTROL_UPPER_BOUND: f64 = 1.0;
+
+/// Default easing curve for the [`Blend`](struct.Blend.html) noise module.
+pub const DEFAULT_BLEND_INTERP: Interp = Interp::Linear;
+
 /// Noise module that outputs a weighted blend of the output values from two
 /// source modules given the output value supplied by a control module.
 ///
 /// This noise module uses linear interpolation to perform the blending
-/// operation.
+/// operation.  The blending weight may first be mapped onto an S-curve by
+/// calling the [`set_interp()`](struct.Blend.html#method.set_interp) method.
+///
+/// The output value from the control module is mapped from the *control range*
+/// onto the 0.0 to 1.0 range to obtain the blending weight.  By default the
+/// control range is -1.0 to +1.0; to use a control module with a different
+/// output range, call the
+/// [`set_control_range()`](struct.Blend.html#method.set_control_range) method.
+/// As in the original libnoise, the weight is not clamped with the default
+/// control range and easing curve, so control values outside the range
+/// extrapolate beyond the output values from the source modules.
 ///
 /// This noise module requires three source modules.
+#[derive(Debug, PartialEq)]
 pub struct Blend<M1: Module, M2: Module, MC: Module> {
     module1: M1,
     module2: M2,
     mcontrol: MC,
+    control_lower_bound: f64,
+    control_upper_bound: f64,
+    interp: Interp,
 }
 
 impl<M1: Module, M2: Module, MC: Module> Blend<M1, M2, MC> {
@@ -37,6 +62,9 @@ impl<M1: Module, M2: Module, MC: Module> Blend<M1, M2, MC> {
             module1: module1,
             module2: module2,
             mcontrol: control,
+            control_lower_bound: DEFAULT_BLEND_CONTROL_LOWER_BOUND,
+            control_upper_bound: DEFAULT_BLEND_CONTROL_UPPER_BOUND,
+            interp: DEFAULT_BLEND_INTERP,
         }
     }
 
@@ -63,8 +91,9 @@ impl<M1: Module, M2: Module, MC: Module> Blend<M1, M2, MC> {
     /// Returns a reference to the control module.
     ///
     /// The control module determines the weight of the blending operation.
-    /// Negative values weigh the blend towards the output value from `module1`.
-    /// Positive values weigh the blend towards the output value from `module2`.
+    /// Values towards the lower bound of the control range weigh the blend
+    /// towards the output value from `module1`, and values towards the upper
+    /// bound weigh it towards the output value from `module2`.
     pub fn control_module(&self) -> &MC {
         &self.mcontrol
     }
@@ -72,12 +101,34 @@ impl<M1: Module, M2: Module, MC: Module> Blend<M1, M2, MC> {
     /// Returns a reference to the control module.
     ///
     /// The control module determines the weight of the blending operation.
-    /// Negative values weigh the blend towards the output value from `module1`.
-    /// Positive values weigh the blend towards the output value from `module2`.
+    /// Values towards the lower bound of the control range weigh the blend
+    /// towards the output value from `module1`, and values towards the upper
+    /// bound weigh it towards the output value from `module2`.
     pub fn control_module_mut(&mut self) -> &mut MC {
         &mut self.mcontrol
     }
 
+    /// Returns the lower bound of the control range.
+    ///
+    /// A control value at or below the lower bound outputs the value from
+    /// `module1`.
+    pub fn control_lower_bound(&self) -> f64 {
+        self.control_lower_bound
+    }
+
+    /// Returns the upper bound of the control range.
+    ///
+    /// A control value at or above the upper bound outputs the value from
+    /// `module2`.
+    pub fn control_upper_bound(&self) -> f64 {
+        self.control_upper_bound
+    }
+
+    /// Returns the easing curve applied to the blending weight.
+    pub fn interp(&self) -> Interp {
+        self.interp
+    }
+
     /// Set the first module to be used.
     pub fn set_module1(&mut self, module1: M1) {
         self.module1 = module1;
@@ -91,19 +142,97 @@ impl<M1: Module, M2: Module, MC: Module> Blend<M1, M2, MC> {
     /// Sets the control module.
     ///
     /// The control module determines the weight of the blending operation.
-    /// Negative values weigh the blend towards the output value from `module1`.
-    /// Positive values weigh the blend towards the output value from `module2`.
+    /// Values towards the lower bound of the control range weigh the blend
+    /// towards the output value from `module1`, and values towards the upper
+    /// bound weigh it towards the output value from `module2`.
     pub fn set_control_module(&mut self, control: MC) {
         self.mcontrol = control;
     }
+
+    /// Sets the lower and upper bounds of the control range.
+    ///
+    /// The blending weight is computed as `(control - lower_bound) /
+    /// (upper_bound - lower_bound)`, clamped to the 0.0 to 1.0 range.  A control
+    /// value at or below the lower bound outputs the value from `module1`, and a
+    /// control value at or above the upper bound outputs the value from
+    /// `module2`.  To keep the output of the original libnoise, the weight is
+    /// not clamped if both the control range and the easing curve are the
+    /// defaults.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either bound is NaN or infinite, or if the given lower bound
+    /// is greater than or equal to the given upper bound.
+    pub fn set_control_range(&mut self, lower_bound: f64, upper_bound: f64) {
+        if !lower_bound.is_finite() || !upper_bound.is_finite() {
+            panic!("Control range bounds must be finite!");
+        }
+        if lower_bound >= upper_bound {
+            panic!("Lower bound is not smaller than upper bound!");
+        }
+        self.control_lower_bound = lower_bound;
+        self.control_upper_bound = upper_bound;
+    }
+
+    /// Sets the easing curve applied to the blending weight.
+    ///
+    /// The blending weight is mapped onto this curve before interpolating
+    /// between the output values from the two source modules.  By default the
+    /// weight is used as is, i.e. [`Interp::Linear`](enum.Interp.html).
+    pub fn set_interp(&mut self, interp: Interp) {
+        self.interp = interp;
+    }
 }
 
 impl<M1: Module, M2: Module, MC: Module> Module for Blend<M1, M2, MC> {
     fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
         let v0 = self.module1.get_value(x, y, z);
         let v1 = self.module2.get_value(x, y, z);
-        let alpha = (self.mcontrol.get_value(x, y, z) + 1.0) / 2.0;
-        linear_interp(v0, v1, alpha)
+        let control_value = self.mcontrol.get_value(x, y, z);
+        let alpha = (control_value - self.control_lower_bound)
+            / (self.control_upper_bound - self.control_lower_bound);
+        let alpha = if self.control_lower_bound == DEFAULT_BLEND_CONTROL_LOWER_BOUND
+                       && self.control_upper_bound == DEFAULT_BLEND_CONTROL_UPPER_BOUND
+                       && self.interp == Interp::Linear {
+            alpha
+        } else {
+            alpha.clamp(0.0, 1.0)
+        };
+        linear_interp(v0, v1, self.interp.ease(alpha))
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Blend");
+        hasher.write_u64(self.module1.config_hash());
+        hasher.write_u64(self.module2.config_hash());
+        hasher.write_u64(self.mcontrol.config_hash());
+        hasher.write_f64(self.control_lower_bound);
+        hasher.write_f64(self.control_upper_bound);
+        hasher.write_u64(self.interp as u64);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        3
+    }
+
+    /// Returns the first and second source modules, followed by the control
+    /// module.
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module1 as &dyn Module, &self.module2, &self.mcontrol]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module1),
+            1 => Some(&self.module2),
+            2 => Some(&self.mcontrol),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module1 as &mut dyn Module, &mut self.module2, &mut self.mcontrol]
     }
 }
 
@@ -115,6 +244,9 @@ impl<M1: Module + Clone,
             module1: self.module1.clone(),
             module2: self.module2.clone(),
             mcontrol: self.mcontrol.clone(),
+            control_lower_bound: self.control_lower_bound,
+            control_upper_bound: self.control_upper_bound,
+            interp: self.interp,
         }
     }
 }
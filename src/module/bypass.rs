@@ -0,0 +1,153 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+
+/// Noise module that can switch a modifier noise module on and off.
+///
+/// This noise module wraps a modifier, such as a
+/// [`Turbulence`](struct.Turbulence.html) or [`Curve`](struct.Curve.html) noise
+/// module.  By default it outputs the value from the modifier unchanged.  When
+/// bypassed, it instead outputs the value from the first source module of the
+/// modifier, as though the modifier had been removed from the graph.  This
+/// allows the effect of a modifier to be compared without rebuilding the
+/// graph.  To bypass the modifier, call the
+/// [`set_bypass()`](struct.Bypass.html#method.set_bypass) method.
+///
+/// For modifiers with more than one source module, such as the
+/// [`Select`](struct.Select.html) noise module, the first source module
+/// reported by [`Module::source()`](trait.Module.html#method.source) is used.
+///
+/// This noise module requires one source module, which must itself have at
+/// least one source module.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Bypass, Invert, Module, Perlin};
+///
+/// let mut bypass = Bypass::new(Invert::new(Perlin::new()));
+/// let value = Perlin::new().get_value(0.5, 0.25, 0.75);
+/// assert_eq!(bypass.get_value(0.5, 0.25, 0.75), -value);
+///
+/// bypass.set_bypass(true);
+/// assert_eq!(bypass.get_value(0.5, 0.25, 0.75), value);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Bypass<M: Module> {
+    module: M,
+    bypass: bool,
+}
+
+impl<M: Module> Bypass<M> {
+    /// Create a new `Bypass` noise module around the specified modifier, which
+    /// is initially not bypassed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the specified module has no source modules.
+    pub fn new(module: M) -> Bypass<M> {
+        check_module(&module);
+        Bypass {
+            module: module,
+            bypass: false,
+        }
+    }
+
+    /// Returns a reference to the modifier used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the modifier used.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Returns true if the modifier is bypassed.
+    pub fn is_bypassed(&self) -> bool {
+        self.bypass
+    }
+
+    /// Set the modifier to be used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the specified module has no source modules.
+    pub fn set_module(&mut self, module: M) {
+        check_module(&module);
+        self.module = module;
+    }
+
+    /// Enables or disables bypassing the modifier.
+    ///
+    /// If `true`, the output value from the first source module of the
+    /// modifier is returned instead of the output value from the modifier.
+    pub fn set_bypass(&mut self, bypass: bool) {
+        self.bypass = bypass;
+    }
+}
+
+fn check_module<M: Module>(module: &M) {
+    if module.source_count() == 0 {
+        panic!("Bypassed module must have at least one source module!");
+    }
+}
+
+impl<M: Module> Module for Bypass<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        if self.bypass {
+            self.module.source(0).unwrap().get_value(x, y, z)
+        } else {
+            self.module.get_value(x, y, z)
+        }
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Bypass");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_bool(self.bypass);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone> Clone for Bypass<M> {
+    fn clone(&self) -> Bypass<M> {
+        Bypass {
+            module: self.module.clone(),
+            bypass: self.bypass,
+        }
+    }
+}
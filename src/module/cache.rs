@@ -14,7 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 use std::cell::Cell;
 
 /// Noise module that caches the last output value generated by a source module.
@@ -41,6 +41,7 @@ use std::cell::Cell;
 /// in which it is included.
 ///
 /// This noise module requires one source module.
+#[derive(Debug)]
 pub struct Cache<M: Module> {
     module: M,
     cached_value: Cell<f64>,
@@ -102,6 +103,33 @@ impl<M: Module> Module for Cache<M> {
             ret
         }
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Cache");
+        hasher.write_u64(self.module.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    /// This operation invalidates the cache.
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        self.is_cached.set(false);
+        vec![&mut self.module as &mut dyn Module]
+    }
 }
 
 impl<M: Module + Clone> Clone for Cache<M> {
@@ -116,3 +144,11 @@ impl<M: Module + Clone> Clone for Cache<M> {
         }
     }
 }
+
+impl<M: Module + PartialEq> PartialEq for Cache<M> {
+    /// Two `Cache` noise modules are equal if their source modules are equal;
+    /// the cached value itself is not compared.
+    fn eq(&self, other: &Cache<M>) -> bool {
+        self.module == other.module
+    }
+}
@@ -14,8 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
-use noisegen::{make_i32_range};
+use module::{ConfigHasher, Module};
 
 /// Noise module that outputs a checkerboard pattern.
 ///
@@ -25,19 +24,36 @@ use noisegen::{make_i32_range};
 /// This noise module is not really useful by itself, but it is often used for
 /// debugging purposes.
 ///
+/// The blocks stay unit-sized arbitrarily far from the origin, until the
+/// coordinates become too large for an `f64` to represent fractional values.
+/// Unlike the coherent-noise generators, the coordinates are not wrapped by
+/// [`make_i32_range()`](../noisegen/fn.make_i32_range.html), which would halve
+/// the size of the blocks beyond 2<sup>30</sup>.
+///
 /// This noise module does not require any source modules.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Checkerboard;
 
+/// Determines if the unit-sized block containing the given coordinate has an
+/// odd integer coordinate.
+///
+/// The parity is taken from the floored coordinate as an `f64`, so it is exact
+/// for every finite coordinate.  Non-finite coordinates count as even.
+fn is_odd(v: f64) -> bool {
+    let rem = v.floor() % 2.0;
+    rem == 1.0 || rem == -1.0
+}
+
 impl Module for Checkerboard {
     fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
-        let ix = make_i32_range(x).floor() as i32;
-        let iy = make_i32_range(y).floor() as i32;
-        let iz = make_i32_range(z).floor() as i32;
-        if (ix & 1 ^ iy & 1 ^ iz & 1) != 0 {
+        if is_odd(x) ^ is_odd(y) ^ is_odd(z) {
             -1.0
         } else {
             1.0
         }
     }
+
+    fn config_hash(&self) -> u64 {
+        ConfigHasher::new("Checkerboard").finish()
+    }
 }
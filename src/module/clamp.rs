@@ -14,7 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Default lower bound of the clamping range for the
 /// [`Clamp`](struct.Clamp.html) noise module.
@@ -40,6 +40,7 @@ pub const DEFAULT_CLAMP_UPPER_BOUND: f64 = 1.0;
 /// [`set_bounds()`](struct.Clamp.html#method.set_bounds) method.
 ///
 /// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
 pub struct Clamp<M: Module> {
     module: M,
     lower_bound: f64,
@@ -121,6 +122,33 @@ impl<M: Module> Module for Clamp<M> {
             value
         }
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Clamp");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_f64(self.lower_bound);
+        hasher.write_f64(self.upper_bound);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
 }
 
 impl<M: Module + Clone> Clone for Clamp<M> {
@@ -0,0 +1,175 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use std::f64;
+
+/// Noise module that clamps the coordinates of the input value to a box before
+/// returning the output value from a source module.
+///
+/// Each of the (`x`, `y`, `z`) coordinates of the input value is clamped to a
+/// range of values before it is passed to the source module.  Input values
+/// outside of the resulting box therefore output the value at the nearest point
+/// on the edge of the box, extruding the edge values outwards.  To set the
+/// clamping range of the individual `x`, `y`, or `z` coordinates, call the
+/// [`set_x_bounds()`](struct.ClampPoint.html#method.set_x_bounds),
+/// [`set_y_bounds()`](struct.ClampPoint.html#method.set_y_bounds) or
+/// [`set_z_bounds()`](struct.ClampPoint.html#method.set_z_bounds) methods,
+/// respectively.  By default the coordinates are unbounded.
+///
+/// Unlike the [`Clamp`](struct.Clamp.html) noise module, which clamps the
+/// output value from the source module, this noise module clamps the input
+/// value.
+///
+/// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
+pub struct ClampPoint<M: Module> {
+    module: M,
+    /// Lower and upper bounds of the `x`, `y` and `z` coordinates.
+    bounds: [(f64, f64); 3],
+}
+
+impl<M: Module> ClampPoint<M> {
+    /// Create a new `ClampPoint` noise module around the specified module,
+    /// using default parameters.
+    pub fn new(module: M) -> ClampPoint<M> {
+        ClampPoint {
+            module: module,
+            bounds: [(f64::NEG_INFINITY, f64::INFINITY); 3],
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Returns the lower and upper bounds of the `x` coordinate.
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.bounds[0]
+    }
+
+    /// Returns the lower and upper bounds of the `y` coordinate.
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.bounds[1]
+    }
+
+    /// Returns the lower and upper bounds of the `z` coordinate.
+    pub fn z_bounds(&self) -> (f64, f64) {
+        self.bounds[2]
+    }
+
+    /// Set the source module to be used.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+    }
+
+    /// Sets the lower and upper bounds of the `x` coordinate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given lower bound is greater than the given upper bound.
+    pub fn set_x_bounds(&mut self, lower_bound: f64, upper_bound: f64) {
+        self.set_bounds(0, lower_bound, upper_bound);
+    }
+
+    /// Sets the lower and upper bounds of the `y` coordinate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given lower bound is greater than the given upper bound.
+    pub fn set_y_bounds(&mut self, lower_bound: f64, upper_bound: f64) {
+        self.set_bounds(1, lower_bound, upper_bound);
+    }
+
+    /// Sets the lower and upper bounds of the `z` coordinate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given lower bound is greater than the given upper bound.
+    pub fn set_z_bounds(&mut self, lower_bound: f64, upper_bound: f64) {
+        self.set_bounds(2, lower_bound, upper_bound);
+    }
+
+    fn set_bounds(&mut self, axis: usize, lower_bound: f64, upper_bound: f64) {
+        if lower_bound > upper_bound {
+            panic!("Lower bound is larger than upper bound!");
+        }
+        self.bounds[axis] = (lower_bound, upper_bound);
+    }
+}
+
+/// Clamps a coordinate to the given bounds.
+fn clamp_coord(value: f64, bounds: (f64, f64)) -> f64 {
+    if value < bounds.0 {
+        bounds.0
+    } else if value > bounds.1 {
+        bounds.1
+    } else {
+        value
+    }
+}
+
+impl<M: Module> Module for ClampPoint<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.module.get_value(clamp_coord(x, self.bounds[0]),
+                              clamp_coord(y, self.bounds[1]),
+                              clamp_coord(z, self.bounds[2]))
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("ClampPoint");
+        hasher.write_u64(self.module.config_hash());
+        for &(lower_bound, upper_bound) in &self.bounds {
+            hasher.write_f64(lower_bound);
+            hasher.write_f64(upper_bound);
+        }
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone> Clone for ClampPoint<M> {
+    fn clone(&self) -> ClampPoint<M> {
+        ClampPoint {
+            module: self.module.clone(),
+            bounds: self.bounds,
+        }
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use std::fmt;
+
+/// Noise module that combines the output values from two source modules with
+/// an arbitrary function.
+///
+/// The [`get_value()`](struct.Combine2.html#method.get_value) method passes the
+/// output values from the two source modules to the combining function and
+/// outputs its result.  This allows one-off combinations of two noise modules
+/// to be written inline, without defining a new noise module.
+///
+/// ```
+/// use noise::module::{Combine2, Module, Perlin, Voronoi};
+///
+/// let combined = Combine2::new(Perlin::new(), Voronoi::new(), |a, b| a.max(b) - 0.1);
+/// let value = combined.get_value(0.5, 1.25, 0.75);
+/// ```
+///
+/// The function cannot be hashed, so the
+/// [`config_hash()`](trait.Module.html#method.config_hash) method only hashes
+/// the source modules.  Noise modules that differ only in their functions
+/// hash the same.
+///
+/// This noise module requires two source modules.
+pub struct Combine2<M1: Module, M2: Module, F: Fn(f64, f64) -> f64> {
+    module1: M1,
+    module2: M2,
+    f: F,
+}
+
+impl<M1: Module, M2: Module, F: Fn(f64, f64) -> f64> Combine2<M1, M2, F> {
+    /// Create a new `Combine2` noise module around the specified modules and
+    /// combining function.
+    pub fn new(module1: M1, module2: M2, f: F) -> Combine2<M1, M2, F> {
+        Combine2 {
+            module1: module1,
+            module2: module2,
+            f: f,
+        }
+    }
+
+    /// Returns a reference to the first source module used.
+    pub fn module1(&self) -> &M1 {
+        &self.module1
+    }
+
+    /// Returns a mutable reference to the first source module used.
+    pub fn module1_mut(&mut self) -> &mut M1 {
+        &mut self.module1
+    }
+
+    /// Returns a reference to the second source module used.
+    pub fn module2(&self) -> &M2 {
+        &self.module2
+    }
+
+    /// Returns a mutable reference to the second source module used.
+    pub fn module2_mut(&mut self) -> &mut M2 {
+        &mut self.module2
+    }
+
+    /// Set the first source module to be used.
+    pub fn set_module1(&mut self, module: M1) {
+        self.module1 = module;
+    }
+
+    /// Set the second source module to be used.
+    pub fn set_module2(&mut self, module: M2) {
+        self.module2 = module;
+    }
+}
+
+impl<M1: Module, M2: Module, F: Fn(f64, f64) -> f64> Module for Combine2<M1, M2, F> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let value1 = self.module1.get_value(x, y, z);
+        let value2 = self.module2.get_value(x, y, z);
+        (self.f)(value1, value2)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Combine2");
+        hasher.write_u64(self.module1.config_hash());
+        hasher.write_u64(self.module2.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        2
+    }
+
+    /// Returns the first and second source modules, in that order.
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module1 as &dyn Module, &self.module2]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module1),
+            1 => Some(&self.module2),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module1 as &mut dyn Module, &mut self.module2]
+    }
+}
+
+impl<M1: Module + Clone,
+     M2: Module + Clone,
+     F: Fn(f64, f64) -> f64 + Clone> Clone for Combine2<M1, M2, F> {
+    fn clone(&self) -> Combine2<M1, M2, F> {
+        Combine2 {
+            module1: self.module1.clone(),
+            module2: self.module2.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<M1: Module + fmt::Debug,
+     M2: Module + fmt::Debug,
+     F: Fn(f64, f64) -> f64> fmt::Debug for Combine2<M1, M2, F> {
+    // The combining function cannot be printed, so it is left out.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Combine2")
+            .field("module1", &self.module1)
+            .field("module2", &self.module2)
+            .finish()
+    }
+}
+
+/// Noise module that maps the output value from a source module through an
+/// arbitrary function.
+///
+/// The [`get_value()`](struct.Map1.html#method.get_value) method passes the
+/// output value from the source module to the mapping function and outputs its
+/// result.  This allows one-off modifications of a noise module to be written
+/// inline, without defining a new noise module.
+///
+/// ```
+/// use noise::module::{Map1, Module, Perlin};
+///
+/// let mapped = Map1::new(Perlin::new(), |v| v.tanh());
+/// let value = mapped.get_value(0.5, 1.25, 0.75);
+/// ```
+///
+/// The function cannot be hashed, so the
+/// [`config_hash()`](trait.Module.html#method.config_hash) method only hashes
+/// the source modules.  Noise modules that differ only in their functions
+/// hash the same.
+///
+/// This noise module requires one source module.
+pub struct Map1<M: Module, F: Fn(f64) -> f64> {
+    module: M,
+    f: F,
+}
+
+impl<M: Module, F: Fn(f64) -> f64> Map1<M, F> {
+    /// Create a new `Map1` noise module around the specified module and mapping
+    /// function.
+    pub fn new(module: M, f: F) -> Map1<M, F> {
+        Map1 {
+            module: module,
+            f: f,
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Set the source module to be used.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+    }
+}
+
+impl<M: Module, F: Fn(f64) -> f64> Module for Map1<M, F> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        (self.f)(self.module.get_value(x, y, z))
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Map1");
+        hasher.write_u64(self.module.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone, F: Fn(f64) -> f64 + Clone> Clone for Map1<M, F> {
+    fn clone(&self) -> Map1<M, F> {
+        Map1 {
+            module: self.module.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<M: Module + fmt::Debug, F: Fn(f64) -> f64> fmt::Debug for Map1<M, F> {
+    // The mapping function cannot be printed, so it is left out.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Map1")
+            .field("module", &self.module)
+            .finish()
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::Module;
+
+/// Hasher used to calculate the configuration hash of a noise module.
+///
+/// See [`Module::config_hash()`](trait.Module.html#method.config_hash).  This
+/// hasher implements 64-bit FNV-1a, writing every value in little-endian byte
+/// order, so that the hashes it produces are the same on every platform and
+/// with every version of Rust.  Floating-point values are hashed by their bit
+/// patterns, so `0.0` and `-0.0` hash differently, and a NaN always hashes the
+/// same as another NaN with the same bit pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfigHasher {
+    state: u64,
+}
+
+impl ConfigHasher {
+    /// Create a new `ConfigHasher`, starting with the name of the type of the
+    /// noise module being hashed.
+    ///
+    /// Hashing the name first makes noise modules of different types with the
+    /// same parameters hash differently.
+    pub fn new(name: &str) -> ConfigHasher {
+        let mut hasher = ConfigHasher { state: 0xcbf29ce484222325 };
+        hasher.write_bytes(name.as_bytes());
+        hasher
+    }
+
+    /// Writes the given bytes into the hash.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u64;
+            self.state = self.state.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    /// Writes a `u64` into the hash.
+    pub fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    /// Writes an `i32` into the hash.
+    pub fn write_i32(&mut self, value: i32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    /// Writes an `f64` into the hash, by its bit pattern.
+    pub fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    /// Writes a `bool` into the hash.
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_bytes(&[value as u8]);
+    }
+
+    /// Returns the hash of the values written so far.
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+/// Determines if two noise module graphs have the same structure and
+/// parameters, without sampling them.
+///
+/// The two graphs are walked together through their
+/// [`sources()`](trait.Module.html#method.sources).  They are structurally
+/// equal if, at every level, the two noise modules have the same
+/// [configuration hash](trait.Module.html#method.config_hash) and the same
+/// number of source modules, and each pair of source modules is structurally
+/// equal in turn.  Whether the source modules are owned, boxed or shared
+/// through an `Arc` makes no difference, so a graph optimizer can use this to
+/// find identical subgraphs and replace them with a single shared one.
+///
+/// Structural equality is sufficient but not necessary for the two graphs to
+/// output the same values: graphs built differently, such as `a + b` and
+/// `b + a`, or `a * 2` and `a + a`, output the same values, or values that
+/// differ only by floating-point rounding, but are not structurally equal.
+/// It is only sufficient for noise modules whose configuration hashes cover
+/// everything that determines their output values.  This is not the case for
+/// noise modules built from closures, such as
+/// [`FnModule`](struct.FnModule.html), which hash the same whatever their
+/// closures do, nor for noise modules outside this library that do not
+/// override `config_hash()`.  As with any 64-bit hash, unequal parameters
+/// could also collide, though this is vanishingly unlikely.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{structurally_eq, Abs, Add, BoxedModule, Perlin};
+///
+/// let a = Add::new(Perlin::new(), Abs::new(Perlin::new()));
+/// let abs: BoxedModule = Box::new(Abs::new(Perlin::new()));
+/// let b = Add::new(Perlin::new(), abs);
+/// assert!(structurally_eq(&a, &b));
+///
+/// let mut perlin = Perlin::new();
+/// perlin.set_frequency(2.0);
+/// let c = Add::new(perlin, Abs::new(Perlin::new()));
+/// assert!(!structurally_eq(&a, &c));
+/// ```
+pub fn structurally_eq(a: &dyn Module, b: &dyn Module) -> bool {
+    if a.config_hash() != b.config_hash() || a.source_count() != b.source_count() {
+        return false;
+    }
+    a.sources().into_iter().zip(b.sources()).all(|(a, b)| structurally_eq(a, b))
+}
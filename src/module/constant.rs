@@ -14,7 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Default constant value for the [`Constant`](struct.Constant.html) noise
 /// module.
@@ -26,7 +26,7 @@ pub const DEFAULT_CONST_VALUE: f64 = 0.0;
 /// module for other noise modules.
 ///
 /// This noise module does not require any source modules.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Constant {
     val: f64,
 }
@@ -61,5 +61,15 @@ impl Module for Constant {
     fn get_value(&self, _x: f64, _y: f64, _z: f64) -> f64 {
         self.val
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Constant");
+        hasher.write_f64(self.val);
+        hasher.finish()
+    }
+
+    fn max_gradient_estimate(&self) -> Option<f64> {
+        Some(0.0)
+    }
 }
 
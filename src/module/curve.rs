@@ -14,13 +14,19 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
+use noisegen::value_noise3d;
 use util::{clamp, cubic_interp};
 
+/// Number of straight line segments used to approximate each span of a
+/// [`Curve`](struct.Curve.html) between two control points when measuring its
+/// arc length.
+const ARCLENGTH_SUBDIVISIONS: usize = 64;
+
 /// This structure defines a control point.
 ///
 /// Control points are used for defining splines.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct ControlPoint {
     pub input_value: f64,
     pub output_value: f64,
@@ -44,10 +50,22 @@ pub struct ControlPoint {
 /// points can have the same input value.  There is no limit to the number of
 /// control points that can be added to the curve.
 ///
+/// The curve can also be sampled by arc length instead of by input value, by
+/// calling the
+/// [`build_arclength_table()`](struct.Curve.html#method.build_arclength_table)
+/// method once all the control points are added, then the
+/// [`sample_by_arclength()`](struct.Curve.html#method.sample_by_arclength)
+/// method.
+///
 /// This noise module requires one source module.
+#[derive(Debug)]
 pub struct Curve<M: Module> {
     module: M,
     control_points: Vec<ControlPoint>,
+    /// Input values along the curve paired with the arc length of the curve up
+    /// to each of them, or `None` if the table has not been built since the
+    /// control points last changed.
+    arclength_table: Option<Vec<(f64, f64)>>,
 }
 
 impl<M: Module> Curve<M> {
@@ -56,6 +74,7 @@ impl<M: Module> Curve<M> {
         Curve {
             module: module,
             control_points: Vec::new(),
+            arclength_table: None,
         }
     }
 
@@ -98,32 +117,81 @@ impl<M: Module> Curve<M> {
                     input_value: input_value,
                     output_value: output_value
                 });
+                self.arclength_table = None;
             }
         }
     }
 
+    /// Creates a number of random control points.  The previous control points
+    /// on the curve are deleted.
+    ///
+    /// The input values of the control points range from -1 to +1; the first
+    /// and last input values are always -1 and +1, and the remaining input
+    /// values are each placed at a random offset from equally-spaced positions,
+    /// at most half the distance to the neighbouring positions, so that no two
+    /// control points have the same input value.  The output values are random
+    /// values ranging from -1 to +1.  The control points are determined by
+    /// `seed`; the same seed always produces the same control points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is less than 4.
+    pub fn make_random_control_points(&mut self, count: i32, seed: i32) {
+        if count < 4 {
+            panic!("The number of control points must be greater than or equal to 4!");
+        }
+
+        self.control_points.clear();
+
+        let step = 2.0 / (count as f64 - 1.0);
+        for i in 0..count {
+            let input_value = if i == 0 {
+                -1.0
+            } else if i == count - 1 {
+                1.0
+            } else {
+                // Map the noise value onto the range 0.0 to 1.0 (exclusive) and
+                // use it to pick a position within the interval belonging to
+                // this control point.
+                let jitter = (1.0 - value_noise3d(i, 0, 0, seed)) / 2.0;
+                -1.0 + (i as f64 - 0.5 + jitter) * step
+            };
+            self.control_points.push(ControlPoint {
+                input_value: input_value,
+                output_value: value_noise3d(i, 1, 0, seed),
+            });
+        }
+        self.arclength_table = None;
+    }
+
     /// Deletes all the control points on the curve.
     pub fn clear_control_points(&mut self) {
         self.control_points.clear();
+        self.arclength_table = None;
     }
 
     /// Returns a slice of all the control points on the curve, in order.
     pub fn control_points(&self) -> &[ControlPoint] {
         &self.control_points
     }
-}
 
-impl<M: Module> Module for Curve<M> {
-    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+    /// Maps a value onto the curve.
+    ///
+    /// This performs the same mapping as
+    /// [`get_value()`](struct.Curve.html#method.get_value), but on the given
+    /// value instead of the output value from the source module, allowing the
+    /// curve to be used as a standalone spline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than four control points on the curve.
+    pub fn map_value(&self, source_value: f64) -> f64 {
         if self.control_points.len() < 4 {
             panic!("Fewer than 4 control points on curve!");
         }
 
-        // Get the output value from the source module.
-        let source_value = self.module.get_value(x, y, z);
-
         // Find the first element in the control point array that has an input value
-        // larger than the output value from the source module.
+        // larger than the value to map.
         let f = |x: &ControlPoint| x.input_value.partial_cmp(&source_value).unwrap();
         let idx_pos = match self.control_points.binary_search_by(f) {
             Ok(idx) => idx as isize + 1,
@@ -162,6 +230,143 @@ impl<M: Module> Module for Curve<M> {
             self.control_points[idx3].output_value,
             alpha)
     }
+
+    /// Maps a position along the curve, measured by arc length, onto the
+    /// output value of the curve at that position.
+    ///
+    /// The curve is treated as the graph of
+    /// [`map_value()`](struct.Curve.html#method.map_value) between the
+    /// smallest and largest input values of the control points, where the
+    /// length of each piece of the curve accounts for the change in both the
+    /// input value and the output value.  A `t` of 0 maps to the first control
+    /// point and a `t` of 1 maps to the last one; equal steps in `t` in between
+    /// cover equal lengths of the curve, so a point moving along the curve at a
+    /// constant rate of `t` moves at a constant speed.  Values of `t` outside
+    /// the range 0 to 1 are clamped to that range.
+    ///
+    /// The arc lengths are looked up in a table, which must be built by calling
+    /// the
+    /// [`build_arclength_table()`](struct.Curve.html#method.build_arclength_table)
+    /// method after the control points last changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `t` is NaN, or if the arc length table has not been built
+    /// since the control points last changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Constant, Curve};
+    ///
+    /// let mut curve = Curve::new(Constant::new());
+    /// for &(input_value, output_value) in &[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0)] {
+    ///     curve.add_control_point(input_value, output_value);
+    /// }
+    /// curve.build_arclength_table();
+    /// assert_eq!(curve.sample_by_arclength(0.0), 0.0);
+    /// assert_eq!(curve.sample_by_arclength(1.0), 0.0);
+    /// ```
+    pub fn sample_by_arclength(&self, t: f64) -> f64 {
+        if t.is_nan() {
+            panic!("Tried to sample curve at NaN arc length!");
+        }
+        let table = match self.arclength_table {
+            Some(ref table) => table,
+            None => panic!("Arc length table has not been built!"),
+        };
+
+        let target = t.clamp(0.0, 1.0) * table[table.len() - 1].1;
+
+        // Find the first entry at or beyond the target length, then interpolate
+        // linearly between it and the previous entry.
+        let idx = match table.binary_search_by(|x| x.1.partial_cmp(&target).unwrap()) {
+            Ok(idx) | Err(idx) => idx.clamp(1, table.len() - 1),
+        };
+        let (input0, length0) = table[idx - 1];
+        let (input1, length1) = table[idx];
+        let input_value = if length1 > length0 {
+            input0 + (input1 - input0) * (target - length0) / (length1 - length0)
+        } else {
+            input0
+        };
+        self.map_value(input_value)
+    }
+
+    /// Builds the table of arc lengths used by
+    /// [`sample_by_arclength()`](struct.Curve.html#method.sample_by_arclength)
+    /// for the current control points.
+    ///
+    /// Adding control points discards the table, so call this method once all
+    /// the control points are added.  Building the table evaluates the curve
+    /// at a number of points along each span between two control points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than four control points on the curve.
+    pub fn build_arclength_table(&mut self) {
+        if self.control_points.len() < 4 {
+            panic!("Fewer than 4 control points on curve!");
+        }
+
+        // Approximate each span between two control points with a number of
+        // straight line segments, adding up their lengths.
+        let first = self.control_points[0];
+        let mut table = vec![(first.input_value, 0.0)];
+        let (mut prev_input, mut prev_output) = (first.input_value, first.output_value);
+        let mut length = 0.0;
+        for span in self.control_points.windows(2) {
+            let (input0, input1) = (span[0].input_value, span[1].input_value);
+            for i in 1..ARCLENGTH_SUBDIVISIONS + 1 {
+                let input_value = if i == ARCLENGTH_SUBDIVISIONS {
+                    input1
+                } else {
+                    input0 + (input1 - input0) * i as f64 / ARCLENGTH_SUBDIVISIONS as f64
+                };
+                let output_value = self.map_value(input_value);
+                length += (input_value - prev_input).hypot(output_value - prev_output);
+                table.push((input_value, length));
+                prev_input = input_value;
+                prev_output = output_value;
+            }
+        }
+        self.arclength_table = Some(table);
+    }
+}
+
+impl<M: Module> Module for Curve<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.map_value(self.module.get_value(x, y, z))
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Curve");
+        hasher.write_u64(self.module.config_hash());
+        for control_point in &self.control_points {
+            hasher.write_f64(control_point.input_value);
+            hasher.write_f64(control_point.output_value);
+        }
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
 }
 
 impl<M: Module + Clone> Clone for Curve<M> {
@@ -169,6 +374,16 @@ impl<M: Module + Clone> Clone for Curve<M> {
         Curve {
             module: self.module.clone(),
             control_points: self.control_points.clone(),
+            arclength_table: self.arclength_table.clone(),
         }
     }
 }
+
+impl<M: Module + PartialEq> PartialEq for Curve<M> {
+    /// Compares the source module and the control points.  The arc length
+    /// table is derived from the control points, so whether it has been built
+    /// since they last changed does not affect equality.
+    fn eq(&self, other: &Curve<M>) -> bool {
+        self.module == other.module && self.control_points == other.control_points
+    }
+}
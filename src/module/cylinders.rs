@@ -14,12 +14,16 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Default frequency value for the [`Cylinders`](struct.Cylinders.html) noise
 /// module.
 pub const DEFAULT_CYLINDERS_FREQUENCY: f64 = 1.0;
 
+/// Default radial offset value for the [`Cylinders`](struct.Cylinders.html) noise
+/// module.
+pub const DEFAULT_CYLINDERS_OFFSET: f64 = 0.0;
+
 /// Noise module that outputs concentric cylinders.
 ///
 /// This noise module outputs concentric cylinders centered on the origin.
@@ -40,13 +44,29 @@ pub const DEFAULT_CYLINDERS_FREQUENCY: f64 = 1.0;
 /// specify the frequency, call the
 /// [`set_frequency()`](struct.Cylinders.html#method.set_frequency) method.
 ///
+/// The cylinders can also be shifted outwards with the
+/// [`set_offset()`](struct.Cylinders.html#method.set_offset) method, and the
+/// output value can be inverted, giving -1.0 on the cylinder surfaces and 1.0
+/// between them, with the
+/// [`set_invert()`](struct.Cylinders.html#method.set_invert) method.
+///
+/// Instead of this pattern, the noise module can output the signed distance to
+/// the nearest cylinder surface, for use as a distance field, with the
+/// [`set_signed_distance()`](struct.Cylinders.html#method.set_signed_distance)
+/// method.
+///
 /// This noise module, modified with some low-frequency, low-power turbulence,
 /// is useful for generating wood-like textures.
 ///
 /// This noise module does not require any source modules.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Cylinders {
     frequency: f64,
+    /// Distance the cylinders are shifted outwards, in units of the spacing
+    /// between them.
+    offset: f64,
+    invert: bool,
+    signed_distance: bool,
 }
 
 impl Default for Cylinders {
@@ -54,6 +74,9 @@ impl Default for Cylinders {
     fn default() -> Cylinders {
         Cylinders {
             frequency: DEFAULT_CYLINDERS_FREQUENCY,
+            offset: DEFAULT_CYLINDERS_OFFSET,
+            invert: false,
+            signed_distance: false,
         }
     }
 }
@@ -72,6 +95,24 @@ impl Cylinders {
         self.frequency
     }
 
+    /// Returns the distance the concentric cylinders are shifted outwards.
+    ///
+    /// See [`set_offset()`](struct.Cylinders.html#method.set_offset) for details.
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Determines if the output value is inverted.
+    pub fn is_inverted(&self) -> bool {
+        self.invert
+    }
+
+    /// Determines if the output value is the signed distance to the nearest
+    /// cylinder surface.
+    pub fn is_signed_distance(&self) -> bool {
+        self.signed_distance
+    }
+
     /// Sets the frequenct of the concentric cylinders.
     ///
     /// Increasing the frequency increases the density of the concentric
@@ -79,6 +120,74 @@ impl Cylinders {
     pub fn set_frequency(&mut self, frequency: f64) {
         self.frequency = frequency;
     }
+
+    /// Sets the distance the concentric cylinders are shifted outwards.
+    ///
+    /// The offset shifts the phase of the cylinders, and is measured in units
+    /// of the spacing between them: an offset of 0.5 moves every cylinder half
+    /// way to the next one, and an offset of 1.0 has no visible effect.  The
+    /// cylinder surfaces lie at the distances `(n + offset) / frequency` from
+    /// the origin, for every integer `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Cylinders};
+    ///
+    /// let mut cylinders = Cylinders::new();
+    /// cylinders.set_offset(0.25);
+    /// assert_eq!(cylinders.get_value(0.25, 0.0, 0.0), 1.0);
+    /// assert_eq!(cylinders.get_value(0.0, 0.0, 0.0), 0.0);
+    /// ```
+    pub fn set_offset(&mut self, offset: f64) {
+        self.offset = offset;
+    }
+
+    /// Enables or disables inverting the output value.
+    ///
+    /// When inverted, the input values on the cylinder surfaces are given the
+    /// output value -1.0 and the input values equidistant from two cylinder
+    /// surfaces are given the output value 1.0.
+    pub fn set_invert(&mut self, invert: bool) {
+        self.invert = invert;
+    }
+
+    /// Enables or disables outputting the signed distance to the nearest
+    /// cylinder surface.
+    ///
+    /// When enabled, the output value is the distance from the input value to
+    /// the nearest cylinder surface, in the units of the input value.  It is
+    /// negative if the input value is inside that cylinder and positive if it
+    /// is outside it, so it ranges from `-0.5 / frequency` to `+0.5 /
+    /// frequency` rather than from -1.0 to +1.0.  Halfway between two cylinders
+    /// the nearest surface changes, so the output value jumps from positive to
+    /// negative there.  If the output value is also inverted, its sign is
+    /// flipped.
+    ///
+    /// By default, the output value follows the pattern described for
+    /// [`Cylinders`](struct.Cylinders.html) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Cylinders, Module};
+    ///
+    /// let mut cylinders = Cylinders::new();
+    /// cylinders.set_frequency(2.0);
+    /// cylinders.set_signed_distance(true);
+    ///
+    /// // Just outside the cylinder of radius 0.5, and just inside the next one.
+    /// assert!((cylinders.get_value(0.6, 3.0, 0.0) - 0.1).abs() < 1e-12);
+    /// assert!((cylinders.get_value(0.0, 3.0, 0.9) + 0.1).abs() < 1e-12);
+    ///
+    /// // The default output value only depends on the distance to the
+    /// // surface, not on which side of it the input value is.
+    /// cylinders.set_signed_distance(false);
+    /// assert_eq!(cylinders.get_value(0.6, 3.0, 0.0), cylinders.get_value(0.0, 3.0, 0.9));
+    /// ```
+    pub fn set_signed_distance(&mut self, signed_distance: bool) {
+        self.signed_distance = signed_distance;
+    }
 }
 
 impl Module for Cylinders {
@@ -87,10 +196,33 @@ impl Module for Cylinders {
         let z = z * self.frequency;
 
         let dist_from_centre = (x * x + z * z).sqrt();
+        let dist_from_centre = dist_from_centre - self.offset;
         let dist_from_smaller_sphere = dist_from_centre - dist_from_centre.floor();
         let dist_from_larger_sphere = 1.0 - dist_from_smaller_sphere;
         let nearest_dist = f64::min(dist_from_smaller_sphere, dist_from_larger_sphere);
 
-        1.0 - nearest_dist * 4.0 // Puts it in the -1.0 to +1.0 range.
+        if self.signed_distance {
+            // The input value is inside the larger cylinder, and outside the
+            // smaller one.
+            let dist = if dist_from_smaller_sphere <= dist_from_larger_sphere {
+                dist_from_smaller_sphere
+            } else {
+                -dist_from_larger_sphere
+            };
+            let dist = dist / self.frequency.abs();
+            return if self.invert { -dist } else { dist };
+        }
+
+        let value = 1.0 - nearest_dist * 4.0; // Puts it in the -1.0 to +1.0 range.
+        if self.invert { -value } else { value }
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Cylinders");
+        hasher.write_f64(self.frequency);
+        hasher.write_f64(self.offset);
+        hasher.write_bool(self.invert);
+        hasher.write_bool(self.signed_distance);
+        hasher.finish()
     }
 }
@@ -0,0 +1,179 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Source of the versions of every [`Dirty`](struct.Dirty.html) noise module,
+/// so that no two changes anywhere are given the same version.
+static NEXT_VERSION: AtomicU64 = AtomicU64::new(1);
+
+fn next_version() -> u64 {
+    NEXT_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Noise module that tracks changes to a source module, so that caches built
+/// from it know when to rebuild.
+///
+/// This noise module outputs the value from its source module unchanged.  It
+/// also keeps a version number, which is given a new, larger value whenever
+/// the source module may have changed: when it is created, when
+/// [`module_mut()`](struct.Dirty.html#method.module_mut),
+/// [`set_module()`](struct.Dirty.html#method.set_module) or
+/// [`sources_mut()`](trait.Module.html#method.sources_mut) is called, and
+/// when [`mark_dirty()`](struct.Dirty.html#method.mark_dirty) is called.
+/// The version is returned by
+/// [`version()`](trait.Module.html#method.version), which reports the latest
+/// version of any `Dirty` noise module in a graph, so a cache can tell
+/// whether the graph beneath it has changed without walking or resampling it.
+///
+/// # Editing a graph
+///
+/// In an interactive editor, wrap each noise module whose parameters can be
+/// edited in a `Dirty` noise module, and each expensive subgraph in a
+/// [`GridCache`](struct.GridCache.html) noise module.  Edit parameters
+/// through `module_mut()`, then call
+/// [`refresh()`](struct.GridCache.html#method.refresh) on every cache before
+/// sampling the graph again.  Only the caches above the edited noise module
+/// are rebuilt; the others keep their sampled values.
+///
+/// This noise module requires one source module.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Add, Dirty, GridCache, Module, Perlin};
+///
+/// let grid = |module| GridCache::new(module, (0.0, 0.0), (0.5, 0.5), 9, 9);
+/// let mut graph = Add::new(grid(Dirty::new(Perlin::new())), grid(Dirty::new(Perlin::new())));
+///
+/// graph.module2_mut().module_mut().module_mut().set_frequency(2.0);
+/// assert!(!graph.module1_mut().refresh());
+/// assert!(graph.module2_mut().refresh());
+/// ```
+#[derive(Debug)]
+pub struct Dirty<M: Module> {
+    module: M,
+    version: Cell<u64>,
+}
+
+impl<M: Module> Dirty<M> {
+    /// Create a new `Dirty` noise module around the specified module.
+    pub fn new(module: M) -> Dirty<M> {
+        Dirty {
+            module: module,
+            version: Cell::new(next_version()),
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    ///
+    /// This operation gives the noise module a new version, whether or not
+    /// the source module is changed.
+    pub fn module_mut(&mut self) -> &mut M {
+        self.mark_dirty();
+        &mut self.module
+    }
+
+    /// Set the source module to be used.
+    ///
+    /// This operation gives the noise module a new version.
+    pub fn set_module(&mut self, module: M) {
+        self.mark_dirty();
+        self.module = module;
+    }
+
+    /// Gives the noise module a new version, marking any caches built from it
+    /// as out of date.
+    ///
+    /// This only needs to be called for changes this noise module cannot see,
+    /// for example to a parameter that an [`FnModule`](struct.FnModule.html)
+    /// reads from a shared `Cell`.
+    pub fn mark_dirty(&self) {
+        self.version.set(next_version());
+    }
+}
+
+impl<M: Module> Module for Dirty<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.module.get_value(x, y, z)
+    }
+
+    fn get_value_row(&self, x: f64, y: f64, z: f64, x_step: f64, values: &mut [f64]) {
+        self.module.get_value_row(x, y, z, x_step, values);
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Dirty");
+        hasher.write_u64(self.module.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    /// This operation gives the noise module a new version, as
+    /// [`module_mut()`](struct.Dirty.html#method.module_mut) does.
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        self.mark_dirty();
+        vec![&mut self.module as &mut dyn Module]
+    }
+
+    /// Returns the version of this noise module, or of a `Dirty` noise module
+    /// beneath it if that has changed more recently.
+    fn version(&self) -> u64 {
+        ::std::cmp::max(self.version.get(), self.module.version())
+    }
+
+    fn max_gradient_estimate(&self) -> Option<f64> {
+        self.module.max_gradient_estimate()
+    }
+}
+
+impl<M: Module + Clone> Clone for Dirty<M> {
+    fn clone(&self) -> Dirty<M> {
+        Dirty {
+            module: self.module.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+impl<M: Module + PartialEq> PartialEq for Dirty<M> {
+    /// Two `Dirty` noise modules are equal if their source modules are equal;
+    /// the versions are not compared.
+    fn eq(&self, other: &Dirty<M>) -> bool {
+        self.module == other.module
+    }
+}
@@ -14,7 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Noise module that uses three source modules to displace each coordinate of
 /// the input value before returning the output value from a source module.
@@ -29,6 +29,7 @@ use module::Module;
 /// Perlin-noise modules that perform the displacement operation.
 ///
 /// This noise module requires four source modules.
+#[derive(Debug, PartialEq)]
 pub struct Displace<MS: Module, MX: Module, MY: Module, MZ: Module> {
     msource: MS,
     mdisp_x: MX,
@@ -152,6 +153,42 @@ impl<MS: Module, MX: Module, MY: Module, MZ: Module> Module for Displace<MS, MX,
         // the original input value.
         self.msource.get_value(x_displace, y_displace, z_displace)
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Displace");
+        hasher.write_u64(self.msource.config_hash());
+        hasher.write_u64(self.mdisp_x.config_hash());
+        hasher.write_u64(self.mdisp_y.config_hash());
+        hasher.write_u64(self.mdisp_z.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        4
+    }
+
+    /// Returns the source module, followed by the `x`, `y` and `z`
+    /// displacement modules.
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.msource as &dyn Module, &self.mdisp_x, &self.mdisp_y, &self.mdisp_z]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.msource),
+            1 => Some(&self.mdisp_x),
+            2 => Some(&self.mdisp_y),
+            3 => Some(&self.mdisp_z),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.msource as &mut dyn Module,
+             &mut self.mdisp_x,
+             &mut self.mdisp_y,
+             &mut self.mdisp_z]
+    }
 }
 
 impl<MS: Module + Clone,
@@ -167,3 +204,189 @@ impl<MS: Module + Clone,
         }
     }
 }
+
+/// Default offset added to the input value when sampling the `x` displacement
+/// for the [`DisplaceUniform`](struct.DisplaceUniform.html) noise module.
+pub const DEFAULT_DISPLACE_UNIFORM_X_OFFSET: (f64, f64, f64) =
+    (12414.0 / 65536.0, 65124.0 / 65536.0, 31337.0 / 65536.0);
+
+/// Default offset added to the input value when sampling the `y` displacement
+/// for the [`DisplaceUniform`](struct.DisplaceUniform.html) noise module.
+pub const DEFAULT_DISPLACE_UNIFORM_Y_OFFSET: (f64, f64, f64) =
+    (26519.0 / 65536.0, 18128.0 / 65536.0, 60493.0 / 65536.0);
+
+/// Default offset added to the input value when sampling the `z` displacement
+/// for the [`DisplaceUniform`](struct.DisplaceUniform.html) noise module.
+pub const DEFAULT_DISPLACE_UNIFORM_Z_OFFSET: (f64, f64, f64) =
+    (53820.0 / 65536.0, 11213.0 / 65536.0, 44845.0 / 65536.0);
+
+/// Noise module that uses a single displacement module, sampled at three
+/// offset input values, to displace each coordinate of the input value before
+/// returning the output value from a source module.
+///
+/// The [`get_value()`](struct.DisplaceUniform.html#method.get_value) method
+/// adds the output value from the displacement module at the input value plus
+/// the `x` offset to the `x` coordinate of the input value, and likewise for
+/// the `y` and `z` coordinates, before retrieving the output value from the
+/// source module.  To set the offsets, call the
+/// [`set_offsets()`](struct.DisplaceUniform.html#method.set_offsets) method.
+///
+/// Unlike the [`Displace`](struct.Displace.html) noise module, which takes a
+/// separate displacement module for each coordinate, this noise module uses
+/// the same displacement module for all three.  This suits the common case
+/// where the three displacement modules would be identical apart from an
+/// offset, as in the [`Turbulence`](struct.Turbulence.html) noise module,
+/// whose offsets are used by default.  The three displacements are only
+/// decorrelated by the offsets, so the offsets should be large compared to
+/// the features of the displacement module.
+///
+/// This noise module requires two source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Constant, DisplaceUniform, FnModule, Module};
+///
+/// let x_coord = FnModule::new(|x: f64, _y: f64, _z: f64| x);
+/// let mut disp = Constant::new();
+/// disp.set_const_value(0.25);
+///
+/// // Every coordinate of the input value is displaced by 0.25, so the `x`
+/// // coordinate becomes 1.25.
+/// let displace = DisplaceUniform::new(x_coord, disp);
+/// assert_eq!(displace.get_value(1.0, 2.0, 3.0), 1.25);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct DisplaceUniform<MS: Module, MD: Module> {
+    msource: MS,
+    mdisp: MD,
+    x_offset: (f64, f64, f64),
+    y_offset: (f64, f64, f64),
+    z_offset: (f64, f64, f64),
+}
+
+impl<MS: Module, MD: Module> DisplaceUniform<MS, MD> {
+    /// Create a new `DisplaceUniform` noise module around the specified
+    /// modules, using default parameters.
+    pub fn new(msource: MS, mdisp: MD) -> DisplaceUniform<MS, MD> {
+        DisplaceUniform {
+            msource: msource,
+            mdisp: mdisp,
+            x_offset: DEFAULT_DISPLACE_UNIFORM_X_OFFSET,
+            y_offset: DEFAULT_DISPLACE_UNIFORM_Y_OFFSET,
+            z_offset: DEFAULT_DISPLACE_UNIFORM_Z_OFFSET,
+        }
+    }
+
+    /// Returns a reference to the module whose input values are being
+    /// displaced.
+    pub fn module(&self) -> &MS {
+        &self.msource
+    }
+
+    /// Returns a mutable reference to the module whose input values are being
+    /// displaced.
+    pub fn module_mut(&mut self) -> &mut MS {
+        &mut self.msource
+    }
+
+    /// Returns a reference to the displacement module.
+    pub fn displace_module(&self) -> &MD {
+        &self.mdisp
+    }
+
+    /// Returns a mutable reference to the displacement module.
+    pub fn displace_module_mut(&mut self) -> &mut MD {
+        &mut self.mdisp
+    }
+
+    /// Returns the offsets added to the input value when sampling the `x`,
+    /// `y` and `z` displacements, respectively.
+    pub fn offsets(&self) -> [(f64, f64, f64); 3] {
+        [self.x_offset, self.y_offset, self.z_offset]
+    }
+
+    /// Sets the module whose input values are going to be displaced.
+    pub fn set_module(&mut self, module: MS) {
+        self.msource = module;
+    }
+
+    /// Sets the displacement module.
+    pub fn set_displace_module(&mut self, module: MD) {
+        self.mdisp = module;
+    }
+
+    /// Sets the offsets added to the input value when sampling the `x`, `y`
+    /// and `z` displacements, respectively.
+    pub fn set_offsets(&mut self, x_offset: (f64, f64, f64), y_offset: (f64, f64, f64),
+                       z_offset: (f64, f64, f64)) {
+        self.x_offset = x_offset;
+        self.y_offset = y_offset;
+        self.z_offset = z_offset;
+    }
+}
+
+impl<MS: Module, MD: Module> Module for DisplaceUniform<MS, MD> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        // Sample the displacement module at each of the three offset input
+        // values, and add each value to the corresponding coordinate of the
+        // input value.
+        let (xo, yo, zo) = (self.x_offset, self.y_offset, self.z_offset);
+        let x_displace = x + self.mdisp.get_value(x + xo.0, y + xo.1, z + xo.2);
+        let y_displace = y + self.mdisp.get_value(x + yo.0, y + yo.1, z + yo.2);
+        let z_displace = z + self.mdisp.get_value(x + zo.0, y + zo.1, z + zo.2);
+
+        // Retrieve the output value using the offsetted input value instead of
+        // the original input value.
+        self.msource.get_value(x_displace, y_displace, z_displace)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("DisplaceUniform");
+        hasher.write_u64(self.msource.config_hash());
+        hasher.write_u64(self.mdisp.config_hash());
+        hasher.write_f64(self.x_offset.0);
+        hasher.write_f64(self.x_offset.1);
+        hasher.write_f64(self.x_offset.2);
+        hasher.write_f64(self.y_offset.0);
+        hasher.write_f64(self.y_offset.1);
+        hasher.write_f64(self.y_offset.2);
+        hasher.write_f64(self.z_offset.0);
+        hasher.write_f64(self.z_offset.1);
+        hasher.write_f64(self.z_offset.2);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        2
+    }
+
+    /// Returns the source module, followed by the displacement module.
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.msource as &dyn Module, &self.mdisp]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.msource),
+            1 => Some(&self.mdisp),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.msource as &mut dyn Module, &mut self.mdisp]
+    }
+}
+
+impl<MS: Module + Clone, MD: Module + Clone> Clone for DisplaceUniform<MS, MD> {
+    fn clone(&self) -> DisplaceUniform<MS, MD> {
+        DisplaceUniform {
+            msource: self.msource.clone(),
+            mdisp: self.mdisp.clone(),
+            x_offset: self.x_offset,
+            y_offset: self.y_offset,
+            z_offset: self.z_offset,
+        }
+    }
+}
@@ -14,7 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Default exponent for the [`Exponent`](struct.Exponent.html) noise module.
 pub const DEFAULT_EXPONENT: f64 = 1.0;
@@ -27,10 +27,24 @@ pub const DEFAULT_EXPONENT: f64 = 1.0;
 /// to 1.0), maps that value onto an exponential curve, then rescales that value
 /// back to the original range.
 ///
+/// By default, a normalized value below 0.0 (an output value from the source
+/// module below -1.0) is replaced with its absolute value before it is mapped
+/// onto the curve, so the output value rises again as the output value from
+/// the source module falls further below -1.0.  To clamp the normalized value
+/// to the range 0.0 to 1.0 instead, giving an output value that never
+/// decreases as the output value from the source module increases, call the
+/// [`set_clamp_input()`](struct.Exponent.html#method.set_clamp_input) method.
+/// Clamping is recommended, unless the previous output values must be
+/// preserved.
+///
 /// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
 pub struct Exponent<M: Module> {
     module: M,
     exponent: f64,
+    /// Whether to clamp the normalized value rather than take its absolute
+    /// value.
+    clamp_input: bool,
 }
 
 impl<M: Module> Exponent<M> {
@@ -40,6 +54,7 @@ impl<M: Module> Exponent<M> {
         Exponent {
             module: module,
             exponent: DEFAULT_EXPONENT,
+            clamp_input: false,
         }
     }
 
@@ -64,6 +79,12 @@ impl<M: Module> Exponent<M> {
         self.exponent
     }
 
+    /// Determines if the normalized value is clamped to the range 0.0 to 1.0,
+    /// rather than replaced with its absolute value.
+    pub fn is_input_clamped(&self) -> bool {
+        self.clamp_input
+    }
+
     /// Set the source module to be used.
     pub fn set_module(&mut self, module: M) {
         self.module = module;
@@ -79,12 +100,80 @@ impl<M: Module> Exponent<M> {
     pub fn set_exponent(&mut self, exponent: f64) {
         self.exponent = exponent;
     }
+
+    /// Enables or disables clamping the normalized value to the range 0.0 to
+    /// 1.0.
+    ///
+    /// When disabled, which is the default, the absolute value of the
+    /// normalized value is mapped onto the exponential curve.  Output values
+    /// from the source module below -1.0 are then reflected back upwards, so
+    /// the output value of this noise module jumps back up as the output value
+    /// from the source module dips below -1.0.  When enabled, the normalized
+    /// value is clamped instead, so output values from the source module
+    /// outside of the range -1.0 to +1.0 give output values of -1.0 or +1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Constant, Exponent, Module};
+    ///
+    /// let exponent_at = |source_value: f64, clamp_input: bool| {
+    ///     let mut source = Constant::new();
+    ///     source.set_const_value(source_value);
+    ///     let mut exponent = Exponent::new(source);
+    ///     exponent.set_exponent(2.0);
+    ///     exponent.set_clamp_input(clamp_input);
+    ///     exponent.get_value(0.0, 0.0, 0.0)
+    /// };
+    ///
+    /// // By default the output value rises again below -1.0.
+    /// assert!(exponent_at(-1.2, false) > exponent_at(-1.0, false));
+    ///
+    /// // With clamping it stays at its minimum.
+    /// assert_eq!(exponent_at(-1.2, true), exponent_at(-1.0, true));
+    /// assert!(exponent_at(-1.0, true) < exponent_at(-0.8, true));
+    /// ```
+    pub fn set_clamp_input(&mut self, clamp_input: bool) {
+        self.clamp_input = clamp_input;
+    }
 }
 
 impl<M: Module> Module for Exponent<M> {
     fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
-        let value = self.module.get_value(x, y, z);
-        ((value + 1.0) / 2.0).abs().powf(self.exponent) * 2.0 - 1.0
+        let value = (self.module.get_value(x, y, z) + 1.0) / 2.0;
+        let value = if self.clamp_input {
+            value.clamp(0.0, 1.0)
+        } else {
+            value.abs()
+        };
+        value.powf(self.exponent) * 2.0 - 1.0
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Exponent");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_f64(self.exponent);
+        hasher.write_bool(self.clamp_input);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
     }
 }
 
@@ -93,6 +182,7 @@ impl<M: Module + Clone> Clone for Exponent<M> {
         Exponent {
             module: self.module.clone(),
             exponent: self.exponent,
+            clamp_input: self.clamp_input,
         }
     }
 }
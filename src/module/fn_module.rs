@@ -0,0 +1,76 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use std::fmt;
+
+/// Noise module that outputs the value of an arbitrary function of the input
+/// value.
+///
+/// The [`get_value()`](struct.FnModule.html#method.get_value) method passes the
+/// (`x`, `y`, `z`) coordinates of the input value to the function and outputs
+/// its result.  This allows existing functions and closures to be used as
+/// generators, without defining a new noise module.
+///
+/// ```
+/// use noise::module::{FnModule, Module};
+///
+/// let waves = FnModule::new(|x: f64, _y: f64, z: f64| (x * 3.0).sin() * (z * 3.0).cos());
+/// assert_eq!(waves.get_value(0.5, 1.25, 0.0), (1.5f64).sin());
+/// ```
+///
+/// The function cannot be hashed, so the
+/// [`config_hash()`](trait.Module.html#method.config_hash) method only hashes
+/// the name of this noise module.  All `FnModule` noise modules hash the same.
+///
+/// This noise module does not require any source modules.
+pub struct FnModule<F: Fn(f64, f64, f64) -> f64> {
+    f: F,
+}
+
+impl<F: Fn(f64, f64, f64) -> f64> FnModule<F> {
+    /// Create a new `FnModule` noise module around the specified function.
+    pub fn new(f: F) -> FnModule<F> {
+        FnModule {
+            f: f,
+        }
+    }
+}
+
+impl<F: Fn(f64, f64, f64) -> f64> Module for FnModule<F> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        (self.f)(x, y, z)
+    }
+
+    fn config_hash(&self) -> u64 {
+        ConfigHasher::new("FnModule").finish()
+    }
+}
+
+impl<F: Fn(f64, f64, f64) -> f64 + Clone> Clone for FnModule<F> {
+    fn clone(&self) -> FnModule<F> {
+        FnModule {
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<F: Fn(f64, f64, f64) -> f64> fmt::Debug for FnModule<F> {
+    // The function cannot be printed, so it is left out.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FnModule").finish()
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{Add, BoxedModule, Constant, Max, Min, Multiply, ScaleBias};
+
+/// Replaces every subtree of a noise module graph that always outputs the same
+/// value with a single [`Constant`](struct.Constant.html) noise module.
+///
+/// A subtree is folded if it consists solely of `Constant` noise modules
+/// combined by the following noise modules:
+///
+///   * [`Add`](struct.Add.html)
+///   * [`Multiply`](struct.Multiply.html)
+///   * [`Min`](struct.Min.html)
+///   * [`Max`](struct.Max.html)
+///   * [`ScaleBias`](struct.ScaleBias.html)
+///
+/// These noise modules are only recognised when their source modules are
+/// [`BoxedModule`](type.BoxedModule.html)s.  The graph is walked through these
+/// noise modules only; subtrees beneath any other noise module are left
+/// untouched, even if they could be folded.
+pub fn fold_constants(mut tree: BoxedModule) -> BoxedModule {
+    match fold(&mut tree) {
+        Some(value) => make_constant(value),
+        None => tree,
+    }
+}
+
+/// Folds the foldable subtrees beneath `module`, returning the output value of
+/// `module` if it is itself constant.
+fn fold(module: &mut BoxedModule) -> Option<f64> {
+    let any = module.as_any_mut();
+    if let Some(m) = any.downcast_ref::<Constant>() {
+        return Some(m.const_value());
+    }
+    if let Some(m) = any.downcast_mut::<Add<BoxedModule, BoxedModule>>() {
+        let value1 = fold_source(m.module1_mut());
+        let value2 = fold_source(m.module2_mut());
+        return value1.and_then(|v1| value2.map(|v2| v1 + v2));
+    }
+    if let Some(m) = any.downcast_mut::<Multiply<BoxedModule, BoxedModule>>() {
+        let value1 = fold_source(m.module1_mut());
+        let value2 = fold_source(m.module2_mut());
+        return value1.and_then(|v1| value2.map(|v2| v1 * v2));
+    }
+    if let Some(m) = any.downcast_mut::<Min<BoxedModule, BoxedModule>>() {
+        let value1 = fold_source(m.module1_mut());
+        let value2 = fold_source(m.module2_mut());
+        return value1.and_then(|v1| value2.map(|v2| f64::min(v1, v2)));
+    }
+    if let Some(m) = any.downcast_mut::<Max<BoxedModule, BoxedModule>>() {
+        let value1 = fold_source(m.module1_mut());
+        let value2 = fold_source(m.module2_mut());
+        return value1.and_then(|v1| value2.map(|v2| f64::max(v1, v2)));
+    }
+    if let Some(m) = any.downcast_mut::<ScaleBias<BoxedModule>>() {
+        let (scale, bias, output_clamp) = (m.scale(), m.bias(), m.output_clamp());
+        return fold_source(m.module_mut()).map(|v| {
+            let value = v * scale + bias;
+            match output_clamp {
+                Some((lower_bound, upper_bound)) => value.max(lower_bound).min(upper_bound),
+                None => value,
+            }
+        });
+    }
+    None
+}
+
+/// Folds a source module, replacing it with a `Constant` noise module if it is
+/// constant.
+fn fold_source(module: &mut BoxedModule) -> Option<f64> {
+    let value = fold(module);
+    if let Some(value) = value {
+        if !(**module).as_any().is::<Constant>() {
+            *module = make_constant(value);
+        }
+    }
+    value
+}
+
+fn make_constant(value: f64) -> BoxedModule {
+    let mut constant = Constant::new();
+    constant.set_const_value(value);
+    Box::new(constant)
+}
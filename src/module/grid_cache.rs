@@ -0,0 +1,264 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use noise_map::NoiseMap;
+use util::linear_interp;
+
+/// Noise module that samples a source module once over a grid, then
+/// interpolates between the sampled output values.
+///
+/// The grid lies on the `z = 0` plane, and is defined by the input value of
+/// its first sample point, the distance between adjacent sample points along
+/// the `x` and `y` axes, and the number of sample points along each axis.  The
+/// source module is sampled at every point of the grid when this noise module
+/// is created.  Input values within the bounds of the grid then output a value
+/// bilinearly interpolated from the four surrounding sample points, without
+/// calling the source module.  All other input values output the value from
+/// the source module.
+///
+/// Unlike the [`Cache`](struct.Cache.html) noise module, which only remembers
+/// the last output value, this noise module is useful when the same region is
+/// sampled many times, for example once per frame of an animation.
+///
+/// The interpolated output values are only an approximation of the output
+/// values from the source module.  At the sample points the error is at most a
+/// rounding error, but between them any detail finer than the distance between
+/// sample points is lost.  The error shrinks as the grid is made finer, at the
+/// cost of memory and the time taken to sample the grid.  The output value
+/// also has a seam where an input value leaves the grid.
+///
+/// The grid is not updated when the source module changes through
+/// [`module_mut()`](struct.GridCache.html#method.module_mut); call the
+/// [`rebuild()`](struct.GridCache.html#method.rebuild) method afterwards.  If
+/// the source module is changed through a [`Dirty`](struct.Dirty.html) noise
+/// module beneath this one, the
+/// [`refresh()`](struct.GridCache.html#method.refresh) method rebuilds the
+/// grid only if it is out of date.
+///
+/// This noise module requires one source module.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{GridCache, Module, Perlin};
+///
+/// let perlin = Perlin::new();
+/// let grid_cache = GridCache::new(perlin.clone(), (-1.0, -1.0), (0.25, 0.25), 9, 9);
+///
+/// // Within the grid, output values are interpolated.
+/// let error = grid_cache.get_value(0.25, 0.5, 0.0) - perlin.get_value(0.25, 0.5, 0.0);
+/// assert!(error.abs() < 1e-9);
+///
+/// // Outside of it, they come from the source module.
+/// assert_eq!(grid_cache.get_value(3.0, 0.5, 0.0), perlin.get_value(3.0, 0.5, 0.0));
+/// assert_eq!(grid_cache.get_value(0.1, 0.5, 0.2), perlin.get_value(0.1, 0.5, 0.2));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct GridCache<M: Module> {
+    module: M,
+    origin: (f64, f64),
+    step: (f64, f64),
+    /// Output values from the source module at the sample points.
+    map: NoiseMap,
+    /// Version of the source module when the grid was sampled.
+    version: u64,
+}
+
+impl<M: Module> GridCache<M> {
+    /// Create a new `GridCache` noise module around the specified module,
+    /// sampling it over the given grid.
+    ///
+    ///   * `origin` - The (`x`, `y`) coordinates of the input value of the
+    ///     first sample point.
+    ///   * `step` - The distance between adjacent sample points along the `x`
+    ///     and `y` axes.
+    ///   * `width`, `height` - The number of sample points along the `x` and
+    ///     `y` axes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either coordinate of `step` is not greater than zero, or if
+    /// `width` or `height` is less than two.
+    pub fn new(module: M, origin: (f64, f64), step: (f64, f64), width: usize,
+               height: usize) -> GridCache<M> {
+        if step.0.is_nan() || step.1.is_nan() || step.0 <= 0.0 || step.1 <= 0.0 {
+            panic!("`step` must be greater than zero along each axis!");
+        }
+        if width < 2 || height < 2 {
+            panic!("`width` and `height` must be at least two!");
+        }
+        let map = NoiseMap::from_module(&module, origin, step, width, height);
+        let version = module.version();
+        GridCache {
+            module: module,
+            origin: origin,
+            step: step,
+            map: map,
+            version: version,
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    ///
+    /// Changes to the source module are not reflected in the grid until the
+    /// [`rebuild()`](struct.GridCache.html#method.rebuild) method is called.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Returns the (`x`, `y`) coordinates of the input value of the first
+    /// sample point.
+    pub fn origin(&self) -> (f64, f64) {
+        self.origin
+    }
+
+    /// Returns the distance between adjacent sample points along the `x` and
+    /// `y` axes.
+    pub fn step(&self) -> (f64, f64) {
+        self.step
+    }
+
+    /// Returns the output values from the source module at the sample points.
+    pub fn map(&self) -> &NoiseMap {
+        &self.map
+    }
+
+    /// Set the source module to be used.
+    ///
+    /// This operation samples the new source module over the grid.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+        self.rebuild();
+    }
+
+    /// Samples the source module over the grid again.
+    ///
+    /// Call this after changing the source module through
+    /// [`module_mut()`](struct.GridCache.html#method.module_mut).
+    pub fn rebuild(&mut self) {
+        self.map = NoiseMap::from_module(&self.module, self.origin, self.step, self.map.width(),
+                                         self.map.height());
+        self.version = self.module.version();
+    }
+
+    /// Determines if a [`Dirty`](struct.Dirty.html) noise module beneath this
+    /// one has changed since the grid was sampled.
+    ///
+    /// Changes made in other ways, such as through
+    /// [`module_mut()`](struct.GridCache.html#method.module_mut) of this noise
+    /// module, are not detected.
+    pub fn is_out_of_date(&self) -> bool {
+        self.module.version() != self.version
+    }
+
+    /// Samples the source module over the grid again if it is out of date,
+    /// and returns whether it did.
+    ///
+    /// See [`is_out_of_date()`](struct.GridCache.html#method.is_out_of_date)
+    /// for which changes are detected.
+    pub fn refresh(&mut self) -> bool {
+        let out_of_date = self.is_out_of_date();
+        if out_of_date {
+            self.rebuild();
+        }
+        out_of_date
+    }
+
+    /// Returns the position of the given coordinate within the grid along one
+    /// axis, as the index of the sample point below it and the fraction of
+    /// the way to the next, or `None` if it is outside of the grid.
+    fn grid_coord(value: f64, origin: f64, step: f64, count: usize) -> Option<(usize, f64)> {
+        let pos = (value - origin) / step;
+        let last = (count - 1) as f64;
+        if pos >= 0.0 && pos <= last {
+            // The last sample point interpolates from the cell below it.
+            let index = ::std::cmp::min(pos.floor() as usize, count - 2);
+            Some((index, pos - index as f64))
+        } else {
+            None
+        }
+    }
+}
+
+impl<M: Module> Module for GridCache<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        if z == 0.0 {
+            let x_pos = Self::grid_coord(x, self.origin.0, self.step.0, self.map.width());
+            let y_pos = Self::grid_coord(y, self.origin.1, self.step.1, self.map.height());
+            if let (Some((x0, xa)), Some((y0, ya))) = (x_pos, y_pos) {
+                let v0 = linear_interp(self.map.get_value(x0, y0),
+                                       self.map.get_value(x0 + 1, y0),
+                                       xa);
+                let v1 = linear_interp(self.map.get_value(x0, y0 + 1),
+                                       self.map.get_value(x0 + 1, y0 + 1),
+                                       xa);
+                return linear_interp(v0, v1, ya);
+            }
+        }
+        self.module.get_value(x, y, z)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("GridCache");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_f64(self.origin.0);
+        hasher.write_f64(self.origin.1);
+        hasher.write_f64(self.step.0);
+        hasher.write_f64(self.step.1);
+        hasher.write_u64(self.map.width() as u64);
+        hasher.write_u64(self.map.height() as u64);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    /// Changes to the source module are not reflected in the grid until the
+    /// [`rebuild()`](struct.GridCache.html#method.rebuild) method is called.
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone> Clone for GridCache<M> {
+    fn clone(&self) -> GridCache<M> {
+        GridCache {
+            module: self.module.clone(),
+            origin: self.origin,
+            step: self.step,
+            map: self.map.clone(),
+            version: self.version,
+        }
+    }
+}
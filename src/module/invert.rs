@@ -14,11 +14,12 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Noise module that inverts the output value from a source module.
 ///
 /// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
 pub struct Invert<M: Module> {
     module: M,
 }
@@ -52,6 +53,31 @@ impl<M: Module> Module for Invert<M> {
         let value = self.module.get_value(x, y, z);
         -value
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Invert");
+        hasher.write_u64(self.module.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
 }
 
 impl<M: Module + Clone> Clone for Invert<M> {
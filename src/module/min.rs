@@ -14,12 +14,16 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{BoxedModule, ConfigHasher, Module};
+use std::f64;
+use std::fmt;
+use util::winner;
 
 /// Noise module that outputs the smaller of the two output values from two
 /// source modules.
 ///
 /// This noise module requires two source modules.
+#[derive(Debug, PartialEq)]
 pub struct Min<M1: Module, M2: Module> {
     module1: M1,
     module2: M2,
@@ -71,6 +75,34 @@ impl<M1: Module, M2: Module> Module for Min<M1, M2> {
         let value2 = self.module2.get_value(x, y, z);
         f64::min(value1, value2)
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Min");
+        hasher.write_u64(self.module1.config_hash());
+        hasher.write_u64(self.module2.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        2
+    }
+
+    /// Returns the first and second source modules, in that order.
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module1 as &dyn Module, &self.module2]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module1),
+            1 => Some(&self.module2),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module1 as &mut dyn Module, &mut self.module2]
+    }
 }
 
 impl<M1: Module + Clone, M2: Module + Clone> Clone for Min<M1, M2> {
@@ -81,3 +113,135 @@ impl<M1: Module + Clone, M2: Module + Clone> Clone for Min<M1, M2> {
         }
     }
 }
+
+/// Noise module that outputs the smallest of the output values from any number
+/// of source modules.
+///
+/// This is equivalent to a chain of nested [`Min`](struct.Min.html) noise
+/// modules, but is easier to build and avoids the deep nesting.  The source
+/// modules are added with the [`push()`](struct.MinN.html#method.push)
+/// method.  As with `Min`, NaN output values are ignored unless every output
+/// value is NaN.  With no source modules, the output value is positive infinity.
+///
+/// The [`winning_index()`](struct.MinN.html#method.winning_index) method
+/// returns which source module the output value came from, for example to
+/// choose a material for each point of a terrain built from several layers.
+///
+/// This noise module accepts any number of source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Constant, Module, MinN};
+///
+/// let mut min_n = MinN::new();
+/// for &value in &[0.2, -0.7, 0.4] {
+///     let mut constant = Constant::new();
+///     constant.set_const_value(value);
+///     min_n.push(Box::new(constant));
+/// }
+/// assert_eq!(min_n.get_value(0.0, 0.0, 0.0), -0.7);
+/// assert_eq!(min_n.winning_index(0.0, 0.0, 0.0), 1);
+/// ```
+pub struct MinN {
+    modules: Vec<BoxedModule>,
+}
+
+impl Default for MinN {
+    /// Create a new `MinN` noise module with no source modules.
+    fn default() -> MinN {
+        MinN {
+            modules: Vec::new(),
+        }
+    }
+}
+
+impl MinN {
+    /// Create a new `MinN` noise module with no source modules.
+    pub fn new() -> MinN {
+        Default::default()
+    }
+
+    /// Returns the source modules, in the order they were added.
+    pub fn modules(&self) -> &[BoxedModule] {
+        &self.modules
+    }
+
+    /// Returns a mutable reference to the source modules.
+    pub fn modules_mut(&mut self) -> &mut Vec<BoxedModule> {
+        &mut self.modules
+    }
+
+    /// Adds a source module.
+    pub fn push(&mut self, module: BoxedModule) {
+        self.modules.push(module);
+    }
+
+    /// Returns the index of the source module whose output value is output at
+    /// the given input value.
+    ///
+    /// The index is that of the source module with the smallest output value,
+    /// in the order the source modules were added.  If several source modules
+    /// output the same value, the first of them wins.  NaN output values are
+    /// ignored, unless every output value is NaN, in which case the index is
+    /// 0.  The output value of this noise module at the same input value is
+    /// always the output value of this source module.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no source modules.
+    pub fn winning_index(&self, x: f64, y: f64, z: f64) -> usize {
+        match self.winner(x, y, z) {
+            Some((index, _)) => index,
+            None => panic!("`MinN` has no source modules!"),
+        }
+    }
+
+    /// Returns the index and output value of the winning source module, or
+    /// `None` if there are no source modules.
+    fn winner(&self, x: f64, y: f64, z: f64) -> Option<(usize, f64)> {
+        winner(self.modules.iter().map(|module| module.get_value(x, y, z)),
+               |value, best| value < best)
+    }
+}
+
+impl Module for MinN {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.winner(x, y, z).map_or(f64::INFINITY, |(_, value)| value)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("MinN");
+        hasher.write_u64(self.modules.len() as u64);
+        for module in &self.modules {
+            hasher.write_u64(module.config_hash());
+        }
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Returns the source modules, in the order they were added.
+    fn sources(&self) -> Vec<&dyn Module> {
+        self.modules.iter().map(|module| &**module).collect()
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        self.modules.get(index).map(|module| &**module as &dyn Module)
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        self.modules.iter_mut().map(|module| &mut **module as &mut dyn Module).collect()
+    }
+}
+
+impl fmt::Debug for MinN {
+    // The boxed source modules cannot be printed, so only their number is.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MinN")
+            .field("module_count", &self.modules.len())
+            .finish()
+    }
+}
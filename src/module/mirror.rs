@@ -0,0 +1,145 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+
+/// Noise module that reflects the coordinates of the input value before
+/// returning the output value from a source module.
+///
+/// For each axis on which mirroring is enabled, the
+/// [`get_value()`](struct.Mirror.html#method.get_value) method replaces that
+/// coordinate of the input value with its absolute value.  The output value is
+/// therefore symmetric across the plane through the origin perpendicular to
+/// that axis; for example, with mirroring enabled on the `x` axis, the input
+/// values (`-x`, `y`, `z`) and (`x`, `y`, `z`) give the same output value.
+/// Enabling mirroring on several axes produces kaleidoscope-like patterns.
+///
+/// To enable mirroring on the individual `x`, `y`, or `z` axes, call the
+/// [`set_mirror_x()`](struct.Mirror.html#method.set_mirror_x),
+/// [`set_mirror_y()`](struct.Mirror.html#method.set_mirror_y) or
+/// [`set_mirror_z()`](struct.Mirror.html#method.set_mirror_z) methods,
+/// respectively.  By default mirroring is disabled on all axes.  To mirror
+/// across a plane other than through the origin, use a
+/// [`TranslatePoint`](struct.TranslatePoint.html) noise module.
+///
+/// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
+pub struct Mirror<M: Module> {
+    module: M,
+    mirror: (bool, bool, bool),
+}
+
+impl<M: Module> Mirror<M> {
+    /// Create a new `Mirror` noise module around the specified module, using
+    /// default parameters.
+    pub fn new(module: M) -> Mirror<M> {
+        Mirror {
+            module: module,
+            mirror: (false, false, false),
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Determines if the input value is mirrored across the `x` axis.
+    pub fn mirror_x(&self) -> bool {
+        self.mirror.0
+    }
+
+    /// Determines if the input value is mirrored across the `y` axis.
+    pub fn mirror_y(&self) -> bool {
+        self.mirror.1
+    }
+
+    /// Determines if the input value is mirrored across the `z` axis.
+    pub fn mirror_z(&self) -> bool {
+        self.mirror.2
+    }
+
+    /// Set the source module to be used.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+    }
+
+    /// Enables or disables mirroring the input value across the `x` axis.
+    pub fn set_mirror_x(&mut self, mirror: bool) {
+        self.mirror.0 = mirror;
+    }
+
+    /// Enables or disables mirroring the input value across the `y` axis.
+    pub fn set_mirror_y(&mut self, mirror: bool) {
+        self.mirror.1 = mirror;
+    }
+
+    /// Enables or disables mirroring the input value across the `z` axis.
+    pub fn set_mirror_z(&mut self, mirror: bool) {
+        self.mirror.2 = mirror;
+    }
+}
+
+impl<M: Module> Module for Mirror<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let x = if self.mirror.0 { x.abs() } else { x };
+        let y = if self.mirror.1 { y.abs() } else { y };
+        let z = if self.mirror.2 { z.abs() } else { z };
+        self.module.get_value(x, y, z)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Mirror");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_bool(self.mirror.0);
+        hasher.write_bool(self.mirror.1);
+        hasher.write_bool(self.mirror.2);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone> Clone for Mirror<M> {
+    fn clone(&self) -> Mirror<M> {
+        Mirror {
+            module: self.module.clone(),
+            mirror: self.mirror,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{BoxedModule, ConfigHasher, Module};
+use std::fmt;
+
+/// Default frequency for the [`MixedFbm`](struct.MixedFbm.html) noise module.
+pub const DEFAULT_MIXED_FBM_FREQUENCY: f64 = 1.0;
+
+/// Default lacunarity for the [`MixedFbm`](struct.MixedFbm.html) noise module.
+pub const DEFAULT_MIXED_FBM_LACUNARITY: f64 = 2.0;
+
+/// Default number of octaves for the [`MixedFbm`](struct.MixedFbm.html) noise
+/// module.
+pub const DEFAULT_MIXED_FBM_OCTAVE_COUNT: i32 = 6;
+
+/// Default persistence value for the [`MixedFbm`](struct.MixedFbm.html) noise
+/// module.
+pub const DEFAULT_MIXED_FBM_PERSISTENCE: f64 = 0.5;
+
+/// Maximum number of octaves for the [`MixedFbm`](struct.MixedFbm.html) noise
+/// module.
+pub const MIXED_FBM_MAX_OCTAVE: i32 = 30;
+
+/// Noise module that outputs fractal noise built from a different source
+/// module in each octave.
+///
+/// Like [`Perlin`](struct.Perlin.html) noise, the output value is the sum of
+/// several octaves of ever-increasing frequencies and ever-decreasing
+/// amplitudes.  Instead of coherent noise, each octave samples one of the
+/// source modules, known as *base modules*, at the input value scaled by the
+/// frequency of that octave, then scales the result by the amplitude of that
+/// octave.  Octave `i` uses the base module with the index `i` modulo the
+/// number of base modules, so if there are fewer base modules than octaves
+/// they are cycled through.  With no base modules, the output value is 0.0.
+///
+/// For example, alternating a [`Perlin`](struct.Perlin.html) and a
+/// [`Billow`](struct.Billow.html) base module mixes smooth and billowy detail
+/// at every scale.  The base modules are usually generators with a single
+/// octave.
+///
+/// The frequency of the first octave, the lacunarity and the persistence have
+/// the same meaning as for the `Perlin` noise module.
+///
+/// This noise module accepts any number of source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Constant, MixedFbm, Module};
+///
+/// let mut high = Constant::new();
+/// high.set_const_value(1.0);
+/// let mut low = Constant::new();
+/// low.set_const_value(-0.5);
+///
+/// let mut fbm = MixedFbm::new();
+/// fbm.push_base(Box::new(high));
+/// fbm.push_base(Box::new(low));
+/// fbm.set_octave_count(3);
+///
+/// // Octaves 0 and 2 use the first base module, octave 1 the second.
+/// assert_eq!(fbm.get_value(0.3, 0.5, 0.7), 1.0 * 1.0 + -0.5 * 0.5 + 1.0 * 0.25);
+/// ```
+pub struct MixedFbm {
+    bases: Vec<BoxedModule>,
+    frequency: f64,
+    lacunarity: f64,
+    octave_count: i32,
+    persistence: f64,
+}
+
+impl Default for MixedFbm {
+    /// Create a new `MixedFbm` noise module with no base modules, using
+    /// default parameters.
+    fn default() -> MixedFbm {
+        MixedFbm {
+            bases: Vec::new(),
+            frequency: DEFAULT_MIXED_FBM_FREQUENCY,
+            lacunarity: DEFAULT_MIXED_FBM_LACUNARITY,
+            octave_count: DEFAULT_MIXED_FBM_OCTAVE_COUNT,
+            persistence: DEFAULT_MIXED_FBM_PERSISTENCE,
+        }
+    }
+}
+
+impl MixedFbm {
+    /// Create a new `MixedFbm` noise module with no base modules, using
+    /// default parameters.
+    pub fn new() -> MixedFbm {
+        Default::default()
+    }
+
+    /// Returns the base modules, in the order they were added.
+    pub fn bases(&self) -> &[BoxedModule] {
+        &self.bases
+    }
+
+    /// Returns a mutable reference to the base modules.
+    pub fn bases_mut(&mut self) -> &mut Vec<BoxedModule> {
+        &mut self.bases
+    }
+
+    /// Returns the frequency of the first octave.
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Returns the lacunarity of the fractal noise.
+    ///
+    /// The lacunarity is the frequency multiplier between successive octaves.
+    pub fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    /// Returns the number of octaves that generate the fractal noise.
+    pub fn octave_count(&self) -> i32 {
+        self.octave_count
+    }
+
+    /// Returns the persistence value of the fractal noise.
+    ///
+    /// The persistence value is the amplitude multiplier between successive
+    /// octaves.
+    pub fn persistence(&self) -> f64 {
+        self.persistence
+    }
+
+    /// Adds a base module, used by the next octave after those of the
+    /// existing base modules.
+    pub fn push_base(&mut self, module: BoxedModule) {
+        self.bases.push(module);
+    }
+
+    /// Sets the frequency of the first octave.
+    pub fn set_frequency(&mut self, frequency: f64) {
+        self.frequency = frequency;
+    }
+
+    /// Sets the lacunarity of the fractal noise.
+    ///
+    /// The lacunarity is the frequency multiplier between successive octaves.
+    pub fn set_lacunarity(&mut self, lacunarity: f64) {
+        self.lacunarity = lacunarity;
+    }
+
+    /// Sets the number of octaves that generate the fractal noise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given octave count is outside the range from 1 to
+    /// [`MIXED_FBM_MAX_OCTAVE`](constant.MIXED_FBM_MAX_OCTAVE.html) inclusive.
+    pub fn set_octave_count(&mut self, octave_count: i32) {
+        if !(1..=MIXED_FBM_MAX_OCTAVE).contains(&octave_count) {
+            panic!("`octave_count` must be in the range [{}, {}]", 1, MIXED_FBM_MAX_OCTAVE);
+        }
+        self.octave_count = octave_count;
+    }
+
+    /// Sets the persistence value of the fractal noise.
+    ///
+    /// The persistence value is the amplitude multiplier between successive
+    /// octaves.
+    pub fn set_persistence(&mut self, persistence: f64) {
+        self.persistence = persistence;
+    }
+}
+
+impl Module for MixedFbm {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        if self.bases.is_empty() {
+            return 0.0;
+        }
+
+        let mut value = 0.0;
+        let mut cur_persistence = 1.0;
+        let mut x = x * self.frequency;
+        let mut y = y * self.frequency;
+        let mut z = z * self.frequency;
+
+        for cur_octave in 0..self.octave_count as usize {
+            let base = &self.bases[cur_octave % self.bases.len()];
+            value += base.get_value(x, y, z) * cur_persistence;
+
+            // Prepare the next octave.
+            x *= self.lacunarity;
+            y *= self.lacunarity;
+            z *= self.lacunarity;
+            cur_persistence *= self.persistence;
+        }
+
+        value
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("MixedFbm");
+        hasher.write_u64(self.bases.len() as u64);
+        for base in &self.bases {
+            hasher.write_u64(base.config_hash());
+        }
+        hasher.write_f64(self.frequency);
+        hasher.write_f64(self.lacunarity);
+        hasher.write_i32(self.octave_count);
+        hasher.write_f64(self.persistence);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        self.bases.len()
+    }
+
+    /// Returns the base modules, in the order they were added.
+    fn sources(&self) -> Vec<&dyn Module> {
+        self.bases.iter().map(|base| &**base).collect()
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        self.bases.get(index).map(|base| &**base as &dyn Module)
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        self.bases.iter_mut().map(|base| &mut **base as &mut dyn Module).collect()
+    }
+}
+
+impl fmt::Debug for MixedFbm {
+    // The boxed base modules cannot be printed, so only their number is.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MixedFbm")
+            .field("base_count", &self.bases.len())
+            .field("frequency", &self.frequency)
+            .field("lacunarity", &self.lacunarity)
+            .field("octave_count", &self.octave_count)
+            .field("persistence", &self.persistence)
+            .finish()
+    }
+}
@@ -2,69 +2,630 @@ mod abs;
 mod add;
 mod billow;
 mod blend;
+mod bypass;
 mod cache;
 mod checkerboard;
 mod clamp;
+mod clamp_point;
+mod combine;
+mod config_hash;
 mod constant;
 mod curve;
 mod cylinders;
+mod dirty;
 mod displace;
 mod exponent;
+mod fn_module;
+mod fold;
+mod grid_cache;
 mod invert;
 mod max;
 mod min;
+mod mirror;
+mod mixed_fbm;
+mod multi_select;
 mod multiply;
+mod normalize;
+mod overlay;
 mod perlin;
+mod point_field;
+mod position_blend;
 mod power;
+mod radial_gradient;
+mod random_tile;
+mod recording;
 mod ridged_multi;
 mod rotate_point;
 mod scale_bias;
 mod scale_point;
+mod seamless_plane;
+mod seeded;
 mod select;
+mod sine;
+mod small_cache;
 mod spheres;
 mod terrace;
+mod timed;
 mod translate_point;
 mod turbulence;
 mod voronoi;
 
-use std::ops::Deref;
+use std::any::Any;
+use std::rc::Rc;
+use std::sync::Arc;
+use util::{scurve3, scurve5};
 
 pub use self::abs::*;
 pub use self::add::*;
 pub use self::billow::*;
 pub use self::blend::*;
+pub use self::bypass::*;
 pub use self::cache::*;
 pub use self::checkerboard::*;
 pub use self::clamp::*;
+pub use self::clamp_point::*;
+pub use self::combine::*;
+pub use self::config_hash::*;
 pub use self::constant::*;
 pub use self::curve::*;
 pub use self::cylinders::*;
+pub use self::dirty::*;
 pub use self::displace::*;
 pub use self::exponent::*;
+pub use self::fn_module::*;
+pub use self::fold::*;
+pub use self::grid_cache::*;
 pub use self::invert::*;
 pub use self::max::*;
 pub use self::min::*;
+pub use self::mirror::*;
+pub use self::mixed_fbm::*;
+pub use self::multi_select::*;
 pub use self::multiply::*;
+pub use self::normalize::*;
+pub use self::overlay::*;
 pub use self::perlin::*;
+pub use self::point_field::*;
+pub use self::position_blend::*;
 pub use self::power::*;
+pub use self::radial_gradient::*;
+pub use self::random_tile::*;
+pub use self::recording::*;
 pub use self::ridged_multi::*;
 pub use self::rotate_point::*;
 pub use self::scale_bias::*;
 pub use self::scale_point::*;
+pub use self::seamless_plane::*;
+pub use self::seeded::*;
 pub use self::select::*;
+pub use self::sine::*;
+pub use self::small_cache::*;
 pub use self::spheres::*;
 pub use self::terrace::*;
+pub use self::timed::*;
 pub use self::translate_point::*;
 pub use self::turbulence::*;
 pub use self::voronoi::*;
 
-pub trait Module {
+/// Enumerates the easing curves used by noise modules that transition between
+/// the output values of two source modules.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Interp {
+    /// Transitions at a constant rate.  The transition has a visible "crease"
+    /// at either end, because its slope is discontinuous there.
+    Linear,
+    /// Transitions along a cubic S-curve, whose slope is zero at either end of
+    /// the transition.
+    Cubic,
+    /// Transitions along a quintic S-curve, whose slope and second derivative
+    /// are zero at either end of the transition, producing the smoothest
+    /// transition.
+    Quintic,
+}
+
+impl Interp {
+    /// Maps an alpha value ranging from 0.0 to 1.0 onto this easing curve.
+    pub fn ease(self, a: f64) -> f64 {
+        match self {
+            Interp::Linear => a,
+            Interp::Cubic => scurve3(a),
+            Interp::Quintic => scurve5(a),
+        }
+    }
+}
+
+/// Enumerates the coordinate axes of the input value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Axis {
+    /// The `x` axis.
+    X,
+    /// The `y` axis.
+    Y,
+    /// The `z` axis.
+    Z,
+}
+
+/// A noise module graph with its concrete module types erased.
+pub type BoxedModule = Box<dyn Module>;
+
+/// A noise module graph with its concrete module types erased, which can be
+/// shared between several places in a graph and between threads.
+///
+/// A `Box`, `Rc`, `Arc` or reference pointing to a noise module is itself a
+/// noise module, which forwards to the noise module it points to.  Wrapping a
+/// noise module in an `Arc` lets one instance appear as the source module of
+/// several other noise modules without cloning it, so that an expensive
+/// subgraph, and any cache within it, is shared.  A shared noise module can
+/// only be accessed through shared references, so its parameters cannot be
+/// changed while it is shared.
+///
+/// An `Arc<M>` for a concrete noise module type `M` is `Send` and `Sync`
+/// whenever `M` is.  This alias adds the `Send + Sync` bounds to the erased
+/// type, so that a graph built from `SharedModule`s can be sampled from
+/// several threads at once.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Add, Module, Perlin, SharedModule};
+/// use std::sync::Arc;
+///
+/// let perlin: SharedModule = Arc::new(Perlin::new());
+/// let sum = Add::new(perlin.clone(), perlin.clone());
+/// assert_eq!(sum.get_value(0.3, 0.5, 0.7), 2.0 * perlin.get_value(0.3, 0.5, 0.7));
+/// ```
+pub type SharedModule = Arc<dyn Module + Send + Sync>;
+
+/// Converts a value to `Any`.
+///
+/// This is implemented for every type, and provides the default
+/// implementations of [`Module::as_any()`](trait.Module.html#method.as_any)
+/// and [`Module::as_any_mut()`](trait.Module.html#method.as_any_mut), so
+/// noise modules do not need to implement it themselves.
+pub trait ToAny {
+    /// Returns a reference to this value as `Any`.
+    fn to_any(&self) -> &dyn Any where Self: 'static;
+
+    /// Returns a mutable reference to this value as `Any`.
+    fn to_any_mut(&mut self) -> &mut dyn Any where Self: 'static;
+}
+
+impl<T> ToAny for T {
+    fn to_any(&self) -> &dyn Any where Self: 'static {
+        self
+    }
+
+    fn to_any_mut(&mut self) -> &mut dyn Any where Self: 'static {
+        self
+    }
+}
+
+pub trait Module: ToAny {
+    /// Returns the output value at the given input value.
+    ///
+    /// The output value is the same whichever optional features of this
+    /// library are enabled, except for `old-noise-version`, which selects
+    /// different noise.  In particular, the `simd` feature does not change the
+    /// output value at all.
     fn get_value(&self, x: f64, y: f64, z: f64) -> f64;
+
+    /// Fills `values` with the output values along a row of input values,
+    /// starting at (`x`, `y`, `z`) and stepping `x_step` along the `x` axis.
+    ///
+    /// The element at index `i` is set to the output value at
+    /// (`x + i * x_step`, `y`, `z`), exactly as returned by
+    /// [`get_value()`](#tymethod.get_value).  The input values are calculated
+    /// from the index rather than by repeatedly adding `x_step`, so no
+    /// rounding error accumulates along long rows.  Noise modules may override
+    /// this method to share work between neighbouring input values, but must
+    /// calculate the input values the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Voronoi};
+    ///
+    /// let mut voronoi = Voronoi::new();
+    /// voronoi.enable_distance(true);
+    ///
+    /// let mut row = [0.0; 256];
+    /// voronoi.get_value_row(-3.7, 1.2, 0.4, 0.03, &mut row);
+    /// for (i, &value) in row.iter().enumerate() {
+    ///     assert_eq!(value, voronoi.get_value(-3.7 + i as f64 * 0.03, 1.2, 0.4));
+    /// }
+    /// ```
+    fn get_value_row(&self, x: f64, y: f64, z: f64, x_step: f64, values: &mut [f64]) {
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = self.get_value(x + i as f64 * x_step, y, z);
+        }
+    }
+
+    /// Returns a hash of the configuration of this noise module.
+    ///
+    /// Two noise modules with the same configuration hash the same, so the
+    /// hash can be used as a key when caching output values.  The hash covers
+    /// the type and parameters of this noise module and, for noise modules
+    /// that require source modules, the configuration hashes of the source
+    /// modules.  It is calculated with a
+    /// [`ConfigHasher`](struct.ConfigHasher.html), so it is the same on every
+    /// platform; floating-point parameters are hashed by their bit patterns, so
+    /// a NaN parameter always hashes the same.
+    ///
+    /// The default implementation only hashes the name of the type, which is
+    /// not guaranteed to be the same between versions of Rust.  Every noise
+    /// module in this library overrides it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Perlin, ScaleBias};
+    ///
+    /// let perlin = Perlin::new();
+    /// let hash = perlin.config_hash();
+    /// assert_eq!(hash, Perlin::new().config_hash());
+    ///
+    /// let mut other = perlin.clone();
+    /// other.set_frequency(2.0);
+    /// assert!(other.config_hash() != hash);
+    /// let mut other = perlin.clone();
+    /// other.set_octave_count(5);
+    /// assert!(other.config_hash() != hash);
+    /// let mut other = perlin.clone();
+    /// other.set_seed(1);
+    /// assert!(other.config_hash() != hash);
+    ///
+    /// let mut scale_bias = ScaleBias::new(perlin.clone());
+    /// let hash = scale_bias.config_hash();
+    /// scale_bias.set_bias(0.5);
+    /// assert!(scale_bias.config_hash() != hash);
+    /// scale_bias.module_mut().set_persistence(0.25);
+    /// assert!(scale_bias.config_hash() != hash);
+    /// ```
+    fn config_hash(&self) -> u64 {
+        ConfigHasher::new(::std::any::type_name::<Self>()).finish()
+    }
+
+    /// Returns the number of source modules this noise module requires.
+    ///
+    /// This is the static arity of the noise module type, e.g. 0 for
+    /// generators such as [`Perlin`](struct.Perlin.html), 1 for modifiers
+    /// such as [`Abs`](struct.Abs.html), 2 for [`Add`](struct.Add.html), 3 for
+    /// [`Select`](struct.Select.html) and 4 for
+    /// [`Displace`](struct.Displace.html).  It does not depend on the output
+    /// values or parameters of the noise module.  The only exceptions are
+    /// [`Sum`](struct.Sum.html), [`Product`](struct.Product.html),
+    /// [`MaxN`](struct.MaxN.html), [`MinN`](struct.MinN.html),
+    /// [`MixedFbm`](struct.MixedFbm.html) and
+    /// [`MultiSelect`](struct.MultiSelect.html), which accept any number of
+    /// source modules and return the number they currently have.
+    ///
+    /// The default implementation returns 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Abs, Add, Module, Perlin};
+    ///
+    /// assert_eq!(Perlin::new().source_count(), 0);
+    /// assert_eq!(Abs::new(Perlin::new()).source_count(), 1);
+    /// assert_eq!(Add::new(Perlin::new(), Perlin::new()).source_count(), 2);
+    /// ```
+    fn source_count(&self) -> usize {
+        0
+    }
+
+    /// Returns references to the source modules of this noise module.
+    ///
+    /// This allows a noise module graph, such as a
+    /// [`BoxedModule`](type.BoxedModule.html), to be walked without knowing
+    /// the concrete types of its noise modules.  The source modules are
+    /// returned in the order they are passed to the constructor of the noise
+    /// module, e.g. the first source module, the second source module and then
+    /// the control module for [`Select`](struct.Select.html).  The number of
+    /// source modules returned is always equal to
+    /// [`source_count()`](#method.source_count).
+    ///
+    /// The returned references are only valid for the lifetime of this noise
+    /// module, so they cannot be downcast with
+    /// [`as_any()`](#method.as_any), which requires a `'static` noise
+    /// module.
+    ///
+    /// The default implementation returns no source modules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Abs, BoxedModule, Constant, Module, Perlin, Select};
+    ///
+    /// fn count_modules(module: &dyn Module) -> usize {
+    ///     1 + module.sources().into_iter().map(count_modules).sum::<usize>()
+    /// }
+    ///
+    /// let graph: BoxedModule = Box::new(Select::new(Perlin::new(),
+    ///                                               Abs::new(Perlin::new()),
+    ///                                               Constant::new()));
+    /// assert_eq!(count_modules(&*graph), 5);
+    /// assert_eq!(graph.sources()[1].source_count(), 1);
+    /// ```
+    fn sources(&self) -> Vec<&dyn Module> {
+        Vec::new()
+    }
+
+    /// Returns a reference to the source module at `index`, in the order
+    /// returned by [`sources()`](#method.sources), or `None` if `index` is
+    /// not less than [`source_count()`](#method.source_count).
+    ///
+    /// Unlike `sources()`, this does not allocate, so it can be called for
+    /// every sample.  The default implementation calls `sources()`; every
+    /// noise module in this library overrides it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Abs, Add, Constant, Module, Perlin};
+    ///
+    /// let add = Add::new(Perlin::new(), Abs::new(Constant::new()));
+    /// assert_eq!(add.source(1).unwrap().source_count(), 1);
+    /// assert!(add.source(2).is_none());
+    /// ```
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        self.sources().into_iter().nth(index)
+    }
+
+    /// Returns mutable references to the source modules of this noise module.
+    ///
+    /// This is the mutable counterpart of [`sources()`](#method.sources),
+    /// allowing a noise module graph to be edited in place without knowing the
+    /// concrete types of its noise modules, e.g. to change a parameter of one
+    /// noise module or reseed every generator with
+    /// [`reseed_all()`](fn.reseed_all.html).
+    ///
+    /// Every noise module in this library returns either exactly the same
+    /// source modules as `sources()`, in the same order, so that the `i`th
+    /// element of each refers to the same source module, or no source modules
+    /// at all, if they cannot be changed in place.  The latter is the case for
+    /// [`VoronoiCache`](struct.VoronoiCache.html), whose precalculated seed
+    /// points depend on its source module, and for an `Rc` or `Arc` that
+    /// shares its noise module with another pointer.  Noise modules that cache
+    /// output values invalidate their caches when this is called, as their
+    /// `module_mut()` methods do; [`GridCache`](struct.GridCache.html) must be
+    /// rebuilt explicitly.
+    ///
+    /// The default implementation returns no source modules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Abs, BoxedModule, Constant, Module, Perlin, Seeded, Select};
+    ///
+    /// let mut graph: BoxedModule = Box::new(Select::new(Perlin::new(),
+    ///                                                   Abs::new(Perlin::new()),
+    ///                                                   Constant::new()));
+    /// assert_eq!(graph.sources_mut().len(), graph.sources().len());
+    ///
+    /// // Reseed the first source module, then read it back through `sources()`.
+    /// graph.sources_mut()[0].as_seeded_mut().unwrap().set_seed(5);
+    /// let mut expected = Perlin::new();
+    /// expected.set_seed(5);
+    /// assert_eq!(graph.sources()[0].config_hash(), expected.config_hash());
+    /// ```
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        Vec::new()
+    }
+
+    /// Returns the latest version of any [`Dirty`](struct.Dirty.html) noise
+    /// module in the graph beneath and including this noise module.
+    ///
+    /// Every `Dirty` noise module is given a new version, larger than any
+    /// given before, whenever its source module may have changed.  A cache
+    /// such as [`GridCache`](struct.GridCache.html) records the version of
+    /// its source module when it samples it, and only needs to sample it
+    /// again once the version differs.  Changes that are not made through a
+    /// `Dirty` noise module do not change the version.
+    ///
+    /// The default implementation returns the largest version of the source
+    /// modules returned by [`sources()`](#method.sources), or 0 if there are
+    /// none.
+    fn version(&self) -> u64 {
+        self.sources().into_iter().map(|source| source.version()).max().unwrap_or(0)
+    }
+
+    /// Returns an upper bound on how fast the output value can change, if one
+    /// is known.
+    ///
+    /// The bound is on the magnitude of the gradient of the output value, so
+    /// two input values a distance `d` apart never have output values that
+    /// differ by more than `d` times the bound (i.e. it is a Lipschitz
+    /// constant).  This allows a mesher or sampler to choose a step size small
+    /// enough not to miss any features, without sampling the noise module to
+    /// find out.
+    ///
+    /// The bound is an estimate, not the exact steepest gradient; it may be
+    /// several times larger.  Noise modules for which no bound can be
+    /// calculated, such as modifiers whose bound depends on their source
+    /// modules, return `None`.  The default implementation returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Constant, Module, Perlin};
+    ///
+    /// assert_eq!(Constant::new().max_gradient_estimate(), Some(0.0));
+    ///
+    /// let perlin = Perlin::new();
+    /// let bound = perlin.max_gradient_estimate().unwrap();
+    /// let step = (perlin.get_value(0.5, 0.0, 0.0) - perlin.get_value(0.25, 0.0, 0.0)).abs();
+    /// assert!(step <= bound * 0.25);
+    /// ```
+    fn max_gradient_estimate(&self) -> Option<f64> {
+        None
+    }
+
+    /// Returns this noise module as a [`Seeded`](trait.Seeded.html) noise
+    /// module, if it has a seed.
+    ///
+    /// This allows the seeds of a noise module graph to be changed without
+    /// knowing the concrete types of its noise modules, as
+    /// [`reseed_all()`](fn.reseed_all.html) does.  The default implementation
+    /// returns `None`.
+    fn as_seeded_mut(&mut self) -> Option<&mut dyn Seeded> {
+        None
+    }
+
+    /// Returns a reference to this noise module as `Any`, allowing a
+    /// type-erased module to be downcast to its concrete type.
+    ///
+    /// The default implementation returns this noise module itself.
+    fn as_any(&self) -> &dyn Any where Self: 'static {
+        self.to_any()
+    }
+
+    /// Returns a mutable reference to this noise module as `Any`, allowing a
+    /// type-erased module to be downcast to its concrete type.
+    ///
+    /// A `Box` forwards to the noise module it points to, as does an `Rc` or
+    /// `Arc` that is the only pointer to its noise module.  The default
+    /// implementation returns this noise module itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{BoxedModule, Module, Perlin};
+    ///
+    /// let mut graph: BoxedModule = Box::new(Perlin::new());
+    /// graph.as_any_mut().downcast_mut::<Perlin>().unwrap().set_seed(5);
+    ///
+    /// let mut expected = Perlin::new();
+    /// expected.set_seed(5);
+    /// assert_eq!(graph.config_hash(), expected.config_hash());
+    /// ```
+    fn as_any_mut(&mut self) -> &mut dyn Any where Self: 'static {
+        self.to_any_mut()
+    }
+}
+
+/// Implements the methods of `Module` that only need shared access for a
+/// pointer to a noise module, forwarding them to the noise module.
+macro_rules! forward_module_methods {
+    () => {
+        fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+            (**self).get_value(x, y, z)
+        }
+
+        fn get_value_row(&self, x: f64, y: f64, z: f64, x_step: f64, values: &mut [f64]) {
+            (**self).get_value_row(x, y, z, x_step, values)
+        }
+
+        fn config_hash(&self) -> u64 {
+            (**self).config_hash()
+        }
+
+        fn source_count(&self) -> usize {
+            (**self).source_count()
+        }
+
+        fn sources(&self) -> Vec<&dyn Module> {
+            (**self).sources()
+        }
+
+        fn source(&self, index: usize) -> Option<&dyn Module> {
+            (**self).source(index)
+        }
+
+        fn version(&self) -> u64 {
+            (**self).version()
+        }
+
+        fn max_gradient_estimate(&self) -> Option<f64> {
+            (**self).max_gradient_estimate()
+        }
+
+        fn as_any(&self) -> &dyn Any where Self: 'static {
+            (**self).as_any()
+        }
+    }
+}
+
+impl<M: Module + ?Sized + 'static> Module for Box<M> {
+    forward_module_methods!();
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        (**self).sources_mut()
+    }
+
+    fn as_seeded_mut(&mut self) -> Option<&mut dyn Seeded> {
+        (**self).as_seeded_mut()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any where Self: 'static {
+        (**self).as_any_mut()
+    }
+}
+
+impl<M: Module + ?Sized + 'static> Module for Rc<M> {
+    forward_module_methods!();
+
+    /// Returns the source modules of the shared noise module only if this is
+    /// the only pointer to it, and none otherwise.
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        match Rc::get_mut(self) {
+            Some(module) => module.sources_mut(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the shared noise module only if this is the only pointer to it.
+    fn as_seeded_mut(&mut self) -> Option<&mut dyn Seeded> {
+        Rc::get_mut(self).and_then(|module| module.as_seeded_mut())
+    }
+
+    /// # Panics
+    ///
+    /// Panics if this is not the only pointer to the shared noise module.
+    fn as_any_mut(&mut self) -> &mut dyn Any where Self: 'static {
+        match Rc::get_mut(self) {
+            Some(module) => module.as_any_mut(),
+            None => panic!("Cannot mutably downcast a shared module!"),
+        }
+    }
+}
+
+impl<M: Module + ?Sized + 'static> Module for Arc<M> {
+    forward_module_methods!();
+
+    /// Returns the source modules of the shared noise module only if this is
+    /// the only pointer to it, and none otherwise.
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        match Arc::get_mut(self) {
+            Some(module) => module.sources_mut(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the shared noise module only if this is the only pointer to it.
+    fn as_seeded_mut(&mut self) -> Option<&mut dyn Seeded> {
+        Arc::get_mut(self).and_then(|module| module.as_seeded_mut())
+    }
+
+    /// # Panics
+    ///
+    /// Panics if this is not the only pointer to the shared noise module.
+    fn as_any_mut(&mut self) -> &mut dyn Any where Self: 'static {
+        match Arc::get_mut(self) {
+            Some(module) => module.as_any_mut(),
+            None => panic!("Cannot mutably downcast a shared module!"),
+        }
+    }
 }
 
+impl<M: Module + ?Sized + 'static> Module for &M {
+    forward_module_methods!();
 
-impl<T: Deref<Target=Module>> Module for T {
-    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
-        self.deref().get_value(x, y, z)
+    /// # Panics
+    ///
+    /// Always panics, as a reference to a noise module only gives shared
+    /// access to it.
+    fn as_any_mut(&mut self) -> &mut dyn Any where Self: 'static {
+        panic!("Cannot mutably downcast a module through a shared reference!")
     }
 }
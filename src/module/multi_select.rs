@@ -0,0 +1,304 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{BoxedModule, ConfigHasher, Interp, Module};
+use std::fmt;
+use util::linear_interp;
+
+/// Default edge-falloff value for the [`MultiSelect`](struct.MultiSelect.html)
+/// noise module.
+pub const DEFAULT_MULTI_SELECT_EDGE_FALLOFF: f64 = 0.0;
+
+/// Default easing curve of the edge transitions for the
+/// [`MultiSelect`](struct.MultiSelect.html) noise module.
+pub const DEFAULT_MULTI_SELECT_INTERP: Interp = Interp::Cubic;
+
+/// Noise module that outputs the value selected from one of many source
+/// modules chosen by the output value from a control module.
+///
+/// Each source module belongs to a *band* of control values, given by the
+/// upper bound of the band.  The bands are kept sorted by their upper bounds,
+/// so each band covers the control values from the upper bound of the previous
+/// band up to, but not including, its own upper bound.  The first band extends
+/// downwards without limit, and the last band extends upwards without limit,
+/// so its upper bound is only used to order it.  To add a band, call the
+/// [`add_band()`](struct.MultiSelect.html#method.add_band) method.  With no
+/// bands, the output value is 0.0.
+///
+/// By default, there is an abrupt transition between the output values from
+/// adjacent bands.  To smooth the transitions, pass a non-zero value to the
+/// [`set_edge_falloff()`](struct.MultiSelect.html#method.set_edge_falloff)
+/// method.  The shape of the transitions is set by the
+/// [`set_interp()`](struct.MultiSelect.html#method.set_interp) method.
+///
+/// This is a generalization of the [`Select`](struct.Select.html) noise module
+/// to any number of source modules.
+///
+/// This noise module requires a control module and accepts any number of
+/// source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Constant, MultiSelect, Module, Perlin};
+///
+/// let mut select = MultiSelect::new(Perlin::new());
+/// for (i, &bound) in [-0.25, 0.25, 1.0].iter().enumerate() {
+///     let mut biome = Constant::new();
+///     biome.set_const_value(i as f64);
+///     select.add_band(bound, Box::new(biome));
+/// }
+///
+/// let control = Perlin::new().get_value(0.3, 0.5, 0.7);
+/// let expected = if control < -0.25 { 0.0 } else if control < 0.25 { 1.0 } else { 2.0 };
+/// assert_eq!(select.get_value(0.3, 0.5, 0.7), expected);
+/// ```
+pub struct MultiSelect<MC: Module> {
+    mcontrol: MC,
+    /// Upper bounds of the bands and their source modules, sorted by bound.
+    bands: Vec<(f64, BoxedModule)>,
+    edge_falloff: f64,
+    interp: Interp,
+}
+
+impl<MC: Module> MultiSelect<MC> {
+    /// Create a new `MultiSelect` noise module around the specified control
+    /// module, with no bands and default parameters.
+    pub fn new(control: MC) -> MultiSelect<MC> {
+        MultiSelect {
+            mcontrol: control,
+            bands: Vec::new(),
+            edge_falloff: DEFAULT_MULTI_SELECT_EDGE_FALLOFF,
+            interp: DEFAULT_MULTI_SELECT_INTERP,
+        }
+    }
+
+    /// Returns a reference to the control module.
+    ///
+    /// The control module determines which band, and so which source module,
+    /// the output value is selected from.
+    pub fn control_module(&self) -> &MC {
+        &self.mcontrol
+    }
+
+    /// Returns a mutable reference to the control module.
+    pub fn control_module_mut(&mut self) -> &mut MC {
+        &mut self.mcontrol
+    }
+
+    /// Returns a slice of the upper bounds of the bands and their source
+    /// modules, in order.
+    pub fn bands(&self) -> &[(f64, BoxedModule)] {
+        &self.bands
+    }
+
+    /// Returns a mutable reference to the source module of the band with the
+    /// given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the number of bands.
+    pub fn band_module_mut(&mut self, index: usize) -> &mut BoxedModule {
+        &mut self.bands[index].1
+    }
+
+    /// Returns the falloff value at the edge transitions.
+    ///
+    /// The falloff value is the width of the edge transition at either side of
+    /// the boundary between two adjacent bands.
+    pub fn edge_falloff(&self) -> f64 {
+        self.edge_falloff
+    }
+
+    /// Returns the easing curve of the edge transitions.
+    pub fn interp(&self) -> Interp {
+        self.interp
+    }
+
+    /// Sets the control module.
+    pub fn set_control_module(&mut self, control: MC) {
+        self.mcontrol = control;
+    }
+
+    /// Adds a band with the given upper bound, selecting the output value from
+    /// the given source module.
+    ///
+    /// It does not matter which order the bands are added.  The edge falloff
+    /// is reduced if necessary so that the edge transitions do not overlap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `upper_bound` is NaN, or if a band with the given upper bound
+    /// has already been added.
+    pub fn add_band(&mut self, upper_bound: f64, module: BoxedModule) {
+        if upper_bound.is_nan() {
+            // With this check the `unwrap()` in the binary search should always
+            // succeed.
+            panic!("Tried to insert NaN upper_bound!");
+        }
+        let f = |x: &(f64, BoxedModule)| x.0.partial_cmp(&upper_bound).unwrap();
+        match self.bands.binary_search_by(f) {
+            Ok(_) => {
+                panic!("Band with given upper bound already exists!");
+            },
+            Err(idx) => {
+                self.bands.insert(idx, (upper_bound, module));
+            }
+        }
+        self.clamp_falloff();
+    }
+
+    /// Deletes all the bands.
+    pub fn clear_bands(&mut self) {
+        self.bands.clear();
+    }
+
+    /// Sets the falloff value at the edge transitions.
+    ///
+    /// The falloff value is the width of the edge transition at either side of
+    /// the boundary between two adjacent bands.  Within the edge transition,
+    /// the output values from the source modules of the two bands are blended.
+    ///
+    /// For example, if two adjacent bands meet at 0.5 and the edge falloff
+    /// value is 0.1, the output value blends from the source module of the
+    /// lower band to that of the upper band as the output value from the
+    /// control module increases from 0.4 (= 0.5 - 0.1) to 0.6 (= 0.5 + 0.1).
+    ///
+    /// The edge falloff is reduced if necessary so that the edge transitions
+    /// do not overlap, i.e. to no more than half the width of the narrowest
+    /// band.
+    pub fn set_edge_falloff(&mut self, edge_falloff: f64) {
+        self.edge_falloff = edge_falloff;
+        self.clamp_falloff();
+    }
+
+    /// Sets the easing curve of the edge transitions.
+    ///
+    /// Within an edge transition, the output values from the two source
+    /// modules are blended with a weight mapped onto this curve.  By default
+    /// the weight follows a cubic S-curve, i.e.
+    /// [`Interp::Cubic`](enum.Interp.html).
+    pub fn set_interp(&mut self, interp: Interp) {
+        self.interp = interp;
+    }
+
+    /// Makes sure that the edge falloff curves do not overlap.
+    fn clamp_falloff(&mut self) {
+        // The upper bound of the last band is not a boundary, so it does not
+        // limit the falloff.
+        let boundaries = self.bands.len().saturating_sub(1);
+        for i in 1..boundaries {
+            let band_size = self.bands[i].0 - self.bands[i - 1].0;
+            if band_size / 2.0 < self.edge_falloff {
+                self.edge_falloff = band_size / 2.0;
+            }
+        }
+    }
+
+    /// Blends the output values from the source modules of the band with the
+    /// given index and the band above it, across the boundary between them.
+    fn blend_bands(&self, index: usize, control_value: f64, x: f64, y: f64, z: f64) -> f64 {
+        let lower_curve = self.bands[index].0 - self.edge_falloff;
+        let upper_curve = self.bands[index].0 + self.edge_falloff;
+        let alpha = self.interp.ease((control_value - lower_curve) / (upper_curve - lower_curve));
+        linear_interp(self.bands[index].1.get_value(x, y, z),
+                      self.bands[index + 1].1.get_value(x, y, z),
+                      alpha)
+    }
+}
+
+impl<MC: Module> Module for MultiSelect<MC> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        if self.bands.is_empty() {
+            return 0.0;
+        }
+
+        // Find the first band whose upper bound is above the control value;
+        // the last band also takes every control value above it.
+        let control_value = self.mcontrol.get_value(x, y, z);
+        let last = self.bands.len() - 1;
+        let index = self.bands[..last].iter()
+            .position(|band| control_value < band.0)
+            .unwrap_or(last);
+
+        if self.edge_falloff > 0.0 {
+            if index < last && control_value > self.bands[index].0 - self.edge_falloff {
+                // The control value is near the top of this band; blend with
+                // the band above.
+                return self.blend_bands(index, control_value, x, y, z);
+            } else if index > 0 && control_value < self.bands[index - 1].0 + self.edge_falloff {
+                // The control value is near the bottom of this band; blend
+                // with the band below.
+                return self.blend_bands(index - 1, control_value, x, y, z);
+            }
+        }
+        self.bands[index].1.get_value(x, y, z)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("MultiSelect");
+        hasher.write_u64(self.mcontrol.config_hash());
+        hasher.write_u64(self.bands.len() as u64);
+        for &(upper_bound, ref module) in &self.bands {
+            hasher.write_f64(upper_bound);
+            hasher.write_u64(module.config_hash());
+        }
+        hasher.write_f64(self.edge_falloff);
+        hasher.write_u64(self.interp as u64);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        self.bands.len() + 1
+    }
+
+    /// Returns the source modules of the bands in order, followed by the
+    /// control module.
+    fn sources(&self) -> Vec<&dyn Module> {
+        let mut sources: Vec<&dyn Module> = self.bands.iter().map(|band| &*band.1).collect();
+        sources.push(&self.mcontrol);
+        sources
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match self.bands.get(index) {
+            Some(band) => Some(&*band.1),
+            None if index == self.bands.len() => Some(&self.mcontrol),
+            None => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        let mut sources: Vec<&mut dyn Module> =
+            self.bands.iter_mut().map(|band| &mut *band.1 as &mut dyn Module).collect();
+        sources.push(&mut self.mcontrol);
+        sources
+    }
+}
+
+impl<MC: Module + fmt::Debug> fmt::Debug for MultiSelect<MC> {
+    // The boxed source modules cannot be printed, so only the upper bounds of
+    // their bands are.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bounds: Vec<f64> = self.bands.iter().map(|&(bound, _)| bound).collect();
+        f.debug_struct("MultiSelect")
+            .field("mcontrol", &self.mcontrol)
+            .field("bounds", &bounds)
+            .field("edge_falloff", &self.edge_falloff)
+            .field("interp", &self.interp)
+            .finish()
+    }
+}
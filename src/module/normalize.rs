@@ -0,0 +1,204 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+
+/// Default lower bound of the source range for the
+/// [`Normalize`](struct.Normalize.html) noise module.
+pub const DEFAULT_NORMALIZE_SOURCE_LOWER: f64 = -1.0;
+
+/// Default upper bound of the source range for the
+/// [`Normalize`](struct.Normalize.html) noise module.
+pub const DEFAULT_NORMALIZE_SOURCE_UPPER: f64 = 1.0;
+
+/// Default lower bound of the output range for the
+/// [`Normalize`](struct.Normalize.html) noise module.
+pub const DEFAULT_NORMALIZE_OUTPUT_LOWER: f64 = -1.0;
+
+/// Default upper bound of the output range for the
+/// [`Normalize`](struct.Normalize.html) noise module.
+pub const DEFAULT_NORMALIZE_OUTPUT_UPPER: f64 = 1.0;
+
+/// Noise module that clamps the output value from a source module to a range
+/// of values, then linearly maps that range onto another.
+///
+/// The range the output value from the source module is clamped to is called
+/// the *source range*, and the range it is mapped onto the *output range*.
+/// An output value from the source module at the lower bound of the source
+/// range, or below it, gives the lower bound of the output range, and one at
+/// the upper bound, or above it, gives the upper bound of the output range.
+/// Values in between are mapped linearly.
+///
+/// This is the usual way to normalize a noise module whose range has been
+/// measured, for example with
+/// [`NoiseMap::bounds()`](../noise_map/struct.NoiseMap.html#method.bounds),
+/// and does the work of a [`Clamp`](struct.Clamp.html) noise module followed
+/// by a [`ScaleBias`](struct.ScaleBias.html) noise module in a single noise
+/// module.
+///
+/// To specify the source range, call the
+/// [`set_source_range()`](struct.Normalize.html#method.set_source_range)
+/// method, and to specify the output range, call the
+/// [`set_output_range()`](struct.Normalize.html#method.set_output_range)
+/// method.  Both ranges default to -1.0 to +1.0.
+///
+/// This noise module requires one source module.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Constant, Module, Normalize};
+///
+/// let mut constant = Constant::new();
+/// constant.set_const_value(0.25);
+/// let mut normalize = Normalize::new(constant);
+/// normalize.set_source_range(0.0, 0.5);
+/// assert_eq!(normalize.get_value(0.0, 0.0, 0.0), 0.0);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Normalize<M: Module> {
+    module: M,
+    source_range: (f64, f64),
+    output_range: (f64, f64),
+}
+
+impl<M: Module> Normalize<M> {
+    /// Create a new `Normalize` noise module around the specified module,
+    /// using default parameters.
+    pub fn new(module: M) -> Normalize<M> {
+        Normalize {
+            module: module,
+            source_range: (DEFAULT_NORMALIZE_SOURCE_LOWER, DEFAULT_NORMALIZE_SOURCE_UPPER),
+            output_range: (DEFAULT_NORMALIZE_OUTPUT_LOWER, DEFAULT_NORMALIZE_OUTPUT_UPPER),
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Returns the lower and upper bounds of the source range.
+    pub fn source_range(&self) -> (f64, f64) {
+        self.source_range
+    }
+
+    /// Returns the lower and upper bounds of the output range.
+    pub fn output_range(&self) -> (f64, f64) {
+        self.output_range
+    }
+
+    /// Set the source module to be used.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+    }
+
+    /// Sets the lower and upper bounds of the source range.
+    ///
+    /// The output value from the source module is clamped to this range
+    /// before it is mapped onto the output range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either bound is not finite, or if the lower bound is not
+    /// smaller than the upper bound.
+    pub fn set_source_range(&mut self, lower_bound: f64, upper_bound: f64) {
+        if !lower_bound.is_finite() || !upper_bound.is_finite() {
+            panic!("Bounds must be finite!");
+        }
+        if lower_bound >= upper_bound {
+            panic!("Lower bound is not smaller than upper bound!");
+        }
+        self.source_range = (lower_bound, upper_bound);
+    }
+
+    /// Sets the lower and upper bounds of the output range.
+    ///
+    /// The lower bound of the source range is mapped onto `lower_bound`, and
+    /// the upper bound onto `upper_bound`.  A lower bound larger than the upper
+    /// bound inverts the output value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either bound is not finite.
+    pub fn set_output_range(&mut self, lower_bound: f64, upper_bound: f64) {
+        if !lower_bound.is_finite() || !upper_bound.is_finite() {
+            panic!("Bounds must be finite!");
+        }
+        self.output_range = (lower_bound, upper_bound);
+    }
+}
+
+impl<M: Module> Module for Normalize<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (source_lower, source_upper) = self.source_range;
+        let (output_lower, output_upper) = self.output_range;
+        let value = self.module.get_value(x, y, z);
+        let value = if value < source_lower {
+            source_lower
+        } else if value > source_upper {
+            source_upper
+        } else {
+            value
+        };
+        let a = (value - source_lower) / (source_upper - source_lower);
+        output_lower + a * (output_upper - output_lower)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Normalize");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_f64(self.source_range.0);
+        hasher.write_f64(self.source_range.1);
+        hasher.write_f64(self.output_range.0);
+        hasher.write_f64(self.output_range.1);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone> Clone for Normalize<M> {
+    fn clone(&self) -> Normalize<M> {
+        Normalize {
+            module: self.module.clone(),
+            source_range: self.source_range,
+            output_range: self.output_range,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use util::overlay_blend;
+
+/// Noise module that outputs the overlay blend of the output values from two
+/// source modules.
+///
+/// This is the *overlay* blend mode of image editors.  Where the output value
+/// from the first source module, the base, is low, the two output values are
+/// multiplied, darkening the result; where it is high, they are *screened*,
+/// brightening the result.  This increases the contrast of the second source
+/// module's detail while following the shape of the base.
+///
+/// Both output values are first mapped from the range -1.0 to +1.0 onto the
+/// range 0.0 to 1.0.  With `a` as the mapped base value and `b` as the other
+/// mapped value, the result is `2ab` if `a` is less than 0.5, and `1 - 2(1 -
+/// a)(1 - b)` otherwise.  The result is mapped back onto the range -1.0 to
+/// +1.0.  Output values outside of the range -1.0 to +1.0 are not clamped, so
+/// the result may also lie outside of it.
+///
+/// This noise module requires two source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Constant, Module, Overlay};
+///
+/// let mut base = Constant::new();
+/// base.set_const_value(-0.5);
+/// let mut detail = Constant::new();
+/// detail.set_const_value(0.5);
+///
+/// // The base maps to 0.25 and the detail to 0.75, so the result is
+/// // 2 * 0.25 * 0.75 = 0.375, which maps back to -0.25.
+/// let overlay = Overlay::new(base, detail);
+/// assert_eq!(overlay.get_value(0.0, 0.0, 0.0), -0.25);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Overlay<M1: Module, M2: Module> {
+    module1: M1,
+    module2: M2,
+}
+
+impl<M1: Module, M2: Module> Overlay<M1, M2> {
+    /// Create a new `Overlay` noise module around the specified modules, where
+    /// `module1` is the base.
+    pub fn new(module1: M1, module2: M2) -> Overlay<M1, M2> {
+        Overlay {
+            module1: module1,
+            module2: module2,
+        }
+    }
+
+    /// Returns a reference to the first source module used, i.e. the base.
+    pub fn module1(&self) -> &M1 {
+        &self.module1
+    }
+
+    /// Returns a mutable reference to the first source module used.
+    pub fn module1_mut(&mut self) -> &mut M1 {
+        &mut self.module1
+    }
+
+    /// Returns a reference to the second source module used.
+    pub fn module2(&self) -> &M2 {
+        &self.module2
+    }
+
+    /// Returns a mutable reference to the second source module used.
+    pub fn module2_mut(&mut self) -> &mut M2 {
+        &mut self.module2
+    }
+
+    /// Set the first source module to be used.
+    pub fn set_module1(&mut self, module: M1) {
+        self.module1 = module;
+    }
+
+    /// Set the second source module to be used.
+    pub fn set_module2(&mut self, module: M2) {
+        self.module2 = module;
+    }
+}
+
+impl<M1: Module, M2: Module> Module for Overlay<M1, M2> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let value1 = self.module1.get_value(x, y, z);
+        let value2 = self.module2.get_value(x, y, z);
+        overlay_blend(value1, value2)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Overlay");
+        hasher.write_u64(self.module1.config_hash());
+        hasher.write_u64(self.module2.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        2
+    }
+
+    /// Returns the first and second source modules, in that order.
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module1 as &dyn Module, &self.module2]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module1),
+            1 => Some(&self.module2),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module1 as &mut dyn Module, &mut self.module2]
+    }
+}
+
+impl<M1: Module + Clone, M2: Module + Clone> Clone for Overlay<M1, M2> {
+    fn clone(&self) -> Overlay<M1, M2> {
+        Overlay {
+            module1: self.module1.clone(),
+            module2: self.module2.clone(),
+        }
+    }
+}
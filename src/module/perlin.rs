@@ -14,8 +14,18 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
-use noisegen::{gradient_coherent_noise3d, make_i32_range, NoiseQuality};
+use module::{ConfigHasher, Module, Seeded};
+#[cfg(feature = "simd")]
+use noisegen::gradient_coherent_noise3d_x4;
+use noisegen::{gradient_coherent_noise2d, gradient_coherent_noise2d_max_gradient,
+               gradient_coherent_noise2d_with_gradient, gradient_coherent_noise3d,
+               gradient_coherent_noise3d_custom, gradient_coherent_noise3d_max_gradient,
+               gradient_coherent_noise3d_table, gradient_coherent_noise3d_with_gradient,
+               gradient_coherent_noise3d_with_gradient_custom,
+               gradient_coherent_noise3d_with_gradient_table, make_i32_range,
+               i32_value_noise3d, make_i32_range_deriv, Dimensions, GradientTable, NoiseQuality,
+               PermTable};
+use util::amplitude_sum;
 
 /// Default frequency for the [`Perlin`](struct.Perlin.html) noise module.
 pub const DEFAULT_PERLIN_FREQUENCY: f64 = 1.0;
@@ -37,10 +47,25 @@ pub const DEFAULT_PERLIN_QUALITY: NoiseQuality = NoiseQuality::Standard;
 /// Default noise seed for the [`Perlin`](struct.Perlin.html) noise module.
 pub const DEFAULT_PERLIN_SEED: i32 = 0;
 
+/// Default number of dimensions of the coherent noise for the
+/// [`Perlin`](struct.Perlin.html) noise module.
+pub const DEFAULT_PERLIN_DIMENSIONS: Dimensions = Dimensions::Dim3;
+
 /// Maximum number of octaves for the [`Perlin`](struct.Perlin.html) noise
 /// module.
 pub const PERLIN_MAX_OCTAVE: i32 = 30;
 
+/// Rotation applied to the input value between successive octaves when octave
+/// rotation is enabled.  Neither its axis nor its angle is aligned with the
+/// grid of the coherent noise.
+const OCTAVE_ROTATION: [[f64; 3]; 3] = [[0.0, 0.8, 0.6],
+                                        [-0.8, 0.36, -0.48],
+                                        [-0.6, -0.48, 0.64]];
+
+/// Largest translation along each axis applied to the input value of an
+/// octave when octave offsets are enabled.
+const OCTAVE_OFFSET_RANGE: f64 = 256.0;
+
 /// Noise module that outputs 3-dimensional Perlin noise.
 ///
 /// Perlin noise is the sum of several coherent-noise functions of
@@ -52,7 +77,8 @@ pub const PERLIN_MAX_OCTAVE: i32 = 30;
 ///
 /// This noise module outputs Perlin-noise values that usually range from -1.0
 /// to +1.0, but there are no guarantees that all output values will exist
-/// within that range.
+/// within that range.  To guarantee that range, call the
+/// [`set_normalize()`](struct.Perlin.html#method.set_normalize) method.
 ///
 /// For a better description of Perlin noise, see the links in the *References
 /// and Acknowledgments* section.
@@ -74,6 +100,10 @@ pub const PERLIN_MAX_OCTAVE: i32 = 30;
 /// An application may specify the number of octaves that generate Perlin noise
 /// by calling the
 /// [`set_octave_count()`](struct.Perlin.html#method.set_octave_count) method.
+/// To fade detail in smoothly, a fractional number of octaves may be set with
+/// the
+/// [`set_octave_count_f()`](struct.Perlin.html#method.set_octave_count_f)
+/// method.
 ///
 /// These coherent-noise functions are called octaves because each octave has,
 /// by default, double the frequency of the previous octave.  Musical tones have
@@ -108,6 +138,31 @@ pub const PERLIN_MAX_OCTAVE: i32 = 30;
 /// the lacunarity value to determine the effects.  For best results, set the
 /// lacunarity to a number between 1.5 and 3.5.
 ///
+/// ## Octave rotation
+///
+/// The coherent noise of every octave is built on a grid aligned with the
+/// axes, so its faint directional artifacts line up across octaves and add
+/// up to visible streaking along the axes.  Rotating the input value by a
+/// different angle for each octave decorrelates the grids.  To enable this,
+/// call the
+/// [`set_octave_rotation()`](struct.Perlin.html#method.set_octave_rotation)
+/// method.
+///
+/// A cheaper way to misalign the grids is to translate the input value of
+/// each octave by a different pseudo-random offset, which also stops the
+/// lattice points of the octaves from coinciding at the origin.  To enable
+/// this, call the
+/// [`set_octave_offset()`](struct.Perlin.html#method.set_octave_offset)
+/// method.  The two can be combined.
+///
+/// ## Dimensions
+///
+/// By default the octaves are three-dimensional coherent noise.  For
+/// two-dimensional uses such as height maps, the octaves can instead be
+/// genuinely two-dimensional coherent noise, which ignores the `z` coordinate
+/// and is faster to calculate.  To select this, call the
+/// [`set_dimensions()`](struct.Perlin.html#method.set_dimensions) method.
+///
 /// ## References & Acknowledgments
 ///
 /// [The Noise Machine](http://www.noisemachine.com/talk1/) - From the master,
@@ -129,14 +184,25 @@ pub const PERLIN_MAX_OCTAVE: i32 = 30;
 /// terrain features.  This page describes a better coherent-noise function
 /// called *gradient noise*.  This version of Perlin uses gradient coherent
 /// noise to generate Perlin noise.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Perlin {
     frequency: f64,
     lacunarity: f64,
     quality: NoiseQuality,
     octave_count: i32,
+    /// Amplitude scale of the partial octave after the last full octave.
+    octave_fraction: f64,
     persistence: f64,
     seed: i32,
+    /// 64-bit seed hashed into the seed of each octave, if one is set.
+    seed_key: Option<u64>,
+    perm_table: Option<PermTable>,
+    /// Custom gradient vectors, if any; `None` uses the standard set.
+    gradient_table: Option<GradientTable>,
+    normalize: bool,
+    octave_rotation: bool,
+    octave_offset: bool,
+    dimensions: Dimensions,
 }
 
 impl Default for Perlin {
@@ -147,8 +213,16 @@ impl Default for Perlin {
             lacunarity: DEFAULT_PERLIN_LACUNARITY,
             quality: DEFAULT_PERLIN_QUALITY,
             octave_count: DEFAULT_PERLIN_OCTAVE_COUNT,
+            octave_fraction: 0.0,
             persistence: DEFAULT_PERLIN_PERSISTENCE,
             seed: DEFAULT_PERLIN_SEED,
+            seed_key: None,
+            perm_table: None,
+            gradient_table: None,
+            normalize: false,
+            octave_rotation: false,
+            octave_offset: false,
+            dimensions: DEFAULT_PERLIN_DIMENSIONS,
         }
     }
 }
@@ -182,10 +256,19 @@ impl Perlin {
     /// Returns the number of octaves that generate the Perlin noise.
     ///
     /// The number of octaves controls the amount of detail in the Perlin noise.
+    ///
+    /// If a fractional number of octaves is set, this is the number of full
+    /// octaves.
     pub fn octave_count(&self) -> i32 {
         self.octave_count
     }
 
+    /// Returns the number of octaves that generate the Perlin noise, including
+    /// any fractional part.
+    pub fn octave_count_f(&self) -> f64 {
+        self.octave_count as f64 + self.octave_fraction
+    }
+
     /// Returns the persistence value of the Perlin noise.
     ///
     /// The persistence value controls the roughness of the Perlin noise.
@@ -194,10 +277,56 @@ impl Perlin {
     }
 
     /// Returns the seed value used by the Perlin-noise function.
+    ///
+    /// If a 64-bit seed is set, this is the seed of the first octave derived
+    /// from it.
     pub fn seed(&self) -> i32 {
         self.seed
     }
 
+    /// Returns the 64-bit seed value used by the Perlin-noise function, if one
+    /// is set.
+    ///
+    /// See [`set_seed_u64()`](struct.Perlin.html#method.set_seed_u64) for
+    /// details.
+    pub fn seed_u64(&self) -> Option<u64> {
+        self.seed_key
+    }
+
+    /// Determines if the output value is normalized to the range -1.0 to
+    /// +1.0.
+    pub fn is_normalized(&self) -> bool {
+        self.normalize
+    }
+
+    /// Determines if the input value is rotated differently for each octave.
+    pub fn is_octave_rotation(&self) -> bool {
+        self.octave_rotation
+    }
+
+    /// Determines if the input value is translated differently for each
+    /// octave.
+    pub fn is_octave_offset(&self) -> bool {
+        self.octave_offset
+    }
+
+    /// Returns the number of dimensions of the coherent noise.
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    /// Returns the permutation table used to generate the coherent noise, if
+    /// any.
+    pub fn perm_table(&self) -> Option<&PermTable> {
+        self.perm_table.as_ref()
+    }
+
+    /// Returns the custom gradient vectors used to generate the coherent
+    /// noise, if any.
+    pub fn gradient_table(&self) -> Option<&GradientTable> {
+        self.gradient_table.as_ref()
+    }
+
     /// Sets the frequency of the first octave.
     pub fn set_frequency(&mut self, frequency: f64) {
         self.frequency = frequency;
@@ -237,6 +366,50 @@ impl Perlin {
             panic!("`octave_count` must be in the range [{}, {}]", 1, PERLIN_MAX_OCTAVE);
         }
         self.octave_count = octave_count;
+        self.octave_fraction = 0.0;
+    }
+
+    /// Sets the number of octaves that generate the Perlin noise, which need
+    /// not be a whole number.
+    ///
+    /// The whole part of `octave_count` is the number of full octaves.  The
+    /// fractional part scales the amplitude of one further, partial octave, so
+    /// increasing `octave_count` smoothly fades in the next octave rather than
+    /// adding it all at once.  This avoids visible popping when the level of
+    /// detail is animated.  A whole `octave_count` is the same as passing it
+    /// to [`set_octave_count()`](struct.Perlin.html#method.set_octave_count).
+    ///
+    /// The partial octave counts towards the limit of
+    /// [`PERLIN_MAX_OCTAVE`](constant.PERLIN_MAX_OCTAVE.html), so the greatest
+    /// accepted value is `PERLIN_MAX_OCTAVE` itself, which has no partial
+    /// octave.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given octave count is outside the range from 1.0 to
+    /// [`PERLIN_MAX_OCTAVE`](constant.PERLIN_MAX_OCTAVE.html) inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Perlin};
+    ///
+    /// let value_at = |octave_count: f64| {
+    ///     let mut perlin = Perlin::new();
+    ///     perlin.set_octave_count_f(octave_count);
+    ///     perlin.get_value(0.3, 0.5, 0.7)
+    /// };
+    ///
+    /// let (lower, upper) = (value_at(2.0), value_at(3.0));
+    /// let between = value_at(2.25);
+    /// assert!(lower.min(upper) < between && between < lower.max(upper));
+    /// ```
+    pub fn set_octave_count_f(&mut self, octave_count: f64) {
+        if octave_count.is_nan() || octave_count < 1.0 || octave_count > PERLIN_MAX_OCTAVE as f64 {
+            panic!("`octave_count` must be in the range [{}, {}]", 1, PERLIN_MAX_OCTAVE);
+        }
+        self.octave_count = octave_count.floor() as i32;
+        self.octave_fraction = octave_count - octave_count.floor();
     }
 
     /// Sets the persistence value of the Perlin noise.
@@ -249,18 +422,443 @@ impl Perlin {
     }
 
     /// Sets the seed value used by the Perlin-noise function.
+    ///
+    /// Every `i32` is a valid seed.  Each octave uses the seed value plus the
+    /// index of the octave, wrapping around on overflow, with the sign bit
+    /// cleared, as in [`RidgedMulti`](../ridged_multi/struct.RidgedMulti.html).
+    /// Seeds that differ only in their sign bit therefore generate the same
+    /// noise.
+    ///
+    /// Because of this, the octaves of adjacent seeds overlap: each octave of
+    /// seed `n + 1` is the next octave of seed `n`.  To seed many independent
+    /// regions with consecutive numbers, use
+    /// [`set_seed_u64()`](struct.Perlin.html#method.set_seed_u64) instead.
+    ///
+    /// This clears any 64-bit seed.
     pub fn set_seed(&mut self, seed: i32) {
         self.seed = seed;
+        self.seed_key = None;
     }
-}
 
-impl Module for Perlin {
-    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+    /// Sets a 64-bit seed value used by the Perlin-noise function.
+    ///
+    /// Instead of adding the index of each octave to the seed, the seed of
+    /// each octave is derived by hashing the 64-bit seed together with the
+    /// index of the octave.  No two octaves of different 64-bit seeds are
+    /// related, so even consecutive seeds, such as the IDs of adjacent
+    /// chunks, produce uncorrelated noise.  The output values differ from
+    /// those of every `i32` seed set by
+    /// [`set_seed()`](struct.Perlin.html#method.set_seed).
+    ///
+    /// The coherent-noise functions only take a 32-bit seed per octave, so two
+    /// 64-bit seeds have a small chance, about one in 2<sup>32</sup> per
+    /// octave, of sharing an octave.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Perlin};
+    ///
+    /// let mut chunk = Perlin::new();
+    /// chunk.set_seed_u64(41);
+    /// let mut neighbour = Perlin::new();
+    /// neighbour.set_seed_u64(42);
+    /// assert!(chunk.get_value(0.3, 0.5, 0.7) != neighbour.get_value(0.3, 0.5, 0.7));
+    /// assert_eq!(chunk.seed_u64(), Some(41));
+    /// ```
+    pub fn set_seed_u64(&mut self, seed: u64) {
+        self.seed_key = Some(seed);
+        self.seed = self.octave_seed(0);
+    }
+
+    /// Sets the permutation table used to generate the coherent noise.
+    ///
+    /// By default no table is used, and the coherent noise hashes the integer
+    /// coordinates on the fly.  Hashing through a
+    /// [`PermTable`](../noisegen/struct.PermTable.html) changes the output
+    /// values, which then repeat every 256 units at the frequency of each
+    /// octave.  See that type for details.
+    ///
+    /// The table is not affected by the seed of this noise module, which only
+    /// selects a variation of the table.  To reseed the noise, also set a new
+    /// table created with the new seed.
+    pub fn set_perm_table(&mut self, perm_table: Option<PermTable>) {
+        self.perm_table = perm_table;
+    }
+
+    /// Sets the gradient vectors used to generate the coherent noise.
+    ///
+    /// By default the standard set of gradient vectors is used, which point
+    /// evenly in every direction.  A restricted set of gradient vectors, such
+    /// as only the axis-aligned ones, gives the noise a stylized character; see
+    /// [`GradientTable`](../noisegen/struct.GradientTable.html) for details.
+    /// Each octave picks its gradient vectors from the same table, in the same
+    /// way as it would pick them from the standard set, so setting
+    /// [`GradientTable::standard()`](../noisegen/struct.GradientTable.html#method.standard)
+    /// leaves the output values unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Perlin};
+    /// use noise::noisegen::GradientTable;
+    ///
+    /// let mut perlin = Perlin::new();
+    /// let value = perlin.get_value(0.3, 0.5, 0.7);
+    /// perlin.set_gradient_table(Some(GradientTable::new(&[[1.0, 0.0, 0.0], [-1.0, 0.0, 0.0],
+    ///                                                     [0.0, 1.0, 0.0], [0.0, -1.0, 0.0],
+    ///                                                     [0.0, 0.0, 1.0], [0.0, 0.0, -1.0]])));
+    /// assert!(perlin.get_value(0.3, 0.5, 0.7) != value);
+    /// ```
+    pub fn set_gradient_table(&mut self, gradient_table: Option<GradientTable>) {
+        self.gradient_table = gradient_table;
+    }
+
+    /// Enables or disables normalizing the output value to the range -1.0 to
+    /// +1.0.
+    ///
+    /// When enabled, the sum of the octaves is divided by the sum of their
+    /// amplitudes, which is `(1 - persistence^octave_count) / (1 -
+    /// persistence)` for a whole number of octaves.  Because the coherent
+    /// noise of a single octave can itself slightly exceed the range -1.0 to
+    /// +1.0, the result is then clamped to that range, which guarantees it.
+    ///
+    /// The octaves rarely all reach their extremes at the same input value, so
+    /// normalized output values cluster more tightly around zero than the
+    /// range allows, reducing the contrast compared with the default output.
+    ///
+    /// By default, normalization is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Perlin};
+    ///
+    /// let mut perlin = Perlin::new();
+    /// perlin.set_persistence(0.9);
+    /// perlin.set_normalize(true);
+    /// for i in 0..10000 {
+    ///     let value = perlin.get_value(i as f64 * 0.0173, i as f64 * 0.0071, 0.5);
+    ///     assert!(value >= -1.0 && value <= 1.0);
+    /// }
+    /// ```
+    pub fn set_normalize(&mut self, normalize: bool) {
+        self.normalize = normalize;
+    }
+
+    /// Enables or disables rotating the input value differently for each
+    /// octave.
+    ///
+    /// When enabled, the input value of each octave after the first is
+    /// rotated by a fixed rotation relative to the previous octave, as well as
+    /// being scaled by the lacunarity.  The rotation does not depend on the
+    /// input value or seed, so the output value is still deterministic.  This
+    /// breaks up the streaking along the axes caused by the grids of the
+    /// octaves lining up, at the cost of a matrix multiplication per octave,
+    /// which is small next to the cost of the coherent noise.
+    ///
+    /// By default, octave rotation is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Perlin};
+    ///
+    /// let mut perlin = Perlin::new();
+    /// let value = perlin.get_value(0.3, 0.5, 0.7);
+    /// perlin.set_octave_rotation(true);
+    /// let rotated = perlin.get_value(0.3, 0.5, 0.7);
+    /// assert!(rotated != value);
+    ///
+    /// let mut other = Perlin::new();
+    /// other.set_octave_rotation(true);
+    /// assert_eq!(other.get_value(0.3, 0.5, 0.7), rotated);
+    /// ```
+    pub fn set_octave_rotation(&mut self, octave_rotation: bool) {
+        self.octave_rotation = octave_rotation;
+    }
+
+    /// Enables or disables translating the input value differently for each
+    /// octave.
+    ///
+    /// When enabled, the input value of each octave is translated by an offset
+    /// of up to 256 units along each axis just before its coherent noise is
+    /// calculated.  The offset is derived from the seed of the octave, so the
+    /// output value is still deterministic, and it changes with the seed.
+    /// This misaligns the grids of the octaves more cheaply than
+    /// [octave rotation](struct.Perlin.html#method.set_octave_rotation), at
+    /// the cost of three integer hashes per octave.
+    ///
+    /// By default, octave offsets are disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Perlin};
+    ///
+    /// let mut perlin = Perlin::new();
+    /// let value = perlin.get_value(0.3, 0.5, 0.7);
+    /// perlin.set_octave_offset(true);
+    /// let offset = perlin.get_value(0.3, 0.5, 0.7);
+    /// assert!(offset != value);
+    ///
+    /// let mut other = Perlin::new();
+    /// other.set_octave_offset(true);
+    /// assert_eq!(other.get_value(0.3, 0.5, 0.7), offset);
+    /// ```
+    pub fn set_octave_offset(&mut self, octave_offset: bool) {
+        self.octave_offset = octave_offset;
+    }
+
+    /// Sets the number of dimensions of the coherent noise.
+    ///
+    /// With [`Dimensions::Dim2`](../noisegen/enum.Dimensions.html), the `z`
+    /// coordinate of the input value is ignored, and each octave is
+    /// two-dimensional coherent noise from
+    /// [`gradient_coherent_noise2d()`](../noisegen/fn.gradient_coherent_noise2d.html),
+    /// which is faster to calculate than three-dimensional coherent noise.
+    /// This changes the output values, not just the speed: two-dimensional
+    /// noise uses different gradient vectors, and is not the same as
+    /// three-dimensional noise sampled on the `z = 0` plane.  The permutation
+    /// table and custom gradient vectors only apply to three-dimensional
+    /// noise, and are ignored in two-dimensional mode.  Octave rotation still
+    /// applies; the rotated input value of each octave has a `z` coordinate,
+    /// but only its `x` and `y` coordinates are used.
+    ///
+    /// By default, the coherent noise is three-dimensional.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Perlin};
+    /// use noise::noisegen::Dimensions;
+    ///
+    /// let mut perlin = Perlin::new();
+    /// perlin.set_dimensions(Dimensions::Dim2);
+    /// assert_eq!(perlin.get_value(0.3, 0.5, 0.0), perlin.get_value(0.3, 0.5, 7.0));
+    /// assert!(perlin.get_value(0.3, 0.5, 0.0) != Perlin::new().get_value(0.3, 0.5, 0.0));
+    /// ```
+    pub fn set_dimensions(&mut self, dimensions: Dimensions) {
+        self.dimensions = dimensions;
+    }
+
+    /// Returns the output value at the given input value, along with the
+    /// gradient of the Perlin noise at that point.
+    ///
+    /// The output value is the same as that returned by
+    /// [`get_value()`](struct.Perlin.html#method.get_value).  The gradient is
+    /// given as the partial derivatives `[d/dx, d/dy, d/dz]` of the output
+    /// value with respect to each coordinate of the input value.  It is
+    /// calculated analytically by summing the derivative of each octave, scaled
+    /// by that octave's amplitude and frequency, so it is both exact and much
+    /// cheaper than sampling the noise at neighbouring points.
+    ///
+    /// Analytic gradients are only available from noise modules that provide a
+    /// method like this one; other noise modules must be differentiated
+    /// numerically.
+    pub fn get_value_and_gradient(&self, x: f64, y: f64, z: f64) -> (f64, [f64; 3]) {
+        let mut value = 0.0;
+        let mut gradient = [0.0; 3];
+        let mut cur_persistence = 1.0;
+        let mut cur_frequency = self.frequency;
+        // Total rotation of the input value of the current octave.
+        let mut rotation = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let (mut x, mut y, mut z) = self.first_octave_input(x, y, z);
+
+        for cur_octave in 0..self.octave_count {
+            let nx = make_i32_range(x);
+            let ny = make_i32_range(y);
+            let nz = make_i32_range(z);
+
+            // Get the coherent-noise value and its gradient, then apply the
+            // chain rule for the scaling of the input value to this octave.
+            let seed = self.octave_seed(cur_octave);
+            let (signal, signal_gradient) = self.signal_and_gradient(nx, ny, nz, seed);
+            value += signal * cur_persistence;
+            let scale = cur_persistence * cur_frequency;
+            self.add_octave_gradient(&mut gradient,
+                                     [signal_gradient[0] * make_i32_range_deriv(x) * scale,
+                                      signal_gradient[1] * make_i32_range_deriv(y) * scale,
+                                      signal_gradient[2] * make_i32_range_deriv(z) * scale],
+                                     &rotation);
+
+            // Prepare the next octave.
+            let next = self.next_octave(x, y, z);
+            x = next.0;
+            y = next.1;
+            z = next.2;
+            cur_persistence *= self.persistence;
+            cur_frequency *= self.lacunarity;
+            if self.octave_rotation {
+                rotation = mat_mul(&OCTAVE_ROTATION, &rotation);
+            }
+        }
+
+        if self.octave_fraction > 0.0 {
+            let nx = make_i32_range(x);
+            let ny = make_i32_range(y);
+            let nz = make_i32_range(z);
+
+            let seed = self.octave_seed(self.octave_count);
+            let (signal, signal_gradient) = self.signal_and_gradient(nx, ny, nz, seed);
+            let amplitude = cur_persistence * self.octave_fraction;
+            value += signal * amplitude;
+            let scale = amplitude * cur_frequency;
+            self.add_octave_gradient(&mut gradient,
+                                     [signal_gradient[0] * make_i32_range_deriv(x) * scale,
+                                      signal_gradient[1] * make_i32_range_deriv(y) * scale,
+                                      signal_gradient[2] * make_i32_range_deriv(z) * scale],
+                                     &rotation);
+        }
+
+        // In two-dimensional mode the `z` coordinate of the input value is
+        // ignored, but octave rotation can still turn the gradient of an
+        // octave out of the `x`-`y` plane.
+        if self.dimensions == Dimensions::Dim2 {
+            gradient[2] = 0.0;
+        }
+
+        if self.normalize {
+            let amplitude_sum = self.amplitude_sum();
+            let value = value / amplitude_sum;
+            if value.abs() > 1.0 {
+                // The output value is clamped, so does not change.
+                return (value.signum(), [0.0; 3]);
+            }
+            for component in &mut gradient {
+                *component /= amplitude_sum;
+            }
+            return (value, gradient);
+        }
+
+        (value, gradient)
+    }
+
+    /// Returns the index of the octave that contributes the most to the output
+    /// value at the given input value.
+    ///
+    /// The contribution of each octave is the magnitude of its coherent-noise
+    /// signal multiplied by its amplitude.  Octave 0 has the lowest frequency.
+    /// If several octaves contribute equally, the lowest of them is returned.
+    ///
+    /// This can guide level-of-detail decisions, such as how densely to sample
+    /// a region or whether higher-frequency detail can be culled at a
+    /// distance.  It evaluates every octave, so it costs the same as
+    /// [`get_value()`](struct.Perlin.html#method.get_value).
+    ///
+    /// # Examples
+    ///
+    /// Raising the persistence gives the higher octaves larger amplitudes, so
+    /// they tend to dominate:
+    ///
+    /// ```
+    /// use noise::module::Perlin;
+    ///
+    /// fn mean_dominant_octave(persistence: f64) -> f64 {
+    ///     let mut perlin = Perlin::new();
+    ///     perlin.set_persistence(persistence);
+    ///     let mut total = 0;
+    ///     for i in 0..100 {
+    ///         total += perlin.dominant_octave(i as f64 * 0.37, 0.5, 0.25);
+    ///     }
+    ///     total as f64 / 100.0
+    /// }
+    ///
+    /// assert!(mean_dominant_octave(0.9) > mean_dominant_octave(0.3));
+    /// ```
+    pub fn dominant_octave(&self, x: f64, y: f64, z: f64) -> i32 {
+        let mut dominant = 0;
+        let mut dominant_contribution = -1.0;
+        let mut cur_persistence = 1.0;
+        let (mut x, mut y, mut z) = self.first_octave_input(x, y, z);
+
+        for cur_octave in 0..self.octave_count {
+            let nx = make_i32_range(x);
+            let ny = make_i32_range(y);
+            let nz = make_i32_range(z);
+
+            let signal = self.signal(nx, ny, nz, self.octave_seed(cur_octave));
+            let contribution = (signal * cur_persistence).abs();
+            if contribution > dominant_contribution {
+                dominant = cur_octave;
+                dominant_contribution = contribution;
+            }
+
+            // Prepare the next octave.
+            let next = self.next_octave(x, y, z);
+            x = next.0;
+            y = next.1;
+            z = next.2;
+            cur_persistence *= self.persistence;
+        }
+
+        // A partial octave counts with its scaled amplitude.
+        if self.octave_fraction > 0.0 &&
+           self.partial_octave(x, y, z, cur_persistence).abs() > dominant_contribution {
+            dominant = self.octave_count;
+        }
+
+        dominant
+    }
+
+    /// Returns the contribution of each octave to the output value at the given
+    /// input value, in order from the lowest frequency.
+    ///
+    /// The contribution of an octave is its coherent-noise signal multiplied by
+    /// its amplitude, keeping its sign, so the elements add up to exactly the
+    /// output value returned by
+    /// [`get_value()`](struct.Perlin.html#method.get_value) when summed in
+    /// order, unless normalization is enabled.  There is one element per
+    /// octave, plus one for the partial octave if a fractional number of
+    /// octaves is set.
+    ///
+    /// This is intended for debugging, such as checking which octaves are
+    /// responsible for unwanted detail when tuning the frequency and
+    /// persistence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Perlin};
+    ///
+    /// let perlin = Perlin::new();
+    /// let contributions = perlin.octave_contributions(0.3, 0.5, 0.7);
+    /// assert_eq!(contributions.len(), perlin.octave_count() as usize);
+    /// assert_eq!(contributions.iter().sum::<f64>(), perlin.get_value(0.3, 0.5, 0.7));
+    /// ```
+    pub fn octave_contributions(&self, x: f64, y: f64, z: f64) -> Vec<f64> {
+        let mut contributions = Vec::with_capacity(self.octave_count as usize + 1);
+        let mut cur_persistence = 1.0;
+        let (mut x, mut y, mut z) = self.first_octave_input(x, y, z);
+
+        for cur_octave in 0..self.octave_count {
+            let nx = make_i32_range(x);
+            let ny = make_i32_range(y);
+            let nz = make_i32_range(z);
+
+            let signal = self.signal(nx, ny, nz, self.octave_seed(cur_octave));
+            contributions.push(signal * cur_persistence);
+
+            // Prepare the next octave.
+            let next = self.next_octave(x, y, z);
+            x = next.0;
+            y = next.1;
+            z = next.2;
+            cur_persistence *= self.persistence;
+        }
+
+        if self.octave_fraction > 0.0 {
+            contributions.push(self.partial_octave(x, y, z, cur_persistence));
+        }
+
+        contributions
+    }
+
+    /// Calculates the output value one octave at a time.
+    fn get_value_scalar(&self, x: f64, y: f64, z: f64) -> f64 {
         let mut value = 0.0;
         let mut cur_persistence = 1.0;
-        let mut x = x * self.frequency;
-        let mut y = y * self.frequency;
-        let mut z = z * self.frequency;
+        let (mut x, mut y, mut z) = self.first_octave_input(x, y, z);
 
         for cur_octave in 0..self.octave_count {
             // Make sure that these floating-point values have the same range as
@@ -272,17 +870,314 @@ impl Module for Perlin {
 
             // Get the coherent-noise value from the input value and add it to
             // the final result.
-            let seed = self.seed + cur_octave;
-            let signal = gradient_coherent_noise3d(nx, ny, nz, seed, self.quality);
+            let signal = self.signal(nx, ny, nz, self.octave_seed(cur_octave));
             value += signal * cur_persistence;
 
             // Prepare the next octave.
-            x *= self.lacunarity;
-            y *= self.lacunarity;
-            z *= self.lacunarity;
+            let next = self.next_octave(x, y, z);
+            x = next.0;
+            y = next.1;
+            z = next.2;
             cur_persistence *= self.persistence;
         }
 
-        value
+        self.normalize_value(value + self.partial_octave(x, y, z, cur_persistence))
+    }
+
+    /// Returns the input value of the first octave, which drops the `z`
+    /// coordinate in two-dimensional mode.
+    fn first_octave_input(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let z = match self.dimensions {
+            Dimensions::Dim2 => 0.0,
+            Dimensions::Dim3 => z * self.frequency,
+        };
+        (x * self.frequency, y * self.frequency, z)
+    }
+
+    /// Returns the input value of the octave after the one with the given
+    /// input value.
+    fn next_octave(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let (x, y, z) = if self.octave_rotation {
+            let m = &OCTAVE_ROTATION;
+            (m[0][0] * x + m[0][1] * y + m[0][2] * z,
+             m[1][0] * x + m[1][1] * y + m[1][2] * z,
+             m[2][0] * x + m[2][1] * y + m[2][2] * z)
+        } else {
+            (x, y, z)
+        };
+        (x * self.lacunarity, y * self.lacunarity, z * self.lacunarity)
+    }
+
+    /// Adds the gradient of an octave with respect to its own input value to
+    /// the gradient with respect to the input value of the first octave,
+    /// undoing the given rotation of the octave's input value.
+    fn add_octave_gradient(&self, gradient: &mut [f64; 3], octave_gradient: [f64; 3],
+                           rotation: &[[f64; 3]; 3]) {
+        if self.octave_rotation {
+            for (i, component) in gradient.iter_mut().enumerate() {
+                *component += rotation[0][i] * octave_gradient[0]
+                            + rotation[1][i] * octave_gradient[1]
+                            + rotation[2][i] * octave_gradient[2];
+            }
+        } else {
+            for (component, octave_component) in gradient.iter_mut().zip(&octave_gradient) {
+                *component += *octave_component;
+            }
+        }
+    }
+
+    /// Normalizes the sum of the octaves if normalization is enabled.
+    fn normalize_value(&self, value: f64) -> f64 {
+        if self.normalize {
+            (value / self.amplitude_sum()).clamp(-1.0, 1.0)
+        } else {
+            value
+        }
+    }
+
+    /// Returns the sum of the amplitudes of all octaves.
+    fn amplitude_sum(&self) -> f64 {
+        amplitude_sum(self.persistence, self.octave_count, self.octave_fraction)
+    }
+
+    /// Returns the contribution of the partial octave, given the input value
+    /// and amplitude of the octave after the last full octave.
+    fn partial_octave(&self, x: f64, y: f64, z: f64, persistence: f64) -> f64 {
+        if self.octave_fraction > 0.0 {
+            let nx = make_i32_range(x);
+            let ny = make_i32_range(y);
+            let nz = make_i32_range(z);
+            let signal = self.signal(nx, ny, nz, self.octave_seed(self.octave_count));
+            signal * persistence * self.octave_fraction
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the seed of the octave with the given index.
+    fn octave_seed(&self, octave: i32) -> i32 {
+        match self.seed_key {
+            Some(seed_key) => hash_octave_seed(seed_key, octave),
+            None => self.seed.wrapping_add(octave) & 0x7fffffff,
+        }
+    }
+
+    /// Returns the translation of the input value of the octave with the given
+    /// seed, which is zero unless octave offsets are enabled.
+    fn octave_translation(&self, seed: i32) -> (f64, f64, f64) {
+        if !self.octave_offset {
+            return (0.0, 0.0, 0.0);
+        }
+        let component = |axis: i32| {
+            i32_value_noise3d(axis, 0, 0, seed) as f64 / i32::MAX as f64 * OCTAVE_OFFSET_RANGE
+        };
+        (component(0), component(1), component(2))
+    }
+
+    /// Returns the coherent-noise value of a single octave.
+    fn signal(&self, x: f64, y: f64, z: f64, seed: i32) -> f64 {
+        let (dx, dy, dz) = self.octave_translation(seed);
+        let (x, y, z) = (x + dx, y + dy, z + dz);
+        if self.dimensions == Dimensions::Dim2 {
+            return gradient_coherent_noise2d(x, y, seed, self.quality);
+        }
+        if let Some(ref gradients) = self.gradient_table {
+            return gradient_coherent_noise3d_custom(x, y, z, gradients, self.perm_table.as_ref(),
+                                                    seed, self.quality);
+        }
+        match self.perm_table {
+            Some(ref table) => gradient_coherent_noise3d_table(x, y, z, table, seed, self.quality),
+            None => gradient_coherent_noise3d(x, y, z, seed, self.quality),
+        }
+    }
+
+    /// Returns the coherent-noise value of a single octave and its gradient.
+    fn signal_and_gradient(&self, x: f64, y: f64, z: f64, seed: i32) -> (f64, [f64; 3]) {
+        let (dx, dy, dz) = self.octave_translation(seed);
+        let (x, y, z) = (x + dx, y + dy, z + dz);
+        if self.dimensions == Dimensions::Dim2 {
+            let (value, gradient) = gradient_coherent_noise2d_with_gradient(x, y, seed,
+                                                                            self.quality);
+            return (value, [gradient[0], gradient[1], 0.0]);
+        }
+        if let Some(ref gradients) = self.gradient_table {
+            return gradient_coherent_noise3d_with_gradient_custom(x, y, z, gradients,
+                                                                  self.perm_table.as_ref(), seed,
+                                                                  self.quality);
+        }
+        match self.perm_table {
+            Some(ref table) => {
+                gradient_coherent_noise3d_with_gradient_table(x, y, z, table, seed, self.quality)
+            }
+            None => gradient_coherent_noise3d_with_gradient(x, y, z, seed, self.quality),
+        }
+    }
+}
+
+/// Derives the seed of an octave from a 64-bit seed.
+///
+/// The index of the octave is spread across the bits with the golden ratio
+/// constant, then the result is mixed with the SplitMix64 finalizer, so that
+/// changing any bit of either input changes about half of the output bits.
+fn hash_octave_seed(seed_key: u64, octave: i32) -> i32 {
+    let mut z = seed_key ^ (octave as u64).wrapping_add(1).wrapping_mul(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^= z >> 31;
+    (z >> 32) as i32
+}
+
+impl Module for Perlin {
+    #[cfg(not(feature = "simd"))]
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.get_value_scalar(x, y, z)
+    }
+
+    #[cfg(feature = "simd")]
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        // The vectorised coherent noise only hashes on the fly, with the
+        // standard three-dimensional gradient vectors.
+        if self.perm_table.is_some() || self.gradient_table.is_some()
+           || self.dimensions == Dimensions::Dim2 {
+            return self.get_value_scalar(x, y, z);
+        }
+
+        let mut value = 0.0;
+        let mut cur_persistence = 1.0;
+        let (mut x, mut y, mut z) = self.first_octave_input(x, y, z);
+
+        // Calculate the octaves four at a time.  Unused lanes of the last batch
+        // are left at zero and ignored.
+        let mut cur_octave = 0;
+        while cur_octave < self.octave_count {
+            let batch_size = ::std::cmp::min(4, self.octave_count - cur_octave) as usize;
+            let mut nx = [0.0; 4];
+            let mut ny = [0.0; 4];
+            let mut nz = [0.0; 4];
+            let mut seed = [0; 4];
+            let mut persistence = [0.0; 4];
+            for i in 0..batch_size {
+                // Make sure that these floating-point values have the same
+                // range as a 32-bit integer so that we can pass them to the
+                // coherent-noise functions.
+                seed[i] = self.octave_seed(cur_octave + i as i32);
+                let (dx, dy, dz) = self.octave_translation(seed[i]);
+                nx[i] = make_i32_range(x) + dx;
+                ny[i] = make_i32_range(y) + dy;
+                nz[i] = make_i32_range(z) + dz;
+                persistence[i] = cur_persistence;
+
+                // Prepare the next octave.
+                let next = self.next_octave(x, y, z);
+                x = next.0;
+                y = next.1;
+                z = next.2;
+                cur_persistence *= self.persistence;
+            }
+
+            // Get the coherent-noise values from the input values and add them
+            // to the final result.
+            let signal = gradient_coherent_noise3d_x4(nx, ny, nz, seed, self.quality);
+            for i in 0..batch_size {
+                value += signal[i] * persistence[i];
+            }
+
+            cur_octave += batch_size as i32;
+        }
+
+        self.normalize_value(value + self.partial_octave(x, y, z, cur_persistence))
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Perlin");
+        hasher.write_f64(self.frequency);
+        hasher.write_f64(self.lacunarity);
+        hasher.write_u64(self.quality as u64);
+        hasher.write_i32(self.octave_count);
+        hasher.write_f64(self.octave_fraction);
+        hasher.write_f64(self.persistence);
+        hasher.write_i32(self.seed);
+        hasher.write_bool(self.seed_key.is_some());
+        if let Some(seed_key) = self.seed_key {
+            hasher.write_u64(seed_key);
+        }
+        hasher.write_bool(self.normalize);
+        hasher.write_bool(self.octave_rotation);
+        hasher.write_bool(self.octave_offset);
+        hasher.write_u64(self.dimensions as u64);
+        hasher.write_bool(self.perm_table.is_some());
+        if let Some(ref table) = self.perm_table {
+            hasher.write_i32(table.seed());
+        }
+        hasher.write_bool(self.gradient_table.is_some());
+        if let Some(ref gradients) = self.gradient_table {
+            for index in 0..256 {
+                for &component in &gradients.vector(index as u8) {
+                    hasher.write_f64(component);
+                }
+            }
+        }
+        hasher.finish()
+    }
+
+    /// The bound is the sum over the octaves of the amplitude times the
+    /// frequency of each octave, times a bound on the gradient of a single
+    /// octave of coherent noise.  It only holds while the input value of every
+    /// octave lies within the range where
+    /// [`make_i32_range()`](../noisegen/fn.make_i32_range.html) leaves it
+    /// unchanged.
+    fn max_gradient_estimate(&self) -> Option<f64> {
+        let persistence = self.persistence.abs();
+        let lacunarity = self.lacunarity.abs();
+        let mut bound = 0.0;
+        let mut cur_persistence = 1.0;
+        let mut cur_frequency = self.frequency.abs();
+        for _ in 0..self.octave_count {
+            bound += cur_persistence * cur_frequency;
+            cur_persistence *= persistence;
+            cur_frequency *= lacunarity;
+        }
+        bound += cur_persistence * cur_frequency * self.octave_fraction;
+        match self.dimensions {
+            Dimensions::Dim2 => bound *= gradient_coherent_noise2d_max_gradient(self.quality),
+            Dimensions::Dim3 => {
+                bound *= gradient_coherent_noise3d_max_gradient(self.quality);
+                if let Some(ref gradients) = self.gradient_table {
+                    bound *= gradients.max_length();
+                }
+            },
+        }
+
+        // Normalization divides the output value by the sum of the amplitudes,
+        // and clamping it cannot make it any steeper.
+        if self.normalize {
+            bound /= self.amplitude_sum();
+        }
+        Some(bound)
+    }
+
+    fn as_seeded_mut(&mut self) -> Option<&mut dyn Seeded> {
+        Some(self)
+    }
+}
+
+impl Seeded for Perlin {
+    fn seed(&self) -> i32 {
+        Perlin::seed(self)
+    }
+
+    fn set_seed(&mut self, seed: i32) {
+        Perlin::set_seed(self, seed)
+    }
+}
+
+/// Multiplies two 3x3 matrices.
+fn mat_mul(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut result = [[0.0; 3]; 3];
+    for (i, row) in result.iter_mut().enumerate() {
+        for (j, element) in row.iter_mut().enumerate() {
+            *element = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+        }
     }
+    result
 }
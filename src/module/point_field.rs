@@ -0,0 +1,187 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Falloff, Module};
+
+/// Default radius for the [`PointField`](struct.PointField.html) noise module.
+pub const DEFAULT_POINT_FIELD_RADIUS: f64 = 1.0;
+
+/// Default falloff curve for the [`PointField`](struct.PointField.html) noise
+/// module.
+pub const DEFAULT_POINT_FIELD_FALLOFF: Falloff = Falloff::Linear;
+
+/// Noise module that outputs the distance to the nearest of a set of points.
+///
+/// The points are chosen by the application, by calling the
+/// [`add_point()`](struct.PointField.html#method.add_point) method, which
+/// allows hand-authored features such as a specific mountain or lake to be
+/// placed at exact positions, then blended with other noise modules.  Unlike
+/// the seed points of the [`Voronoi`](struct.Voronoi.html) noise module, the
+/// points are not placed procedurally.
+///
+/// The distance from the input value to the nearest point is divided by the
+/// *radius*, then mapped onto a [`Falloff`](enum.Falloff.html) curve, which
+/// maps a scaled distance of zero onto zero and one onto one.  By default the
+/// radius is 1.0 and the curve is linear, so the output value is the distance
+/// itself.  To change them, call the
+/// [`set_radius()`](struct.PointField.html#method.set_radius) and
+/// [`set_falloff()`](struct.PointField.html#method.set_falloff) methods.  The
+/// output value is not clamped, so it keeps increasing beyond the radius.  To
+/// negate the output value, so that it is highest at the points, call the
+/// [`set_invert()`](struct.PointField.html#method.set_invert) method.
+///
+/// The nearest point is found by checking every point, so the time taken
+/// increases with the number of points.  At least one point must be added; if
+/// this is not done, the
+/// [`get_value()`](struct.PointField.html#method.get_value) method panics.
+///
+/// This noise module does not require any source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Module, PointField};
+///
+/// let mut field = PointField::new();
+/// field.add_point(1.0, 2.0, 3.0);
+/// assert_eq!(field.get_value(1.0, 2.0, 3.0), 0.0);
+/// assert_eq!(field.get_value(1.0, 2.0, 5.0), 2.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointField {
+    points: Vec<(f64, f64, f64)>,
+    radius: f64,
+    falloff: Falloff,
+    invert: bool,
+}
+
+impl Default for PointField {
+    /// Create a new `PointField` noise module with default parameters and no
+    /// points.
+    fn default() -> PointField {
+        PointField {
+            points: Vec::new(),
+            radius: DEFAULT_POINT_FIELD_RADIUS,
+            falloff: DEFAULT_POINT_FIELD_FALLOFF,
+            invert: false,
+        }
+    }
+}
+
+impl PointField {
+    /// Create a new `PointField` noise module with default parameters and no
+    /// points.
+    pub fn new() -> PointField {
+        Default::default()
+    }
+
+    /// Returns a slice of all the points, in the order they were added.
+    pub fn points(&self) -> &[(f64, f64, f64)] {
+        &self.points
+    }
+
+    /// Returns the distance from a point at which the falloff curve reaches
+    /// one.
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Returns the curve the scaled distance is mapped onto.
+    pub fn falloff(&self) -> Falloff {
+        self.falloff
+    }
+
+    /// Determines if the output value is inverted.
+    pub fn is_inverted(&self) -> bool {
+        self.invert
+    }
+
+    /// Adds a point to the field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any coordinate of the point is NaN.
+    pub fn add_point(&mut self, x: f64, y: f64, z: f64) {
+        if x.is_nan() || y.is_nan() || z.is_nan() {
+            panic!("Tried to insert point with NaN coordinate!");
+        }
+        self.points.push((x, y, z));
+    }
+
+    /// Deletes all the points in the field.
+    pub fn clear_points(&mut self) {
+        self.points.clear();
+    }
+
+    /// Sets the distance from a point at which the falloff curve reaches one.
+    ///
+    /// The distance to the nearest point is divided by the radius before it
+    /// is mapped onto the falloff curve.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radius` is not greater than zero.
+    pub fn set_radius(&mut self, radius: f64) {
+        if radius.is_nan() || radius <= 0.0 {
+            panic!("`radius` must be greater than zero");
+        }
+        self.radius = radius;
+    }
+
+    /// Sets the curve the scaled distance is mapped onto.
+    pub fn set_falloff(&mut self, falloff: Falloff) {
+        self.falloff = falloff;
+    }
+
+    /// Enables or disables inverting the output value.
+    pub fn set_invert(&mut self, invert: bool) {
+        self.invert = invert;
+    }
+}
+
+impl Module for PointField {
+    /// # Panics
+    ///
+    /// Panics if there are no points in the field.
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        if self.points.is_empty() {
+            panic!("No points in field!");
+        }
+
+        let mut min_dist_squared = f64::INFINITY;
+        for &(px, py, pz) in &self.points {
+            let (dx, dy, dz) = (x - px, y - py, z - pz);
+            min_dist_squared = min_dist_squared.min(dx * dx + dy * dy + dz * dz);
+        }
+
+        let value = self.falloff.apply(min_dist_squared.sqrt() / self.radius);
+        if self.invert { -value } else { value }
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("PointField");
+        hasher.write_u64(self.points.len() as u64);
+        for &(x, y, z) in &self.points {
+            hasher.write_f64(x);
+            hasher.write_f64(y);
+            hasher.write_f64(z);
+        }
+        hasher.write_f64(self.radius);
+        hasher.write_u64(self.falloff as u64);
+        hasher.write_bool(self.invert);
+        hasher.finish()
+    }
+}
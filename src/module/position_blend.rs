@@ -0,0 +1,210 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{Axis, ConfigHasher, Module};
+use util::linear_interp;
+
+/// Default axis for the [`PositionBlend`](struct.PositionBlend.html) noise
+/// module.
+pub const DEFAULT_POSITION_BLEND_AXIS: Axis = Axis::X;
+
+/// Default lower bound of the blending range for the
+/// [`PositionBlend`](struct.PositionBlend.html) noise module.
+pub const DEFAULT_POSITION_BLEND_LOWER_BOUND: f64 = -1.0;
+
+/// Default upper bound of the blending range for the
+/// [`PositionBlend`](struct.PositionBlend.html) noise module.
+pub const DEFAULT_POSITION_BLEND_UPPER_BOUND: f64 = 1.0;
+
+/// Noise module that outputs a weighted blend of the output values from two
+/// source modules given the position of the input value.
+///
+/// The coordinate of the input value along the chosen axis is mapped from the
+/// *blending range* onto the 0.0 to 1.0 range to obtain the blending weight,
+/// which is then used to linearly interpolate between the output values from
+/// the two source modules.  Input values at or below the lower bound of the
+/// range output the value from `module1`, and input values at or above the
+/// upper bound output the value from `module2`, so the output fades from one
+/// source module to the other across the range.
+///
+/// By default the weight follows the `x` coordinate over the range -1.0 to
+/// +1.0.  To change these, call the
+/// [`set_axis()`](struct.PositionBlend.html#method.set_axis) and
+/// [`set_range()`](struct.PositionBlend.html#method.set_range) methods.
+///
+/// This is equivalent to a [`Blend`](struct.Blend.html) noise module whose
+/// control module outputs the coordinate, without the cost of evaluating one.
+///
+/// This noise module requires two source modules.
+#[derive(Debug, PartialEq)]
+pub struct PositionBlend<M1: Module, M2: Module> {
+    module1: M1,
+    module2: M2,
+    axis: Axis,
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+impl<M1: Module, M2: Module> PositionBlend<M1, M2> {
+    /// Create a new `PositionBlend` noise module around the specified modules,
+    /// using default parameters.
+    pub fn new(module1: M1, module2: M2) -> PositionBlend<M1, M2> {
+        PositionBlend {
+            module1: module1,
+            module2: module2,
+            axis: DEFAULT_POSITION_BLEND_AXIS,
+            lower_bound: DEFAULT_POSITION_BLEND_LOWER_BOUND,
+            upper_bound: DEFAULT_POSITION_BLEND_UPPER_BOUND,
+        }
+    }
+
+    /// Returns a reference to the first source module.
+    pub fn module1(&self) -> &M1 {
+        &self.module1
+    }
+
+    /// Returns a mutable reference to the first source module used.
+    pub fn module1_mut(&mut self) -> &mut M1 {
+        &mut self.module1
+    }
+
+    /// Returns a reference to the second source module.
+    pub fn module2(&self) -> &M2 {
+        &self.module2
+    }
+
+    /// Returns a mutable reference to the second source module used.
+    pub fn module2_mut(&mut self) -> &mut M2 {
+        &mut self.module2
+    }
+
+    /// Returns the axis of the input value that determines the blending
+    /// weight.
+    pub fn axis(&self) -> Axis {
+        self.axis
+    }
+
+    /// Returns the lower bound of the blending range.
+    ///
+    /// An input value at or below the lower bound outputs the value from
+    /// `module1`.
+    pub fn lower_bound(&self) -> f64 {
+        self.lower_bound
+    }
+
+    /// Returns the upper bound of the blending range.
+    ///
+    /// An input value at or above the upper bound outputs the value from
+    /// `module2`.
+    pub fn upper_bound(&self) -> f64 {
+        self.upper_bound
+    }
+
+    /// Set the first module to be used.
+    pub fn set_module1(&mut self, module1: M1) {
+        self.module1 = module1;
+    }
+
+    /// Set the second module to be used.
+    pub fn set_module2(&mut self, module2: M2) {
+        self.module2 = module2;
+    }
+
+    /// Sets the axis of the input value that determines the blending weight.
+    pub fn set_axis(&mut self, axis: Axis) {
+        self.axis = axis;
+    }
+
+    /// Sets the lower and upper bounds of the blending range.
+    ///
+    /// The blending weight is computed as `(coord - lower_bound) /
+    /// (upper_bound - lower_bound)`, clamped to the 0.0 to 1.0 range, where
+    /// `coord` is the coordinate of the input value along the chosen axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given lower bound is greater than or equal to the given
+    /// upper bound.
+    pub fn set_range(&mut self, lower_bound: f64, upper_bound: f64) {
+        if lower_bound >= upper_bound {
+            panic!("Lower bound is not smaller than upper bound!");
+        }
+        self.lower_bound = lower_bound;
+        self.upper_bound = upper_bound;
+    }
+}
+
+impl<M1: Module, M2: Module> Module for PositionBlend<M1, M2> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let coord = match self.axis {
+            Axis::X => x,
+            Axis::Y => y,
+            Axis::Z => z,
+        };
+        let alpha = (coord - self.lower_bound) / (self.upper_bound - self.lower_bound);
+        if alpha <= 0.0 {
+            self.module1.get_value(x, y, z)
+        } else if alpha >= 1.0 {
+            self.module2.get_value(x, y, z)
+        } else {
+            let v0 = self.module1.get_value(x, y, z);
+            let v1 = self.module2.get_value(x, y, z);
+            linear_interp(v0, v1, alpha)
+        }
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("PositionBlend");
+        hasher.write_u64(self.module1.config_hash());
+        hasher.write_u64(self.module2.config_hash());
+        hasher.write_u64(self.axis as u64);
+        hasher.write_f64(self.lower_bound);
+        hasher.write_f64(self.upper_bound);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        2
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module1 as &dyn Module, &self.module2]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module1),
+            1 => Some(&self.module2),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module1 as &mut dyn Module, &mut self.module2]
+    }
+}
+
+impl<M1: Module + Clone, M2: Module + Clone> Clone for PositionBlend<M1, M2> {
+    fn clone(&self) -> PositionBlend<M1, M2> {
+        PositionBlend {
+            module1: self.module1.clone(),
+            module2: self.module2.clone(),
+            axis: self.axis,
+            lower_bound: self.lower_bound,
+            upper_bound: self.upper_bound,
+        }
+    }
+}
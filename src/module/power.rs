@@ -14,12 +14,13 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Noise module that raises the output value from a first source module to the
 /// power of the output value from a second source module.
 ///
 /// This noise module requires two source modules.
+#[derive(Debug, PartialEq)]
 pub struct Power<M1: Module, M2: Module> {
     module1: M1,
     module2: M2,
@@ -71,6 +72,34 @@ impl<M1: Module, M2: Module> Module for Power<M1, M2> {
         let value2 = self.module2.get_value(x, y, z);
         value1.powf(value2)
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Power");
+        hasher.write_u64(self.module1.config_hash());
+        hasher.write_u64(self.module2.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        2
+    }
+
+    /// Returns the base and exponent source modules, in that order.
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module1 as &dyn Module, &self.module2]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module1),
+            1 => Some(&self.module2),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module1 as &mut dyn Module, &mut self.module2]
+    }
 }
 
 impl<M1: Module + Clone, M2: Module + Clone> Clone for Power<M1, M2> {
@@ -0,0 +1,140 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Falloff, Module};
+
+/// Default radius for the [`RadialGradient`](struct.RadialGradient.html) noise
+/// module.
+pub const DEFAULT_RADIAL_GRADIENT_RADIUS: f64 = 1.0;
+
+/// Default falloff curve for the
+/// [`RadialGradient`](struct.RadialGradient.html) noise module.
+pub const DEFAULT_RADIAL_GRADIENT_FALLOFF: Falloff = Falloff::Linear;
+
+/// Noise module that outputs a radial gradient around a center point.
+///
+/// The output value is 1.0 at the center and falls to -1.0 at the *radius*
+/// from the center.  Beyond the radius, the output value stays at -1.0.  This
+/// is the classic mask for islands: multiplying or selecting terrain with it
+/// keeps the land near the center and sinks the edges below the sea.  Unlike
+/// the [`Spheres`](struct.Spheres.html) noise module, which repeats in
+/// concentric rings, the gradient has a single peak.
+///
+/// The distance from the center, divided by the radius, is mapped onto a
+/// [`Falloff`](enum.Falloff.html) curve, which sets the shape of the decay;
+/// for example, `Falloff::Quadratic` keeps the output value high further from
+/// the center, and `Falloff::Smoothstep` rounds off both the peak and the
+/// edge.  By default the center is the origin, the radius is 1.0 and the
+/// curve is linear.  To change them, call the
+/// [`set_center()`](struct.RadialGradient.html#method.set_center),
+/// [`set_radius()`](struct.RadialGradient.html#method.set_radius) and
+/// [`set_falloff()`](struct.RadialGradient.html#method.set_falloff) methods.
+///
+/// This noise module does not require any source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Module, RadialGradient};
+///
+/// let mut gradient = RadialGradient::new();
+/// gradient.set_center(1.0, 0.0, 0.0);
+/// gradient.set_radius(4.0);
+/// assert_eq!(gradient.get_value(1.0, 0.0, 0.0), 1.0);
+/// assert_eq!(gradient.get_value(1.0, 2.0, 0.0), 0.0);
+/// assert_eq!(gradient.get_value(9.0, 0.0, 0.0), -1.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadialGradient {
+    center: (f64, f64, f64),
+    radius: f64,
+    falloff: Falloff,
+}
+
+impl Default for RadialGradient {
+    /// Create a new `RadialGradient` noise module with default parameters.
+    fn default() -> RadialGradient {
+        RadialGradient {
+            center: (0.0, 0.0, 0.0),
+            radius: DEFAULT_RADIAL_GRADIENT_RADIUS,
+            falloff: DEFAULT_RADIAL_GRADIENT_FALLOFF,
+        }
+    }
+}
+
+impl RadialGradient {
+    /// Create a new `RadialGradient` noise module with default parameters.
+    pub fn new() -> RadialGradient {
+        Default::default()
+    }
+
+    /// Returns the center of the gradient, where the output value is 1.0.
+    pub fn center(&self) -> (f64, f64, f64) {
+        self.center
+    }
+
+    /// Returns the distance from the center at which the output value reaches
+    /// -1.0.
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Returns the curve the output value decays along.
+    pub fn falloff(&self) -> Falloff {
+        self.falloff
+    }
+
+    /// Sets the center of the gradient, where the output value is 1.0.
+    pub fn set_center(&mut self, x: f64, y: f64, z: f64) {
+        self.center = (x, y, z);
+    }
+
+    /// Sets the distance from the center at which the output value reaches
+    /// -1.0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radius` is not greater than zero.
+    pub fn set_radius(&mut self, radius: f64) {
+        if radius.is_nan() || radius <= 0.0 {
+            panic!("`radius` must be greater than zero");
+        }
+        self.radius = radius;
+    }
+
+    /// Sets the curve the output value decays along.
+    pub fn set_falloff(&mut self, falloff: Falloff) {
+        self.falloff = falloff;
+    }
+}
+
+impl Module for RadialGradient {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (dx, dy, dz) = (x - self.center.0, y - self.center.1, z - self.center.2);
+        let dist = (dx * dx + dy * dy + dz * dz).sqrt() / self.radius;
+        1.0 - 2.0 * self.falloff.apply(dist.min(1.0))
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("RadialGradient");
+        hasher.write_f64(self.center.0);
+        hasher.write_f64(self.center.1);
+        hasher.write_f64(self.center.2);
+        hasher.write_f64(self.radius);
+        hasher.write_u64(self.falloff as u64);
+        hasher.finish()
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module, Seeded};
+use noisegen::i32_value_noise3d;
+
+/// Default size of a tile for the [`RandomTile`](struct.RandomTile.html)
+/// noise module.
+pub const DEFAULT_RANDOM_TILE_PERIOD: f64 = 1.0;
+
+/// Default seed for the [`RandomTile`](struct.RandomTile.html) noise module.
+pub const DEFAULT_RANDOM_TILE_SEED: i32 = 0;
+
+/// Noise module that repeats one tile of the output value from a source
+/// module across the `x`-`y` plane, turning or mirroring each copy to hide the
+/// repetition.
+///
+/// The plane is divided into square tiles of `period` by `period` units, with
+/// a corner at the origin.  Every tile samples the source module within the
+/// tile at the origin, the *base tile*, so the output value repeats with the
+/// period like a plain tiling.  Before sampling, though, the input value is
+/// rotated about the center of its tile by a multiple of 90 degrees, and
+/// optionally mirrored across the `x` axis first.  The choice between these
+/// eight orientations is made by hashing the indices of the tile with the
+/// seed, so it is deterministic but varies from tile to tile, and the eye no
+/// longer picks out a repeating pattern.  The `z` coordinate is passed to the
+/// source module unchanged.  To query the orientation of a tile, call the
+/// [`tile_orientation()`](struct.RandomTile.html#method.tile_orientation)
+/// method.
+///
+/// The output value is only continuous across the border between two tiles
+/// if the base tile looks the same along each edge after the two
+/// orientations are applied, which holds for content that is symmetric under
+/// the turns and mirrors of a square, such as a source module that is
+/// constant along the edges of the base tile.  With general content, such as
+/// [`Perlin`](struct.Perlin.html) noise, the output value jumps at most tile
+/// borders; blend the output value with the source module near the borders,
+/// or use content that fades to a common value at the edges of the tile, to
+/// hide the seams.
+///
+/// This noise module requires one source module.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Module, Perlin, RandomTile};
+///
+/// let mut tiles = RandomTile::new(Perlin::new());
+/// tiles.set_period(2.0);
+/// let (quarter_turns, _mirrored) = tiles.tile_orientation(3, -1);
+/// assert!(quarter_turns < 4);
+/// // Every orientation leaves the center of a tile in place.
+/// assert_eq!(tiles.get_value(7.0, -1.0, 0.5), Perlin::new().get_value(1.0, 1.0, 0.5));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct RandomTile<M: Module> {
+    module: M,
+    period: f64,
+    seed: i32,
+}
+
+impl<M: Module> RandomTile<M> {
+    /// Create a new `RandomTile` noise module around the specified module,
+    /// using default parameters.
+    pub fn new(module: M) -> RandomTile<M> {
+        RandomTile {
+            module: module,
+            period: DEFAULT_RANDOM_TILE_PERIOD,
+            seed: DEFAULT_RANDOM_TILE_SEED,
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Returns the size of a tile along the `x` and `y` axes.
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    /// Returns the seed used to choose the orientation of each tile.
+    pub fn seed(&self) -> i32 {
+        self.seed
+    }
+
+    /// Set the source module to be used.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+    }
+
+    /// Sets the size of a tile along the `x` and `y` axes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is not a finite number greater than zero.
+    pub fn set_period(&mut self, period: f64) {
+        if !period.is_finite() || period <= 0.0 {
+            panic!("`period` must be finite and greater than zero!");
+        }
+        self.period = period;
+    }
+
+    /// Sets the seed used to choose the orientation of each tile.
+    pub fn set_seed(&mut self, seed: i32) {
+        self.seed = seed;
+    }
+
+    /// Returns the orientation of the tile with the given indices.
+    ///
+    /// The tile with the indices (`tile_x`, `tile_y`) covers the input values
+    /// from (`tile_x * period`, `tile_y * period`) up to, but not including,
+    /// one period further along each axis.  The orientation is returned as the
+    /// number of counterclockwise quarter turns, from 0 to 3, and whether the
+    /// tile is mirrored across the `x` axis before it is turned.
+    pub fn tile_orientation(&self, tile_x: i32, tile_y: i32) -> (u32, bool) {
+        let hash = i32_value_noise3d(tile_x, tile_y, 0, self.seed) >> 8;
+        ((hash & 3) as u32, hash & 4 != 0)
+    }
+}
+
+impl<M: Module> Module for RandomTile<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let tile_x = (x / self.period).floor();
+        let tile_y = (y / self.period).floor();
+        let (quarter_turns, mirrored) = self.tile_orientation(tile_x as i32, tile_y as i32);
+
+        // Turn the offset from the center of the tile, then apply it to the
+        // center of the base tile.
+        let half_period = self.period * 0.5;
+        let mut dx = x - tile_x * self.period - half_period;
+        let mut dy = y - tile_y * self.period - half_period;
+        if mirrored {
+            dy = -dy;
+        }
+        for _ in 0..quarter_turns {
+            let turned = (-dy, dx);
+            dx = turned.0;
+            dy = turned.1;
+        }
+        self.module.get_value(half_period + dx, half_period + dy, z)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("RandomTile");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_f64(self.period);
+        hasher.write_i32(self.seed);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+
+    fn as_seeded_mut(&mut self) -> Option<&mut dyn Seeded> {
+        Some(self)
+    }
+}
+
+impl<M: Module> Seeded for RandomTile<M> {
+    fn seed(&self) -> i32 {
+        RandomTile::<M>::seed(self)
+    }
+
+    fn set_seed(&mut self, seed: i32) {
+        RandomTile::<M>::set_seed(self, seed)
+    }
+}
+
+impl<M: Module + Clone> Clone for RandomTile<M> {
+    fn clone(&self) -> RandomTile<M> {
+        RandomTile {
+            module: self.module.clone(),
+            period: self.period,
+            seed: self.seed,
+        }
+    }
+}
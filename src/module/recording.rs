@@ -0,0 +1,166 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use std::cell::RefCell;
+
+/// Noise module that records every input value passed to a source module.
+///
+/// This noise module outputs the value from its source module unchanged, and
+/// appends each input value to a list, in the order the output values are
+/// requested.  The list is returned by the
+/// [`recorded_inputs()`](struct.Recording.html#method.recorded_inputs)
+/// method, and can be passed to [`replay()`](fn.replay.html) to request the
+/// same output values again, for example to reproduce a bug seen by someone
+/// else with only the list of input values.
+///
+/// Every input value takes 24 bytes, and the list is never shortened on its
+/// own, so recording a renderer that samples millions of input values per
+/// frame quickly uses a lot of memory.  Call the
+/// [`clear()`](struct.Recording.html#method.clear) method to empty the list
+/// once it has been saved.  The list is updated through a shared reference,
+/// so this noise module cannot be shared between threads.
+///
+/// This noise module requires one source module.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{replay, Module, Perlin, Recording};
+///
+/// let recording = Recording::new(Perlin::new());
+/// let values = [recording.get_value(0.5, 1.0, 2.0), recording.get_value(-3.0, 0.25, 0.0)];
+///
+/// let inputs = recording.recorded_inputs();
+/// assert_eq!(inputs, vec![(0.5, 1.0, 2.0), (-3.0, 0.25, 0.0)]);
+/// assert_eq!(replay(&Perlin::new(), &inputs), values);
+/// ```
+#[derive(Debug)]
+pub struct Recording<M: Module> {
+    module: M,
+    inputs: RefCell<Vec<(f64, f64, f64)>>,
+}
+
+impl<M: Module> Recording<M> {
+    /// Create a new `Recording` noise module around the specified module, with
+    /// no input values recorded.
+    pub fn new(module: M) -> Recording<M> {
+        Recording {
+            module: module,
+            inputs: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Returns a copy of the input values recorded since this noise module
+    /// was created or last cleared, in the order they were passed in.
+    pub fn recorded_inputs(&self) -> Vec<(f64, f64, f64)> {
+        self.inputs.borrow().clone()
+    }
+
+    /// Set the source module to be used.
+    ///
+    /// The recorded input values are kept.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+    }
+
+    /// Discards the recorded input values, freeing their memory.
+    pub fn clear(&self) {
+        *self.inputs.borrow_mut() = Vec::new();
+    }
+}
+
+impl<M: Module> Module for Recording<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.inputs.borrow_mut().push((x, y, z));
+        self.module.get_value(x, y, z)
+    }
+
+    /// Records the input value of every element of the row, as if
+    /// [`get_value()`](#tymethod.get_value) were called for each.
+    fn get_value_row(&self, x: f64, y: f64, z: f64, x_step: f64, values: &mut [f64]) {
+        {
+            let mut inputs = self.inputs.borrow_mut();
+            for i in 0..values.len() {
+                inputs.push((x + i as f64 * x_step, y, z));
+            }
+        }
+        self.module.get_value_row(x, y, z, x_step, values);
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Recording");
+        hasher.write_u64(self.module.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone> Clone for Recording<M> {
+    fn clone(&self) -> Recording<M> {
+        Recording {
+            module: self.module.clone(),
+            inputs: self.inputs.clone(),
+        }
+    }
+}
+
+impl<M: Module + PartialEq> PartialEq for Recording<M> {
+    /// Two `Recording` noise modules are equal if their source modules are
+    /// equal; the recorded input values are not compared.
+    fn eq(&self, other: &Recording<M>) -> bool {
+        self.module == other.module
+    }
+}
+
+/// Returns the output values from a noise module at each of the given input
+/// values, in order.
+///
+/// Together with [`Recording`](struct.Recording.html), this reproduces the
+/// exact sequence of output values a noise module was asked for.  The noise
+/// module is sampled with [`get_value()`](trait.Module.html#tymethod.get_value)
+/// for every input value, including those recorded from rows.
+pub fn replay<M: Module + ?Sized>(module: &M, inputs: &[(f64, f64, f64)]) -> Vec<f64> {
+    inputs.iter().map(|&(x, y, z)| module.get_value(x, y, z)).collect()
+}
@@ -14,8 +14,9 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
-use noisegen::{gradient_coherent_noise3d, make_i32_range, NoiseQuality};
+use module::{ConfigHasher, Module, Seeded};
+use noisegen::{gradient_coherent_noise2d, gradient_coherent_noise3d, make_i32_range, Dimensions,
+               NoiseQuality};
 
 /// Default frequency for the [`RidgedMulti`](struct.RidgedMulti.html) noise
 /// module.
@@ -37,6 +38,10 @@ pub const DEFAULT_RIDGED_QUALITY: NoiseQuality = NoiseQuality::Standard;
 /// module.
 pub const DEFAULT_RIDGED_SEED: i32 = 0;
 
+/// Default number of dimensions of the coherent noise for the
+/// [`RidgedMulti`](struct.RidgedMulti.html) noise module.
+pub const DEFAULT_RIDGED_DIMENSIONS: Dimensions = Dimensions::Dim3;
+
 /// Maximum number of octaves for the [`RidgedMulti`](struct.RidgedMulti.html)
 /// noise module.
 pub const RIDGED_MAX_OCTAVE: i32 = 30;
@@ -56,7 +61,8 @@ pub const RIDGED_MAX_OCTAVE: i32 = 30;
 ///
 /// This noise module outputs ridged-multifractal-noise values that usually
 /// range from -1.0 to +1.0, but there are no guarantees that all output values
-/// will exist within that range.
+/// will exist within that range.  To guarantee that range, call the
+/// [`set_normalize()`](struct.RidgedMulti.html#method.set_normalize) method.
 ///
 /// **Note:** For ridged-multifractal noise generated with only one octave, the
 /// output value ranges from -1.0 to 0.0.
@@ -103,7 +109,7 @@ pub const RIDGED_MAX_OCTAVE: i32 = 30;
 /// Musgrave, the person who created [MojoWorld](http://www.pandromeda.com).  He
 /// is also one of the authors in *Texturing and Modeling: A Procedural
 /// Approach* (Morgan Kaufmann, 2002. ISBN 1-55860-848-6.)
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct RidgedMulti {
     frequency: f64,
     lacunarity: f64,
@@ -112,6 +118,8 @@ pub struct RidgedMulti {
     /// Contains the spectral weights for each octave.
     spectral_weights: [f64; RIDGED_MAX_OCTAVE as usize],
     seed: i32,
+    normalize: bool,
+    dimensions: Dimensions,
 }
 
 /// Calculates the spectral weights for each octave.
@@ -139,6 +147,8 @@ impl Default for RidgedMulti {
             octave_count: DEFAULT_RIDGED_OCTAVE_COUNT,
             spectral_weights: spectral_weights,
             seed: DEFAULT_RIDGED_SEED,
+            normalize: false,
+            dimensions: DEFAULT_RIDGED_DIMENSIONS,
         }
     }
 }
@@ -183,6 +193,17 @@ impl RidgedMulti {
         self.seed
     }
 
+    /// Determines if the output value is normalized to the range -1.0 to
+    /// +1.0.
+    pub fn is_normalized(&self) -> bool {
+        self.normalize
+    }
+
+    /// Returns the number of dimensions of the coherent noise.
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
     /// Sets the frequency of the first octave.
     pub fn set_frequency(&mut self, frequency: f64) {
         self.frequency = frequency;
@@ -226,9 +247,60 @@ impl RidgedMulti {
     }
 
     /// Sets the seed value used by the ridged-multifractal-noise function.
+    ///
+    /// Every `i32` is a valid seed.  Each octave uses the seed value plus the
+    /// index of the octave, wrapping around on overflow, with the sign bit
+    /// cleared, as in the original libnoise.  Seeds that differ only in their
+    /// sign bit therefore generate the same noise.
     pub fn set_seed(&mut self, seed: i32) {
         self.seed = seed;
     }
+
+    /// Enables or disables normalizing the output value to the range -1.0 to
+    /// +1.0.
+    ///
+    /// Each octave adds a ridge signal between 0.0 and 1.0, multiplied by the
+    /// spectral weight of that octave.  When normalization is enabled, the
+    /// sum of the octaves is divided by the sum of the spectral weights and
+    /// mapped from the range 0.0 to 1.0 onto the range -1.0 to +1.0, then
+    /// clamped to that range to guard against rounding errors.  This replaces
+    /// the fixed scale and bias otherwise applied to the sum.
+    ///
+    /// The signals of later octaves are weighted by those of earlier ones, so
+    /// the sum rarely approaches the sum of the spectral weights, and
+    /// normalized output values use less of the range than it allows.  This
+    /// reduces the contrast compared with the default output.
+    ///
+    /// By default, normalization is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, RidgedMulti};
+    ///
+    /// let mut ridged_multi = RidgedMulti::new();
+    /// ridged_multi.set_normalize(true);
+    /// for i in 0..10000 {
+    ///     let value = ridged_multi.get_value(i as f64 * 0.0173, i as f64 * 0.0071, 0.5);
+    ///     assert!(value >= -1.0 && value <= 1.0);
+    /// }
+    /// ```
+    pub fn set_normalize(&mut self, normalize: bool) {
+        self.normalize = normalize;
+    }
+
+    /// Sets the number of dimensions of the coherent noise.
+    ///
+    /// With [`Dimensions::Dim2`](../noisegen/enum.Dimensions.html), the `z`
+    /// coordinate of the input value is ignored, and each octave is
+    /// two-dimensional coherent noise, which is faster to calculate.  This
+    /// changes the output values, not just the speed; see
+    /// [`Dimensions`](../noisegen/enum.Dimensions.html).
+    ///
+    /// By default, the coherent noise is three-dimensional.
+    pub fn set_dimensions(&mut self, dimensions: Dimensions) {
+        self.dimensions = dimensions;
+    }
 }
 
 impl Module for RidgedMulti {
@@ -256,8 +328,11 @@ impl Module for RidgedMulti {
             let nz = make_i32_range(z);
 
             // Get the coherent-noise value.
-            let seed = (self.seed + cur_octave) & 0x7fffffff;
-            let mut signal = gradient_coherent_noise3d(nx, ny, nz, seed, self.quality);
+            let seed = self.seed.wrapping_add(cur_octave) & 0x7fffffff;
+            let mut signal = match self.dimensions {
+                Dimensions::Dim2 => gradient_coherent_noise2d(nx, ny, seed, self.quality),
+                Dimensions::Dim3 => gradient_coherent_noise3d(nx, ny, nz, seed, self.quality),
+            };
 
             // Make the ridges.
             signal = signal.abs();
@@ -290,6 +365,37 @@ impl Module for RidgedMulti {
             z *= self.lacunarity;
         }
 
+        if self.normalize {
+            let weight_sum: f64 = self.spectral_weights[..self.octave_count as usize].iter().sum();
+            return (value / weight_sum * 2.0 - 1.0).clamp(-1.0, 1.0);
+        }
+
         (value * 1.25) - 1.0
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("RidgedMulti");
+        hasher.write_f64(self.frequency);
+        hasher.write_f64(self.lacunarity);
+        hasher.write_u64(self.quality as u64);
+        hasher.write_i32(self.octave_count);
+        hasher.write_i32(self.seed);
+        hasher.write_bool(self.normalize);
+        hasher.write_u64(self.dimensions as u64);
+        hasher.finish()
+    }
+
+    fn as_seeded_mut(&mut self) -> Option<&mut dyn Seeded> {
+        Some(self)
+    }
+}
+
+impl Seeded for RidgedMulti {
+    fn seed(&self) -> i32 {
+        RidgedMulti::seed(self)
+    }
+
+    fn set_seed(&mut self, seed: i32) {
+        RidgedMulti::set_seed(self, seed)
+    }
 }
@@ -14,7 +14,8 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
+use std::f64;
 
 /// Default `x` rotation angle for the [`RotatePoint`](struct.RotatePoint.html)
 /// noise module.
@@ -41,10 +42,17 @@ pub const DEFAULT_ROTATE_Z: f64 = 0.0;
 /// [`set_z_angle()`](struct.RotatePoint.html#method.set_z_angle) methods,
 /// respectively.
 ///
+/// Alternatively, the rotation can be given as an axis and an angle by creating
+/// the noise module with
+/// [`from_axis_angle()`](struct.RotatePoint.html#method.from_axis_angle), or as
+/// an arbitrary rotation matrix with the
+/// [`set_matrix()`](struct.RotatePoint.html#method.set_matrix) method.
+///
 /// The coordinate system of the input value is assumed to be "left-handed" (`x`
 /// increases to the right, `y` increases upward, and `z` increases inward.)
 ///
 /// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
 pub struct RotatePoint<M: Module> {
     module: M,
     /// `x`, `y` and `z` rotation angle applied to the input value, in degrees.
@@ -66,6 +74,62 @@ impl<M: Module> RotatePoint<M> {
         tmp
     }
 
+    /// Create a new `RotatePoint` noise module around the specified module,
+    /// rotating the input value by `angle` degrees around `axis`.
+    ///
+    /// The rotation matrix is calculated directly with Rodrigues' rotation
+    /// formula, avoiding the gimbal lock of rotation angles around the `x`,
+    /// `y` and `z` axes.  Looking from the tip of the axis towards the origin,
+    /// a positive angle rotates the input value counterclockwise in a
+    /// right-handed coordinate system.  The axis does not need to be
+    /// normalised.
+    ///
+    /// The rotation angles returned by
+    /// [`x_angle()`](struct.RotatePoint.html#method.x_angle),
+    /// [`y_angle()`](struct.RotatePoint.html#method.y_angle) and
+    /// [`z_angle()`](struct.RotatePoint.html#method.z_angle) are NaN for the
+    /// returned noise module.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` has zero length, or if any component of `axis` or
+    /// `angle` is NaN or infinite.
+    ///
+    /// # Examples
+    ///
+    /// A rotation of 120 degrees around the diagonal axis cycles the `x`, `y`
+    /// and `z` axes:
+    ///
+    /// ```
+    /// use noise::module::{Constant, RotatePoint};
+    ///
+    /// let rotate = RotatePoint::from_axis_angle(Constant::new(), [1.0, 1.0, 1.0], 120.0);
+    /// let matrix = rotate.matrix();
+    /// let rotated = [matrix[0][0], matrix[1][0], matrix[2][0]];
+    /// let expected = [0.0, 1.0, 0.0];
+    /// for i in 0..3 {
+    ///     assert!((rotated[i] - expected[i]).abs() < 1e-12);
+    /// }
+    /// ```
+    pub fn from_axis_angle(module: M, axis: [f64; 3], angle: f64) -> RotatePoint<M> {
+        if axis.iter().any(|c| !c.is_finite()) || !angle.is_finite() {
+            panic!("`axis` and `angle` must be finite!");
+        }
+        let len = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+        if len == 0.0 {
+            panic!("`axis` must not have zero length!");
+        }
+        let (kx, ky, kz) = (axis[0] / len, axis[1] / len, axis[2] / len);
+        let (sin, cos) = f64::sin_cos(angle.to_radians());
+        let t = 1.0 - cos;
+
+        let mut tmp = RotatePoint::new(module);
+        tmp.set_matrix([[cos + kx * kx * t, kx * ky * t - kz * sin, kx * kz * t + ky * sin],
+                        [ky * kx * t + kz * sin, cos + ky * ky * t, ky * kz * t - kx * sin],
+                        [kz * kx * t - ky * sin, kz * ky * t + kx * sin, cos + kz * kz * t]]);
+        tmp
+    }
+
     /// Returns a reference to the source module used.
     pub fn module(&self) -> &M {
         &self.module
@@ -94,6 +158,14 @@ impl<M: Module> RotatePoint<M> {
         self.angles.2
     }
 
+    /// Returns the 3x3 rotation matrix applied to the input value.
+    ///
+    /// The input value (`x`, `y`, `z`) is rotated to the point whose `i`th
+    /// coordinate is `matrix[i][0] * x + matrix[i][1] * y + matrix[i][2] * z`.
+    pub fn matrix(&self) -> [[f64; 3]; 3] {
+        self.matrix
+    }
+
     /// Set the source module to be used.
     pub fn set_module(&mut self, module: M) {
         self.module = module;
@@ -140,6 +212,33 @@ impl<M: Module> RotatePoint<M> {
         self.update_matrix();
     }
 
+    /// Sets the 3x3 rotation matrix applied to the input value directly.
+    ///
+    /// See [`matrix()`](struct.RotatePoint.html#method.matrix) for how the
+    /// matrix is applied.  The matrix is not checked, so any linear
+    /// transformation can be given, not only rotations.
+    ///
+    /// The matrix is not decomposed into rotation angles around the `x`, `y`
+    /// and `z` axes, so the angles returned by
+    /// [`x_angle()`](struct.RotatePoint.html#method.x_angle),
+    /// [`y_angle()`](struct.RotatePoint.html#method.y_angle) and
+    /// [`z_angle()`](struct.RotatePoint.html#method.z_angle) become NaN until
+    /// the angles are set again.  Call
+    /// [`set_angles()`](struct.RotatePoint.html#method.set_angles) to return to
+    /// rotation angles; setting a single angle while the others are NaN gives a
+    /// NaN matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any element of `matrix` is NaN or infinite.
+    pub fn set_matrix(&mut self, matrix: [[f64; 3]; 3]) {
+        if matrix.iter().flat_map(|row| row.iter()).any(|e| !e.is_finite()) {
+            panic!("Every element of the rotation matrix must be finite!");
+        }
+        self.angles = (f64::NAN, f64::NAN, f64::NAN);
+        self.matrix = matrix;
+    }
+
     /// Updates the rotation matrix after the angles have been changed.
     fn update_matrix(&mut self) {
         let (x_sin, x_cos) = f64::sin_cos(self.angles.0.to_radians());
@@ -165,6 +264,36 @@ impl<M: Module> Module for RotatePoint<M> {
         let nz = self.matrix[2][0] * x + self.matrix[2][1] * y + self.matrix[2][2] * z;
         self.module.get_value(nx, ny, nz)
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("RotatePoint");
+        hasher.write_u64(self.module.config_hash());
+        for row in &self.matrix {
+            for &element in row {
+                hasher.write_f64(element);
+            }
+        }
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
 }
 
 impl<M: Module + Clone> Clone for RotatePoint<M> {
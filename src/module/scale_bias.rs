@@ -14,7 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Default bias for the [`ScaleBias`](struct.ScaleBias.html) noise module.
 pub const DEFAULT_BIAS: f64 = 0.0;
@@ -29,11 +29,19 @@ pub const DEFAULT_SCALE: f64 = 1.0;
 /// the output value from the source module, multiplies it with a scaling
 /// factor, adds a bias to it, then outputs the value.
 ///
+/// A large scaling factor can push the output value far outside of the range
+/// -1.0 to +1.0 that many other noise modules expect.  Call the
+/// [`set_output_clamp()`](struct.ScaleBias.html#method.set_output_clamp)
+/// method to clamp the scaled and biased value to a range.  By default the
+/// output value is not clamped.
+///
 /// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
 pub struct ScaleBias<M: Module> {
     module: M,
     scale: f64,
     bias: f64,
+    output_clamp: Option<(f64, f64)>,
 }
 
 impl<M: Module> ScaleBias<M> {
@@ -44,6 +52,7 @@ impl<M: Module> ScaleBias<M> {
             module: module,
             scale: DEFAULT_SCALE,
             bias: DEFAULT_BIAS,
+            output_clamp: None,
         }
     }
 
@@ -77,6 +86,12 @@ impl<M: Module> ScaleBias<M> {
         self.bias
     }
 
+    /// Returns the range the scaled and biased value is clamped to, or `None`
+    /// if it is not clamped.
+    pub fn output_clamp(&self) -> Option<(f64, f64)> {
+        self.output_clamp
+    }
+
     /// Set the source module to be used.
     pub fn set_module(&mut self, module: M) {
         self.module = module;
@@ -101,12 +116,67 @@ impl<M: Module> ScaleBias<M> {
     pub fn set_bias(&mut self, bias: f64) {
         self.bias = bias;
     }
+
+    /// Sets the range to clamp the scaled and biased value to, or `None` to
+    /// output it unclamped.
+    ///
+    /// The range is given as a (`lower_bound`, `upper_bound`) pair.  Output
+    /// values below the lower bound are set to the lower bound, and output
+    /// values above the upper bound are set to the upper bound.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given lower bound is greater than the given upper bound.
+    pub fn set_output_clamp(&mut self, output_clamp: Option<(f64, f64)>) {
+        if let Some((lower_bound, upper_bound)) = output_clamp {
+            if lower_bound > upper_bound {
+                panic!("Lower bound is larger than upper bound!");
+            }
+        }
+        self.output_clamp = output_clamp;
+    }
 }
 
 impl<M: Module> Module for ScaleBias<M> {
     fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
         let value = self.module.get_value(x, y, z);
-        value * self.scale + self.bias
+        let value = value * self.scale + self.bias;
+        match self.output_clamp {
+            Some((lower_bound, upper_bound)) => value.max(lower_bound).min(upper_bound),
+            None => value,
+        }
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("ScaleBias");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_f64(self.scale);
+        hasher.write_f64(self.bias);
+        hasher.write_bool(self.output_clamp.is_some());
+        if let Some((lower_bound, upper_bound)) = self.output_clamp {
+            hasher.write_f64(lower_bound);
+            hasher.write_f64(upper_bound);
+        }
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
     }
 }
 
@@ -116,6 +186,7 @@ impl<M: Module + Clone> Clone for ScaleBias<M> {
             module: self.module.clone(),
             scale: self.scale,
             bias: self.bias,
+            output_clamp: self.output_clamp,
         }
     }
 }
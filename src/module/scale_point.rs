@@ -14,7 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Default scaling factor applied to the `x` coordinate for the
 /// [`ScalePoint`](struct.ScalePoint.html) noise module.
@@ -43,6 +43,7 @@ pub const DEFAULT_SCALE_POINT_Z: f64 = 1.0;
 /// respectively.
 ///
 /// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
 pub struct ScalePoint<M: Module> {
     module: M,
     scale: (f64, f64, f64),
@@ -145,6 +146,34 @@ impl<M: Module> Module for ScalePoint<M> {
     fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
         self.module.get_value(x * self.scale.0, y * self.scale.1, z * self.scale.2)
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("ScalePoint");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_f64(self.scale.0);
+        hasher.write_f64(self.scale.1);
+        hasher.write_f64(self.scale.2);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
 }
 
 impl<M: Module + Clone> Clone for ScalePoint<M> {
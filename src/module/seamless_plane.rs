@@ -0,0 +1,215 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use util::linear_interp;
+
+/// Default period along the `x` and `y` axes for the
+/// [`SeamlessPlane`](struct.SeamlessPlane.html) noise module.
+pub const DEFAULT_SEAMLESS_PERIOD: f64 = 1.0;
+
+/// Default seam-blend width for the [`SeamlessPlane`](struct.SeamlessPlane.html)
+/// noise module, which matches the seamless mode of the original libnoise.
+pub const DEFAULT_SEAM_BLEND: f64 = 1.0;
+
+/// Noise module that makes the output value from a source module tile
+/// seamlessly on the `x`-`y` plane.
+///
+/// This is the seamless mode of the plane noise map builder from the original
+/// libnoise.  The plane is divided into tiles of `period_x` by `period_y`
+/// units, with a corner at the origin, and every tile outputs the same
+/// values.  Within a tile, the output value blends the output values from the
+/// source module at the input value and at the three input values one period
+/// further along the `x` axis, the `y` axis, or both.  The weights are chosen
+/// so that the values on opposite edges of the tile match, so the `x` and `y`
+/// coordinates wrap around without a seam.  The `z` coordinate is passed to
+/// the source module unchanged.  To set the size of the tile, call the
+/// [`set_period()`](struct.SeamlessPlane.html#method.set_period) method.
+///
+/// The *seam-blend width* is the fraction of the tile, from its lower edge
+/// along each axis, over which the output values one period further along are
+/// blended in.  With the default width of 1.0, the blend spans the whole tile,
+/// exactly as in libnoise; this hides the seam completely, but the blend
+/// flattens the contrast of the noise towards the middle of the tile, which
+/// can show as a soft band.  Narrower widths leave the rest of the tile
+/// unblended, but the transition across the blended strip is steeper, and it
+/// can show as a seam as the width approaches zero.  To set the width, call
+/// the [`set_seam_blend()`](struct.SeamlessPlane.html#method.set_seam_blend)
+/// method.
+///
+/// This noise module requires one source module.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Perlin, SeamlessPlane};
+/// use noise::sampling::is_seamless;
+///
+/// let mut plane = SeamlessPlane::new(Perlin::new());
+/// plane.set_period(4.0, 2.0);
+/// assert!(is_seamless(&plane, 4.0, 2.0, 0.5, 1e-12, 64).is_ok());
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct SeamlessPlane<M: Module> {
+    module: M,
+    period: (f64, f64),
+    seam_blend: f64,
+}
+
+impl<M: Module> SeamlessPlane<M> {
+    /// Create a new `SeamlessPlane` noise module around the specified module,
+    /// using default parameters.
+    pub fn new(module: M) -> SeamlessPlane<M> {
+        SeamlessPlane {
+            module: module,
+            period: (DEFAULT_SEAMLESS_PERIOD, DEFAULT_SEAMLESS_PERIOD),
+            seam_blend: DEFAULT_SEAM_BLEND,
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Returns the size of the tile along the `x` and `y` axes.
+    pub fn period(&self) -> (f64, f64) {
+        self.period
+    }
+
+    /// Returns the fraction of the tile over which the output values are
+    /// blended.
+    pub fn seam_blend(&self) -> f64 {
+        self.seam_blend
+    }
+
+    /// Set the source module to be used.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+    }
+
+    /// Sets the size of the tile along the `x` and `y` axes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either period is not a finite number greater than zero.
+    pub fn set_period(&mut self, period_x: f64, period_y: f64) {
+        if !period_x.is_finite() || period_x <= 0.0 || !period_y.is_finite() || period_y <= 0.0 {
+            panic!("Periods must be finite and greater than zero!");
+        }
+        self.period = (period_x, period_y);
+    }
+
+    /// Sets the fraction of the tile, from its lower edge along each axis,
+    /// over which the output values are blended.
+    ///
+    /// Wider blends hide the seam better but flatten the noise over more of
+    /// the tile; 1.0 matches libnoise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seam_blend` is not greater than zero and no greater than
+    /// one.
+    pub fn set_seam_blend(&mut self, seam_blend: f64) {
+        if seam_blend.is_nan() || seam_blend <= 0.0 || seam_blend > 1.0 {
+            panic!("`seam_blend` must be greater than zero and no greater than one!");
+        }
+        self.seam_blend = seam_blend;
+    }
+
+    /// Returns the blend weight of the output value one period further along
+    /// an axis, given the position within the tile along that axis as a
+    /// fraction of the period.
+    fn blend_weight(&self, a: f64) -> f64 {
+        (1.0 - a / self.seam_blend).max(0.0)
+    }
+}
+
+/// Wraps a coordinate into the range from zero to `period`.
+fn wrap(value: f64, period: f64) -> f64 {
+    let wrapped = value - (value / period).floor() * period;
+    // Rounding can give exactly `period` for values just below a multiple.
+    if wrapped >= period { 0.0 } else { wrapped }
+}
+
+impl<M: Module> Module for SeamlessPlane<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (period_x, period_y) = self.period;
+        let x = wrap(x, period_x);
+        let y = wrap(y, period_y);
+        let x_blend = self.blend_weight(x / period_x);
+        let y_blend = self.blend_weight(y / period_y);
+
+        let value = |dx: f64, dy: f64| self.module.get_value(x + dx, y + dy, z);
+        let lower = if x_blend > 0.0 {
+            linear_interp(value(0.0, 0.0), value(period_x, 0.0), x_blend)
+        } else {
+            value(0.0, 0.0)
+        };
+        if y_blend == 0.0 {
+            return lower;
+        }
+        let upper = if x_blend > 0.0 {
+            linear_interp(value(0.0, period_y), value(period_x, period_y), x_blend)
+        } else {
+            value(0.0, period_y)
+        };
+        linear_interp(lower, upper, y_blend)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("SeamlessPlane");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_f64(self.period.0);
+        hasher.write_f64(self.period.1);
+        hasher.write_f64(self.seam_blend);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone> Clone for SeamlessPlane<M> {
+    fn clone(&self) -> SeamlessPlane<M> {
+        SeamlessPlane {
+            module: self.module.clone(),
+            period: self.period,
+            seam_blend: self.seam_blend,
+        }
+    }
+}
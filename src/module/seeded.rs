@@ -0,0 +1,99 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+use module::Module;
+
+/// A noise module whose output values are chosen by a seed.
+///
+/// This is implemented by the [`Perlin`](struct.Perlin.html),
+/// [`Billow`](struct.Billow.html), [`RidgedMulti`](struct.RidgedMulti.html),
+/// [`Voronoi`](struct.Voronoi.html), [`Turbulence`](struct.Turbulence.html)
+/// and [`RandomTile`](struct.RandomTile.html) noise modules.  A type-erased noise
+/// module can be converted to a `Seeded` noise module with
+/// [`Module::as_seeded_mut()`](trait.Module.html#method.as_seeded_mut).
+pub trait Seeded {
+    /// Returns the seed value.
+    fn seed(&self) -> i32;
+
+    /// Sets the seed value.
+    fn set_seed(&mut self, seed: i32);
+}
+
+/// Derives a seed from a base seed and an index.
+///
+/// The base seed and the index are mixed with the SplitMix64 finalizer, so
+/// that changing any bit of either input changes about half of the output
+/// bits.  Unlike adding the index to the base seed, this gives unrelated
+/// seeds for consecutive indices, and for consecutive base seeds with the
+/// same index.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::split_seed;
+///
+/// assert_eq!(split_seed(42, 0), split_seed(42, 0));
+/// assert!(split_seed(42, 0) != split_seed(42, 1));
+/// assert!(split_seed(42, 1) != split_seed(43, 0));
+/// ```
+pub fn split_seed(base_seed: i32, index: u32) -> i32 {
+    let mut z = ((base_seed as u32 as u64) << 32 | index as u64)
+        .wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^= z >> 31;
+    (z >> 32) as i32
+}
+
+/// Gives every seeded noise module in a graph a new seed, derived from a base
+/// seed.
+///
+/// The graph is walked depth first through
+/// [`sources_mut()`](trait.Module.html#method.sources_mut), visiting each
+/// noise module before its source modules.  Every noise module that is
+/// [`Seeded`](trait.Seeded.html) is given the seed
+/// [`split_seed(base_seed, n)`](fn.split_seed.html), where `n` counts the
+/// seeded noise modules visited before it, so that no two get the same seed.
+/// The same graph and base seed always give the same seeds.
+///
+/// Noise modules that do not give mutable access to their source modules are
+/// not walked through.  In particular, a noise module shared through more
+/// than one `Rc` or `Arc` is left unchanged, along with its source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{reseed_all, Add, BoxedModule, Module, Perlin, Voronoi};
+///
+/// let mut graph: BoxedModule = Box::new(Add::new(Perlin::new(), Voronoi::new()));
+/// let before = graph.get_value(0.3, 0.5, 0.7);
+/// reseed_all(&mut *graph, 1234);
+/// assert!(graph.get_value(0.3, 0.5, 0.7) != before);
+/// ```
+pub fn reseed_all(module: &mut dyn Module, base_seed: i32) {
+    reseed(module, base_seed, &mut 0);
+}
+
+fn reseed(module: &mut dyn Module, base_seed: i32, index: &mut u32) {
+    if let Some(seeded) = module.as_seeded_mut() {
+        seeded.set_seed(split_seed(base_seed, *index));
+        *index = index.wrapping_add(1);
+    }
+    for source in module.sources_mut() {
+        reseed(source, base_seed, index);
+    }
+}
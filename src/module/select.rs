@@ -14,8 +14,8 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
-use util::{linear_interp, scurve3};
+use module::{ConfigHasher, Interp, Module};
+use util::linear_interp;
 
 /// Default edge-falloff value for the [`Select`](struct.Select.html) noise
 /// module.
@@ -29,6 +29,10 @@ pub const DEFAULT_SELECT_LOWER_BOUND: f64 = -1.0;
 /// [`Select`](struct.Select.html) noise module.
 pub const DEFAULT_SELECT_UPPER_BOUND: f64 = 1.0;
 
+/// Default easing curve of the edge transition for the
+/// [`Select`](struct.Select.html) noise module.
+pub const DEFAULT_SELECT_INTERP: Interp = Interp::Cubic;
+
 /// Noise module that outputs the value selected from one of two source modules
 /// chosen by the output value from a control module.
 ///
@@ -39,9 +43,11 @@ pub const DEFAULT_SELECT_UPPER_BOUND: f64 = 1.0;
 /// two source modules at the selection-range boundary.  To smooth the
 /// transition, pass a non-zero value to the
 /// [`set_edge_falloff()`](struct.Select.html#method.set_edge_falloff) method.
-/// Higher values result in a smoother transition.
+/// Higher values result in a smoother transition.  The shape of the transition
+/// is set by the [`set_interp()`](struct.Select.html#method.set_interp) method.
 ///
 /// This noise module requires three source modules.
+#[derive(Debug, PartialEq)]
 pub struct Select<M1: Module, M2: Module, MC: Module> {
     module1: M1,
     module2: M2,
@@ -49,6 +55,7 @@ pub struct Select<M1: Module, M2: Module, MC: Module> {
     edge_falloff: f64,
     lower_bound: f64,
     upper_bound: f64,
+    interp: Interp,
 }
 
 impl<M1: Module, M2: Module, MC: Module> Select<M1, M2, MC> {
@@ -62,6 +69,7 @@ impl<M1: Module, M2: Module, MC: Module> Select<M1, M2, MC> {
             edge_falloff: DEFAULT_SELECT_EDGE_FALLOFF,
             lower_bound: DEFAULT_SELECT_LOWER_BOUND,
             upper_bound: DEFAULT_SELECT_UPPER_BOUND,
+            interp: DEFAULT_SELECT_INTERP,
         }
     }
 
@@ -135,6 +143,11 @@ impl<M1: Module, M2: Module, MC: Module> Select<M1, M2, MC> {
         self.upper_bound
     }
 
+    /// Returns the easing curve of the edge transition.
+    pub fn interp(&self) -> Interp {
+        self.interp
+    }
+
     /// Set the first module to be used.
     pub fn set_module1(&mut self, module1: M1) {
         self.module1 = module1;
@@ -209,6 +222,16 @@ impl<M1: Module, M2: Module, MC: Module> Select<M1, M2, MC> {
         self.clamp_falloff();
     }
 
+    /// Sets the easing curve of the edge transition.
+    ///
+    /// Within the edge transition, the output values from the two source
+    /// modules are blended with a weight mapped onto this curve.  By default
+    /// the weight follows a cubic S-curve, i.e.
+    /// [`Interp::Cubic`](enum.Interp.html).
+    pub fn set_interp(&mut self, interp: Interp) {
+        self.interp = interp;
+    }
+
     /// Makes sure that the edge falloff curves do not overlap.
     fn clamp_falloff(&mut self) {
         let bound_size = self.upper_bound - self.lower_bound;
@@ -234,10 +257,10 @@ impl<M1: Module, M2: Module, MC: Module> Module for Select<M1, M2, MC> {
                 // and second source modules.
                 let lower_curve = self.lower_bound - self.edge_falloff;
                 let upper_curve = self.lower_bound + self.edge_falloff;
-                let alpha = scurve3((control_value - lower_curve) / (upper_curve - lower_curve));
+                let alpha = (control_value - lower_curve) / (upper_curve - lower_curve);
                 linear_interp(self.module1.get_value(x, y, z),
                               self.module2.get_value(x, y, z),
-                              alpha)
+                              self.interp.ease(alpha))
             } else if control_value < self.upper_bound - self.edge_falloff {
                 // The output value from the control module is within the
                 // selector threshold; return the output value from the second
@@ -250,10 +273,10 @@ impl<M1: Module, M2: Module, MC: Module> Module for Select<M1, M2, MC> {
                 // and second source modules.
                 let lower_curve = self.upper_bound - self.edge_falloff;
                 let upper_curve = self.upper_bound + self.edge_falloff;
-                let alpha = scurve3((control_value - lower_curve) / (upper_curve - lower_curve));
+                let alpha = (control_value - lower_curve) / (upper_curve - lower_curve);
                 linear_interp(self.module2.get_value(x, y, z),
                               self.module1.get_value(x, y, z),
-                              alpha)
+                              self.interp.ease(alpha))
             } else {
                 // Output value from the control module is above the selector threshold;
                 // return the output value from the first source module.
@@ -267,6 +290,41 @@ impl<M1: Module, M2: Module, MC: Module> Module for Select<M1, M2, MC> {
             }
         }
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Select");
+        hasher.write_u64(self.module1.config_hash());
+        hasher.write_u64(self.module2.config_hash());
+        hasher.write_u64(self.mcontrol.config_hash());
+        hasher.write_f64(self.edge_falloff);
+        hasher.write_f64(self.lower_bound);
+        hasher.write_f64(self.upper_bound);
+        hasher.write_u64(self.interp as u64);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        3
+    }
+
+    /// Returns the first and second source modules, followed by the control
+    /// module.
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module1 as &dyn Module, &self.module2, &self.mcontrol]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module1),
+            1 => Some(&self.module2),
+            2 => Some(&self.mcontrol),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module1 as &mut dyn Module, &mut self.module2, &mut self.mcontrol]
+    }
 }
 
 impl<M1: Module + Clone,
@@ -280,6 +338,7 @@ impl<M1: Module + Clone,
             edge_falloff: self.edge_falloff,
             lower_bound: self.lower_bound,
             upper_bound: self.upper_bound,
+            interp: self.interp,
         }
     }
 }
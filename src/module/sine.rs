@@ -0,0 +1,132 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{Axis, ConfigHasher, Module};
+use std::f64::consts::PI;
+
+/// Default frequency for the [`Sine`](struct.Sine.html) noise module.
+pub const DEFAULT_SINE_FREQUENCY: f64 = 1.0;
+
+/// Default phase for the [`Sine`](struct.Sine.html) noise module.
+pub const DEFAULT_SINE_PHASE: f64 = 0.0;
+
+/// Default axis for the [`Sine`](struct.Sine.html) noise module.
+pub const DEFAULT_SINE_AXIS: Axis = Axis::X;
+
+/// Noise module that outputs a sine wave.
+///
+/// The output value is `sin(2π * frequency * coord + phase)`, where `coord` is
+/// the coordinate of the input value along the chosen axis, so the wave forms
+/// parallel bands perpendicular to that axis.  The output value ranges from
+/// -1.0 to +1.0, and repeats every `1.0 / frequency` units.  To choose the
+/// axis, call the [`set_axis()`](struct.Sine.html#method.set_axis) method;
+/// by default the wave runs along the `x` axis.
+///
+/// Since its output value is known exactly, this noise module is useful as a
+/// test pattern.  Combined with some turbulence, it can also produce banded
+/// textures such as wood grain or stripes.
+///
+/// This noise module does not require any source modules.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Axis, Module, Sine};
+///
+/// let mut sine = Sine::new();
+/// sine.set_frequency(0.5);
+/// sine.set_axis(Axis::Z);
+/// assert!((sine.get_value(7.0, 3.0, 0.5) - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sine {
+    frequency: f64,
+    phase: f64,
+    axis: Axis,
+}
+
+impl Default for Sine {
+    /// Create a new `Sine` noise module with default parameters.
+    fn default() -> Sine {
+        Sine {
+            frequency: DEFAULT_SINE_FREQUENCY,
+            phase: DEFAULT_SINE_PHASE,
+            axis: DEFAULT_SINE_AXIS,
+        }
+    }
+}
+
+impl Sine {
+    /// Create a new `Sine` noise module with default parameters.
+    pub fn new() -> Sine {
+        Default::default()
+    }
+
+    /// Returns the frequency of the sine wave, in cycles per unit.
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Returns the phase of the sine wave, in radians.
+    pub fn phase(&self) -> f64 {
+        self.phase
+    }
+
+    /// Returns the axis of the input value that drives the sine wave.
+    pub fn axis(&self) -> Axis {
+        self.axis
+    }
+
+    /// Sets the frequency of the sine wave, in cycles per unit.
+    pub fn set_frequency(&mut self, frequency: f64) {
+        self.frequency = frequency;
+    }
+
+    /// Sets the phase of the sine wave, in radians.
+    ///
+    /// The phase is added to the angle passed to `sin()`, so a phase of `π/2`
+    /// turns the sine wave into a cosine wave.
+    pub fn set_phase(&mut self, phase: f64) {
+        self.phase = phase;
+    }
+
+    /// Sets the axis of the input value that drives the sine wave.
+    ///
+    /// The output value only depends on the coordinate of the input value
+    /// along this axis.
+    pub fn set_axis(&mut self, axis: Axis) {
+        self.axis = axis;
+    }
+}
+
+impl Module for Sine {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let coord = match self.axis {
+            Axis::X => x,
+            Axis::Y => y,
+            Axis::Z => z,
+        };
+        (2.0 * PI * self.frequency * coord + self.phase).sin()
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Sine");
+        hasher.write_f64(self.frequency);
+        hasher.write_f64(self.phase);
+        hasher.write_u64(self.axis as u64);
+        hasher.finish()
+    }
+}
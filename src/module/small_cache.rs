@@ -0,0 +1,149 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// Number of output values remembered by the
+/// [`SmallCache`](struct.SmallCache.html) noise module.
+pub const SMALL_CACHE_CAPACITY: usize = 8;
+
+/// Noise module that caches the last few output values generated by a source
+/// module.
+///
+/// This noise module is like the [`Cache`](struct.Cache.html) noise module, but
+/// remembers the output values of the last
+/// [`SMALL_CACHE_CAPACITY`](constant.SMALL_CACHE_CAPACITY.html) distinct input
+/// values instead of only the last one.  When the cache is full, the least
+/// recently used output value is forgotten.
+///
+/// This suits passes that sample each point together with a few of its
+/// neighbours, such as calculating normals by finite differences: a neighbour
+/// sampled for one point is often sampled again, a moment later, as the next
+/// point itself.  The input values are compared bit for bit, so this only
+/// helps when exactly the same coordinates recur.  Coordinates calculated in
+/// two different ways, such as `x + step` and `(i + 1) * step`, often differ in
+/// the last bit and miss the cache.
+///
+/// The cache is updated through a shared reference, so this noise module
+/// cannot be shared between threads.  If an application passes a new source
+/// module to the [`set_module()`](struct.SmallCache.html#method.set_module)
+/// method, the cache is invalidated.
+///
+/// This noise module requires one source module.
+#[derive(Debug)]
+pub struct SmallCache<M: Module> {
+    module: M,
+    /// The bits of the cached input values and their output values, from the
+    /// most to the least recently used.
+    entries: RefCell<VecDeque<([u64; 3], f64)>>,
+}
+
+impl<M: Module> SmallCache<M> {
+    /// Create a new `SmallCache` noise module around the specified module.
+    pub fn new(module: M) -> SmallCache<M> {
+        SmallCache {
+            module: module,
+            entries: RefCell::new(VecDeque::with_capacity(SMALL_CACHE_CAPACITY)),
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    ///
+    /// This operation invalidates the cache.
+    pub fn module_mut(&mut self) -> &mut M {
+        self.entries.borrow_mut().clear();
+        &mut self.module
+    }
+
+    /// Set the source module to be used.
+    ///
+    /// This operation invalidates the cache.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+        self.entries.borrow_mut().clear();
+    }
+}
+
+impl<M: Module> Module for SmallCache<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let key = [x.to_bits(), y.to_bits(), z.to_bits()];
+        let mut entries = self.entries.borrow_mut();
+        if let Some(index) = entries.iter().position(|entry| entry.0 == key) {
+            // Move the entry to the front, as the most recently used.
+            let entry = entries.remove(index).unwrap();
+            entries.push_front(entry);
+            return entry.1;
+        }
+
+        let value = self.module.get_value(x, y, z);
+        if entries.len() == SMALL_CACHE_CAPACITY {
+            entries.pop_back();
+        }
+        entries.push_front((key, value));
+        value
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("SmallCache");
+        hasher.write_u64(self.module.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    /// This operation invalidates the cache.
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        self.entries.borrow_mut().clear();
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone> Clone for SmallCache<M> {
+    fn clone(&self) -> SmallCache<M> {
+        SmallCache {
+            module: self.module.clone(),
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<M: Module + PartialEq> PartialEq for SmallCache<M> {
+    /// Two `SmallCache` noise modules are equal if their source modules are
+    /// equal; the cached values themselves are not compared.
+    fn eq(&self, other: &SmallCache<M>) -> bool {
+        self.module == other.module
+    }
+}
@@ -14,12 +14,16 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Default frequency value for the [`Spheres`](struct.Spheres.html) noise
 /// module.
 pub const DEFAULT_SPHERES_FREQUENCY: f64 = 1.0;
 
+/// Default radial offset value for the [`Spheres`](struct.Spheres.html) noise
+/// module.
+pub const DEFAULT_SPHERES_OFFSET: f64 = 0.0;
+
 /// Noise module that outputs concentric spheres.
 ///
 /// This noise module outputs concentric spheres centered on the origin like the
@@ -39,13 +43,29 @@ pub const DEFAULT_SPHERES_FREQUENCY: f64 = 1.0;
 /// the frequency, call the
 /// [`set_frequency()`](struct.Spheres.html#method.set_frequency) method.
 ///
+/// The spheres can also be shifted outwards with the
+/// [`set_offset()`](struct.Spheres.html#method.set_offset) method, and the
+/// output value can be inverted, giving -1.0 on the sphere surfaces and 1.0
+/// between them, with the
+/// [`set_invert()`](struct.Spheres.html#method.set_invert) method.
+///
+/// Instead of this pattern, the noise module can output the signed distance to
+/// the nearest sphere surface, for use as a distance field, with the
+/// [`set_signed_distance()`](struct.Spheres.html#method.set_signed_distance)
+/// method.
+///
 /// This noise module, modified with some low-frequency, low-power turbulence,
 /// is useful for generating agate-like textures.
 ///
 /// This noise module does not require any source modules.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Spheres {
     frequency: f64,
+    /// Distance the spheres are shifted outwards, in units of the spacing
+    /// between them.
+    offset: f64,
+    invert: bool,
+    signed_distance: bool,
 }
 
 impl Default for Spheres {
@@ -53,6 +73,9 @@ impl Default for Spheres {
     fn default() -> Spheres {
         Spheres {
             frequency: DEFAULT_SPHERES_FREQUENCY,
+            offset: DEFAULT_SPHERES_OFFSET,
+            invert: false,
+            signed_distance: false,
         }
     }
 }
@@ -71,6 +94,24 @@ impl Spheres {
         self.frequency
     }
 
+    /// Returns the distance the concentric spheres are shifted outwards.
+    ///
+    /// See [`set_offset()`](struct.Spheres.html#method.set_offset) for details.
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Determines if the output value is inverted.
+    pub fn is_inverted(&self) -> bool {
+        self.invert
+    }
+
+    /// Determines if the output value is the signed distance to the nearest
+    /// sphere surface.
+    pub fn is_signed_distance(&self) -> bool {
+        self.signed_distance
+    }
+
     /// Sets the frequenct of the concentric spheres.
     ///
     /// Increasing the frequency increases the density of the concentric
@@ -78,6 +119,74 @@ impl Spheres {
     pub fn set_frequency(&mut self, frequency: f64) {
         self.frequency = frequency;
     }
+
+    /// Sets the distance the concentric spheres are shifted outwards.
+    ///
+    /// The offset shifts the phase of the spheres, and is measured in units
+    /// of the spacing between them: an offset of 0.5 moves every sphere half
+    /// way to the next one, and an offset of 1.0 has no visible effect.  The
+    /// sphere surfaces lie at the distances `(n + offset) / frequency` from
+    /// the origin, for every integer `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Module, Spheres};
+    ///
+    /// let mut spheres = Spheres::new();
+    /// spheres.set_offset(0.25);
+    /// assert_eq!(spheres.get_value(0.0, 0.25, 0.0), 1.0);
+    /// assert_eq!(spheres.get_value(0.0, 0.0, 0.0), 0.0);
+    /// ```
+    pub fn set_offset(&mut self, offset: f64) {
+        self.offset = offset;
+    }
+
+    /// Enables or disables inverting the output value.
+    ///
+    /// When inverted, the input values on the sphere surfaces are given the
+    /// output value -1.0 and the input values equidistant from two sphere
+    /// surfaces are given the output value 1.0.
+    pub fn set_invert(&mut self, invert: bool) {
+        self.invert = invert;
+    }
+
+    /// Enables or disables outputting the signed distance to the nearest
+    /// sphere surface.
+    ///
+    /// When enabled, the output value is the distance from the input value to
+    /// the nearest sphere surface, in the units of the input value.  It is
+    /// negative if the input value is inside that sphere and positive if it
+    /// is outside it, so it ranges from `-0.5 / frequency` to `+0.5 /
+    /// frequency` rather than from -1.0 to +1.0.  Halfway between two spheres
+    /// the nearest surface changes, so the output value jumps from positive to
+    /// negative there.  If the output value is also inverted, its sign is
+    /// flipped.
+    ///
+    /// By default, the output value follows the pattern described for
+    /// [`Spheres`](struct.Spheres.html) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Spheres, Module};
+    ///
+    /// let mut spheres = Spheres::new();
+    /// spheres.set_frequency(2.0);
+    /// spheres.set_signed_distance(true);
+    ///
+    /// // Just outside the sphere of radius 0.5, and just inside the next one.
+    /// assert!((spheres.get_value(0.6, 0.0, 0.0) - 0.1).abs() < 1e-12);
+    /// assert!((spheres.get_value(0.9, 0.0, 0.0) + 0.1).abs() < 1e-12);
+    ///
+    /// // The default output value only depends on the distance to the
+    /// // surface, not on which side of it the input value is.
+    /// spheres.set_signed_distance(false);
+    /// assert_eq!(spheres.get_value(0.6, 0.0, 0.0), spheres.get_value(0.9, 0.0, 0.0));
+    /// ```
+    pub fn set_signed_distance(&mut self, signed_distance: bool) {
+        self.signed_distance = signed_distance;
+    }
 }
 
 impl Module for Spheres {
@@ -87,9 +196,33 @@ impl Module for Spheres {
         let z = z * self.frequency;
 
         let dist_from_centre = (x * x + y * y + z * z).sqrt();
+        let dist_from_centre = dist_from_centre - self.offset;
         let dist_from_smaller_sphere = dist_from_centre - dist_from_centre.floor();
         let dist_from_larger_sphere = 1.0 - dist_from_smaller_sphere;
         let nearest_dist = f64::min(dist_from_smaller_sphere, dist_from_larger_sphere);
-        return 1.0 - (nearest_dist * 4.0); // Puts it in the -1.0 to +1.0 range.
+
+        if self.signed_distance {
+            // The input value is inside the larger sphere, and outside the
+            // smaller one.
+            let dist = if dist_from_smaller_sphere <= dist_from_larger_sphere {
+                dist_from_smaller_sphere
+            } else {
+                -dist_from_larger_sphere
+            };
+            let dist = dist / self.frequency.abs();
+            return if self.invert { -dist } else { dist };
+        }
+
+        let value = 1.0 - (nearest_dist * 4.0); // Puts it in the -1.0 to +1.0 range.
+        if self.invert { -value } else { value }
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Spheres");
+        hasher.write_f64(self.frequency);
+        hasher.write_f64(self.offset);
+        hasher.write_bool(self.invert);
+        hasher.write_bool(self.signed_distance);
+        hasher.finish()
     }
 }
@@ -14,7 +14,8 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
+use noisegen::value_noise3d;
 use util::{clamp, linear_interp};
 
 /// Noise module that maps the output value from a source module onto a
@@ -44,6 +45,7 @@ use util::{clamp, linear_interp};
 /// stereotypical desert canyon.
 ///
 /// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
 pub struct Terrace<M: Module> {
     module: M,
     invert_terraces: bool,
@@ -151,19 +153,60 @@ impl<M: Module> Terrace<M> {
             cur_value += terrace_step;
         }
     }
-}
 
-impl<M: Module> Module for Terrace<M> {
-    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+    /// Creates a number of randomly-spaced control points that range from -1
+    /// to +1.  The previous control points on the terrace-forming curve are
+    /// deleted.
+    ///
+    /// The first and last control points are always -1 and +1.  The remaining
+    /// control points are each placed at a random offset from where
+    /// [`make_control_points()`](struct.Terrace.html#method.make_control_points)
+    /// would place them, at most half the distance to the neighbouring
+    /// equally-spaced positions, so that the control points remain distinct
+    /// and in order.  The offsets are determined by `seed`; the same seed
+    /// always produces the same control points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is less than 2.
+    pub fn make_random_control_points(&mut self, count: i32, seed: i32) {
+        if count < 2 {
+            panic!("The number of control points must be greater than or equal to 2!");
+        }
+
+        self.control_points.clear();
+
+        let terrace_step = 2.0 / (count as f64 - 1.0);
+        self.control_points.push(-1.0);
+        for i in 1..(count - 1) {
+            // Map the noise value onto the range 0.0 to 1.0 (exclusive) and use
+            // it to pick a position within the interval belonging to this
+            // control point.
+            let jitter = (1.0 - value_noise3d(i, 0, 0, seed)) / 2.0;
+            self.control_points.push(-1.0 + (i as f64 - 0.5 + jitter) * terrace_step);
+        }
+        self.control_points.push(1.0);
+    }
+
+    /// Maps a value onto the terrace-forming curve.
+    ///
+    /// This performs the same mapping as
+    /// [`get_value()`](struct.Terrace.html#method.get_value), including the
+    /// inversion of the terraces if enabled, but on the given value instead of
+    /// the output value from the source module.  Values below the lowest
+    /// control point or above the highest control point are clamped to that
+    /// control point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than two control points on the curve.
+    pub fn map_value(&self, source_value: f64) -> f64 {
         if self.control_points.len() < 2 {
             panic!("Fewer than 2 control points on curve!");
         }
 
-        // Get the output value from the source module.
-        let source_value = self.module.get_value(x, y, z);
-
         // Find the first element in the control point array that has a value
-        // larger than the output value from the source module.
+        // larger than the value to map.
         let f = |x: &f64| x.partial_cmp(&source_value).unwrap();
         let idx_pos = match self.control_points.binary_search_by(f) {
             Ok(idx) => idx as isize + 1,
@@ -203,6 +246,41 @@ impl<M: Module> Module for Terrace<M> {
     }
 }
 
+impl<M: Module> Module for Terrace<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.map_value(self.module.get_value(x, y, z))
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Terrace");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_bool(self.invert_terraces);
+        for &control_point in &self.control_points {
+            hasher.write_f64(control_point);
+        }
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
 impl<M: Module + Clone> Clone for Terrace<M> {
     fn clone(&self) -> Terrace<M> {
         Terrace {
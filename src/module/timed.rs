@@ -0,0 +1,152 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::{ConfigHasher, Module};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// Noise module that measures the time spent generating output values from a
+/// source module.
+///
+/// This noise module outputs the value from its source module unchanged, and
+/// adds the wall-clock time taken by each call to the source module to a
+/// running total, which is returned by the
+/// [`total_time()`](struct.Timed.html#method.total_time) method.  The total
+/// includes the time spent in all of the noise modules beneath the source
+/// module, so wrapping several noise modules of a graph shows where the time
+/// goes.  To start measuring again from zero, call the
+/// [`reset()`](struct.Timed.html#method.reset) method.
+///
+/// Reading the clock twice for every output value adds overhead, which is
+/// significant for cheap source modules such as
+/// [`Constant`](struct.Constant.html), so the totals of small subgraphs are
+/// overestimated.  The total is updated through a shared reference, so this
+/// noise module cannot be shared between threads.
+///
+/// This noise module requires one source module.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Module, Perlin, Timed};
+///
+/// let timed = Timed::new(Perlin::new());
+/// for i in 0..100 {
+///     timed.get_value(i as f64 * 0.1, 0.0, 0.0);
+/// }
+/// println!("Perlin took {:?}", timed.total_time());
+/// ```
+#[derive(Debug)]
+pub struct Timed<M: Module> {
+    module: M,
+    total_time: Cell<Duration>,
+}
+
+impl<M: Module> Timed<M> {
+    /// Create a new `Timed` noise module around the specified module, with a
+    /// total time of zero.
+    pub fn new(module: M) -> Timed<M> {
+        Timed {
+            module: module,
+            total_time: Cell::new(Duration::new(0, 0)),
+        }
+    }
+
+    /// Returns a reference to the source module used.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the source module used.
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    /// Returns the total time spent generating output values from the source
+    /// module since this noise module was created or last reset.
+    pub fn total_time(&self) -> Duration {
+        self.total_time.get()
+    }
+
+    /// Set the source module to be used.
+    ///
+    /// The total time is not reset.
+    pub fn set_module(&mut self, module: M) {
+        self.module = module;
+    }
+
+    /// Resets the total time to zero.
+    pub fn reset(&self) {
+        self.total_time.set(Duration::new(0, 0));
+    }
+}
+
+impl<M: Module> Module for Timed<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let start = Instant::now();
+        let value = self.module.get_value(x, y, z);
+        self.total_time.set(self.total_time.get() + start.elapsed());
+        value
+    }
+
+    fn get_value_row(&self, x: f64, y: f64, z: f64, x_step: f64, values: &mut [f64]) {
+        let start = Instant::now();
+        self.module.get_value_row(x, y, z, x_step, values);
+        self.total_time.set(self.total_time.get() + start.elapsed());
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Timed");
+        hasher.write_u64(self.module.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
+}
+
+impl<M: Module + Clone> Clone for Timed<M> {
+    fn clone(&self) -> Timed<M> {
+        Timed {
+            module: self.module.clone(),
+            total_time: self.total_time.clone(),
+        }
+    }
+}
+
+impl<M: Module + PartialEq> PartialEq for Timed<M> {
+    /// Two `Timed` noise modules are equal if their source modules are equal;
+    /// the total times are not compared.
+    fn eq(&self, other: &Timed<M>) -> bool {
+        self.module == other.module
+    }
+}
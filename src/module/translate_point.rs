@@ -14,7 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module};
 
 /// Default translation applied to the `x` coordinate for the
 /// [`TranslatePoint`](struct.TranslatePoint.html) noise module.
@@ -44,6 +44,7 @@ pub const DEFAULT_TRANSLATE_POINT_Z: f64 = 0.0;
 /// methods, respectively.
 ///
 /// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
 pub struct TranslatePoint<M: Module> {
     module: M,
     trans: (f64, f64, f64),
@@ -148,6 +149,34 @@ impl<M: Module> Module for TranslatePoint<M> {
     fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
         self.module.get_value(x + self.trans.0, y + self.trans.1, z + self.trans.2)
     }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("TranslatePoint");
+        hasher.write_u64(self.module.config_hash());
+        hasher.write_f64(self.trans.0);
+        hasher.write_f64(self.trans.1);
+        hasher.write_f64(self.trans.2);
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.module as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.module),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.module as &mut dyn Module]
+    }
 }
 
 impl<M: Module + Clone> Clone for TranslatePoint<M> {
@@ -14,7 +14,7 @@
 // along with this library; if not, write to the Free Software Foundation,
 // Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
 
-use module::Module;
+use module::{ConfigHasher, Module, Seeded};
 use module::perlin;
 
 /// Default frequency for the [`Turbulence`](struct.Turbulence.html) noise
@@ -61,6 +61,9 @@ pub const DEFAULT_TURBULENCE_SEED: i32 = perlin::DEFAULT_PERLIN_SEED;
 ///   * Set the frequency to the same frequency as the source module.
 ///   * Set the power to the reciprocal of the frequency.
 ///
+/// To keep the power at the reciprocal of the frequency automatically, call the
+/// [`set_auto_power()`](struct.Turbulence.html#method.set_auto_power) method.
+///
 /// From these initial frequency and power values, modify these values until
 /// this noise module produce the desired changes in your terrain or texture.
 /// For example:
@@ -83,11 +86,17 @@ pub const DEFAULT_TURBULENCE_SEED: i32 = perlin::DEFAULT_PERLIN_SEED;
 ///
 /// Internally, there are three [`Perlin`](../perlin/struct.Perlin.html) noise
 /// modules that displace the input value; one for the `x`, one for the `y`, and
-/// one for the `z` coordinate.
+/// one for the `z` coordinate.  These can be accessed with the
+/// [`x_distort_mut()`](struct.Turbulence.html#method.x_distort_mut) method and
+/// its siblings, to change parameters of the displacement that have no setter
+/// here, such as the quality or lacunarity.
 ///
 /// This noise module requires one source module.
+#[derive(Debug, PartialEq)]
 pub struct Turbulence<M: Module> {
     power: f64,
+    /// Whether the power is kept at the reciprocal of the frequency.
+    auto_power: bool,
     msource: M,
     x_distort: perlin::Perlin,
     y_distort: perlin::Perlin,
@@ -103,6 +112,7 @@ impl<M: Module> Turbulence<M> {
         let z_distort = perlin::Perlin::default();
         let mut rv = Turbulence {
             power: DEFAULT_TURBULENCE_POWER,
+            auto_power: false,
             msource: module,
             x_distort: x_distort,
             y_distort: y_distort,
@@ -144,6 +154,12 @@ impl<M: Module> Turbulence<M> {
         self.power
     }
 
+    /// Determines if the power of the turbulence is automatically kept at the
+    /// reciprocal of the frequency.
+    pub fn is_auto_power(&self) -> bool {
+        self.auto_power
+    }
+
     /// Returns the roughness of the turbulence.
     ///
     /// The roughness of the turbulence determines the roughness of the changes
@@ -164,6 +180,71 @@ impl<M: Module> Turbulence<M> {
         self.x_distort.seed()
     }
 
+    /// Returns a reference to the internal Perlin-noise module that displaces
+    /// the `x` coordinate of the input value.
+    pub fn x_distort(&self) -> &perlin::Perlin {
+        &self.x_distort
+    }
+
+    /// Returns a reference to the internal Perlin-noise module that displaces
+    /// the `y` coordinate of the input value.
+    pub fn y_distort(&self) -> &perlin::Perlin {
+        &self.y_distort
+    }
+
+    /// Returns a reference to the internal Perlin-noise module that displaces
+    /// the `z` coordinate of the input value.
+    pub fn z_distort(&self) -> &perlin::Perlin {
+        &self.z_distort
+    }
+
+    /// Returns a mutable reference to the internal Perlin-noise module that
+    /// displaces the `x` coordinate of the input value.
+    ///
+    /// This allows any parameter of the displacement to be changed, such as
+    /// the quality or lacunarity.  Changes made this way bypass the setters of
+    /// this noise module, which keep the three Perlin-noise modules in step:
+    /// the frequency, roughness and seed of the modules may then differ, in
+    /// which case [`frequency()`](struct.Turbulence.html#method.frequency),
+    /// [`roughness()`](struct.Turbulence.html#method.roughness) and
+    /// [`seed()`](struct.Turbulence.html#method.seed) report those of the `x`
+    /// module.  Calling those setters again overwrites the changes to the
+    /// matching parameters of all three modules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Perlin, Turbulence};
+    /// use noise::noisegen::NoiseQuality;
+    ///
+    /// let mut turbulence = Turbulence::new(Perlin::new());
+    /// turbulence.x_distort_mut().set_quality(NoiseQuality::Best);
+    /// turbulence.y_distort_mut().set_quality(NoiseQuality::Best);
+    /// turbulence.z_distort_mut().set_quality(NoiseQuality::Best);
+    /// assert_eq!(turbulence.y_distort().quality(), NoiseQuality::Best);
+    /// ```
+    pub fn x_distort_mut(&mut self) -> &mut perlin::Perlin {
+        &mut self.x_distort
+    }
+
+    /// Returns a mutable reference to the internal Perlin-noise module that
+    /// displaces the `y` coordinate of the input value.
+    ///
+    /// See [`x_distort_mut()`](struct.Turbulence.html#method.x_distort_mut)
+    /// for details.
+    pub fn y_distort_mut(&mut self) -> &mut perlin::Perlin {
+        &mut self.y_distort
+    }
+
+    /// Returns a mutable reference to the internal Perlin-noise module that
+    /// displaces the `z` coordinate of the input value.
+    ///
+    /// See [`x_distort_mut()`](struct.Turbulence.html#method.x_distort_mut)
+    /// for details.
+    pub fn z_distort_mut(&mut self) -> &mut perlin::Perlin {
+        &mut self.z_distort
+    }
+
     /// Sets the module whose input values are going to be displaced randomly.
     pub fn set_module(&mut self, module: M) {
         self.msource = module;
@@ -173,10 +254,16 @@ impl<M: Module> Turbulence<M> {
     ///
     /// The frequency of the turbulence determines how rapidly the
     /// displacement amount changes.
+    ///
+    /// If automatic power is enabled, this also sets the power to the
+    /// reciprocal of the given frequency.
     pub fn set_frequency(&mut self, frequency: f64) {
         self.x_distort.set_frequency(frequency);
         self.y_distort.set_frequency(frequency);
         self.z_distort.set_frequency(frequency);
+        if self.auto_power {
+            self.power = 1.0 / frequency;
+        }
     }
 
     /// Sets the power of the turbulence.
@@ -187,6 +274,36 @@ impl<M: Module> Turbulence<M> {
         self.power = power;
     }
 
+    /// Enables or disables automatic power.
+    ///
+    /// While automatic power is enabled, the power of the turbulence is set to
+    /// the reciprocal of the frequency whenever the frequency is set, which is
+    /// the recommended starting point for the power.  Enabling automatic power
+    /// also sets the power immediately.  The power can still be overridden
+    /// with [`set_power()`](struct.Turbulence.html#method.set_power) until
+    /// the frequency is next set.
+    ///
+    /// By default, automatic power is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Perlin, Turbulence};
+    ///
+    /// let mut turbulence = Turbulence::new(Perlin::new());
+    /// turbulence.set_auto_power(true);
+    /// assert_eq!(turbulence.power(), 1.0 / turbulence.frequency());
+    ///
+    /// turbulence.set_frequency(4.0);
+    /// assert_eq!(turbulence.power(), 0.25);
+    /// ```
+    pub fn set_auto_power(&mut self, auto_power: bool) {
+        self.auto_power = auto_power;
+        if auto_power {
+            self.power = 1.0 / self.frequency();
+        }
+    }
+
     /// Sets the roughness of the turbulence.
     ///
     /// The roughness of the turbulence determines the roughness of the changes
@@ -201,13 +318,43 @@ impl<M: Module> Turbulence<M> {
     ///
     /// # Panics
     ///
-    /// Panics if the given `roughness` is outside the valid range for
-    /// `octave_count` accepted by
-    /// [`Perlin::set_octave_count()`](../perlin/struct.Perlin.html#method.set_octave_count).
+    /// Panics if `roughness` is less than 1 or greater than
+    /// [`PERLIN_MAX_OCTAVE`](constant.PERLIN_MAX_OCTAVE.html).  Use
+    /// [`try_set_roughness()`](struct.Turbulence.html#method.try_set_roughness)
+    /// to handle an invalid roughness without panicking.
     pub fn set_roughness(&mut self, roughness: i32) {
+        if let Err(message) = self.try_set_roughness(roughness) {
+            panic!("{}", message);
+        }
+    }
+
+    /// Sets the roughness of the turbulence, returning an error instead of
+    /// panicking if the roughness is invalid.
+    ///
+    /// The roughness must be between 1 and
+    /// [`PERLIN_MAX_OCTAVE`](constant.PERLIN_MAX_OCTAVE.html) inclusive.  If it
+    /// is not, the roughness is left unchanged.  Otherwise this behaves the
+    /// same as
+    /// [`set_roughness()`](struct.Turbulence.html#method.set_roughness).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{Perlin, Turbulence};
+    ///
+    /// let mut turbulence = Turbulence::new(Perlin::new());
+    /// assert!(turbulence.try_set_roughness(0).is_err());
+    /// assert!(turbulence.try_set_roughness(5).is_ok());
+    /// assert_eq!(turbulence.roughness(), 5);
+    /// ```
+    pub fn try_set_roughness(&mut self, roughness: i32) -> Result<(), &'static str> {
+        if !(1..=perlin::PERLIN_MAX_OCTAVE).contains(&roughness) {
+            return Err("roughness must be in [1, 30]");
+        }
         self.x_distort.set_octave_count(roughness);
         self.y_distort.set_octave_count(roughness);
         self.z_distort.set_octave_count(roughness);
+        Ok(())
     }
 
     /// Sets the seed value of the internal noise modules that are used to
@@ -221,22 +368,43 @@ impl<M: Module> Turbulence<M> {
     ///   * It assigns the seed value (`seed + 0`) to the `x` noise module.
     ///   * It assigns the seed value (`seed + 1`) to the `y` noise module.
     ///   * It assigns the seed value (`seed + 2`) to the `z` noise module.
+    ///
+    /// These additions wrap around on overflow, so every `i32` is a valid
+    /// seed.  As the `Perlin` noise modules clear the sign bit of the seed of
+    /// each octave, seeds that differ only in their sign bit generate the same
+    /// displacement.
     pub fn set_seed(&mut self, seed: i32) {
         // Set the seed of each `Perlin` noise modules.  To prevent any sort of
         // weird artifacting, use a slightly different seed for each noise
         // module.
         self.x_distort.set_seed(seed);
-        self.y_distort.set_seed(seed + 1);
-        self.z_distort.set_seed(seed + 2);
+        self.y_distort.set_seed(seed.wrapping_add(1));
+        self.z_distort.set_seed(seed.wrapping_add(2));
     }
-}
 
-impl<M: Module> Module for Turbulence<M> {
-    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
-        // Get the values from the three `Perlin` noise modules and add each
-        // value to each coordinate of the input value.  There are also some
-        // offsets added to the coordinates of the input values.  This prevents
-        // the distortion modules from returning zero if the (x, y, z)
+    /// Returns the displacement this noise module adds to the given input
+    /// value before passing it to the source module.
+    ///
+    /// Each coordinate of the displacement is the output value of the matching
+    /// internal Perlin-noise module, multiplied by the power.  The
+    /// [`get_value()`](struct.Turbulence.html#method.get_value) method returns
+    /// the output value from the source module at (`x + dx`, `y + dy`,
+    /// `z + dz`), so the length of the displacement shows where the
+    /// turbulence is strongest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::module::{FnModule, Module, Turbulence};
+    ///
+    /// let turbulence = Turbulence::new(FnModule::new(|x: f64, _y: f64, _z: f64| x));
+    /// let (dx, _dy, _dz) = turbulence.displacement_at(0.5, 1.0, 1.5);
+    /// assert_eq!(turbulence.get_value(0.5, 1.0, 1.5), 0.5 + dx);
+    /// ```
+    pub fn displacement_at(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        // Get the values from the three `Perlin` noise modules.  There are also
+        // some offsets added to the coordinates of the input values.  This
+        // prevents the distortion modules from returning zero if the (x, y, z)
         // coordinates, when multiplied by the frequency, are near an integer
         // boundary.  This is due to a property of gradient coherent noise,
         // which returns zero at integer boundaries.
@@ -249,13 +417,63 @@ impl<M: Module> Module for Turbulence<M> {
         let x2 = x + (53820.0 / 65536.0);
         let y2 = y + (11213.0 / 65536.0);
         let z2 = z + (44845.0 / 65536.0);
-        let x_distort = x + self.x_distort.get_value(x0, y0, z0) * self.power;
-        let y_distort = y + self.y_distort.get_value(x1, y1, z1) * self.power;
-        let z_distort = z + self.z_distort.get_value(x2, y2, z2) * self.power;
+        (self.x_distort.get_value(x0, y0, z0) * self.power,
+         self.y_distort.get_value(x1, y1, z1) * self.power,
+         self.z_distort.get_value(x2, y2, z2) * self.power)
+    }
+}
+
+impl<M: Module> Module for Turbulence<M> {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (dx, dy, dz) = self.displacement_at(x, y, z);
 
         // Retrieve the output value at the offsetted input value instead of the
         // original input value.
-        self.msource.get_value(x_distort, y_distort, z_distort)
+        self.msource.get_value(x + dx, y + dy, z + dz)
+    }
+
+    fn config_hash(&self) -> u64 {
+        let mut hasher = ConfigHasher::new("Turbulence");
+        hasher.write_f64(self.power);
+        hasher.write_bool(self.auto_power);
+        hasher.write_u64(self.msource.config_hash());
+        hasher.write_u64(self.x_distort.config_hash());
+        hasher.write_u64(self.y_distort.config_hash());
+        hasher.write_u64(self.z_distort.config_hash());
+        hasher.finish()
+    }
+
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn sources(&self) -> Vec<&dyn Module> {
+        vec![&self.msource as &dyn Module]
+    }
+
+    fn source(&self, index: usize) -> Option<&dyn Module> {
+        match index {
+            0 => Some(&self.msource),
+            _ => None,
+        }
+    }
+
+    fn sources_mut(&mut self) -> Vec<&mut dyn Module> {
+        vec![&mut self.msource as &mut dyn Module]
+    }
+
+    fn as_seeded_mut(&mut self) -> Option<&mut dyn Seeded> {
+        Some(self)
+    }
+}
+
+impl<M: Module> Seeded for Turbulence<M> {
+    fn seed(&self) -> i32 {
+        Turbulence::<M>::seed(self)
+    }
+
+    fn set_seed(&mut self, seed: i32) {
+        Turbulence::<M>::set_seed(self, seed)
     }
 }
 
@@ -263,6 +481,7 @@ impl<M: Module + Clone> Clone for Turbulence<M> {
     fn clone(&self) -> Turbulence<M> {
         Turbulence {
             power: self.power,
+            auto_power: self.auto_power,
             msource: self.msource.clone(),
             x_distort: self.x_distort.clone(),
             y_distort: self.y_distort.clone(),
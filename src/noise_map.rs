@@ -0,0 +1,741 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::Module;
+use noisegen::value_noise3d;
+use std::f64;
+
+/// Returns the coordinate of sample `index` of `count` samples spread evenly
+/// from the lower to the upper edge of `range`, inclusive.
+fn edge_coord(range: (f64, f64), index: usize, count: usize) -> f64 {
+    if count < 2 {
+        range.0
+    } else if index == count - 1 {
+        range.1
+    } else {
+        range.0 + (range.1 - range.0) * index as f64 / (count - 1) as f64
+    }
+}
+
+/// Converts a latitude and longitude, in degrees, to a point on the unit
+/// sphere.
+///
+/// The longitude is wrapped to the range -180 to 180 first so that meridians
+/// 360 degrees apart map to exactly the same point, and the poles map to
+/// exactly (`0`, `±1`, `0`) whatever the longitude.
+fn sphere_point(lat: f64, lon: f64) -> (f64, f64, f64) {
+    if lat >= 90.0 {
+        return (0.0, 1.0, 0.0);
+    } else if lat <= -90.0 {
+        return (0.0, -1.0, 0.0);
+    }
+    let lon = lon - 360.0 * ((lon + 180.0) / 360.0).floor();
+    let (lat, lon) = (lat.to_radians(), lon.to_radians());
+    (lat.cos() * lon.cos(), lat.sin(), lat.cos() * lon.sin())
+}
+
+/// Parameters for [`erode_hydraulic()`](struct.NoiseMap.html#method.erode_hydraulic).
+///
+/// The heights of the map are the values of the map, and adjacent values are
+/// one unit apart.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErosionParams {
+    /// Number of droplets to simulate.
+    pub droplets: usize,
+    /// Seed for the starting positions of the droplets.
+    pub seed: i32,
+    /// Maximum number of steps a droplet takes before it evaporates.
+    pub max_lifetime: usize,
+    /// How much a droplet keeps moving in its previous direction rather than
+    /// downhill, from 0.0 to 1.0.
+    pub inertia: f64,
+    /// Multiplier for how much sediment a droplet can carry.
+    pub capacity: f64,
+    /// Smallest slope used when calculating how much sediment a droplet can
+    /// carry, so that droplets on flat ground still erode it.
+    pub min_slope: f64,
+    /// Fraction of the excess sediment a droplet deposits at each step, from
+    /// 0.0 to 1.0.
+    pub deposition: f64,
+    /// Fraction of the spare capacity a droplet fills by eroding the ground at
+    /// each step, from 0.0 to 1.0.
+    pub erosion: f64,
+    /// Fraction of the water of a droplet that evaporates at each step, from
+    /// 0.0 to 1.0.
+    pub evaporation: f64,
+    /// Acceleration of a droplet as it moves downhill.
+    pub gravity: f64,
+}
+
+impl Default for ErosionParams {
+    fn default() -> ErosionParams {
+        ErosionParams {
+            droplets: 10000,
+            seed: 0,
+            max_lifetime: 30,
+            inertia: 0.05,
+            capacity: 4.0,
+            min_slope: 0.01,
+            deposition: 0.3,
+            erosion: 0.3,
+            evaporation: 0.01,
+            gravity: 4.0,
+        }
+    }
+}
+
+/// The contour lines of a [`NoiseMap`](struct.NoiseMap.html) at a single
+/// level, as returned by
+/// [`contours()`](struct.NoiseMap.html#method.contours).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contour {
+    /// The value the contour lines follow.
+    pub level: f64,
+    /// The line segments making up the contour lines, as pairs of end points
+    /// in map coordinates.
+    pub segments: Vec<[(f64, f64); 2]>,
+}
+
+/// A two-dimensional grid of output values from a noise module.
+///
+/// The values are stored in row-major order: the value at (`x`, `y`) is stored
+/// at index `y * width + x` of the slice returned by
+/// [`values()`](struct.NoiseMap.html#method.values).
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoiseMap {
+    width: usize,
+    height: usize,
+    values: Vec<f64>,
+}
+
+impl NoiseMap {
+    /// Create a new `NoiseMap` of the given size, with every value set to
+    /// zero.
+    pub fn new(width: usize, height: usize) -> NoiseMap {
+        NoiseMap {
+            width: width,
+            height: height,
+            values: vec![0.0; width * height],
+        }
+    }
+
+    /// Create a new `NoiseMap` by sampling a noise module over a regular grid
+    /// on the `z = 0` plane.
+    ///
+    ///   * `module` - The noise module to sample.
+    ///   * `origin` - The (`x`, `y`) coordinates of the input value of the
+    ///     first sample point.
+    ///   * `step` - The distance between adjacent sample points along the `x`
+    ///     and `y` axes.
+    ///   * `width`, `height` - The number of sample points along the `x` and
+    ///     `y` axes.
+    ///
+    /// The value at (`x`, `y`) in the map is the output value at the input
+    /// value (`origin.0 + x * step.0`, `origin.1 + y * step.1`, `0.0`).
+    /// Each coordinate is calculated from its index rather than by repeatedly
+    /// adding the step, so no rounding error accumulates across large maps.
+    pub fn from_module<M: Module>(module: &M, origin: (f64, f64), step: (f64, f64),
+                                  width: usize, height: usize) -> NoiseMap {
+        let mut values = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let y_cur = origin.1 + y as f64 * step.1;
+            for x in 0..width {
+                let x_cur = origin.0 + x as f64 * step.0;
+                values.push(module.get_value(x_cur, y_cur, 0.0));
+            }
+        }
+        NoiseMap {
+            width: width,
+            height: height,
+            values: values,
+        }
+    }
+
+    /// Create a new `NoiseMap` by sampling a noise module over the surface of
+    /// a unit sphere, producing an equirectangular map.
+    ///
+    ///   * `module` - The noise module to sample.
+    ///   * `width`, `height` - The number of sample points along the lines of
+    ///     latitude and longitude respectively.
+    ///   * `lat_range` - The southernmost and northernmost latitudes, in
+    ///     degrees.
+    ///   * `lon_range` - The westernmost and easternmost longitudes, in
+    ///     degrees.
+    ///
+    /// Row `0` of the map lies on the southern edge of `lat_range` and the last
+    /// row lies on its northern edge; likewise column `0` lies on the western
+    /// edge of `lon_range` and the last column on its eastern edge.  A point at
+    /// latitude `lat` and longitude `lon` is mapped to the input value
+    /// (`cos(lat) * cos(lon)`, `sin(lat)`, `cos(lat) * sin(lon)`), as in the
+    /// sphere model of libnoise.
+    ///
+    /// Since both edges are sampled, a map spanning a full 360 degrees of
+    /// longitude has identical first and last columns, matching the texture
+    /// coordinates at the seam of a UV sphere.  A row at latitude -90 or 90
+    /// samples the pole itself for every column, so each pole row holds a
+    /// single value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either latitude is outside of the range -90 to 90, or if the
+    /// lower bound of either range is greater than its upper bound.
+    pub fn from_sphere<M: Module>(module: &M, width: usize, height: usize, lat_range: (f64, f64),
+                                  lon_range: (f64, f64)) -> NoiseMap {
+        if !(-90.0..=90.0).contains(&lat_range.0) || !(-90.0..=90.0).contains(&lat_range.1) {
+            panic!("Latitude must be between -90 and 90 degrees!");
+        }
+        if lat_range.0 > lat_range.1 || lon_range.0 > lon_range.1 {
+            panic!("Lower bound is larger than upper bound!");
+        }
+        let mut values = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let lat = edge_coord(lat_range, y, height);
+            for x in 0..width {
+                let lon = edge_coord(lon_range, x, width);
+                let (x_cur, y_cur, z_cur) = sphere_point(lat, lon);
+                values.push(module.get_value(x_cur, y_cur, z_cur));
+            }
+        }
+        NoiseMap {
+            width: width,
+            height: height,
+            values: values,
+        }
+    }
+
+    /// Returns the number of values along the `x` axis.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of values along the `y` axis.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the value at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside of the map.
+    pub fn get_value(&self, x: usize, y: usize) -> f64 {
+        self.values[self.index(x, y)]
+    }
+
+    /// Sets the value at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside of the map.
+    pub fn set_value(&mut self, x: usize, y: usize, value: f64) {
+        let index = self.index(x, y);
+        self.values[index] = value;
+    }
+
+    /// Returns all of the values in the map, in row-major order.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns a mutable reference to all of the values in the map, in
+    /// row-major order.
+    pub fn values_mut(&mut self) -> &mut [f64] {
+        &mut self.values
+    }
+
+    /// Returns the smallest and largest values in the map.
+    ///
+    /// An empty map returns (`INFINITY`, `NEG_INFINITY`).
+    pub fn bounds(&self) -> (f64, f64) {
+        self.values.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+            (f64::min(min, value), f64::max(max, value))
+        })
+    }
+
+    /// Combines every value in this map with the value at the same position in
+    /// `other`, replacing it with `f(self_value, other_value)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two maps do not have the same width and height.
+    pub fn blend_with<F: Fn(f64, f64) -> f64>(&mut self, other: &NoiseMap, f: F) {
+        if self.width != other.width || self.height != other.height {
+            panic!("Cannot blend a {}x{} map with a {}x{} map!",
+                   self.width, self.height, other.width, other.height);
+        }
+        for (value, &other_value) in self.values.iter_mut().zip(&other.values) {
+            *value = f(*value, other_value);
+        }
+    }
+
+    /// Adds the value at each position in `other` to the value at the same
+    /// position in this map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two maps do not have the same width and height.
+    pub fn add(&mut self, other: &NoiseMap) {
+        self.blend_with(other, |a, b| a + b);
+    }
+
+    /// Multiplies the value at each position in this map by the value at the
+    /// same position in `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two maps do not have the same width and height.
+    pub fn multiply(&mut self, other: &NoiseMap) {
+        self.blend_with(other, |a, b| a * b);
+    }
+
+    /// Replaces the value at each position in this map with the larger of it
+    /// and the value at the same position in `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two maps do not have the same width and height.
+    pub fn max_with(&mut self, other: &NoiseMap) {
+        self.blend_with(other, f64::max);
+    }
+
+    /// Carves drainage channels into the map by simulating droplets of water
+    /// that run downhill, carrying sediment with them.
+    ///
+    /// Each droplet starts at a random position on the map, chosen by
+    /// `params.seed`, then repeatedly moves one unit in a direction between its
+    /// previous direction and the steepest way downhill.  As it moves, it
+    /// erodes the ground while it can carry more sediment, and deposits
+    /// sediment where it slows down, moves uphill, or carries more than it
+    /// can.  The amount of sediment it can carry depends on the slope, its
+    /// speed and how much water it has left.  The droplet stops when it
+    /// reaches flat ground, leaves the map, or reaches
+    /// `params.max_lifetime` steps, and deposits all of its remaining
+    /// sediment where it stops.  The sediment is eroded from and deposited on
+    /// the four values surrounding the droplet, so the total of the values in
+    /// the map is conserved, apart from rounding error.
+    ///
+    /// The same parameters always produce the same result.  Maps smaller than
+    /// two values along either axis are left unchanged.
+    ///
+    /// This takes time proportional to `params.droplets *
+    /// params.max_lifetime`, independent of the size of the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `params.inertia`, `params.deposition`, `params.erosion` or
+    /// `params.evaporation` is not within the range 0.0 to 1.0.
+    pub fn erode_hydraulic(&mut self, params: ErosionParams) {
+        for &(value, name) in &[(params.inertia, "inertia"),
+                                (params.deposition, "deposition"),
+                                (params.erosion, "erosion"),
+                                (params.evaporation, "evaporation")] {
+            if !(0.0..=1.0).contains(&value) {
+                panic!("`{}` must be within the range 0.0 to 1.0!", name);
+            }
+        }
+        if self.width < 2 || self.height < 2 {
+            return;
+        }
+
+        let x_max = (self.width - 1) as f64;
+        let y_max = (self.height - 1) as f64;
+        for i in 0..params.droplets {
+            let i = i as i32;
+            let mut pos = ((1.0 - value_noise3d(i, 0, 0, params.seed)) / 2.0 * x_max,
+                           (1.0 - value_noise3d(i, 1, 0, params.seed)) / 2.0 * y_max);
+            let mut dir = (0.0, 0.0);
+            let mut speed = 1.0;
+            let mut water = 1.0;
+            let mut sediment = 0.0;
+
+            for _ in 0..params.max_lifetime {
+                let (height, gradient) = self.height_and_gradient(pos);
+
+                // Turn towards the steepest way downhill.
+                dir = (dir.0 * params.inertia - gradient.0 * (1.0 - params.inertia),
+                       dir.1 * params.inertia - gradient.1 * (1.0 - params.inertia));
+                let len = (dir.0 * dir.0 + dir.1 * dir.1).sqrt();
+                if len == 0.0 {
+                    break;
+                }
+                dir = (dir.0 / len, dir.1 / len);
+                let new_pos = (pos.0 + dir.0, pos.1 + dir.1);
+                if !(0.0..x_max).contains(&new_pos.0) || !(0.0..y_max).contains(&new_pos.1) {
+                    break;
+                }
+
+                let delta = self.height_and_gradient(new_pos).0 - height;
+                let capacity = f64::max(-delta, params.min_slope) * speed * water
+                    * params.capacity;
+                if delta > 0.0 || sediment > capacity {
+                    // Fill the pit being climbed out of, or drop the excess.
+                    let amount = if delta > 0.0 {
+                        f64::min(delta, sediment)
+                    } else {
+                        (sediment - capacity) * params.deposition
+                    };
+                    sediment -= amount;
+                    self.add_bilinear(pos, amount);
+                } else {
+                    // Never erode deeper than the step downhill, to avoid
+                    // digging pits.
+                    let amount = f64::min((capacity - sediment) * params.erosion, -delta);
+                    sediment += amount;
+                    self.add_bilinear(pos, -amount);
+                }
+
+                speed = f64::max(speed * speed - delta * params.gravity, 0.0).sqrt();
+                water *= 1.0 - params.evaporation;
+                pos = new_pos;
+            }
+
+            self.add_bilinear(pos, sediment);
+        }
+    }
+
+    /// Flattens slopes steeper than the talus angle by moving material
+    /// downhill, as loose rock slides down to form scree slopes.
+    ///
+    /// The heights of the map are the values of the map, and adjacent values
+    /// are one unit apart, so a slope is steeper than `talus_angle` (in
+    /// radians) if the difference between adjacent values is greater than
+    /// `tan(talus_angle)`.
+    ///
+    /// In each iteration, every value that is higher than any of its
+    /// neighbours by more than this difference gives away half of the largest
+    /// excess, shared between those neighbours in proportion to how far each
+    /// is below it.  The values are updated together at the end of the
+    /// iteration, so the result does not depend on the order in which they
+    /// are visited.  The total of the values in the map is conserved, apart
+    /// from rounding error.  Repeated iterations converge towards a map with
+    /// no slope steeper than the talus angle; this stops early once no
+    /// material moves.
+    ///
+    /// Only the four neighbours along the `x` and `y` axes are considered.
+    /// This is cheaper than also considering the four diagonal neighbours,
+    /// but the slope along a diagonal can stay steeper than the talus angle,
+    /// by up to a factor of the square root of two.
+    ///
+    /// This takes time proportional to `iterations` times the number of values
+    /// in the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `talus_angle` is not within the range 0 to pi / 2, excluding
+    /// pi / 2.
+    pub fn erode_thermal(&mut self, talus_angle: f64, iterations: usize) {
+        if !(0.0..f64::consts::FRAC_PI_2).contains(&talus_angle) {
+            panic!("`talus_angle` must be within the range 0 to pi / 2!");
+        }
+        let talus = talus_angle.tan();
+
+        let mut deltas = vec![0.0; self.values.len()];
+        for _ in 0..iterations {
+            let mut moved = false;
+            for y in 0..self.height {
+                for x in 0..self.width {
+                    let index = y * self.width + x;
+                    let neighbours = [
+                        if x > 0 { Some(index - 1) } else { None },
+                        if x + 1 < self.width { Some(index + 1) } else { None },
+                        if y > 0 { Some(index - self.width) } else { None },
+                        if y + 1 < self.height { Some(index + self.width) } else { None },
+                    ];
+
+                    let mut max_diff = 0.0;
+                    let mut total_diff = 0.0;
+                    for &n in neighbours.iter().flatten() {
+                        let diff = self.values[index] - self.values[n];
+                        if diff > talus {
+                            max_diff = f64::max(max_diff, diff);
+                            total_diff += diff;
+                        }
+                    }
+                    if max_diff <= talus {
+                        continue;
+                    }
+
+                    let amount = (max_diff - talus) / 2.0;
+                    deltas[index] -= amount;
+                    for &n in neighbours.iter().flatten() {
+                        let diff = self.values[index] - self.values[n];
+                        if diff > talus {
+                            deltas[n] += amount * diff / total_diff;
+                        }
+                    }
+                    moved = true;
+                }
+            }
+            if !moved {
+                break;
+            }
+            for (value, delta) in self.values.iter_mut().zip(deltas.iter_mut()) {
+                *value += *delta;
+                *delta = 0.0;
+            }
+        }
+    }
+
+    /// Extracts the contour lines of the map at each of the given levels, using
+    /// marching squares.
+    ///
+    /// One [`Contour`](struct.Contour.html) is returned for each level, in the
+    /// same order.  Its segments are in map coordinates, where the value at
+    /// (`x`, `y`) lies at the point (`x`, `y`), and each end of a segment lies
+    /// on an edge between two adjacent values, interpolated linearly between
+    /// them.  A value equal to the level counts as above it.  The segments are
+    /// not joined into polylines, and cells with a NaN value at any corner
+    /// produce no segments.
+    ///
+    /// When the two diagonally opposite corners of a cell are above the level
+    /// and the other two are below it, the contour could cross the cell either
+    /// way.  This is resolved by the average of the four corners: if it is
+    /// above the level the two corners above it are joined through the middle
+    /// of the cell, and otherwise the two corners below it are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noise::noise_map::NoiseMap;
+    ///
+    /// let mut map = NoiseMap::new(2, 2);
+    /// map.set_value(1, 0, 1.0);
+    /// map.set_value(1, 1, 1.0);
+    ///
+    /// let contours = map.contours(&[0.25]);
+    /// assert_eq!(contours[0].segments, vec![[(0.25, 0.0), (0.25, 1.0)]]);
+    /// ```
+    pub fn contours(&self, levels: &[f64]) -> Vec<Contour> {
+        levels.iter().map(|&level| {
+            let mut segments = Vec::new();
+            for y in 1..self.height {
+                for x in 1..self.width {
+                    self.contour_cell(x - 1, y - 1, level, &mut segments);
+                }
+            }
+            Contour {
+                level: level,
+                segments: segments,
+            }
+        }).collect()
+    }
+
+    /// Appends the contour segments at `level` within the cell whose top-left
+    /// corner is the value at (`x`, `y`).
+    fn contour_cell(&self, x: usize, y: usize, level: f64, segments: &mut Vec<[(f64, f64); 2]>) {
+        // The corners in order around the cell, so that edge `i` joins corner
+        // `i` to the next corner.
+        let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+        let mut values = [0.0; 4];
+        for (value, &(cx, cy)) in values.iter_mut().zip(&corners) {
+            *value = self.values[self.index(cx, cy)];
+            if value.is_nan() {
+                return;
+            }
+        }
+
+        let above = |value: f64| value >= level;
+        let mut crossings = Vec::with_capacity(4);
+        for i in 0..4 {
+            let j = (i + 1) % 4;
+            let (a, b) = (values[i], values[j]);
+            if above(a) != above(b) {
+                let t = (level - a) / (b - a);
+                let (ax, ay) = (corners[i].0 as f64, corners[i].1 as f64);
+                let (bx, by) = (corners[j].0 as f64, corners[j].1 as f64);
+                crossings.push((ax + (bx - ax) * t, ay + (by - ay) * t));
+            }
+        }
+
+        if crossings.len() == 2 {
+            segments.push([crossings[0], crossings[1]]);
+        } else if crossings.len() == 4 {
+            // Saddle point: every edge is crossed.  If the middle of the cell
+            // is on the same side as corner 1, the contours cut off corners 0
+            // and 2; otherwise they cut off corners 1 and 3.
+            let average = (values[0] + values[1] + values[2] + values[3]) * 0.25;
+            if above(average) == above(values[1]) {
+                segments.push([crossings[3], crossings[0]]);
+                segments.push([crossings[1], crossings[2]]);
+            } else {
+                segments.push([crossings[0], crossings[1]]);
+                segments.push([crossings[2], crossings[3]]);
+            }
+        }
+    }
+
+    /// Returns the bilinearly interpolated height and its gradient at the
+    /// given position, which must be within the map, excluding the last row
+    /// and column.
+    fn height_and_gradient(&self, pos: (f64, f64)) -> (f64, (f64, f64)) {
+        let (index, fx, fy) = self.cell(pos);
+        let h00 = self.values[index];
+        let h10 = self.values[index + 1];
+        let h01 = self.values[index + self.width];
+        let h11 = self.values[index + self.width + 1];
+        let height = h00 * (1.0 - fx) * (1.0 - fy) + h10 * fx * (1.0 - fy)
+            + h01 * (1.0 - fx) * fy + h11 * fx * fy;
+        let gradient = ((h10 - h00) * (1.0 - fy) + (h11 - h01) * fy,
+                        (h01 - h00) * (1.0 - fx) + (h11 - h10) * fx);
+        (height, gradient)
+    }
+
+    /// Distributes `amount` between the four values surrounding the given
+    /// position, which must be within the map, excluding the last row and
+    /// column.
+    fn add_bilinear(&mut self, pos: (f64, f64), amount: f64) {
+        let (index, fx, fy) = self.cell(pos);
+        let width = self.width;
+        self.values[index] += amount * (1.0 - fx) * (1.0 - fy);
+        self.values[index + 1] += amount * fx * (1.0 - fy);
+        self.values[index + width] += amount * (1.0 - fx) * fy;
+        self.values[index + width + 1] += amount * fx * fy;
+    }
+
+    /// Returns the index of the value at the top-left corner of the cell
+    /// containing the given position, and the offset of the position within
+    /// the cell.
+    fn cell(&self, pos: (f64, f64)) -> (usize, f64, f64) {
+        let x = ::std::cmp::min(pos.0 as usize, self.width - 2);
+        let y = ::std::cmp::min(pos.1 as usize, self.height - 2);
+        (y * self.width + x, pos.0 - x as f64, pos.1 - y as f64)
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        if x >= self.width || y >= self.height {
+            panic!("Position ({}, {}) is outside of the map!", x, y);
+        }
+        y * self.width + x
+    }
+}
+
+/// Iterator that samples a noise module over a large grid one tile at a time.
+///
+/// The grid covers the rectangle on the `z = 0` plane from `lower` to `upper`,
+/// with `resolution` sample points along the `x` and `y` axes, including the
+/// sample points on the edges of the rectangle.  The grid is divided into
+/// square tiles of `tile_size` by `tile_size` sample points, which are yielded
+/// one at a time in row-major order, so that the whole grid never has to be
+/// held in memory.  The tiles in the last column and row are cut short if the
+/// grid does not divide evenly.
+///
+/// Adjacent tiles overlap by one row or column of sample points, so the edge
+/// values of a tile are exactly the same as those of its neighbour.  Each
+/// sample point is calculated from its index in the whole grid, not from the
+/// origin of its tile, so that the shared edges match to the last bit.
+///
+/// Each item is the index of the first sample point of the tile within the
+/// whole grid, and the tile itself.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::Perlin;
+/// use noise::noise_map::TileIterator;
+///
+/// let perlin = Perlin::new();
+/// let tiles: Vec<_> =
+///     TileIterator::new(&perlin, (-1.0, -1.0), (1.0, 1.0), (33, 33), 17).collect();
+/// assert_eq!(tiles.len(), 4);
+///
+/// // The right edge of the first tile is the left edge of the second.
+/// let (origin0, ref tile0) = tiles[0];
+/// let (origin1, ref tile1) = tiles[1];
+/// assert_eq!((origin0, origin1), ((0, 0), (16, 0)));
+/// for y in 0..17 {
+///     assert_eq!(tile0.get_value(16, y), tile1.get_value(0, y));
+/// }
+/// ```
+pub struct TileIterator<'a, M: Module + 'a> {
+    module: &'a M,
+    lower: (f64, f64),
+    step: (f64, f64),
+    resolution: (usize, usize),
+    tile_size: usize,
+    /// Index of the first sample point of the next tile.
+    next_origin: (usize, usize),
+}
+
+impl<'a, M: Module> TileIterator<'a, M> {
+    /// Create a new `TileIterator` over the grid with the given bounds and
+    /// resolution, yielding tiles of `tile_size` by `tile_size` sample points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either coordinate of `resolution` is less than two, or if
+    /// `tile_size` is less than two.
+    pub fn new(module: &'a M, lower: (f64, f64), upper: (f64, f64), resolution: (usize, usize),
+               tile_size: usize) -> TileIterator<'a, M> {
+        if resolution.0 < 2 || resolution.1 < 2 {
+            panic!("`resolution` must be at least two along each axis!");
+        }
+        if tile_size < 2 {
+            panic!("`tile_size` must be at least two!");
+        }
+        TileIterator {
+            module: module,
+            lower: lower,
+            step: ((upper.0 - lower.0) / (resolution.0 - 1) as f64,
+                   (upper.1 - lower.1) / (resolution.1 - 1) as f64),
+            resolution: resolution,
+            tile_size: tile_size,
+            next_origin: (0, 0),
+        }
+    }
+
+    /// Returns the distance between adjacent sample points along the `x` and
+    /// `y` axes.
+    pub fn step(&self) -> (f64, f64) {
+        self.step
+    }
+}
+
+impl<'a, M: Module> Iterator for TileIterator<'a, M> {
+    type Item = ((usize, usize), NoiseMap);
+
+    fn next(&mut self) -> Option<((usize, usize), NoiseMap)> {
+        let (x0, y0) = self.next_origin;
+        if y0 + 1 >= self.resolution.1 {
+            return None;
+        }
+
+        let width = ::std::cmp::min(self.tile_size, self.resolution.0 - x0);
+        let height = ::std::cmp::min(self.tile_size, self.resolution.1 - y0);
+        let mut values = Vec::with_capacity(width * height);
+        for y in y0..(y0 + height) {
+            let y_cur = self.lower.1 + y as f64 * self.step.1;
+            for x in x0..(x0 + width) {
+                let x_cur = self.lower.0 + x as f64 * self.step.0;
+                values.push(self.module.get_value(x_cur, y_cur, 0.0));
+            }
+        }
+
+        // Move on to the next tile, overlapping this one by one sample point.
+        let next_x = x0 + self.tile_size - 1;
+        self.next_origin = if next_x + 1 >= self.resolution.0 {
+            (0, y0 + self.tile_size - 1)
+        } else {
+            (next_x, y0)
+        };
+
+        let tile = NoiseMap {
+            width: width,
+            height: height,
+            values: values,
+        };
+        Some(((x0, y0), tile))
+    }
+}
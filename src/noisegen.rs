@@ -44,8 +44,9 @@ const int SEED_NOISE_GEN = 1013;
 const int SHIFT_NOISE_GEN = 8;
 #endif*/
 
+use std::f64::consts::FRAC_1_SQRT_2;
 use std::ops::Rem;
-use util::{linear_interp, scurve3, scurve5};
+use util::{linear_interp, scurve3, scurve3_deriv, scurve5, scurve5_deriv};
 
 #[cfg(feature = "old-noise-version")]
 mod consts {
@@ -338,7 +339,32 @@ static RANDOM_VECTORS_TABLE: [[f64; 4]; 256] = [
 ];
 
 /// Enumerates the noise quality.
-#[derive(Copy, Clone, Eq, PartialEq)]
+///
+/// The quality selects the curve used to interpolate between the noise values
+/// at the corners of each unit cube.  `Fast` uses the distance into the cube
+/// directly (linear interpolation), `Standard` maps it onto a cubic S-curve,
+/// and `Best` onto a quintic S-curve.  Every generator that takes a quality
+/// passes it through to the coherent-noise functions.
+///
+/// The time taken is dominated by looking up the gradient vectors at the
+/// corners of the cube, so `Fast` is only slightly faster than the other
+/// qualities.  Run `cargo bench --bench quality` to compare them.
+///
+/// # Examples
+///
+/// ```
+/// use noise::noisegen::{gradient_coherent_noise3d, NoiseQuality};
+///
+/// let fast = gradient_coherent_noise3d(0.3, 0.5, 0.7, 0, NoiseQuality::Fast);
+/// let standard = gradient_coherent_noise3d(0.3, 0.5, 0.7, 0, NoiseQuality::Standard);
+/// assert!(fast != standard);
+///
+/// // At the corners of the cube, the curves all agree.
+/// let fast = gradient_coherent_noise3d(0.0, 1.0, 2.0, 0, NoiseQuality::Fast);
+/// let standard = gradient_coherent_noise3d(0.0, 1.0, 2.0, 0, NoiseQuality::Standard);
+/// assert_eq!(fast, standard);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum NoiseQuality {
     /// Generates coherent noise quickly.  When a coherent-noise function with
     /// this quality setting is used to generate a bump-map image, there are
@@ -360,6 +386,32 @@ pub enum NoiseQuality {
     Best,
 }
 
+/// Number of dimensions of the coherent noise generated by a noise module.
+///
+/// Noise modules such as [`Perlin`](../module/struct.Perlin.html) generate
+/// three-dimensional coherent noise by default.  In two-dimensional mode they
+/// ignore the `z` coordinate of the input value and generate genuinely
+/// two-dimensional coherent noise with
+/// [`gradient_coherent_noise2d()`](fn.gradient_coherent_noise2d.html), which
+/// is substantially faster, since it interpolates four noise values rather
+/// than eight.
+///
+/// The two modes use different gradient vectors, so two-dimensional noise is
+/// not the same as three-dimensional noise sampled on the `z = 0` plane.  It is
+/// also free of the artifacts of such a slice: three-dimensional gradient
+/// noise is zero at every integer point, and the gradient vectors at the
+/// corners of the plane's lattice cells are mostly not parallel to the plane,
+/// so a slice is flatter and less isotropic than noise designed for two
+/// dimensions.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Dimensions {
+    /// Generates two-dimensional coherent noise from the `x` and `y`
+    /// coordinates of the input value, ignoring the `z` coordinate.
+    Dim2,
+    /// Generates three-dimensional coherent noise.
+    Dim3,
+}
+
 /// Generates a gradient-coherent-noise value from the coordinates of a
 /// three-dimensional input value.
 ///
@@ -375,6 +427,75 @@ pub enum NoiseQuality {
 /// noise, see the comments for the
 /// [`gradient_noise3d()`](fn.gradient_noise3d.html) function.
 pub fn gradient_coherent_noise3d(x: f64, y: f64, z: f64, seed: i32, quality: NoiseQuality) -> f64 {
+    gradient_coherent_noise3d_by(x, y, z, quality, |ix, iy, iz| gradient_vector(ix, iy, iz, seed))
+}
+
+/// Generates a gradient-coherent-noise value from the coordinates of a
+/// three-dimensional input value, looking up the gradient vectors through a
+/// permutation table.
+///
+///   * `x` - The x coordinate of the input value.
+///   * `y` - The y coordinate of the input value.
+///   * `z` - The z coordinate of the input value.
+///   * `table` - The permutation table.
+///   * `seed` - The random number seed.
+///   * `quality` - The quality of the coherent-noise.
+///
+/// The return value ranges from -1.0 to +1.0.
+///
+/// This function is an alternative to
+/// [`gradient_coherent_noise3d()`](fn.gradient_coherent_noise3d.html) that
+/// returns different values.  See [`PermTable`](struct.PermTable.html) for the
+/// differences.
+pub fn gradient_coherent_noise3d_table(x: f64, y: f64, z: f64, table: &PermTable, seed: i32,
+                                       quality: NoiseQuality) -> f64 {
+    gradient_coherent_noise3d_by(x, y, z, quality, |ix, iy, iz| {
+        &RANDOM_VECTORS_TABLE[table.hash(ix, iy, iz, seed)]
+    })
+}
+
+/// Generates a gradient-coherent-noise value from the coordinates of a
+/// three-dimensional input value, using the gradient vectors from the given
+/// gradient table.
+///
+///   * `x` - The x coordinate of the input value.
+///   * `y` - The y coordinate of the input value.
+///   * `z` - The z coordinate of the input value.
+///   * `gradients` - The gradient vectors.
+///   * `table` - The permutation table to look up the gradient vectors
+///     through, if any.
+///   * `seed` - The random number seed.
+///   * `quality` - The quality of the coherent-noise.
+///
+/// The gradient vectors are picked exactly as by
+/// [`gradient_coherent_noise3d()`](fn.gradient_coherent_noise3d.html), or by
+/// [`gradient_coherent_noise3d_table()`](fn.gradient_coherent_noise3d_table.html)
+/// if a permutation table is given, but from `gradients` instead of the
+/// standard set of gradient vectors.  With
+/// [`GradientTable::standard()`](struct.GradientTable.html#method.standard)
+/// the same values are returned as by those functions.
+///
+/// The return value ranges from -1.0 to +1.0 if the gradient vectors are unit
+/// vectors, as described for [`GradientTable`](struct.GradientTable.html).
+pub fn gradient_coherent_noise3d_custom(x: f64, y: f64, z: f64, gradients: &GradientTable,
+                                        table: Option<&PermTable>, seed: i32,
+                                        quality: NoiseQuality) -> f64 {
+    match table {
+        Some(table) => gradient_coherent_noise3d_by(x, y, z, quality, |ix, iy, iz| {
+            &gradients.vectors[table.hash(ix, iy, iz, seed)]
+        }),
+        None => gradient_coherent_noise3d_by(x, y, z, quality, |ix, iy, iz| {
+            &gradients.vectors[gradient_index(ix, iy, iz, seed)]
+        }),
+    }
+}
+
+/// Generates a gradient-coherent-noise value, using the given function to look
+/// up the gradient vector at each integer coordinate.
+fn gradient_coherent_noise3d_by<'a, F>(x: f64, y: f64, z: f64, quality: NoiseQuality,
+                                       gradient_vector: F) -> f64
+    where F: Fn(i32, i32, i32) -> &'a [f64; 4]
+{
     // Create a unit-length cube aligned along an integer boundary.  This cube
     // surrounds the input point.
     let x0 = if x > 0.0 { x as i32 } else { (x - 1.0) as i32 };
@@ -400,27 +521,430 @@ pub fn gradient_coherent_noise3d(x: f64, y: f64, z: f64, seed: i32, quality: Noi
     // the coherent-noise value at the input point, interpolate these eight
     // noise values using the S-curve value as the interpolant (trilinear
     // interpolation.)
-    let n0 = gradient_noise3d(x, y, z, x0, y0, z0, seed);
-    let n1  = gradient_noise3d(x, y, z, x1, y0, z0, seed);
+    let n0 = gradient_dot(x, y, z, x0, y0, z0, gradient_vector(x0, y0, z0));
+    let n1 = gradient_dot(x, y, z, x1, y0, z0, gradient_vector(x1, y0, z0));
     let ix0 = linear_interp(n0, n1, xs);
 
-    let n0 = gradient_noise3d(x, y, z, x0, y1, z0, seed);
-    let n1 = gradient_noise3d(x, y, z, x1, y1, z0, seed);
+    let n0 = gradient_dot(x, y, z, x0, y1, z0, gradient_vector(x0, y1, z0));
+    let n1 = gradient_dot(x, y, z, x1, y1, z0, gradient_vector(x1, y1, z0));
     let ix1 = linear_interp(n0, n1, xs);
     let iy0 = linear_interp(ix0, ix1, ys);
 
-    let n0 = gradient_noise3d(x, y, z, x0, y0, z1, seed);
-    let n1 = gradient_noise3d(x, y, z, x1, y0, z1, seed);
+    let n0 = gradient_dot(x, y, z, x0, y0, z1, gradient_vector(x0, y0, z1));
+    let n1 = gradient_dot(x, y, z, x1, y0, z1, gradient_vector(x1, y0, z1));
     let ix0 = linear_interp(n0, n1, xs);
 
-    let n0 = gradient_noise3d(x, y, z, x0, y1, z1, seed);
-    let n1 = gradient_noise3d(x, y, z, x1, y1, z1, seed);
+    let n0 = gradient_dot(x, y, z, x0, y1, z1, gradient_vector(x0, y1, z1));
+    let n1 = gradient_dot(x, y, z, x1, y1, z1, gradient_vector(x1, y1, z1));
     let ix1 = linear_interp(n0, n1, xs);
     let iy1 = linear_interp(ix0, ix1, ys);
 
     linear_interp(iy0, iy1, zs)
 }
 
+/// Generates four gradient-coherent-noise values at once, one from each lane
+/// of the given coordinates and seeds.
+///
+/// Each lane produces the same value as
+/// [`gradient_coherent_noise3d()`](fn.gradient_coherent_noise3d.html) given
+/// the coordinates and seed of that lane.  The gradient vectors are looked up
+/// one lane at a time, but the S-curve mapping, the gradient dot products and
+/// the trilinear interpolation are performed on all four lanes together.
+///
+/// This function is only available with the `simd` feature.
+#[cfg(feature = "simd")]
+pub fn gradient_coherent_noise3d_x4(x: [f64; 4], y: [f64; 4], z: [f64; 4], seed: [i32; 4],
+                                    quality: NoiseQuality) -> [f64; 4] {
+    use wide::f64x4;
+
+    // Create a unit-length cube aligned along an integer boundary for each
+    // lane.  This cube surrounds the input point.
+    let mut x0 = [0; 4];
+    let mut y0 = [0; 4];
+    let mut z0 = [0; 4];
+    for i in 0..4 {
+        x0[i] = if x[i] > 0.0 { x[i] as i32 } else { (x[i] - 1.0) as i32 };
+        y0[i] = if y[i] > 0.0 { y[i] as i32 } else { (y[i] - 1.0) as i32 };
+        z0[i] = if z[i] > 0.0 { z[i] as i32 } else { (z[i] - 1.0) as i32 };
+    }
+
+    let to_f64x4 = |v: [i32; 4]| f64x4::from([v[0] as f64, v[1] as f64, v[2] as f64, v[3] as f64]);
+    let (xv, yv, zv) = (f64x4::from(x), f64x4::from(y), f64x4::from(z));
+    let (x0v, y0v, z0v) = (to_f64x4(x0), to_f64x4(y0), to_f64x4(z0));
+    let one = f64x4::splat(1.0);
+
+    // Map the difference between the coordinates of the input value and the
+    // coordinates of the cube's outer-lower-left vertex onto an S-curve.
+    let scurve3 = |a: f64x4| a * a * (f64x4::splat(3.0) - f64x4::splat(2.0) * a);
+    let scurve5 = |a: f64x4| {
+        let a3 = a * a * a;
+        let a4 = a3 * a;
+        let a5 = a4 * a;
+        (f64x4::splat(6.0) * a5) - (f64x4::splat(15.0) * a4) + (f64x4::splat(10.0) * a3)
+    };
+    let (xs, ys, zs) = match quality {
+        NoiseQuality::Fast => (xv - x0v, yv - y0v, zv - z0v),
+        NoiseQuality::Standard => (scurve3(xv - x0v), scurve3(yv - y0v), scurve3(zv - z0v)),
+        NoiseQuality::Best => (scurve5(xv - x0v), scurve5(yv - y0v), scurve5(zv - z0v)),
+    };
+
+    // Calculates the gradient noise at the vertex offset from the
+    // outer-lower-left vertex by the given amount in each lane.
+    let noise = |dx: i32, dy: i32, dz: i32| {
+        let mut xg = [0.0; 4];
+        let mut yg = [0.0; 4];
+        let mut zg = [0.0; 4];
+        for i in 0..4 {
+            let gradient = gradient_vector(x0[i] + dx, y0[i] + dy, z0[i] + dz, seed[i]);
+            xg[i] = gradient[0];
+            yg[i] = gradient[1];
+            zg[i] = gradient[2];
+        }
+        let xp = xv - (x0v + f64x4::splat(dx as f64));
+        let yp = yv - (y0v + f64x4::splat(dy as f64));
+        let zp = zv - (z0v + f64x4::splat(dz as f64));
+        (f64x4::from(xg) * xp + f64x4::from(yg) * yp + f64x4::from(zg) * zp) * f64x4::splat(2.12)
+    };
+    let lerp = |n0: f64x4, n1: f64x4, a: f64x4| ((one - a) * n0) + (a * n1);
+
+    // Now calculate the noise values at each vertex of the cube and
+    // interpolate them, as in the scalar version.
+    let ix0 = lerp(noise(0, 0, 0), noise(1, 0, 0), xs);
+    let ix1 = lerp(noise(0, 1, 0), noise(1, 1, 0), xs);
+    let iy0 = lerp(ix0, ix1, ys);
+
+    let ix0 = lerp(noise(0, 0, 1), noise(1, 0, 1), xs);
+    let ix1 = lerp(noise(0, 1, 1), noise(1, 1, 1), xs);
+    let iy1 = lerp(ix0, ix1, ys);
+
+    lerp(iy0, iy1, zs).to_array()
+}
+
+/// Generates a gradient-coherent-noise value from the coordinates of a
+/// three-dimensional input value, along with the partial derivatives of the
+/// noise with respect to each coordinate.
+///
+///   * `x` - The x coordinate of the input value.
+///   * `y` - The y coordinate of the input value.
+///   * `z` - The z coordinate of the input value.
+///   * `seed` - The random number seed.
+///   * `quality` - The quality of the coherent-noise.
+///
+/// Returns the same value as
+/// [`gradient_coherent_noise3d()`](fn.gradient_coherent_noise3d.html) and the
+/// analytic gradient `[d/dx, d/dy, d/dz]` of that function at the input value.
+///
+/// With [`NoiseQuality::Fast`](enum.NoiseQuality.html) the gradient is
+/// discontinuous at integer boundaries.
+pub fn gradient_coherent_noise3d_with_gradient(x: f64, y: f64, z: f64, seed: i32,
+                                               quality: NoiseQuality) -> (f64, [f64; 3]) {
+    gradient_coherent_noise3d_with_gradient_by(x, y, z, quality, |ix, iy, iz| {
+        gradient_vector(ix, iy, iz, seed)
+    })
+}
+
+/// Generates a gradient-coherent-noise value and its gradient, looking up the
+/// gradient vectors through a permutation table.
+///
+/// Returns the same value as
+/// [`gradient_coherent_noise3d_table()`](fn.gradient_coherent_noise3d_table.html)
+/// and the analytic gradient of that function at the input value, as for
+/// [`gradient_coherent_noise3d_with_gradient()`](fn.gradient_coherent_noise3d_with_gradient.html).
+pub fn gradient_coherent_noise3d_with_gradient_table(x: f64, y: f64, z: f64, table: &PermTable,
+                                                     seed: i32, quality: NoiseQuality)
+                                                     -> (f64, [f64; 3]) {
+    gradient_coherent_noise3d_with_gradient_by(x, y, z, quality, |ix, iy, iz| {
+        &RANDOM_VECTORS_TABLE[table.hash(ix, iy, iz, seed)]
+    })
+}
+
+/// Generates a gradient-coherent-noise value and its gradient, using the
+/// gradient vectors from the given gradient table.
+///
+/// Returns the same value as
+/// [`gradient_coherent_noise3d_custom()`](fn.gradient_coherent_noise3d_custom.html)
+/// and the analytic gradient of that function at the input value, as for
+/// [`gradient_coherent_noise3d_with_gradient()`](fn.gradient_coherent_noise3d_with_gradient.html).
+pub fn gradient_coherent_noise3d_with_gradient_custom(x: f64, y: f64, z: f64,
+                                                      gradients: &GradientTable,
+                                                      table: Option<&PermTable>, seed: i32,
+                                                      quality: NoiseQuality)
+                                                      -> (f64, [f64; 3]) {
+    match table {
+        Some(table) => gradient_coherent_noise3d_with_gradient_by(x, y, z, quality, |ix, iy, iz| {
+            &gradients.vectors[table.hash(ix, iy, iz, seed)]
+        }),
+        None => gradient_coherent_noise3d_with_gradient_by(x, y, z, quality, |ix, iy, iz| {
+            &gradients.vectors[gradient_index(ix, iy, iz, seed)]
+        }),
+    }
+}
+
+/// Returns an upper bound on the magnitude of the gradient of
+/// [`gradient_coherent_noise3d()`](fn.gradient_coherent_noise3d.html) with the
+/// given quality, for any input value and seed.
+///
+/// The bound also holds for
+/// [`gradient_coherent_noise3d_table()`](fn.gradient_coherent_noise3d_table.html),
+/// which uses the same gradient vectors.  For
+/// [`gradient_coherent_noise3d_custom()`](fn.gradient_coherent_noise3d_custom.html),
+/// multiply the bound by the length of the longest gradient vector in the
+/// table.  It is not exact: the bound is several times larger than the
+/// steepest gradient usually found by sampling.
+pub fn gradient_coherent_noise3d_max_gradient(quality: NoiseQuality) -> f64 {
+    // The output value is a weighted sum of the noise values at the corners of
+    // the unit cube, so by the product rule its gradient has two parts.
+    //
+    // The first part comes from the weights.  Along each axis, it is the slope
+    // of the S-curve times a weighted average of the differences between the
+    // noise values at opposite corners.  Each noise value is the dot product
+    // of a gradient vector, of length at most 1 (plus rounding in the table),
+    // with the offset to the corner, scaled by 2.12.  The two offsets to
+    // opposite corners along an axis have lengths summing to at most sqrt(2) +
+    // sqrt(3), which bounds the difference.  Combining the three axes
+    // multiplies this by sqrt(3).
+    //
+    // The second part is the weighted average of the gradient vectors
+    // themselves, scaled by 2.12.
+    let max_slope = match quality {
+        NoiseQuality::Fast => 1.0,
+        NoiseQuality::Standard => scurve3_deriv(0.5),
+        NoiseQuality::Best => scurve5_deriv(0.5),
+    };
+    let max_vector = 2.12 * 1.000001;
+    let max_difference = max_vector * (::std::f64::consts::SQRT_2 + ::consts::SQRT_3);
+    max_slope * max_difference * ::consts::SQRT_3 + max_vector
+}
+
+/// Generates a gradient-coherent-noise value and its gradient, using the given
+/// function to look up the gradient vector at each integer coordinate.
+fn gradient_coherent_noise3d_with_gradient_by<'a, F>(x: f64, y: f64, z: f64,
+                                                     quality: NoiseQuality, gradient_vector: F)
+                                                     -> (f64, [f64; 3])
+    where F: Fn(i32, i32, i32) -> &'a [f64; 4]
+{
+    // Create a unit-length cube aligned along an integer boundary.  This cube
+    // surrounds the input point.
+    let x0 = if x > 0.0 { x as i32 } else { (x - 1.0) as i32 };
+    let y0 = if y > 0.0 { y as i32 } else { (y - 1.0) as i32 };
+    let z0 = if z > 0.0 { z as i32 } else { (z - 1.0) as i32 };
+
+    // Map the difference between the coordinates of the input value and the
+    // coordinates of the cube's outer-lower-left vertex onto an S-curve, and
+    // find the derivative of the S-curve at that point.
+    let (u, v, w) = (x - x0 as f64, y - y0 as f64, z - z0 as f64);
+    let (xs, ys, zs, dxs, dys, dzs) = match quality {
+        NoiseQuality::Fast => (u, v, w, 1.0, 1.0, 1.0),
+        NoiseQuality::Standard => (scurve3(u), scurve3(v), scurve3(w),
+                                   scurve3_deriv(u), scurve3_deriv(v), scurve3_deriv(w)),
+        NoiseQuality::Best => (scurve5(u), scurve5(v), scurve5(w),
+                               scurve5_deriv(u), scurve5_deriv(v), scurve5_deriv(w)),
+    };
+
+    // Calculate the noise values at each vertex of the cube, interpolating
+    // them in the same order as `gradient_coherent_noise3d()` so that the
+    // values are identical.
+    let mut n = [[[0.0; 2]; 2]; 2];
+    let mut g = [[[[0.0; 3]; 2]; 2]; 2];
+    for dz in 0..2 {
+        for dy in 0..2 {
+            for dx in 0..2 {
+                let (ix, iy, iz) = (x0 + dx as i32, y0 + dy as i32, z0 + dz as i32);
+                let gradient = gradient_vector(ix, iy, iz);
+                n[dz][dy][dx] = gradient_dot(x, y, z, ix, iy, iz, gradient);
+                g[dz][dy][dx] = [gradient[0] * 2.12, gradient[1] * 2.12, gradient[2] * 2.12];
+            }
+        }
+    }
+
+    let ix0 = linear_interp(n[0][0][0], n[0][0][1], xs);
+    let ix1 = linear_interp(n[0][1][0], n[0][1][1], xs);
+    let iy0 = linear_interp(ix0, ix1, ys);
+    let ix0 = linear_interp(n[1][0][0], n[1][0][1], xs);
+    let ix1 = linear_interp(n[1][1][0], n[1][1][1], xs);
+    let iy1 = linear_interp(ix0, ix1, ys);
+    let value = linear_interp(iy0, iy1, zs);
+
+    // The value is a weighted sum of the vertex noise values.  Differentiate
+    // each term with the product rule: the weights vary with the S-curve
+    // values, and each vertex noise value varies with its gradient vector.
+    let weights = |a: f64, i: usize| if i == 0 { 1.0 - a } else { a };
+    let signs = [-1.0, 1.0];
+    let mut gradient = [0.0; 3];
+    for dz in 0..2 {
+        for dy in 0..2 {
+            for dx in 0..2 {
+                let (wx, wy, wz) = (weights(xs, dx), weights(ys, dy), weights(zs, dz));
+                let nv = n[dz][dy][dx];
+                let gv = g[dz][dy][dx];
+                let weight = wx * wy * wz;
+                gradient[0] += signs[dx] * dxs * wy * wz * nv + weight * gv[0];
+                gradient[1] += wx * signs[dy] * dys * wz * nv + weight * gv[1];
+                gradient[2] += wx * wy * signs[dz] * dzs * nv + weight * gv[2];
+            }
+        }
+    }
+
+    (value, gradient)
+}
+
+/// Unit-length gradient vectors for two-dimensional gradient noise, evenly
+/// spaced around the circle.
+const GRADIENT_VECTORS_2D: [[f64; 2]; 16] = [
+    [1.0, 0.0], [0.9238795325112867, 0.3826834323650898],
+    [FRAC_1_SQRT_2, FRAC_1_SQRT_2], [0.3826834323650898, 0.9238795325112867],
+    [0.0, 1.0], [-0.3826834323650898, 0.9238795325112867],
+    [-FRAC_1_SQRT_2, FRAC_1_SQRT_2], [-0.9238795325112867, 0.3826834323650898],
+    [-1.0, 0.0], [-0.9238795325112867, -0.3826834323650898],
+    [-FRAC_1_SQRT_2, -FRAC_1_SQRT_2], [-0.3826834323650898, -0.9238795325112867],
+    [0.0, -1.0], [0.3826834323650898, -0.9238795325112867],
+    [FRAC_1_SQRT_2, -FRAC_1_SQRT_2], [0.9238795325112867, -0.3826834323650898],
+];
+
+/// Scaling applied to two-dimensional gradient noise so that it ranges from
+/// -1.0 to +1.0.  The noise of unit-length gradient vectors is largest at the
+/// center of a square, at most half its diagonal.
+const GRADIENT_2D_SCALE: f64 = ::std::f64::consts::SQRT_2;
+
+/// Generates a gradient-coherent-noise value from the coordinates of a
+/// two-dimensional input value.
+///
+///   * `x` - The x coordinate of the input value.
+///   * `y` - The y coordinate of the input value.
+///   * `seed` - The random number seed.
+///   * `quality` - The quality of the coherent-noise.
+///
+/// The return value ranges from -1.0 to +1.0.
+///
+/// This is the two-dimensional counterpart of
+/// [`gradient_coherent_noise3d()`](fn.gradient_coherent_noise3d.html).  It
+/// hashes the integer coordinates in the same way, but chooses between 16
+/// gradient vectors evenly spaced around the circle, and interpolates the
+/// noise values at the four corners of a square rather than the eight corners
+/// of a cube, so it is faster.  It returns different values from the
+/// three-dimensional function on the `z = 0` plane; see
+/// [`Dimensions`](enum.Dimensions.html).
+///
+/// # Examples
+///
+/// ```
+/// use noise::noisegen::{gradient_coherent_noise2d, NoiseQuality};
+///
+/// // Gradient noise is zero at every integer point.
+/// assert_eq!(gradient_coherent_noise2d(3.0, -2.0, 0, NoiseQuality::Standard), 0.0);
+/// let value = gradient_coherent_noise2d(0.3, 0.7, 0, NoiseQuality::Standard);
+/// assert!(value != 0.0 && value.abs() <= 1.0);
+/// ```
+pub fn gradient_coherent_noise2d(x: f64, y: f64, seed: i32, quality: NoiseQuality) -> f64 {
+    // Create a unit-length square aligned along an integer boundary.  This
+    // square surrounds the input point.
+    let x0 = if x > 0.0 { x as i32 } else { (x - 1.0) as i32 };
+    let x1 = x0 + 1;
+    let y0 = if y > 0.0 { y as i32 } else { (y - 1.0) as i32 };
+    let y1 = y0 + 1;
+
+    // Map the difference between the coordinates of the input value and the
+    // coordinates of the square's lower-left vertex onto an S-curve.
+    let (xs, ys) = match quality {
+        NoiseQuality::Fast => (x - x0 as f64, y - y0 as f64),
+        NoiseQuality::Standard => (scurve3(x - x0 as f64), scurve3(y - y0 as f64)),
+        NoiseQuality::Best => (scurve5(x - x0 as f64), scurve5(y - y0 as f64)),
+    };
+
+    // Interpolate the noise values at the four vertices of the square
+    // (bilinear interpolation).
+    let n0 = gradient_dot2d(x, y, x0, y0, seed);
+    let n1 = gradient_dot2d(x, y, x1, y0, seed);
+    let ix0 = linear_interp(n0, n1, xs);
+    let n0 = gradient_dot2d(x, y, x0, y1, seed);
+    let n1 = gradient_dot2d(x, y, x1, y1, seed);
+    let ix1 = linear_interp(n0, n1, xs);
+    linear_interp(ix0, ix1, ys)
+}
+
+/// Generates a gradient-coherent-noise value from the coordinates of a
+/// two-dimensional input value, along with the partial derivatives of the
+/// noise with respect to each coordinate.
+///
+/// Returns the same value as
+/// [`gradient_coherent_noise2d()`](fn.gradient_coherent_noise2d.html) and the
+/// analytic gradient `[d/dx, d/dy]` of that function at the input value.
+///
+/// With [`NoiseQuality::Fast`](enum.NoiseQuality.html) the gradient is
+/// discontinuous at integer boundaries.
+pub fn gradient_coherent_noise2d_with_gradient(x: f64, y: f64, seed: i32,
+                                               quality: NoiseQuality) -> (f64, [f64; 2]) {
+    let x0 = if x > 0.0 { x as i32 } else { (x - 1.0) as i32 };
+    let y0 = if y > 0.0 { y as i32 } else { (y - 1.0) as i32 };
+
+    let (u, v) = (x - x0 as f64, y - y0 as f64);
+    let (xs, ys, dxs, dys) = match quality {
+        NoiseQuality::Fast => (u, v, 1.0, 1.0),
+        NoiseQuality::Standard => (scurve3(u), scurve3(v), scurve3_deriv(u), scurve3_deriv(v)),
+        NoiseQuality::Best => (scurve5(u), scurve5(v), scurve5_deriv(u), scurve5_deriv(v)),
+    };
+
+    // Calculate the noise values at each vertex of the square, interpolating
+    // them in the same order as `gradient_coherent_noise2d()` so that the
+    // values are identical.
+    let n = [[gradient_dot2d(x, y, x0, y0, seed), gradient_dot2d(x, y, x0 + 1, y0, seed)],
+             [gradient_dot2d(x, y, x0, y0 + 1, seed), gradient_dot2d(x, y, x0 + 1, y0 + 1, seed)]];
+    let ix0 = linear_interp(n[0][0], n[0][1], xs);
+    let ix1 = linear_interp(n[1][0], n[1][1], xs);
+    let value = linear_interp(ix0, ix1, ys);
+
+    // Differentiate each term of the weighted sum with the product rule, as
+    // for the three-dimensional noise.
+    let weights = |a: f64, i: usize| if i == 0 { 1.0 - a } else { a };
+    let signs = [-1.0, 1.0];
+    let mut gradient = [0.0; 2];
+    for dy in 0..2 {
+        for dx in 0..2 {
+            let (wx, wy) = (weights(xs, dx), weights(ys, dy));
+            let vector = gradient_vector2d(x0 + dx as i32, y0 + dy as i32, seed);
+            let nv = n[dy][dx];
+            gradient[0] += signs[dx] * dxs * wy * nv + wx * wy * vector[0] * GRADIENT_2D_SCALE;
+            gradient[1] += wx * signs[dy] * dys * nv + wx * wy * vector[1] * GRADIENT_2D_SCALE;
+        }
+    }
+
+    (value, gradient)
+}
+
+/// Returns an upper bound on the magnitude of the gradient of
+/// [`gradient_coherent_noise2d()`](fn.gradient_coherent_noise2d.html) with the
+/// given quality, for any input value and seed.
+///
+/// Like
+/// [`gradient_coherent_noise3d_max_gradient()`](fn.gradient_coherent_noise3d_max_gradient.html),
+/// it is not exact.
+pub fn gradient_coherent_noise2d_max_gradient(quality: NoiseQuality) -> f64 {
+    // The same argument as for the three-dimensional noise, with the offsets to
+    // opposite corners along an axis summing to at most 1 + sqrt(2), and two
+    // axes to combine.
+    let max_slope = match quality {
+        NoiseQuality::Fast => 1.0,
+        NoiseQuality::Standard => scurve3_deriv(0.5),
+        NoiseQuality::Best => scurve5_deriv(0.5),
+    };
+    let max_vector = GRADIENT_2D_SCALE * 1.000001;
+    let max_difference = max_vector * (1.0 + ::std::f64::consts::SQRT_2);
+    max_slope * max_difference * ::std::f64::consts::SQRT_2 + max_vector
+}
+
+/// Calculates the two-dimensional gradient-noise value from the input value
+/// and the integer coordinates of a nearby value.
+fn gradient_dot2d(fx: f64, fy: f64, ix: i32, iy: i32, seed: i32) -> f64 {
+    let gradient = gradient_vector2d(ix, iy, seed);
+    (gradient[0] * (fx - ix as f64) + gradient[1] * (fy - iy as f64)) * GRADIENT_2D_SCALE
+}
+
+/// Returns the random two-dimensional gradient vector for the given integer
+/// coordinates.
+fn gradient_vector2d(ix: i32, iy: i32, seed: i32) -> &'static [f64; 2] {
+    &GRADIENT_VECTORS_2D[gradient_index(ix, iy, 0, seed) & 0x0f]
+}
+
 /// Generates a gradient-noise value from the coordinates of a three-dimensional
 /// input value and the integer coordinates of a nearby three-dimensional value.
 ///
@@ -456,22 +980,15 @@ pub fn gradient_coherent_noise3d(x: f64, y: f64, z: f64, seed: i32, quality: Noi
 /// A noise function differs from a random-number generator because it always
 /// returns the same output value if the same input value is passed to it.
 pub fn gradient_noise3d(fx: f64, fy: f64, fz: f64, ix: i32, iy: i32, iz: i32, seed: i32) -> f64 {
-    // Randomly generate a gradient vector given the integer coordinates of the
-    // input value.  This implementation generates a random number and uses it
-    // as an index into a normalized-vector lookup table.
-    use std::num::Wrapping;
-    let vec_idx =
-        Wrapping(X_NOISE_GEN) * Wrapping(ix)
-        + Wrapping(Y_NOISE_GEN) * Wrapping(iy)
-        + Wrapping(Z_NOISE_GEN) * Wrapping(iz)
-        + Wrapping(SEED_NOISE_GEN) * Wrapping(seed);
-
-    let vec_idx = vec_idx ^ (vec_idx >> SHIFT_NOISE_GEN as usize);
-    let vec_idx = vec_idx & Wrapping(0xff);
+    gradient_dot(fx, fy, fz, ix, iy, iz, gradient_vector(ix, iy, iz, seed))
+}
 
-    let xv_gradient = RANDOM_VECTORS_TABLE[vec_idx.0 as usize][0];
-    let yv_gradient = RANDOM_VECTORS_TABLE[vec_idx.0 as usize][1];
-    let zv_gradient = RANDOM_VECTORS_TABLE[vec_idx.0 as usize][2];
+/// Calculates the gradient-noise value from the input value, the integer
+/// coordinates of a nearby value and the gradient vector at those coordinates.
+fn gradient_dot(fx: f64, fy: f64, fz: f64, ix: i32, iy: i32, iz: i32, gradient: &[f64; 4]) -> f64 {
+    let xv_gradient = gradient[0];
+    let yv_gradient = gradient[1];
+    let zv_gradient = gradient[2];
 
     // Set up us another vector equal to the distance between the two vectors
     // passed to this function.
@@ -487,6 +1004,30 @@ pub fn gradient_noise3d(fx: f64, fy: f64, fz: f64, ix: i32, iy: i32, iz: i32, se
      + zv_gradient * zv_point) * 2.12
 }
 
+/// Returns the random gradient vector for the given integer coordinates.
+fn gradient_vector(ix: i32, iy: i32, iz: i32, seed: i32) -> &'static [f64; 4] {
+    &RANDOM_VECTORS_TABLE[gradient_index(ix, iy, iz, seed)]
+}
+
+/// Returns the index of the random gradient vector for the given integer
+/// coordinates, from 0 to 255.
+fn gradient_index(ix: i32, iy: i32, iz: i32, seed: i32) -> usize {
+    // Randomly generate a gradient vector given the integer coordinates of the
+    // input value.  This implementation generates a random number and uses it
+    // as an index into a normalized-vector lookup table.
+    use std::num::Wrapping;
+    let vec_idx =
+        Wrapping(X_NOISE_GEN) * Wrapping(ix)
+        + Wrapping(Y_NOISE_GEN) * Wrapping(iy)
+        + Wrapping(Z_NOISE_GEN) * Wrapping(iz)
+        + Wrapping(SEED_NOISE_GEN) * Wrapping(seed);
+
+    let vec_idx = vec_idx ^ (vec_idx >> SHIFT_NOISE_GEN as usize);
+    let vec_idx = vec_idx & Wrapping(0xff);
+
+    vec_idx.0 as usize
+}
+
 /// Generates an integer-noise value from the coordinates of a three-dimensional
 /// input value.
 ///
@@ -523,6 +1064,20 @@ pub fn i32_value_noise3d(x: i32, y: i32, z: i32, seed: i32) -> i32 {
 /// Although you could do a straight cast from `f64` to `i32`, the resulting
 /// value may differ between platforms.  By using this function, you ensure that
 /// the resulting value is identical between platforms.
+///
+/// Values from -2<sup>30</sup> to +2<sup>30</sup> (exclusive) are returned
+/// unchanged.  Values from +2<sup>30</sup> upwards are mapped to
+/// `2 * (n % 2^30) - 2^30`, and values from -2<sup>30</sup> downwards to
+/// `2 * (n % 2^30) + 2^30`, where `%` is the remainder with the sign of `n`.
+/// The result always lies within -2<sup>30</sup> to +2<sup>30</sup>
+/// (inclusive).
+///
+/// This wrapping is not seamless.  Wrapped values are scaled by two, so
+/// features beyond the threshold are half the size of those near the origin,
+/// and the result jumps back across the whole range at every multiple of
+/// 2<sup>30</sup>.  Code that must behave consistently far from the origin
+/// should not rely on the integer part of the result; see
+/// [`make_i32_range_deriv()`](fn.make_i32_range_deriv.html) for the scaling.
 pub fn make_i32_range(n: f64) -> f64 {
     if n >= 1073741824.0 {
         (2.0 * n.rem(1073741824.0)) - 1073741824.0
@@ -533,6 +1088,19 @@ pub fn make_i32_range(n: f64) -> f64 {
     }
 }
 
+/// Returns the derivative of [`make_i32_range()`](fn.make_i32_range.html) at
+/// the given value.
+///
+/// Values outside the range of an `i32` are wrapped back into the range, which
+/// also scales them by two.
+pub fn make_i32_range_deriv(n: f64) -> f64 {
+    if n >= 1073741824.0 || n <= -1073741824.0 {
+        2.0
+    } else {
+        1.0
+    }
+}
+
 /// Generates a value-coherent-noise value from the coordinates of a
 /// three-dimensional input value.
 ///
@@ -610,3 +1178,181 @@ pub fn value_noise3d(x: i32, y: i32, z: i32, seed: i32) -> f64 {
     1.0 - (i32_value_noise3d(x, y, z, seed) as f64 / 1073741824.0)
 }
 
+/// Generates a value-noise value from the coordinates of a three-dimensional
+/// input value, hashing the coordinates through a permutation table.
+///
+///   * `table` - The permutation table.
+///   * `x` - The x coordinate of the input value.
+///   * `y` - The y coordinate of the input value.
+///   * `z` - The z coordinate of the input value.
+///   * `seed` - A random number seed.
+///
+/// The return value ranges from -1.0 to +1.0.
+///
+/// This function is an alternative to
+/// [`value_noise3d()`](fn.value_noise3d.html) that returns different values.
+/// See [`PermTable`](struct.PermTable.html) for the differences.
+pub fn value_noise3d_table(table: &PermTable, x: i32, y: i32, z: i32, seed: i32) -> f64 {
+    1.0 - (table.hash(x, y, z, seed) as f64 / 127.5)
+}
+
+/// Table of pseudo-random permutations used to hash integer coordinates.
+///
+/// The functions in this module normally hash integer coordinates by
+/// multiplying them with large primes, every time they are called.  A
+/// permutation table instead hashes the coordinates by repeatedly looking up
+/// their lowest eight bits in a shuffled table of the numbers 0 to 255.  This
+/// is the approach taken by Ken Perlin's reference implementation of his
+/// noise.
+///
+/// A permutation table is often assumed to be much faster, but the hash it
+/// replaces is only a handful of integer operations, whereas each lookup in the
+/// table must wait for the one before it.  Measured with
+/// `cargo bench --bench perm_table`, the table makes
+/// [`Perlin`](../module/struct.Perlin.html) about 5% faster and
+/// [`Voronoi`](../module/struct.Voronoi.html) about 15% faster, but makes a
+/// tight loop generating value noise several times slower, as the compiler can
+/// vectorise the hash but not the lookups.
+///
+/// The table is shuffled once, when it is created, according to its seed.  The
+/// `seed` passed alongside the table to the functions using it only selects
+/// between a few variations of the same table; use it to decorrelate, say, the
+/// octaves of a fractal.  To change the noise entirely, create a new table
+/// with a different seed.
+///
+/// Functions using a permutation table return different values from their
+/// counterparts that hash on the fly, and only give 256 distinct values.  As
+/// only the lowest eight bits of each coordinate are hashed, the noise also
+/// repeats every 256 units along each axis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PermTable {
+    seed: i32,
+    /// A permutation of the numbers 0 to 255, repeated twice so that the sum
+    /// of an entry and an eight-bit coordinate can be looked up directly.
+    perm: [u8; 512],
+}
+
+impl PermTable {
+    /// Create a new `PermTable` shuffled according to the given seed.
+    pub fn new(seed: i32) -> PermTable {
+        let mut perm = [0; 512];
+        for (i, entry) in perm.iter_mut().take(256).enumerate() {
+            *entry = i as u8;
+        }
+
+        // Shuffle the table with a Fisher-Yates shuffle, using integer noise
+        // as the source of random numbers.
+        for i in (1..256).rev() {
+            let j = i32_value_noise3d(i as i32, 0, 0, seed) as usize % (i + 1);
+            perm.swap(i, j);
+        }
+
+        let (lower, upper) = perm.split_at_mut(256);
+        upper.copy_from_slice(lower);
+        PermTable {
+            seed: seed,
+            perm: perm,
+        }
+    }
+
+    /// Returns the seed the table was shuffled with.
+    pub fn seed(&self) -> i32 {
+        self.seed
+    }
+
+    /// Hashes the given integer coordinates and seed to a number from 0 to 255.
+    fn hash(&self, x: i32, y: i32, z: i32, seed: i32) -> usize {
+        let perm = &self.perm;
+        let h = perm[(seed & 0xff) as usize] as usize;
+        let h = perm[h + (x & 0xff) as usize] as usize;
+        let h = perm[h + (y & 0xff) as usize] as usize;
+        perm[h + (z & 0xff) as usize] as usize
+    }
+}
+
+/// Table of the gradient vectors used to generate gradient coherent noise.
+///
+/// Gradient coherent noise picks a pseudo-random gradient vector at each
+/// integer coordinate, and the set of vectors it picks from determines the
+/// character of the noise.  The standard set of 256 vectors, returned by
+/// [`standard()`](struct.GradientTable.html#method.standard), points evenly in
+/// every direction.  A restricted set gives stylized noise: for example, with
+/// only the six axis-aligned vectors the noise has a blocky look, with
+/// features lined up with the axes.
+///
+/// The table always holds 256 vectors, one for each value of the hash of the
+/// integer coordinates.  It is built from a list of between 1 and 256 vectors,
+/// repeated in order to fill the table.  If the length of the list does not
+/// divide 256, the vectors at the start of the list are picked slightly more
+/// often than the others.
+///
+/// Each vector should be a unit vector for the noise to range from -1.0 to
+/// +1.0; the noise is scaled by the length of the vectors.
+///
+/// # Examples
+///
+/// ```
+/// use noise::noisegen::{gradient_coherent_noise3d, gradient_coherent_noise3d_custom,
+///                       GradientTable, NoiseQuality};
+///
+/// let axes = GradientTable::new(&[[1.0, 0.0, 0.0], [-1.0, 0.0, 0.0],
+///                                 [0.0, 1.0, 0.0], [0.0, -1.0, 0.0],
+///                                 [0.0, 0.0, 1.0], [0.0, 0.0, -1.0]]);
+/// let value = gradient_coherent_noise3d_custom(0.3, 0.5, 0.7, &axes, None, 0,
+///                                              NoiseQuality::Standard);
+/// assert!(value >= -1.0 && value <= 1.0);
+///
+/// // The standard table gives the standard noise.
+/// let standard = GradientTable::standard();
+/// assert_eq!(gradient_coherent_noise3d_custom(0.3, 0.5, 0.7, &standard, None, 0,
+///                                             NoiseQuality::Standard),
+///            gradient_coherent_noise3d(0.3, 0.5, 0.7, 0, NoiseQuality::Standard));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradientTable {
+    /// The gradient vectors, padded with a zero like the standard table.
+    /// Boxed as the table is large.
+    vectors: Box<[[f64; 4]; 256]>,
+}
+
+impl GradientTable {
+    /// Create a new `GradientTable` filled with the given gradient vectors,
+    /// repeated in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gradients` is empty or holds more than 256 vectors, or if
+    /// any component of a vector is not finite.
+    pub fn new(gradients: &[[f64; 3]]) -> GradientTable {
+        if gradients.is_empty() || gradients.len() > 256 {
+            panic!("The number of gradient vectors must be in the range [1, 256]!");
+        }
+        if gradients.iter().any(|gradient| gradient.iter().any(|c| !c.is_finite())) {
+            panic!("Gradient vectors must be finite!");
+        }
+        let mut vectors = Box::new([[0.0; 4]; 256]);
+        for (vector, gradient) in vectors.iter_mut().zip(gradients.iter().cycle()) {
+            vector[..3].copy_from_slice(gradient);
+        }
+        GradientTable { vectors: vectors }
+    }
+
+    /// Create a new `GradientTable` holding the standard gradient vectors,
+    /// which are used when no table is given.
+    pub fn standard() -> GradientTable {
+        GradientTable { vectors: Box::new(RANDOM_VECTORS_TABLE) }
+    }
+
+    /// Returns the gradient vector with the given index in the table.
+    pub fn vector(&self, index: u8) -> [f64; 3] {
+        let vector = &self.vectors[index as usize];
+        [vector[0], vector[1], vector[2]]
+    }
+
+    /// Returns the length of the longest gradient vector in the table.
+    pub fn max_length(&self) -> f64 {
+        self.vectors.iter()
+            .map(|v| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt())
+            .fold(0.0, f64::max)
+    }
+}
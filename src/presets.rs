@@ -0,0 +1,195 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+//! Ready-made noise module graphs for common kinds of terrain.
+//!
+//! Each function in this module builds a complete graph of noise modules from
+//! a seed and a small set of parameters, and returns it as a
+//! [`BoxedModule`](../module/type.BoxedModule.html).  The graphs are working
+//! starting points, and their source code shows how the generator and
+//! combiner noise modules are usually put together.  Each layer of a graph is
+//! seeded with a different offset from the given seed, so the layers are
+//! independent.
+//!
+//! # Examples
+//!
+//! ```
+//! use noise::module::Module;
+//! use noise::presets::{self, MountainParams};
+//!
+//! let terrain = presets::mountainous_terrain(42, &MountainParams::default());
+//! let height = terrain.get_value(0.5, 0.0, 1.25);
+//! ```
+
+use module::{Billow, BoxedModule, Constant, Perlin, RidgedMulti, ScaleBias, Select, Turbulence};
+
+/// Parameters for [`mountainous_terrain()`](fn.mountainous_terrain.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MountainParams {
+    /// Frequency of the mountain ridges.  The other layers are scaled with it.
+    pub frequency: f64,
+    /// Height of the terrain; the output values lie roughly within
+    /// +/- this value.
+    pub height: f64,
+    /// Fraction of the terrain covered by mountains rather than lowlands, from
+    /// 0.0 to 1.0.
+    pub mountain_coverage: f64,
+    /// Power of the turbulence that roughens the terrain.
+    pub roughness: f64,
+}
+
+impl Default for MountainParams {
+    fn default() -> MountainParams {
+        MountainParams {
+            frequency: 1.0,
+            height: 1.0,
+            mountain_coverage: 0.5,
+            roughness: 0.125,
+        }
+    }
+}
+
+/// Parameters for [`rolling_hills()`](fn.rolling_hills.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct HillParams {
+    /// Frequency of the hills.
+    pub frequency: f64,
+    /// Height of the terrain; the output values lie roughly within
+    /// +/- this value.
+    pub height: f64,
+    /// Persistence of the hills; lower values give smoother hills.
+    pub persistence: f64,
+}
+
+impl Default for HillParams {
+    fn default() -> HillParams {
+        HillParams {
+            frequency: 0.5,
+            height: 0.5,
+            persistence: 0.4,
+        }
+    }
+}
+
+/// Parameters for [`caves()`](fn.caves.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaveParams {
+    /// Frequency of the cave passages.
+    pub frequency: f64,
+    /// How much of the volume is open, from 0.0 (solid rock) upwards; larger
+    /// values give wider passages.
+    pub openness: f64,
+    /// Power of the turbulence that makes the passage walls uneven.
+    pub roughness: f64,
+}
+
+impl Default for CaveParams {
+    fn default() -> CaveParams {
+        CaveParams {
+            frequency: 1.0,
+            openness: 0.3,
+            roughness: 0.1,
+        }
+    }
+}
+
+/// Builds terrain with ridged mountains rising out of rounded lowlands.
+///
+/// This is the terrain of the classic libnoise tutorial: a
+/// [`Perlin`](../module/struct.Perlin.html) control module
+/// [`Select`](../module/struct.Select.html)s between
+/// [`RidgedMulti`](../module/struct.RidgedMulti.html) mountains and flattened
+/// [`Billow`](../module/struct.Billow.html) lowlands, blending smoothly at
+/// the edges, and the result is roughened with
+/// [`Turbulence`](../module/struct.Turbulence.html).
+pub fn mountainous_terrain(seed: i32, params: &MountainParams) -> BoxedModule {
+    let mut mountains = RidgedMulti::new();
+    mountains.set_frequency(params.frequency);
+    mountains.set_seed(seed);
+
+    let mut lowland_shape = Billow::new();
+    lowland_shape.set_frequency(params.frequency * 2.0);
+    lowland_shape.set_seed(seed.wrapping_add(1));
+    let mut lowlands = ScaleBias::new(lowland_shape);
+    lowlands.set_scale(0.125);
+    lowlands.set_bias(-0.75);
+
+    let mut control = Perlin::new();
+    control.set_frequency(params.frequency * 0.5);
+    control.set_persistence(0.25);
+    control.set_seed(seed.wrapping_add(2));
+
+    // The control module outputs values of roughly +/- 1.0, so select the
+    // mountains above the threshold that covers the requested fraction.
+    let mut terrain = Select::new(lowlands, mountains, control);
+    terrain.set_bounds(1.0 - params.mountain_coverage * 2.0, 1000.0);
+    terrain.set_edge_falloff(0.125);
+
+    let mut rough_terrain = Turbulence::new(terrain);
+    rough_terrain.set_frequency(params.frequency * 4.0);
+    rough_terrain.set_power(params.roughness);
+    rough_terrain.set_seed(seed.wrapping_add(3));
+
+    let mut scaled = ScaleBias::new(rough_terrain);
+    scaled.set_scale(params.height);
+    Box::new(scaled)
+}
+
+/// Builds gently rolling hills.
+///
+/// The hills are [`Billow`](../module/struct.Billow.html) noise, scaled to the
+/// requested height.
+pub fn rolling_hills(seed: i32, params: &HillParams) -> BoxedModule {
+    let mut hills = Billow::new();
+    hills.set_frequency(params.frequency);
+    hills.set_persistence(params.persistence);
+    hills.set_octave_count(4);
+    hills.set_seed(seed);
+
+    let mut scaled = ScaleBias::new(hills);
+    scaled.set_scale(params.height);
+    Box::new(scaled)
+}
+
+/// Builds a three-dimensional network of cave passages.
+///
+/// The output value is 1.0 inside the passages and -1.0 in solid rock.  The
+/// passages follow the ridges of
+/// [`RidgedMulti`](../module/struct.RidgedMulti.html) noise, made uneven with
+/// [`Turbulence`](../module/struct.Turbulence.html), and are cut out with a
+/// [`Select`](../module/struct.Select.html) noise module.
+pub fn caves(seed: i32, params: &CaveParams) -> BoxedModule {
+    let mut ridges = RidgedMulti::new();
+    ridges.set_frequency(params.frequency);
+    ridges.set_octave_count(3);
+    ridges.set_seed(seed);
+
+    let mut passages = Turbulence::new(ridges);
+    passages.set_frequency(params.frequency * 2.0);
+    passages.set_power(params.roughness);
+    passages.set_seed(seed.wrapping_add(1));
+
+    let mut rock = Constant::new();
+    rock.set_const_value(-1.0);
+    let mut open = Constant::new();
+    open.set_const_value(1.0);
+
+    // The ridges peak at about 1.0, so open up everything within `openness`
+    // of the peaks.
+    let mut caves = Select::new(rock, open, passages);
+    caves.set_bounds(1.0 - params.openness, 1000.0);
+    Box::new(caves)
+}
@@ -0,0 +1,275 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::Module;
+use noise_map::NoiseMap;
+
+/// Renders a noise map as a 16-bit grayscale image, mapping the smallest value
+/// in the map to black and the largest to white.
+///
+/// The pixels are returned in the same row-major order as the values of the
+/// map.  Sixteen bits give 65536 shades of gray, enough to avoid the visible
+/// banding of an 8-bit image when the image is used as a heightmap.
+///
+/// If every value in the map is the same, every pixel is black.
+pub fn render_grayscale16(map: &NoiseMap) -> Vec<u16> {
+    let (lower, upper) = map.bounds();
+    if lower < upper {
+        render_grayscale16_range(map, lower, upper)
+    } else {
+        vec![0; map.values().len()]
+    }
+}
+
+/// Renders a noise map as a 16-bit grayscale image, mapping `lower_bound` to
+/// black and `upper_bound` to white.
+///
+/// Values outside of the range are clamped to it.  Using a fixed range, rather
+/// than the range of the map as
+/// [`render_grayscale16()`](fn.render_grayscale16.html) does, keeps the shades
+/// consistent between separately rendered maps.
+///
+/// # Panics
+///
+/// Panics if `lower_bound` is not less than `upper_bound`.
+pub fn render_grayscale16_range(map: &NoiseMap, lower_bound: f64, upper_bound: f64) -> Vec<u16> {
+    if lower_bound.is_nan() || upper_bound.is_nan() || lower_bound >= upper_bound {
+        panic!("Lower bound must be less than upper bound!");
+    }
+    let scale = 65535.0 / (upper_bound - lower_bound);
+    map.values().iter().map(|&value| {
+        let shade = (value - lower_bound) * scale;
+        if shade <= 0.0 {
+            0
+        } else if shade >= 65535.0 {
+            65535
+        } else {
+            shade.round() as u16
+        }
+    }).collect()
+}
+
+/// The 8x8 Bayer matrix used for ordered dithering.
+const BAYER_8X8: [[u8; 8]; 8] = [
+    [ 0, 32,  8, 40,  2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44,  4, 36, 14, 46,  6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [ 3, 35, 11, 43,  1, 33,  9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47,  7, 39, 13, 45,  5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Enumerates the ways the shades of an 8-bit grayscale image can be dithered.
+///
+/// Smooth noise rendered with only 256 shades shows visible bands where the
+/// shade steps from one value to the next.  Dithering breaks up the bands by
+/// mixing the two nearest shades in proportion, so that the average
+/// brightness of an area matches the original values.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DitherKind {
+    /// Rounds each value to the nearest shade, without dithering.
+    None,
+    /// Ordered dithering with an 8x8 Bayer matrix.  Each value is offset by a
+    /// threshold that depends only on the position of the pixel within its
+    /// 8x8 block, so the result is deterministic, every pixel is independent
+    /// of its neighbours, and the pattern tiles seamlessly.
+    Ordered,
+    /// Floyd-Steinberg error diffusion.  The rounding error of each pixel is
+    /// passed on to the pixels to its right and below, which gives a finer,
+    /// less regular pattern than `Ordered`.  The result is deterministic, but
+    /// each pixel depends on those before it, so separately rendered tiles do
+    /// not match at their edges.
+    FloydSteinberg,
+}
+
+/// Renders a noise map as an 8-bit grayscale image, mapping the smallest
+/// value in the map to black and the largest to white, and dithering the
+/// shades with the given method.
+///
+/// The pixels are returned in the same row-major order as the values of the
+/// map.  If every value in the map is the same, every pixel is black.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::Perlin;
+/// use noise::noise_map::NoiseMap;
+/// use noise::render::{render_grayscale_dithered, DitherKind};
+///
+/// let map = NoiseMap::from_module(&Perlin::new(), (0.0, 0.0), (0.01, 0.01), 64, 64);
+/// let pixels = render_grayscale_dithered(&map, &DitherKind::Ordered);
+/// assert_eq!(pixels.len(), 64 * 64);
+/// ```
+pub fn render_grayscale_dithered(map: &NoiseMap, dither: &DitherKind) -> Vec<u8> {
+    let (lower, upper) = map.bounds();
+    if lower >= upper {
+        return vec![0; map.values().len()];
+    }
+    let scale = 255.0 / (upper - lower);
+    let width = map.width();
+    let shade = |index: usize| (map.values()[index] - lower) * scale;
+    let quantize = |shade: f64| shade.round().clamp(0.0, 255.0);
+
+    match *dither {
+        DitherKind::None => (0..map.values().len()).map(|i| quantize(shade(i)) as u8).collect(),
+        DitherKind::Ordered => (0..map.values().len()).map(|i| {
+            // Offsetting by a threshold evenly spread over -0.5 to +0.5
+            // before rounding rounds up in proportion to the fraction.
+            let threshold = (BAYER_8X8[(i / width) % 8][(i % width) % 8] as f64 + 0.5) / 64.0;
+            quantize(shade(i) + threshold - 0.5) as u8
+        }).collect(),
+        DitherKind::FloydSteinberg => {
+            let height = map.height();
+            let mut shades: Vec<f64> = (0..map.values().len()).map(shade).collect();
+            let mut pixels = Vec::with_capacity(shades.len());
+            for y in 0..height {
+                for x in 0..width {
+                    let index = y * width + x;
+                    let value = quantize(shades[index]);
+                    let error = shades[index] - value;
+                    pixels.push(value as u8);
+
+                    if x + 1 < width {
+                        shades[index + 1] += error * 7.0 / 16.0;
+                    }
+                    if y + 1 < height {
+                        let below = index + width;
+                        if x > 0 {
+                            shades[below - 1] += error * 3.0 / 16.0;
+                        }
+                        shades[below] += error * 5.0 / 16.0;
+                        if x + 1 < width {
+                            shades[below + 1] += error * 1.0 / 16.0;
+                        }
+                    }
+                }
+            }
+            pixels
+        }
+    }
+}
+
+/// Renders a noise map as a normal map, treating its values as heights.
+///
+///   * `map` - The height map.
+///   * `world_step` - The distance, in world units, between adjacent values of
+///     the map along the `x` and `y` axes, i.e. the `step` the map was sampled
+///     with.
+///
+/// The slope at each value is estimated from the differences between the
+/// neighbouring values of the map (one-sided at the edges), divided by the
+/// distance between them in world units.  The surface normal is then the unit
+/// vector (`-dh/dx`, `-dh/dy`, `1`), normalized, and each component is mapped
+/// from the range -1.0 to +1.0 onto the red, green and blue channels, from 0 to
+/// 255.  A flat map is therefore (128, 128, 255).
+///
+/// Because the slope is in world units, the normals do not change if the same
+/// region is rendered at a different resolution.  This function reuses the
+/// values of the map and needs no noise module, but the differences span two
+/// values, so detail smaller than `world_step` is smoothed out of the normals.
+/// For sharper normals, see
+/// [`render_normal_map_eps()`](fn.render_normal_map_eps.html).
+///
+/// # Panics
+///
+/// Panics if either component of `world_step` is not greater than zero.
+pub fn render_normal_map(map: &NoiseMap, world_step: (f64, f64)) -> Vec<[u8; 3]> {
+    if !(world_step.0 > 0.0 && world_step.1 > 0.0) {
+        panic!("`world_step` must be greater than zero along each axis!");
+    }
+    let (width, height) = (map.width(), map.height());
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let (y0, y1) = neighbours(y, height);
+        for x in 0..width {
+            let (x0, x1) = neighbours(x, width);
+            let dx = if x0 < x1 {
+                (map.get_value(x1, y) - map.get_value(x0, y)) / ((x1 - x0) as f64 * world_step.0)
+            } else {
+                0.0
+            };
+            let dy = if y0 < y1 {
+                (map.get_value(x, y1) - map.get_value(x, y0)) / ((y1 - y0) as f64 * world_step.1)
+            } else {
+                0.0
+            };
+            pixels.push(encode_normal(dx, dy));
+        }
+    }
+    pixels
+}
+
+/// Renders a noise module as a normal map, sampling the slope directly from
+/// the module.
+///
+///   * `module` - The noise module, whose output values are treated as
+///     heights.
+///   * `origin`, `step`, `width`, `height` - The sample points, as for
+///     [`NoiseMap::from_module()`](../noise_map/struct.NoiseMap.html#method.from_module).
+///   * `epsilon` - The distance either side of each sample point at which the
+///     module is sampled to estimate the slope.
+///
+/// The slope at each sample point is estimated from the output values at
+/// `epsilon` either side of it along the `x` and `y` axes, on the `z = 0`
+/// plane.  The normals are encoded as for
+/// [`render_normal_map()`](fn.render_normal_map.html).
+///
+/// This samples the module four times per pixel, but the slope is measured
+/// over `2 * epsilon` rather than two map steps, so a small `epsilon` gives
+/// normals that keep the detail between the sample points.  Use it when
+/// normals matter more than speed, or when there is no height map to reuse.
+/// An `epsilon` much smaller than the smallest feature of the module, but not
+/// so small that rounding error dominates, works best; about a tenth of
+/// `step` is usually a good choice.
+///
+/// # Panics
+///
+/// Panics if `epsilon` is not greater than zero.
+pub fn render_normal_map_eps<M: Module>(module: &M, origin: (f64, f64), step: (f64, f64),
+                                        width: usize, height: usize, epsilon: f64)
+                                        -> Vec<[u8; 3]> {
+    if epsilon.is_nan() || epsilon <= 0.0 {
+        panic!("`epsilon` must be greater than zero!");
+    }
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let y_cur = origin.1 + y as f64 * step.1;
+        for x in 0..width {
+            let x_cur = origin.0 + x as f64 * step.0;
+            let dx = (module.get_value(x_cur + epsilon, y_cur, 0.0)
+                      - module.get_value(x_cur - epsilon, y_cur, 0.0)) / (2.0 * epsilon);
+            let dy = (module.get_value(x_cur, y_cur + epsilon, 0.0)
+                      - module.get_value(x_cur, y_cur - epsilon, 0.0)) / (2.0 * epsilon);
+            pixels.push(encode_normal(dx, dy));
+        }
+    }
+    pixels
+}
+
+/// Returns the indices of the neighbours either side of index `i` along an axis
+/// with `len` values, or `i` itself at the edges.
+fn neighbours(i: usize, len: usize) -> (usize, usize) {
+    (i.saturating_sub(1), if i + 1 < len { i + 1 } else { i })
+}
+
+/// Encodes the normal of a surface with the given slopes as an RGB pixel.
+fn encode_normal(dx: f64, dy: f64) -> [u8; 3] {
+    let len = (dx * dx + dy * dy + 1.0).sqrt();
+    let channel = |component: f64| ((component / len + 1.0) / 2.0 * 255.0).round() as u8;
+    [channel(-dx), channel(-dy), channel(1.0)]
+}
@@ -0,0 +1,559 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use module::Module;
+
+/// Iterator that samples a noise module at evenly spaced points along a
+/// polyline.
+///
+/// The polyline is defined by a list of *waypoints*; consecutive waypoints are
+/// joined by straight segments.  Starting at the first waypoint, this iterator
+/// walks the polyline in steps of a fixed arc length, yielding each sample
+/// point together with the output value of the module at that point.  The
+/// spacing is measured along the whole polyline, so a step that crosses a
+/// waypoint continues onto the next segment.  The final waypoint is always
+/// sampled last, so the last step may be shorter than the others.
+///
+/// Segments of zero length (repeated waypoints) are skipped.  If the polyline
+/// has no length at all, the first waypoint is sampled once.  An empty list of
+/// waypoints yields no samples.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::Perlin;
+/// use noise::sampling::PathSampler;
+///
+/// let perlin = Perlin::new();
+/// let waypoints = [(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 1.5, 0.0)];
+/// let points: Vec<_> = PathSampler::new(&perlin, &waypoints, 0.5)
+///     .map(|(point, _)| point)
+///     .collect();
+/// assert_eq!(points, [(0.0, 0.0, 0.0), (0.5, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 0.5, 0.0),
+///                     (1.0, 1.0, 0.0), (1.0, 1.5, 0.0)]);
+/// ```
+pub struct PathSampler<'a, M: Module + 'a> {
+    module: &'a M,
+    waypoints: &'a [(f64, f64, f64)],
+    step: f64,
+    /// Index of the waypoint at the start of the current segment.
+    segment: usize,
+    /// Distance along the current segment of the next sample point.
+    dist: f64,
+    finished: bool,
+}
+
+impl<'a, M: Module> PathSampler<'a, M> {
+    /// Create a new `PathSampler` over the given waypoints, sampling the
+    /// specified module every `step` units of arc length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not greater than zero.
+    pub fn new(module: &'a M, waypoints: &'a [(f64, f64, f64)], step: f64) -> PathSampler<'a, M> {
+        if step.is_nan() || step <= 0.0 {
+            panic!("`step` must be greater than zero!");
+        }
+        PathSampler {
+            module: module,
+            waypoints: waypoints,
+            step: step,
+            segment: 0,
+            dist: 0.0,
+            finished: waypoints.is_empty(),
+        }
+    }
+
+    /// Returns the arc length between successive sample points.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    fn sample(&self, point: (f64, f64, f64)) -> ((f64, f64, f64), f64) {
+        (point, self.module.get_value(point.0, point.1, point.2))
+    }
+}
+
+impl<'a, M: Module> Iterator for PathSampler<'a, M> {
+    type Item = ((f64, f64, f64), f64);
+
+    fn next(&mut self) -> Option<((f64, f64, f64), f64)> {
+        if self.finished {
+            return None;
+        }
+
+        while self.segment + 1 < self.waypoints.len() {
+            let p0 = self.waypoints[self.segment];
+            let p1 = self.waypoints[self.segment + 1];
+            let (dx, dy, dz) = (p1.0 - p0.0, p1.1 - p0.1, p1.2 - p0.2);
+            let seg_len = (dx * dx + dy * dy + dz * dz).sqrt();
+
+            if seg_len > 0.0 && self.dist <= seg_len {
+                // The next sample point lies on this segment.
+                let a = self.dist / seg_len;
+                let point = (p0.0 + dx * a, p0.1 + dy * a, p0.2 + dz * a);
+                self.dist += self.step;
+                return Some(self.sample(point));
+            }
+
+            // Carry the remaining distance over onto the next segment.
+            // Degenerate segments have no length and are skipped over.
+            self.dist -= seg_len;
+            self.segment += 1;
+        }
+
+        self.finished = true;
+
+        // `dist` is now the distance of the next sample point beyond the final
+        // waypoint, so the last sample point lies `step - dist` before it, or
+        // no sample has been taken yet if `dist` is zero.  Sample the final
+        // waypoint unless the last sample point already lies on it, allowing
+        // for rounding errors in the accumulated distance.
+        if self.step - self.dist > self.step * 1e-9 {
+            Some(self.sample(self.waypoints[self.waypoints.len() - 1]))
+        } else {
+            None
+        }
+    }
+}
+
+/// Samples a noise module over a regular three-dimensional grid, returning the
+/// output values as a flat buffer of `f32`s.
+///
+///   * `module` - The noise module to sample.
+///   * `origin` - The input value of the first sample point.
+///   * `step` - The distance between adjacent sample points along each axis.
+///   * `dims` - The number of sample points along each axis.
+///
+/// The sample point with the grid indices (`i`, `j`, `k`) is located at
+/// (`origin.0 + i * step.0`, `origin.1 + j * step.1`, `origin.2 + k *
+/// step.2`), and its output value is stored at the buffer index
+/// `(k * dims.1 + j) * dims.0 + i`.  That is, the buffer is in z-major order:
+/// the `x` index varies fastest and the `z` index varies slowest, which is the
+/// layout expected by most 3D texture upload APIs.
+///
+/// The coordinates are calculated from the grid indices rather than by
+/// repeatedly adding the step, so no rounding error accumulates across large
+/// grids.
+///
+/// The returned buffer has `dims.0 * dims.1 * dims.2` elements.
+pub fn sample_slab<M: Module>(module: &M, origin: (f64, f64, f64), step: (f64, f64, f64),
+                              dims: (usize, usize, usize)) -> Vec<f32> {
+    let mut buffer = Vec::with_capacity(dims.0 * dims.1 * dims.2);
+    for k in 0..dims.2 {
+        let z = origin.2 + k as f64 * step.2;
+        for j in 0..dims.1 {
+            let y = origin.1 + j as f64 * step.1;
+            for i in 0..dims.0 {
+                let x = origin.0 + i as f64 * step.0;
+                buffer.push(module.get_value(x, y, z) as f32);
+            }
+        }
+    }
+    buffer
+}
+
+/// Iterator that samples a noise module over a regular two-dimensional grid.
+///
+/// This iterator is created by [`grid_iter()`](fn.grid_iter.html); see that
+/// function for details.
+pub struct GridSampler<'a, M: Module + 'a> {
+    module: &'a M,
+    origin: (f64, f64),
+    step: (f64, f64),
+    dims: (usize, usize),
+    /// Row-major index of the next sample point.
+    index: usize,
+}
+
+impl<'a, M: Module> Iterator for GridSampler<'a, M> {
+    type Item = ((usize, usize), f64);
+
+    fn next(&mut self) -> Option<((usize, usize), f64)> {
+        if self.index == self.dims.0 * self.dims.1 {
+            return None;
+        }
+        let ix = self.index % self.dims.0;
+        let iy = self.index / self.dims.0;
+        let value = self.module.get_value(self.origin.0 + ix as f64 * self.step.0,
+                                          self.origin.1 + iy as f64 * self.step.1,
+                                          0.0);
+        self.index += 1;
+        Some(((ix, iy), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.dims.0 * self.dims.1 - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, M: Module> ExactSizeIterator for GridSampler<'a, M> {}
+
+/// Lazily samples a noise module over a regular two-dimensional grid on the
+/// `z = 0` plane.
+///
+///   * `module` - The noise module to sample.
+///   * `origin` - The (`x`, `y`) coordinates of the input value of the first
+///     sample point.
+///   * `step` - The distance between adjacent sample points along the `x` and
+///     `y` axes.
+///   * `dims` - The number of sample points along the `x` and `y` axes.
+///
+/// The returned iterator yields the grid indices (`ix`, `iy`) of each sample
+/// point together with its output value, in row-major order: `ix` varies
+/// fastest, and each row of `dims.0` samples is finished before the next row
+/// starts.  The sample point with the grid indices (`ix`, `iy`) is located at
+/// (`origin.0 + ix * step.0`, `origin.1 + iy * step.1`, `0.0`), the same
+/// point as in
+/// [`NoiseMap::from_module()`](../noise_map/struct.NoiseMap.html#method.from_module),
+/// so collecting the values gives the values of that noise map in the same
+/// order.
+///
+/// Each output value is calculated when the iterator reaches it, so samples
+/// can be filtered, transformed and written out without buffering the grid.
+/// The iterator borrows the noise module until it is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::Perlin;
+/// use noise::sampling::grid_iter;
+///
+/// let perlin = Perlin::new();
+/// let peaks = grid_iter(&perlin, (0.0, 0.0), (0.25, 0.25), (64, 64))
+///     .filter(|&(_, value)| value > 0.5)
+///     .count();
+/// assert!(peaks < 64 * 64);
+/// ```
+pub fn grid_iter<'a, M: Module>(module: &'a M, origin: (f64, f64), step: (f64, f64),
+                                dims: (usize, usize)) -> GridSampler<'a, M> {
+    GridSampler {
+        module: module,
+        origin: origin,
+        step: step,
+        dims: dims,
+        index: 0,
+    }
+}
+
+/// Largest number of bits per axis supported by
+/// [`sample_morton()`](fn.sample_morton.html) and the Morton code helpers, so
+/// that a Morton index fits in a `u64`.
+pub const MORTON_MAX_BITS: u32 = 21;
+
+/// Iterator that samples a noise module over a cubic grid in Morton order.
+///
+/// This iterator is created by [`sample_morton()`](fn.sample_morton.html); see
+/// that function for details.
+pub struct MortonSampler<'a, M: Module + 'a> {
+    module: &'a M,
+    origin: (f64, f64, f64),
+    step: (f64, f64, f64),
+    /// Morton index of the next sample point.
+    index: u64,
+    /// Number of sample points in the grid.
+    count: u64,
+}
+
+impl<'a, M: Module> Iterator for MortonSampler<'a, M> {
+    type Item = (u64, f64);
+
+    fn next(&mut self) -> Option<(u64, f64)> {
+        if self.index == self.count {
+            return None;
+        }
+        let index = self.index;
+        let (i, j, k) = morton_decode(index);
+        let value = self.module.get_value(self.origin.0 + i as f64 * self.step.0,
+                                          self.origin.1 + j as f64 * self.step.1,
+                                          self.origin.2 + k as f64 * self.step.2);
+        self.index += 1;
+        Some((index, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.count - self.index) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, M: Module> ExactSizeIterator for MortonSampler<'a, M> {}
+
+/// Samples a noise module over a cubic grid of `2^bits` points along each
+/// axis, visiting the points in Morton order (also known as Z-order).
+///
+///   * `module` - The noise module to sample.
+///   * `origin` - The input value of the first sample point.
+///   * `step` - The distance between adjacent sample points along each axis.
+///   * `bits` - The number of bits of each grid index.
+///
+/// The returned iterator yields each *Morton index* together with the output
+/// value at its sample point, in increasing order of the index.  The Morton
+/// index interleaves the bits of the grid indices (`i`, `j`, `k`), with bit
+/// `n` of `i`, `j` and `k` stored in bits `3n`, `3n + 1` and `3n + 2` of the
+/// index respectively; use [`morton_decode()`](fn.morton_decode.html) to
+/// recover the grid indices.  The sample point with the grid indices (`i`,
+/// `j`, `k`) is located at (`origin.0 + i * step.0`, `origin.1 + j * step.1`,
+/// `origin.2 + k * step.2`), as for
+/// [`sample_slab()`](fn.sample_slab.html).
+///
+/// Each group of eight consecutive indices is a 2x2x2 block of sample points,
+/// each group of 64 a 4x4x4 block, and so on, so the output values can be
+/// stored in this order as the leaves of an octree, and neighbouring points
+/// are mostly sampled close together in time.  Morton order only divides a
+/// cube evenly when its size is a power of two, so the grid always has `2^bits`
+/// points along each axis; sample a larger grid and ignore the extra points to
+/// cover other sizes.
+///
+/// # Panics
+///
+/// Panics if `bits` is greater than
+/// [`MORTON_MAX_BITS`](constant.MORTON_MAX_BITS.html).
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::Perlin;
+/// use noise::sampling::{morton_decode, sample_morton};
+///
+/// let samples: Vec<(u64, f64)> = sample_morton(&Perlin::new(), (0.0, 0.0, 0.0),
+///                                              (0.5, 0.5, 0.5), 2).collect();
+/// assert_eq!(samples.len(), 64);
+/// assert_eq!(morton_decode(samples[7].0), (1, 1, 1));
+/// assert_eq!(morton_decode(samples[8].0), (2, 0, 0));
+/// ```
+pub fn sample_morton<'a, M: Module>(module: &'a M, origin: (f64, f64, f64),
+                                    step: (f64, f64, f64), bits: u32) -> MortonSampler<'a, M> {
+    if bits > MORTON_MAX_BITS {
+        panic!("`bits` must be at most {}!", MORTON_MAX_BITS);
+    }
+    MortonSampler {
+        module: module,
+        origin: origin,
+        step: step,
+        index: 0,
+        count: 1 << (3 * bits),
+    }
+}
+
+/// Interleaves the bits of three grid indices into a Morton index.
+///
+/// Bit `n` of `i`, `j` and `k` is stored in bits `3n`, `3n + 1` and `3n + 2`
+/// of the index respectively.  This is the inverse of
+/// [`morton_decode()`](fn.morton_decode.html).
+///
+/// # Panics
+///
+/// Panics if any of the indices does not fit in
+/// [`MORTON_MAX_BITS`](constant.MORTON_MAX_BITS.html) bits.
+pub fn morton_encode(i: u32, j: u32, k: u32) -> u64 {
+    if (i | j | k) >> MORTON_MAX_BITS != 0 {
+        panic!("Grid indices must fit in {} bits!", MORTON_MAX_BITS);
+    }
+    spread_bits(i) | spread_bits(j) << 1 | spread_bits(k) << 2
+}
+
+/// Splits a Morton index into the three grid indices whose bits it
+/// interleaves.
+///
+/// This is the inverse of [`morton_encode()`](fn.morton_encode.html).  The
+/// highest bit of the index is ignored.
+pub fn morton_decode(index: u64) -> (u32, u32, u32) {
+    (compact_bits(index), compact_bits(index >> 1), compact_bits(index >> 2))
+}
+
+/// Spreads the low 21 bits of `v` out so that there are two zero bits between
+/// each of them.
+fn spread_bits(v: u32) -> u64 {
+    let mut v = v as u64 & 0x1fffff;
+    v = (v | v << 32) & 0x1f00000000ffff;
+    v = (v | v << 16) & 0x1f0000ff0000ff;
+    v = (v | v << 8) & 0x100f00f00f00f00f;
+    v = (v | v << 4) & 0x10c30c30c30c30c3;
+    v = (v | v << 2) & 0x1249249249249249;
+    v
+}
+
+/// Gathers every third bit of `v`, starting with the lowest, into the low 21
+/// bits of the result.  This is the inverse of `spread_bits()`.
+fn compact_bits(v: u64) -> u32 {
+    let mut v = v & 0x1249249249249249;
+    v = (v | v >> 2) & 0x10c30c30c30c30c3;
+    v = (v | v >> 4) & 0x100f00f00f00f00f;
+    v = (v | v >> 8) & 0x1f0000ff0000ff;
+    v = (v | v >> 16) & 0x1f00000000ffff;
+    v = (v | v >> 32) & 0x1fffff;
+    v as u32
+}
+
+/// The worst mismatch found by [`is_seamless()`](fn.is_seamless.html) between
+/// opposite edges of a tile.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeamMismatch {
+    /// Input value on the lower edge of the tile.
+    pub point: (f64, f64, f64),
+    /// Input value on the opposite, upper edge of the tile.
+    pub opposite: (f64, f64, f64),
+    /// Absolute difference between the output values at the two input values.
+    pub difference: f64,
+}
+
+/// Checks that a noise module tiles seamlessly with the given periods along
+/// the `x` and `y` axes.
+///
+///   * `module` - The noise module to check.
+///   * `period_x`, `period_y` - The size of the tile along the `x` and `y`
+///     axes.
+///   * `z` - The `z` coordinate of the plane the tile lies on.
+///   * `tolerance` - The largest difference allowed between the output values
+///     on opposite edges.
+///   * `samples` - The number of evenly spaced points sampled along each edge.
+///
+/// The tile covers the rectangle from (`0`, `0`) to (`period_x`, `period_y`).
+/// Each point sampled on its left edge is compared with the matching point on
+/// its right edge, and each point on its bottom edge with the matching point
+/// on its top edge.  If every pair of output values differ by no more than
+/// `tolerance`, `Ok(())` is returned.  Otherwise, the pair with the largest
+/// difference is returned, showing where the tiling breaks down.  A NaN output
+/// value always counts as a mismatch.
+///
+/// This only samples the edges, so it cannot prove that a noise module tiles,
+/// but it catches the common mistake of leaving one subtree of a graph
+/// untiled.
+///
+/// # Panics
+///
+/// Panics if `samples` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{FnModule, Perlin};
+/// use noise::sampling::is_seamless;
+/// use std::f64::consts::PI;
+///
+/// let tileable = FnModule::new(|x: f64, y: f64, _z: f64| (x * PI).sin() + (y * PI / 2.0).cos());
+/// assert!(is_seamless(&tileable, 2.0, 4.0, 0.0, 1e-9, 64).is_ok());
+///
+/// let mismatch = is_seamless(&Perlin::new(), 2.5, 2.5, 0.5, 1e-9, 64).unwrap_err();
+/// assert!(mismatch.difference > 1e-9);
+/// ```
+pub fn is_seamless<M: Module>(module: &M, period_x: f64, period_y: f64, z: f64, tolerance: f64,
+                              samples: usize) -> Result<(), SeamMismatch> {
+    if samples == 0 {
+        panic!("`samples` must be at least one!");
+    }
+
+    let mut worst: Option<SeamMismatch> = None;
+    let mut compare = |point: (f64, f64, f64), opposite: (f64, f64, f64)| {
+        let difference = (module.get_value(point.0, point.1, point.2)
+                          - module.get_value(opposite.0, opposite.1, opposite.2)).abs();
+        let is_mismatch = difference.is_nan() || difference > tolerance;
+        let is_worse = match worst {
+            Some(ref worst) => difference.is_nan() || difference > worst.difference,
+            None => true,
+        };
+        if is_mismatch && is_worse {
+            worst = Some(SeamMismatch {
+                point: point,
+                opposite: opposite,
+                difference: difference,
+            });
+        }
+    };
+
+    for i in 0..samples {
+        let a = i as f64 / samples as f64;
+        compare((0.0, a * period_y, z), (period_x, a * period_y, z));
+        compare((a * period_x, 0.0, z), (a * period_x, period_y, z));
+    }
+
+    match worst {
+        Some(worst) => Err(worst),
+        None => Ok(()),
+    }
+}
+
+/// Samples a noise module at a point on a surface by triplanar mapping.
+///
+///   * `module` - The noise module to sample.
+///   * `pos` - The point on the surface.
+///   * `normal` - The surface normal at `pos`.  It does not need to be of unit
+///     length.
+///   * `sharpness` - How quickly the blend moves from one plane to another as
+///     the normal turns.
+///
+/// The noise module is sampled three times, with `pos` projected onto the
+/// `yz`, `xz` and `xy` planes: the output values at (`pos[1]`, `pos[2]`, 0),
+/// (`pos[0]`, `pos[2]`, 0) and (`pos[0]`, `pos[1]`, 0).  These are blended with
+/// weights proportional to the absolute values of the `x`, `y` and `z`
+/// components of the normal respectively, each raised to the power of
+/// `sharpness`, so that each plane contributes most where the surface faces
+/// along its axis.  The weights sum to one.
+///
+/// A sharpness of 1.0 blends in proportion to the normal; larger values narrow
+/// the regions where two planes are blended, and a sharpness of 0.0 gives the
+/// three planes equal weight.  Texturing a surface this way avoids the seams
+/// and stretching of a single projection, without needing texture
+/// coordinates.
+///
+/// # Panics
+///
+/// Panics if `sharpness` is negative or NaN, or if `normal` has no non-zero
+/// components or a component that is not finite.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::{Module, Perlin};
+/// use noise::sampling::triplanar_sample;
+///
+/// let perlin = Perlin::new();
+/// // A surface facing along the `z` axis only samples the `xy` plane.
+/// let value = triplanar_sample(&perlin, [0.3, 0.7, 1.9], [0.0, 0.0, -2.0], 4.0);
+/// assert_eq!(value, perlin.get_value(0.3, 0.7, 0.0));
+/// ```
+pub fn triplanar_sample<M: Module>(module: &M, pos: [f64; 3], normal: [f64; 3],
+                                   sharpness: f64) -> f64 {
+    if sharpness.is_nan() || sharpness < 0.0 {
+        panic!("`sharpness` must not be negative!");
+    }
+    if normal.iter().any(|n| !n.is_finite()) || normal.iter().all(|&n| n == 0.0) {
+        panic!("`normal` must be finite and non-zero!");
+    }
+
+    // Scale the normal so that its largest component is one, so that raising
+    // the components to a large power cannot underflow all of the weights.
+    let largest = normal.iter().fold(0.0, |largest: f64, n| largest.max(n.abs()));
+    let mut weights = [0.0; 3];
+    for (weight, n) in weights.iter_mut().zip(normal.iter()) {
+        *weight = (n.abs() / largest).powf(sharpness);
+    }
+    let weight_sum = weights[0] + weights[1] + weights[2];
+
+    let mut value = 0.0;
+    if weights[0] != 0.0 {
+        value += weights[0] * module.get_value(pos[1], pos[2], 0.0);
+    }
+    if weights[1] != 0.0 {
+        value += weights[1] * module.get_value(pos[0], pos[2], 0.0);
+    }
+    if weights[2] != 0.0 {
+        value += weights[2] * module.get_value(pos[0], pos[1], 0.0);
+    }
+    value / weight_sum
+}
@@ -0,0 +1,70 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+//! Helpers for regression testing noise modules.
+//!
+//! This module is only available with the `testing` feature.
+
+use module::Module;
+
+/// Asserts that a noise module outputs the expected values at the given input
+/// values, to within a tolerance.
+///
+/// Each element of `expected_samples` is an input value and the output value
+/// expected there.  The noise module is sampled at every input value, and an
+/// output value matches if it differs from the expected value by no more than
+/// `tol`.  A NaN output value only matches an expected NaN.
+///
+/// # Panics
+///
+/// Like the `assert!` macros, panics if any output value does not match.  The
+/// panic message lists every mismatching sample, with its input value, the
+/// expected and actual output values and their difference.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::Constant;
+/// use noise::testing::assert_module_eq;
+///
+/// let mut constant = Constant::new();
+/// constant.set_const_value(0.5);
+/// assert_module_eq(&constant, &[((0.0, 0.0, 0.0), 0.5), ((1.0, 2.0, 3.0), 0.5000001)], 1e-6);
+/// ```
+#[track_caller]
+pub fn assert_module_eq<M: Module>(module: &M, expected_samples: &[((f64, f64, f64), f64)],
+                                   tol: f64) {
+    let mut mismatches = String::new();
+    let mut mismatch_count = 0;
+    for &((x, y, z), expected) in expected_samples {
+        let actual = module.get_value(x, y, z);
+        let matches = if expected.is_nan() || actual.is_nan() {
+            expected.is_nan() && actual.is_nan()
+        } else {
+            (actual - expected).abs() <= tol
+        };
+        if !matches {
+            mismatch_count += 1;
+            mismatches.push_str(&format!("\n    at ({}, {}, {}): expected {}, got {} \
+                                          (difference {})",
+                                         x, y, z, expected, actual, actual - expected));
+        }
+    }
+    if mismatch_count > 0 {
+        panic!("noise module output differs at {} of {} samples (tolerance {}):{}",
+               mismatch_count, expected_samples.len(), tol, mismatches);
+    }
+}
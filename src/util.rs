@@ -28,6 +28,26 @@ pub fn linear_interp(n0: f64, n1: f64, a: f64) -> f64
     ((1.0 - a) * n0) + (a * n1)
 }
 
+/// Applies the overlay blend mode to two values ranging from -1.0 to +1.0.
+///
+///  * `a` - The base value.
+///  * `b` - The blended value.
+///
+/// Both values are mapped onto the range 0.0 to 1.0, where the overlay formula
+/// is `2ab` if `a` is less than 0.5, and `1 - 2(1 - a)(1 - b)` otherwise.  The
+/// result is mapped back onto the range -1.0 to +1.0.
+pub fn overlay_blend(a: f64, b: f64) -> f64
+{
+    let a = (a + 1.0) / 2.0;
+    let b = (b + 1.0) / 2.0;
+    let value = if a < 0.5 {
+        2.0 * a * b
+    } else {
+        1.0 - 2.0 * (1.0 - a) * (1.0 - b)
+    };
+    value * 2.0 - 1.0
+}
+
 /// Performs cubic interpolation between two values bound between two other
 /// values.
 ///
@@ -57,6 +77,12 @@ pub fn scurve3 (a: f64) -> f64
     a * a * (3.0 - 2.0 * a)
 }
 
+/// Returns the derivative of the cubic S-curve
+/// [`scurve3()`](fn.scurve3.html) at `a`.
+pub fn scurve3_deriv(a: f64) -> f64 {
+    6.0 * a * (1.0 - a)
+}
+
 /// Maps a value onto a quintic S-curve. The input should range from 0.0 to 1.0.
 ///
 /// The first derivitive of a quintic S-curve is zero at `a` = 0.0 and `a` = 1.0
@@ -70,6 +96,52 @@ pub fn scurve5(a: f64) -> f64 {
     (6.0 * a5) - (15.0 * a4) + (10.0 * a3)
 }
 
+/// Returns the derivative of the quintic S-curve
+/// [`scurve5()`](fn.scurve5.html) at `a`.
+pub fn scurve5_deriv(a: f64) -> f64 {
+    let a2 = a * a;
+    30.0 * a2 * (a2 - 2.0 * a + 1.0)
+}
+
+/// Returns the sum of the amplitudes of `octave_count` octaves, where the
+/// first octave has an amplitude of 1 and each following octave has the
+/// amplitude of the previous one multiplied by the magnitude of
+/// `persistence`, plus `octave_fraction` times the amplitude of the next
+/// octave.
+pub fn amplitude_sum(persistence: f64, octave_count: i32, octave_fraction: f64) -> f64 {
+    let persistence = persistence.abs();
+    let mut sum = 0.0;
+    let mut cur_persistence = 1.0;
+    for _ in 0..octave_count {
+        sum += cur_persistence;
+        cur_persistence *= persistence;
+    }
+    sum + cur_persistence * octave_fraction
+}
+
+/// Returns the index and value of the first of `values` that `beats` every
+/// other value, or `None` if there are no values.
+///
+/// `beats(value, best)` returns whether `value` beats the best value so far.
+/// NaN values are ignored, unless every value is NaN, in which case the first
+/// value wins.
+pub fn winner<I, F>(values: I, beats: F) -> Option<(usize, f64)>
+    where I: IntoIterator<Item = f64>,
+          F: Fn(f64, f64) -> bool
+{
+    let mut winner: Option<(usize, f64)> = None;
+    for (index, value) in values.into_iter().enumerate() {
+        let wins = match winner {
+            Some((_, best)) => beats(value, best) || (best.is_nan() && !value.is_nan()),
+            None => true,
+        };
+        if wins {
+            winner = Some((index, value));
+        }
+    }
+    winner
+}
+
 pub fn clamp<T: Ord>(value: T, lower_bound: T, upper_bound: T) -> T {
     if value < lower_bound {
         lower_bound
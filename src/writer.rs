@@ -0,0 +1,207 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+use noise_map::NoiseMap;
+use std::io::{self, Read, Write};
+
+/// Magic bytes at the start of a raw `f32` noise map.
+const RAW_F32_MAGIC: [u8; 4] = *b"NMF4";
+
+/// Magic bytes at the start of a raw `f64` noise map.
+const RAW_F64_MAGIC: [u8; 4] = *b"NMF8";
+
+/// Writes 16-bit pixels as a headerless raw image.
+///
+/// Each pixel is written as two bytes in little-endian order, the least
+/// significant byte first, in the order given.  This is the `.r16`/`.raw`
+/// heightmap format accepted by most game engines; check whether the engine
+/// expects little-endian ("Windows" or "PC") byte order.
+///
+/// # Examples
+///
+/// ```
+/// use noise::writer::write_raw16;
+///
+/// let mut bytes = Vec::new();
+/// write_raw16(&mut bytes, &[0x1234, 0xabcd]).unwrap();
+/// assert_eq!(bytes, [0x34, 0x12, 0xcd, 0xab]);
+/// ```
+pub fn write_raw16<W: Write>(writer: &mut W, pixels: &[u16]) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(pixels.len() * 2);
+    for &pixel in pixels {
+        bytes.push(pixel as u8);
+        bytes.push((pixel >> 8) as u8);
+    }
+    writer.write_all(&bytes)
+}
+
+/// Writes a noise map as raw 32-bit floats with a small header.
+///
+/// The format is a simple interchange format for other tools.  Every field is
+/// little-endian, whatever the byte order of the platform:
+///
+///   * The four magic bytes `NMF4`.
+///   * The width of the map, as an unsigned 32-bit integer.
+///   * The height of the map, as an unsigned 32-bit integer.
+///   * The values of the map in row-major order, each converted to a 32-bit
+///     IEEE 754 float.
+///
+/// The map can be read back with [`read_raw_f32()`](fn.read_raw_f32.html).
+/// Converting the values to 32-bit floats rounds them; use
+/// [`write_raw_f64()`](fn.write_raw_f64.html) to keep them exactly.
+///
+/// # Panics
+///
+/// Panics if the width or height of the map does not fit in 32 bits.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::Perlin;
+/// use noise::noise_map::NoiseMap;
+/// use noise::writer::{read_raw_f32, write_raw_f32};
+///
+/// let map = NoiseMap::from_module(&Perlin::new(), (0.0, 0.0), (0.1, 0.1), 8, 4);
+/// let mut bytes = Vec::new();
+/// write_raw_f32(&mut bytes, &map).unwrap();
+/// assert_eq!(bytes.len(), 12 + 8 * 4 * 4);
+///
+/// let read = read_raw_f32(&mut &bytes[..]).unwrap();
+/// assert_eq!((read.width(), read.height()), (8, 4));
+/// for (&read_value, &value) in read.values().iter().zip(map.values()) {
+///     assert_eq!(read_value, value as f32 as f64);
+/// }
+/// ```
+pub fn write_raw_f32<W: Write>(writer: &mut W, map: &NoiseMap) -> io::Result<()> {
+    let mut bytes = raw_header(RAW_F32_MAGIC, map, 4);
+    for &value in map.values() {
+        bytes.extend_from_slice(&(value as f32).to_le_bytes());
+    }
+    writer.write_all(&bytes)
+}
+
+/// Writes a noise map as raw 64-bit floats with a small header.
+///
+/// The format is the same as that written by
+/// [`write_raw_f32()`](fn.write_raw_f32.html), except that the magic bytes
+/// are `NMF8` and each value is a little-endian 64-bit IEEE 754 float, so the
+/// values are kept exactly.  The map can be read back with
+/// [`read_raw_f64()`](fn.read_raw_f64.html).
+///
+/// # Panics
+///
+/// Panics if the width or height of the map does not fit in 32 bits.
+///
+/// # Examples
+///
+/// ```
+/// use noise::module::Perlin;
+/// use noise::noise_map::NoiseMap;
+/// use noise::writer::{read_raw_f64, write_raw_f64};
+///
+/// let map = NoiseMap::from_module(&Perlin::new(), (0.0, 0.0), (0.1, 0.1), 8, 4);
+/// let mut bytes = Vec::new();
+/// write_raw_f64(&mut bytes, &map).unwrap();
+/// assert_eq!(read_raw_f64(&mut &bytes[..]).unwrap(), map);
+/// ```
+pub fn write_raw_f64<W: Write>(writer: &mut W, map: &NoiseMap) -> io::Result<()> {
+    let mut bytes = raw_header(RAW_F64_MAGIC, map, 8);
+    for &value in map.values() {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    writer.write_all(&bytes)
+}
+
+/// Reads a noise map written by [`write_raw_f32()`](fn.write_raw_f32.html).
+///
+/// Returns an error with the kind `InvalidData` if the magic bytes do not
+/// match, or `UnexpectedEof` if the data ends early.
+pub fn read_raw_f32<R: Read>(reader: &mut R) -> io::Result<NoiseMap> {
+    let (width, height) = read_raw_header(reader, RAW_F32_MAGIC)?;
+    let data = read_raw_values(reader, width, height, 4)?;
+    let mut map = NoiseMap::new(width, height);
+    for (value, chunk) in map.values_mut().iter_mut().zip(data.chunks(4)) {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(chunk);
+        *value = f32::from_le_bytes(bytes) as f64;
+    }
+    Ok(map)
+}
+
+/// Reads a noise map written by [`write_raw_f64()`](fn.write_raw_f64.html).
+///
+/// Returns an error with the kind `InvalidData` if the magic bytes do not
+/// match, or `UnexpectedEof` if the data ends early.
+pub fn read_raw_f64<R: Read>(reader: &mut R) -> io::Result<NoiseMap> {
+    let (width, height) = read_raw_header(reader, RAW_F64_MAGIC)?;
+    let data = read_raw_values(reader, width, height, 8)?;
+    let mut map = NoiseMap::new(width, height);
+    for (value, chunk) in map.values_mut().iter_mut().zip(data.chunks(8)) {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(chunk);
+        *value = f64::from_le_bytes(bytes);
+    }
+    Ok(map)
+}
+
+/// Returns the header of a raw noise map, with room for the values of the
+/// given size.
+fn raw_header(magic: [u8; 4], map: &NoiseMap, value_size: usize) -> Vec<u8> {
+    if map.width() > u32::MAX as usize || map.height() > u32::MAX as usize {
+        panic!("The size of the map does not fit in 32 bits!");
+    }
+    let mut bytes = Vec::with_capacity(12 + map.values().len() * value_size);
+    bytes.extend_from_slice(&magic);
+    bytes.extend_from_slice(&(map.width() as u32).to_le_bytes());
+    bytes.extend_from_slice(&(map.height() as u32).to_le_bytes());
+    bytes
+}
+
+/// Reads the header of a raw noise map, returning its width and height.
+fn read_raw_header<R: Read>(reader: &mut R, magic: [u8; 4]) -> io::Result<(usize, usize)> {
+    let mut header = [0; 12];
+    reader.read_exact(&mut header)?;
+    if header[0..4] != magic {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not a raw noise map of this type"));
+    }
+    let width = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    let height = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+    Ok((width as usize, height as usize))
+}
+
+/// Reads the values of a raw noise map of the given size, returning their
+/// bytes.
+///
+/// The bytes are read as they arrive rather than allocated up front, so a
+/// header claiming a huge map cannot allocate more memory than the data that
+/// follows it.
+fn read_raw_values<R: Read>(reader: &mut R, width: usize, height: usize, value_size: usize)
+                            -> io::Result<Vec<u8>> {
+    let len = match width.checked_mul(height).and_then(|len| len.checked_mul(value_size)) {
+        Some(len) => len,
+        None => {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                      "size of raw noise map overflows"));
+        }
+    };
+    let mut data = Vec::new();
+    reader.take(len as u64).read_to_end(&mut data)?;
+    if data.len() != len {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                  "raw noise map ends before all of its values"));
+    }
+    Ok(data)
+}
@@ -0,0 +1,69 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Billow, Module, DEFAULT_BILLOW_BIAS};
+
+fn mean(billow: &Billow) -> f64 {
+    let count = 1000;
+    let sum: f64 = (0..count)
+        .map(|i| billow.get_value(i as f64 * 0.173, i as f64 * 0.071, 0.5))
+        .sum();
+    sum / count as f64
+}
+
+#[test]
+fn default_bias_is_one_half() {
+    let billow = Billow::new();
+    assert_eq!(billow.bias(), DEFAULT_BILLOW_BIAS);
+    assert_eq!(DEFAULT_BILLOW_BIAS, 0.5);
+}
+
+#[test]
+fn bias_shifts_mean() {
+    let default = Billow::new();
+    let mut biased = Billow::new();
+    biased.set_bias(-0.25);
+    assert!((mean(&biased) - mean(&default) + 0.75).abs() < 1e-9);
+}
+
+#[test]
+fn bias_added_after_normalization() {
+    let mut unbiased = Billow::new();
+    unbiased.set_persistence(0.5);
+    unbiased.set_octave_count(2);
+    unbiased.set_normalize(true);
+    unbiased.set_bias(0.0);
+    let mut biased = unbiased.clone();
+    biased.set_bias(0.15);
+    for i in 0..1000 {
+        let (x, y, z) = (i as f64 * 0.173, i as f64 * 0.071, 0.5);
+        let (value, biased_value) = (unbiased.get_value(x, y, z), biased.get_value(x, y, z));
+        // Skip the output values that are clamped.
+        if biased_value < 1.0 && value > -1.0 {
+            assert!((biased_value - value - 0.15).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn bias_changes_config_hash() {
+    let mut biased = Billow::new();
+    biased.set_bias(0.0);
+    assert!(biased.config_hash() != Billow::new().config_hash());
+}
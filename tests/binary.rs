@@ -0,0 +1,191 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::binary::{read_binary, write_binary};
+use noise::module::*;
+use noise::noisegen::{Dimensions, GradientTable, NoiseQuality, PermTable};
+use std::io;
+
+/// Builds a graph several levels deep that uses every supported noise module
+/// with non-default parameters.
+fn make_graph() -> BoxedModule {
+    let mut perlin = Perlin::new();
+    perlin.set_frequency(1.7);
+    perlin.set_octave_count_f(4.4);
+    perlin.set_quality(NoiseQuality::Best);
+    perlin.set_seed_u64(0x0123_4567_89ab_cdef);
+    perlin.set_octave_rotation(true);
+    perlin.set_octave_offset(true);
+    perlin.set_gradient_table(Some(GradientTable::new(&[[0.6, 0.8, 0.0], [0.0, -0.6, 0.8]])));
+    let mut billow = Billow::new();
+    billow.set_persistence(0.4);
+    billow.set_seed(-7);
+    billow.set_bias(0.25);
+    billow.set_normalize(true);
+    billow.set_dimensions(Dimensions::Dim2);
+    let mut ridged = RidgedMulti::new();
+    ridged.set_lacunarity(2.2);
+    ridged.set_octave_count(3);
+    ridged.set_dimensions(Dimensions::Dim2);
+    let mut voronoi = Voronoi::new();
+    voronoi.enable_distance(true);
+    voronoi.set_distance_falloff(Falloff::Quadratic);
+    voronoi.set_jitter(0.6);
+    voronoi.set_perm_table(Some(PermTable::new(12)));
+    voronoi.set_smooth_k(0.25);
+    let mut cylinders = Cylinders::new();
+    cylinders.set_offset(0.25);
+    let mut spheres = Spheres::new();
+    spheres.set_signed_distance(true);
+    let mut constant = Constant::new();
+    constant.set_const_value(0.5);
+
+    let mut clamp = Clamp::new(Box::new(perlin) as BoxedModule);
+    clamp.set_bounds(-0.5, 0.75);
+    let mut exponent = Exponent::new(Box::new(billow) as BoxedModule);
+    exponent.set_exponent(1.5);
+    exponent.set_clamp_input(true);
+    let mut scale_bias = ScaleBias::new(Box::new(ridged) as BoxedModule);
+    scale_bias.set_scale(0.5);
+    scale_bias.set_bias(-0.25);
+    scale_bias.set_output_clamp(Some((-0.5, 0.5)));
+    let mut curve = Curve::new(Box::new(Abs::new(Box::new(voronoi) as BoxedModule)) as BoxedModule);
+    curve.make_random_control_points(6, 3);
+    let mut terrace = Terrace::new(Box::new(Invert::new(Box::new(cylinders) as BoxedModule))
+                                   as BoxedModule);
+    terrace.make_control_points(5);
+    terrace.set_invert_terraces(true);
+
+    let mut scale_point = ScalePoint::new(Box::new(spheres) as BoxedModule);
+    scale_point.set_xyz_scale(1.0, 2.0, 3.0);
+    let mut translate_point = TranslatePoint::new(Box::new(Checkerboard) as BoxedModule);
+    translate_point.set_xyz_trans(0.5, -1.5, 2.0);
+    let mut rotate_point = RotatePoint::new(Box::new(scale_point) as BoxedModule);
+    rotate_point.set_angles(10.0, 20.0, 30.0);
+    let mut matrix_point = RotatePoint::new(Box::new(translate_point) as BoxedModule);
+    matrix_point.set_matrix([[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 2.0]]);
+    let mut turbulence = Turbulence::new(Box::new(curve) as BoxedModule);
+    turbulence.set_roughness(2);
+    turbulence.set_seed(99);
+    turbulence.y_distort_mut().set_frequency(3.0);
+    turbulence.set_power(0.3);
+
+    let mut blend = Blend::new(Box::new(clamp) as BoxedModule,
+                               Box::new(exponent) as BoxedModule,
+                               Box::new(scale_bias) as BoxedModule);
+    blend.set_control_range(-0.5, 0.5);
+    blend.set_interp(Interp::Quintic);
+    let mut select = Select::new(Box::new(terrace) as BoxedModule,
+                                 Box::new(rotate_point) as BoxedModule,
+                                 Box::new(Power::new(Box::new(matrix_point) as BoxedModule,
+                                                     Box::new(constant) as BoxedModule))
+                                 as BoxedModule);
+    select.set_bounds(-0.2, 0.6);
+    select.set_edge_falloff(0.1);
+    let displace = Displace::new(Box::new(turbulence) as BoxedModule,
+                                 Box::new(Checkerboard) as BoxedModule,
+                                 Box::new(Constant::new()) as BoxedModule,
+                                 Box::new(Constant::new()) as BoxedModule);
+
+    Box::new(Add::new(Box::new(Min::new(Box::new(blend) as BoxedModule,
+                                        Box::new(select) as BoxedModule)) as BoxedModule,
+                      Box::new(Multiply::new(Box::new(displace) as BoxedModule,
+                                             Box::new(Max::new(Box::new(Constant::new())
+                                                               as BoxedModule,
+                                                               Box::new(Checkerboard)
+                                                               as BoxedModule))
+                                             as BoxedModule)) as BoxedModule))
+}
+
+fn count_modules(module: &dyn Module) -> usize {
+    1 + module.sources().iter().map(|&source| count_modules(source)).sum::<usize>()
+}
+
+#[test]
+fn round_trip_multi_level_graph() {
+    let graph = make_graph();
+    let mut bytes = Vec::new();
+    write_binary(&*graph, &mut bytes).unwrap();
+
+    let read = read_binary(&mut &bytes[..]).unwrap();
+    assert_eq!(read.config_hash(), graph.config_hash());
+    assert_eq!(count_modules(&*read), count_modules(&*graph));
+    for i in 0..100 {
+        let (x, y, z) = (i as f64 * 0.37 - 10.0, i as f64 * 0.11, i as f64 * -0.23);
+        assert_eq!(read.get_value(x, y, z).to_bits(), graph.get_value(x, y, z).to_bits());
+    }
+
+    // Writing the graph that was read produces the same bytes.
+    let mut rewritten = Vec::new();
+    write_binary(&*read, &mut rewritten).unwrap();
+    assert_eq!(rewritten, bytes);
+}
+
+#[test]
+fn unsupported_module_is_rejected() {
+    let graph: BoxedModule = Box::new(Abs::new(Box::new(Cache::new(Perlin::new())) as BoxedModule));
+    let mut bytes = Vec::new();
+    let err = write_binary(&*graph, &mut bytes).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(bytes.is_empty());
+
+    // Sources that are not boxed are not recognised either.
+    let err = write_binary(&Abs::new(Perlin::new()), &mut bytes).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+/// Returns the kind of error from reading `bytes`, which must fail.
+fn read_error(bytes: &[u8]) -> io::ErrorKind {
+    match read_binary(&mut &bytes[..]) {
+        Ok(_) => panic!("Read invalid data successfully!"),
+        Err(err) => err.kind(),
+    }
+}
+
+#[test]
+fn invalid_data_is_rejected() {
+    let mut bytes = Vec::new();
+    write_binary(&*make_graph(), &mut bytes).unwrap();
+
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] = b'X';
+    assert_eq!(read_error(&bad_magic[..]), io::ErrorKind::InvalidData);
+
+    let mut bad_tag = bytes.clone();
+    bad_tag[4] = 0xff;
+    assert_eq!(read_error(&bad_tag[..]), io::ErrorKind::InvalidData);
+
+    for len in 0..bytes.len() {
+        assert_eq!(read_error(&bytes[..len]),
+                   io::ErrorKind::UnexpectedEof);
+    }
+
+    // A chain of `Abs` noise modules ending in a `Constant` noise module can
+    // be nested up to 128 noise modules deep.
+    let chain = |abs_count: usize| {
+        let mut bytes = b"NMG1".to_vec();
+        bytes.extend(vec![0x20; abs_count]);
+        bytes.push(0x00);
+        bytes.extend_from_slice(&0.0f64.to_le_bytes());
+        bytes
+    };
+    assert!(read_binary(&mut &chain(127)[..]).is_ok());
+    assert_eq!(read_error(&chain(128)[..]), io::ErrorKind::InvalidData);
+    assert_eq!(read_error(&chain(100000)[..]), io::ErrorKind::InvalidData);
+}
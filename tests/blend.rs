@@ -0,0 +1,72 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+mod common;
+
+use common::constant;
+use noise::module::{Blend, Module};
+use std::f64;
+
+fn blend(control: f64, lower_bound: f64, upper_bound: f64) -> f64 {
+    let mut blend = Blend::new(constant(2.0), constant(6.0), constant(control));
+    blend.set_control_range(lower_bound, upper_bound);
+    blend.get_value(0.0, 0.0, 0.0)
+}
+
+#[test]
+fn non_default_control_range() {
+    assert_eq!(blend(0.0, 0.0, 4.0), 2.0);
+    assert_eq!(blend(1.0, 0.0, 4.0), 3.0);
+    assert_eq!(blend(2.0, 0.0, 4.0), 4.0);
+    assert_eq!(blend(4.0, 0.0, 4.0), 6.0);
+    // Control values outside the range are clamped to it.
+    assert_eq!(blend(-3.0, 0.0, 4.0), 2.0);
+    assert_eq!(blend(10.0, 0.0, 4.0), 6.0);
+}
+
+#[test]
+fn default_control_range() {
+    for &control in &[-2.0, -1.0, -0.5, 0.0, 0.5, 1.0, 2.0] {
+        let default = Blend::new(constant(2.0), constant(6.0), constant(control));
+        assert_eq!(default.get_value(0.0, 0.0, 0.0), blend(control, -1.0, 1.0));
+    }
+}
+
+#[test]
+fn default_control_range_is_not_clamped() {
+    // Output values of the original libnoise, with an alpha of
+    // `(control + 1) / 2`.
+    for &(control, expected) in &[(-3.0, -2.0), (-2.0, 0.0), (-1.5, 1.0), (1.5, 7.0),
+                                  (2.0, 8.0), (3.0, 10.0)] {
+        let default = Blend::new(constant(2.0), constant(6.0), constant(control));
+        assert_eq!(default.get_value(0.0, 0.0, 0.0), expected);
+    }
+}
+
+#[test]
+#[should_panic]
+fn nan_bound_panics() {
+    blend(0.0, f64::NAN, 1.0);
+}
+
+#[test]
+#[should_panic]
+fn empty_range_panics() {
+    blend(0.0, 1.0, 1.0);
+}
@@ -0,0 +1,81 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Bypass, Curve, Module, Perlin, Select, Turbulence};
+
+const POINTS: [(f64, f64, f64); 4] = [(0.0, 0.0, 0.0), (0.5, 0.25, 0.75), (-1.3, 2.7, 0.1),
+                                      (10.5, -4.25, 3.0)];
+
+#[test]
+fn enabled_outputs_modifier_value() {
+    let turbulence = Turbulence::new(Perlin::new());
+    let bypass = Bypass::new(turbulence.clone());
+    assert!(!bypass.is_bypassed());
+    for &(x, y, z) in &POINTS {
+        assert_eq!(bypass.get_value(x, y, z), turbulence.get_value(x, y, z));
+    }
+}
+
+#[test]
+fn bypassed_outputs_source_value() {
+    let perlin = Perlin::new();
+    let mut curve = Curve::new(perlin.clone());
+    curve.add_control_point(-1.0, 1.0);
+    curve.add_control_point(0.0, 0.0);
+    curve.add_control_point(0.5, -0.5);
+    curve.add_control_point(1.0, 1.0);
+
+    let mut bypass = Bypass::new(curve);
+    bypass.set_bypass(true);
+    assert!(bypass.is_bypassed());
+    for &(x, y, z) in &POINTS {
+        assert_eq!(bypass.get_value(x, y, z), perlin.get_value(x, y, z));
+    }
+
+    bypass.set_bypass(false);
+    for &(x, y, z) in &POINTS {
+        assert_eq!(bypass.get_value(x, y, z), bypass.module().get_value(x, y, z));
+    }
+}
+
+#[test]
+fn bypass_changes_config_hash() {
+    let mut bypass = Bypass::new(Turbulence::new(Perlin::new()));
+    let enabled_hash = bypass.config_hash();
+    bypass.set_bypass(true);
+    assert!(bypass.config_hash() != enabled_hash);
+}
+
+#[test]
+fn bypassed_multi_source_uses_first_source() {
+    let mut perlin = Perlin::new();
+    perlin.set_seed(7);
+    let select = Select::new(perlin.clone(), Perlin::new(), Perlin::new());
+    let mut bypass = Bypass::new(select);
+    bypass.set_bypass(true);
+    for &(x, y, z) in &POINTS {
+        assert_eq!(bypass.get_value(x, y, z), perlin.get_value(x, y, z));
+    }
+}
+
+#[test]
+#[should_panic]
+fn generator_panics() {
+    Bypass::new(Perlin::new());
+}
@@ -0,0 +1,61 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Checkerboard, Module};
+
+/// Checks that the blocks along the `x` axis alternate every unit, starting
+/// from the block containing `start`.
+fn assert_alternates(start: f64) {
+    let checkerboard = Checkerboard;
+    let first = checkerboard.get_value(start, 0.5, 0.5);
+    for i in 0..8 {
+        let expected = if i % 2 == 0 { first } else { -first };
+        // Sample both ends of each block.
+        let x = start.floor() + i as f64;
+        assert_eq!(checkerboard.get_value(x, 0.5, 0.5), expected, "x = {}", x);
+        assert_eq!(checkerboard.get_value(x + 0.75, 0.5, 0.5), expected, "x = {}", x + 0.75);
+    }
+}
+
+#[test]
+fn parity_near_origin() {
+    let checkerboard = Checkerboard;
+    assert_eq!(checkerboard.get_value(0.5, 0.5, 0.5), 1.0);
+    assert_eq!(checkerboard.get_value(1.5, 0.5, 0.5), -1.0);
+    assert_eq!(checkerboard.get_value(-0.5, 0.5, 0.5), -1.0);
+    assert_eq!(checkerboard.get_value(1.5, 1.5, 0.5), 1.0);
+    assert_eq!(checkerboard.get_value(1.5, 1.5, 1.5), -1.0);
+    assert_alternates(-3.0);
+}
+
+#[test]
+fn parity_across_wrap_boundaries() {
+    let boundary = 1073741824.0;
+    for &start in &[boundary - 4.0, 2.0 * boundary - 4.0, 2147483647.0 - 4.0,
+                    -boundary - 4.0, -2.0 * boundary - 4.0, -2147483648.0 - 4.0] {
+        assert_alternates(start);
+    }
+}
+
+#[test]
+fn parity_far_from_origin() {
+    for &start in &[1.0e12, -1.0e12, 3.5e14, -3.5e14] {
+        assert_alternates(start);
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{ClampPoint, Module, Perlin};
+
+#[test]
+fn out_of_range_inputs_output_edge_value() {
+    let perlin = Perlin::new();
+    let mut clamp_point = ClampPoint::new(perlin.clone());
+    clamp_point.set_x_bounds(-1.0, 2.0);
+    clamp_point.set_y_bounds(0.5, 1.5);
+    clamp_point.set_z_bounds(-3.0, -2.0);
+
+    // Inside the box the input value is passed through unchanged.
+    assert_eq!(clamp_point.get_value(0.3, 0.7, -2.5), perlin.get_value(0.3, 0.7, -2.5));
+    // Outside the box each coordinate is clamped to the nearest edge.
+    assert_eq!(clamp_point.get_value(5.0, 0.7, -2.5), perlin.get_value(2.0, 0.7, -2.5));
+    assert_eq!(clamp_point.get_value(0.3, -4.0, -2.5), perlin.get_value(0.3, 0.5, -2.5));
+    assert_eq!(clamp_point.get_value(0.3, 0.7, 10.0), perlin.get_value(0.3, 0.7, -2.0));
+    assert_eq!(clamp_point.get_value(-7.0, 9.0, -8.0), perlin.get_value(-1.0, 1.5, -3.0));
+}
+
+#[test]
+fn unbounded_by_default() {
+    let perlin = Perlin::new();
+    let clamp_point = ClampPoint::new(perlin.clone());
+    for &(x, y, z) in &[(1e6, -1e6, 3.5), (-250.5, 0.0, 1e9)] {
+        assert_eq!(clamp_point.get_value(x, y, z), perlin.get_value(x, y, z));
+    }
+}
+
+#[test]
+#[should_panic]
+fn inverted_bounds_panic() {
+    ClampPoint::new(Perlin::new()).set_y_bounds(1.0, -1.0);
+}
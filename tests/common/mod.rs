@@ -0,0 +1,34 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use noise::module::{BoxedModule, Constant};
+
+/// Returns a `Constant` noise module outputting `value`.
+pub fn constant(value: f64) -> Constant {
+    let mut constant = Constant::new();
+    constant.set_const_value(value);
+    constant
+}
+
+/// Returns a boxed `Constant` noise module outputting `value`.
+pub fn boxed_constant(value: f64) -> BoxedModule {
+    Box::new(constant(value))
+}
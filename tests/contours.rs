@@ -0,0 +1,75 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::noise_map::NoiseMap;
+
+#[test]
+fn linear_ramp_gives_parallel_segments() {
+    let (width, height) = (6, 5);
+    let mut map = NoiseMap::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            map.set_value(x, y, x as f64);
+        }
+    }
+
+    let levels = [0.5, 2.25, 4.75];
+    let contours = map.contours(&levels);
+    assert_eq!(contours.len(), levels.len());
+    for (contour, &level) in contours.iter().zip(&levels) {
+        assert_eq!(contour.level, level);
+        // One vertical segment per row of cells.
+        assert_eq!(contour.segments.len(), height - 1);
+        for segment in &contour.segments {
+            assert_eq!(segment[0].0, level);
+            assert_eq!(segment[1].0, level);
+            assert_eq!((segment[0].1 - segment[1].1).abs(), 1.0);
+        }
+    }
+}
+
+#[test]
+fn levels_outside_the_map_give_no_segments() {
+    let mut map = NoiseMap::new(4, 4);
+    map.set_value(2, 2, 1.0);
+    let contours = map.contours(&[-1.0, 2.0]);
+    assert!(contours[0].segments.is_empty());
+    assert!(contours[1].segments.is_empty());
+}
+
+#[test]
+fn saddle_is_resolved_by_the_average() {
+    // Corners 0 and 2 are above the level, corners 1 and 3 below it.
+    let mut map = NoiseMap::new(2, 2);
+    map.set_value(0, 0, 1.0);
+    map.set_value(1, 1, 1.0);
+
+    // The average of 0.5 is above the level, so the corners above it are
+    // joined and the corners below it are cut off.
+    let segments = &map.contours(&[0.25])[0].segments;
+    assert_eq!(segments.len(), 2);
+    assert!(segments.contains(&[(0.75, 0.0), (1.0, 0.25)]));
+    assert!(segments.contains(&[(0.25, 1.0), (0.0, 0.75)]));
+
+    // The average is below the level, so the corners above it are cut off.
+    let segments = &map.contours(&[0.75])[0].segments;
+    assert_eq!(segments.len(), 2);
+    assert!(segments.contains(&[(0.0, 0.25), (0.25, 0.0)]));
+    assert!(segments.contains(&[(1.0, 0.75), (0.75, 1.0)]));
+}
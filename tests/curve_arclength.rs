@@ -0,0 +1,134 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Constant, Curve};
+
+fn make_curve() -> Curve<Constant> {
+    // The slope of the curve varies widely, so sampling it at equal steps of
+    // the input value would be very uneven.
+    let mut curve = Curve::new(Constant::new());
+    for &(input_value, output_value) in &[(-1.0, -1.0), (-0.5, 0.8), (0.0, 0.6), (0.2, -0.9),
+                                          (0.7, 0.1), (1.0, 1.0)] {
+        curve.add_control_point(input_value, output_value);
+    }
+    curve.build_arclength_table();
+    curve
+}
+
+/// Returns the input values that divide the curve into `steps` pieces of
+/// equal arc length, measured independently of the curve's own table by
+/// walking it at a much finer resolution.
+fn reference_inputs(curve: &Curve<Constant>, steps: usize) -> Vec<f64> {
+    let fine = 200000;
+    let point = |k: usize| {
+        let input_value = -1.0 + 2.0 * k as f64 / fine as f64;
+        (input_value, curve.map_value(input_value))
+    };
+    let mut lengths = vec![0.0];
+    for k in 1..fine + 1 {
+        let ((x0, y0), (x1, y1)) = (point(k - 1), point(k));
+        let length = lengths[k - 1] + (x1 - x0).hypot(y1 - y0);
+        lengths.push(length);
+    }
+    let total = lengths[fine];
+    (0..steps + 1).map(|i| {
+        let target = total * i as f64 / steps as f64;
+        let k = lengths.iter().position(|&length| length >= target).unwrap_or(fine);
+        point(k).0
+    }).collect()
+}
+
+#[test]
+fn equal_steps_cover_equal_lengths() {
+    let curve = make_curve();
+    let steps = 200;
+    let points: Vec<(f64, f64)> = reference_inputs(&curve, steps).into_iter()
+        .enumerate()
+        .map(|(i, input_value)| {
+            let output_value = curve.sample_by_arclength(i as f64 / steps as f64);
+            assert!((output_value - curve.map_value(input_value)).abs() < 1e-3,
+                    "{} vs {}", output_value, curve.map_value(input_value));
+            (input_value, output_value)
+        })
+        .collect();
+
+    // Successive samples are joined by nearly straight pieces of the curve,
+    // so the distances between them should all be close to equal.
+    let lengths: Vec<f64> = points.windows(2)
+        .map(|w| (w[1].0 - w[0].0).hypot(w[1].1 - w[0].1))
+        .collect();
+    let mean = lengths.iter().sum::<f64>() / steps as f64;
+    for &length in &lengths {
+        assert!((length - mean).abs() < 0.02 * mean, "{} vs mean {}", length, mean);
+    }
+
+    // Sampling by input value instead is far from even.
+    let input_lengths: Vec<f64> = (0..steps).map(|i| {
+        let (x0, x1) = (-1.0 + 2.0 * i as f64 / steps as f64,
+                        -1.0 + 2.0 * (i + 1) as f64 / steps as f64);
+        (x1 - x0).hypot(curve.map_value(x1) - curve.map_value(x0))
+    }).collect();
+    let max = input_lengths.iter().cloned().fold(0.0, f64::max);
+    let min = input_lengths.iter().cloned().fold(f64::INFINITY, f64::min);
+    assert!(max > 2.0 * min);
+}
+
+#[test]
+fn ends_map_to_end_control_points() {
+    let curve = make_curve();
+    assert_eq!(curve.sample_by_arclength(0.0), -1.0);
+    assert_eq!(curve.sample_by_arclength(1.0), 1.0);
+    assert_eq!(curve.sample_by_arclength(-0.5), -1.0);
+    assert_eq!(curve.sample_by_arclength(1.5), 1.0);
+}
+
+#[test]
+fn table_rebuilt_after_control_points_change() {
+    let mut curve = make_curve();
+    let before = curve.sample_by_arclength(0.5);
+    curve.add_control_point(2.0, 3.0);
+    curve.build_arclength_table();
+    assert_eq!(curve.sample_by_arclength(1.0), 3.0);
+    assert!(curve.sample_by_arclength(0.5) != before);
+    assert!(curve == { let mut c = make_curve(); c.add_control_point(2.0, 3.0); c });
+}
+
+#[test]
+#[should_panic]
+fn adding_control_point_discards_table() {
+    let mut curve = make_curve();
+    curve.add_control_point(2.0, 3.0);
+    curve.sample_by_arclength(0.5);
+}
+
+#[test]
+#[should_panic]
+fn unbuilt_table_panics() {
+    let mut curve = Curve::new(Constant::new());
+    for &input_value in &[0.0, 1.0, 2.0, 3.0] {
+        curve.add_control_point(input_value, 0.0);
+    }
+    curve.sample_by_arclength(0.5);
+}
+
+#[test]
+fn curve_is_sync() {
+    fn assert_sync<T: Sync>() {}
+    assert_sync::<Curve<Constant>>();
+}
@@ -0,0 +1,56 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Constant, Curve, Module, Perlin};
+
+fn add_control_points<M: Module>(curve: &mut Curve<M>) {
+    curve.add_control_point(-1.0, -0.5);
+    curve.add_control_point(-0.25, 0.75);
+    curve.add_control_point(0.5, 0.0);
+    curve.add_control_point(1.0, 1.0);
+}
+
+#[test]
+fn map_value_matches_get_value() {
+    let mut source = Constant::new();
+    source.set_const_value(0.3);
+    let mut curve = Curve::new(source);
+    add_control_points(&mut curve);
+    assert_eq!(curve.get_value(1.0, 2.0, 3.0), curve.map_value(0.3));
+
+    let perlin = Perlin::new();
+    let mut curve = Curve::new(perlin.clone());
+    add_control_points(&mut curve);
+    for i in 0..100 {
+        let (x, y, z) = (i as f64 * 0.173, i as f64 * 0.071, 0.5);
+        assert_eq!(curve.get_value(x, y, z), curve.map_value(perlin.get_value(x, y, z)));
+    }
+}
+
+#[test]
+fn map_value_passes_through_control_points() {
+    let mut curve = Curve::new(Constant::new());
+    add_control_points(&mut curve);
+    for control_point in curve.control_points() {
+        assert_eq!(curve.map_value(control_point.input_value), control_point.output_value);
+    }
+    // Values beyond the end control points output the nearest end value.
+    assert_eq!(curve.map_value(-3.0), -0.5);
+    assert_eq!(curve.map_value(3.0), 1.0);
+}
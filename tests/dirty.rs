@@ -0,0 +1,70 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Add, BoxedModule, Dirty, GridCache, Module, Perlin};
+
+fn grid<M: Module>(module: M) -> GridCache<M> {
+    GridCache::new(module, (-1.0, -1.0), (0.25, 0.25), 9, 9)
+}
+
+#[test]
+fn unrelated_change_does_not_invalidate_cache() {
+    let mut graph = Add::new(grid(Dirty::new(Perlin::new())), grid(Dirty::new(Perlin::new())));
+    assert!(!graph.module1().is_out_of_date());
+    assert!(!graph.module2().is_out_of_date());
+
+    graph.module2_mut().module_mut().module_mut().set_seed(7);
+    assert!(!graph.module1().is_out_of_date());
+    assert!(graph.module2().is_out_of_date());
+
+    let map1 = graph.module1().map().clone();
+    assert!(!graph.module1_mut().refresh());
+    assert_eq!(*graph.module1().map(), map1);
+
+    assert!(graph.module2_mut().refresh());
+    assert!(!graph.module2().is_out_of_date());
+    let mut expected = Perlin::new();
+    expected.set_seed(7);
+    assert_eq!(graph.module2().map().get_value(3, 5), expected.get_value(-0.25, 0.25, 0.0));
+}
+
+#[test]
+fn change_deep_in_graph_is_detected() {
+    let inner: BoxedModule = Box::new(Add::new(Perlin::new(), Dirty::new(Perlin::new())));
+    let mut cache = grid(inner);
+    assert!(!cache.is_out_of_date());
+
+    cache.module_mut().sources_mut()[1].sources_mut();
+    assert!(cache.refresh());
+    assert!(!cache.refresh());
+}
+
+#[test]
+fn mark_dirty_invalidates_cache() {
+    let cache = grid(Dirty::new(Perlin::new()));
+    let version = cache.version();
+    cache.module().mark_dirty();
+    assert!(cache.version() > version);
+    assert!(cache.is_out_of_date());
+}
+
+#[test]
+fn graphs_without_dirty_modules_have_version_zero() {
+    assert_eq!(Add::new(Perlin::new(), Perlin::new()).version(), 0);
+}
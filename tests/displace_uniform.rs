@@ -0,0 +1,87 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Displace, DisplaceUniform, FnModule, Module, Perlin, TranslatePoint};
+
+type CoordModule = FnModule<fn(f64, f64, f64) -> f64>;
+
+/// Returns source modules whose output values are the `x`, `y` and `z`
+/// coordinates of the input value, respectively.
+fn coord_modules() -> Vec<CoordModule> {
+    fn x_coord(x: f64, _y: f64, _z: f64) -> f64 {
+        x
+    }
+    fn y_coord(_x: f64, y: f64, _z: f64) -> f64 {
+        y
+    }
+    fn z_coord(_x: f64, _y: f64, z: f64) -> f64 {
+        z
+    }
+    vec![FnModule::new(x_coord), FnModule::new(y_coord), FnModule::new(z_coord)]
+}
+
+fn input_values() -> Vec<(f64, f64, f64)> {
+    (0..50).map(|i| (i as f64 * 0.173 - 4.0, i as f64 * 0.071, i as f64 * -0.029)).collect()
+}
+
+#[test]
+fn same_displacement_on_every_axis() {
+    let perlin = Perlin::new();
+    let displaced: Vec<_> = coord_modules().into_iter().map(|source| {
+        let mut displace = DisplaceUniform::new(source, perlin.clone());
+        displace.set_offsets((0.0, 0.0, 0.0), (0.0, 0.0, 0.0), (0.0, 0.0, 0.0));
+        displace
+    }).collect();
+    for (x, y, z) in input_values() {
+        let displacement = perlin.get_value(x, y, z);
+        assert_eq!(displaced[0].get_value(x, y, z), x + displacement);
+        assert_eq!(displaced[1].get_value(x, y, z), y + displacement);
+        assert_eq!(displaced[2].get_value(x, y, z), z + displacement);
+    }
+}
+
+#[test]
+fn matches_displace_with_same_module() {
+    let perlin = Perlin::new();
+    let source = Perlin::new();
+    let mut uniform = DisplaceUniform::new(source.clone(), perlin.clone());
+    uniform.set_offsets((0.0, 0.0, 0.0), (0.0, 0.0, 0.0), (0.0, 0.0, 0.0));
+    let displace = Displace::new(source, perlin.clone(), perlin.clone(), perlin);
+    for (x, y, z) in input_values() {
+        assert_eq!(uniform.get_value(x, y, z), displace.get_value(x, y, z));
+    }
+}
+
+#[test]
+fn offsets_translate_displacement_module() {
+    let perlin = Perlin::new();
+    let source = Perlin::new();
+    let uniform = DisplaceUniform::new(source.clone(), perlin.clone());
+    let translated = |(x, y, z)| {
+        let mut translate = TranslatePoint::new(perlin.clone());
+        translate.set_xyz_trans(x, y, z);
+        translate
+    };
+    let offsets = uniform.offsets();
+    let displace = Displace::new(source, translated(offsets[0]), translated(offsets[1]),
+                                 translated(offsets[2]));
+    for (x, y, z) in input_values() {
+        assert_eq!(uniform.get_value(x, y, z), displace.get_value(x, y, z));
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{FnModule, Perlin};
+use noise::noise_map::NoiseMap;
+use noise::render::{render_grayscale_dithered, DitherKind};
+
+const KINDS: [DitherKind; 2] = [DitherKind::Ordered, DitherKind::FloydSteinberg];
+
+fn mean(pixels: &[u8]) -> f64 {
+    pixels.iter().map(|&pixel| pixel as f64).sum::<f64>() / pixels.len() as f64
+}
+
+/// Returns the mean of the exact, unquantized shades of the map.
+fn exact_mean(map: &NoiseMap) -> f64 {
+    let (lower, upper) = map.bounds();
+    map.values().iter().map(|&value| (value - lower) / (upper - lower) * 255.0).sum::<f64>()
+        / map.values().len() as f64
+}
+
+#[test]
+fn mean_brightness_matches_undithered() {
+    let map = NoiseMap::from_module(&Perlin::new(), (0.0, 0.0), (0.02, 0.02), 128, 128);
+    let undithered = mean(&render_grayscale_dithered(&map, &DitherKind::None));
+    assert!((undithered - exact_mean(&map)).abs() < 0.5);
+    for kind in &KINDS {
+        let dithered = mean(&render_grayscale_dithered(&map, kind));
+        assert!((dithered - undithered).abs() < 0.5, "{:?}: {} != {}", kind, dithered, undithered);
+    }
+}
+
+#[test]
+fn gentle_ramp_is_mixed_in_proportion() {
+    // A ramp spanning only a few shades is a row of wide bands when rounded.
+    // Dithering mixes neighbouring shades so each area averages its exact
+    // shade.  The last row pins the range of the map to 0 to 255, so the
+    // values are the exact shades.
+    let mut map = NoiseMap::new(64, 64);
+    for y in 0..63 {
+        for x in 0..64 {
+            map.set_value(x, y, 1.0 + x as f64 * 3.0 / 63.0);
+        }
+    }
+    map.set_value(63, 63, 255.0);
+
+    for kind in &KINDS {
+        let pixels = render_grayscale_dithered(&map, kind);
+        // Compare each band of 8 columns, the width of the Bayer matrix.
+        for band in 0..7 {
+            let columns = (band * 8)..(band * 8 + 8);
+            let block: Vec<u8> = (0..56).flat_map(|y| columns.clone().map(move |x| (x, y)))
+                .map(|(x, y)| pixels[y * 64 + x])
+                .collect();
+            let exact = columns.map(|x| map.get_value(x, 0)).sum::<f64>() / 8.0;
+            assert!((mean(&block) - exact).abs() < 0.1, "{:?}: band {}, {} != {}", kind, band,
+                    mean(&block), exact);
+        }
+    }
+}
+
+#[test]
+fn ordered_tiles_every_eight_pixels() {
+    let flat = FnModule::new(|_x: f64, _y: f64, _z: f64| 0.3);
+    let mut map = NoiseMap::from_module(&flat, (0.0, 0.0), (1.0, 1.0), 32, 32);
+    map.set_value(31, 31, 1.0);
+    map.set_value(30, 31, 0.0);
+    let pixels = render_grayscale_dithered(&map, &DitherKind::Ordered);
+    for y in 0..24 {
+        for x in 0..24 {
+            assert_eq!(pixels[y * 32 + x], pixels[(y + 8) * 32 + x]);
+            assert_eq!(pixels[y * 32 + x], pixels[y * 32 + x + 8]);
+        }
+    }
+    assert_eq!(pixels, render_grayscale_dithered(&map, &DitherKind::Ordered));
+}
+
+#[test]
+fn flat_map_is_black() {
+    let map = NoiseMap::new(4, 4);
+    for kind in &KINDS {
+        assert_eq!(render_grayscale_dithered(&map, kind), vec![0; 16]);
+    }
+}
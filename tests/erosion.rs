@@ -0,0 +1,122 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::Perlin;
+use noise::noise_map::{ErosionParams, NoiseMap};
+
+fn terrain() -> NoiseMap {
+    NoiseMap::from_module(&Perlin::new(), (0.0, 0.0), (0.05, 0.05), 64, 64)
+}
+
+fn total(map: &NoiseMap) -> f64 {
+    map.values().iter().sum()
+}
+
+#[test]
+fn mass_is_conserved() {
+    let mut map = terrain();
+    let before = total(&map);
+    map.erode_hydraulic(ErosionParams { droplets: 5000, ..ErosionParams::default() });
+    assert!(map != terrain(), "erosion left the map unchanged");
+    assert!((total(&map) - before).abs() < 1e-9, "total changed from {} to {}", before,
+            total(&map));
+}
+
+#[test]
+fn deterministic_for_seed() {
+    let params = ErosionParams { droplets: 1000, seed: 7, ..ErosionParams::default() };
+    let mut map1 = terrain();
+    let mut map2 = terrain();
+    map1.erode_hydraulic(params.clone());
+    map2.erode_hydraulic(params.clone());
+    assert_eq!(map1, map2);
+
+    let mut map3 = terrain();
+    map3.erode_hydraulic(ErosionParams { seed: 8, ..params });
+    assert!(map1 != map3);
+}
+
+#[test]
+fn small_maps_are_unchanged() {
+    let mut map = NoiseMap::from_module(&Perlin::new(), (0.0, 0.0), (0.05, 0.05), 1, 16);
+    let original = map.clone();
+    map.erode_hydraulic(ErosionParams::default());
+    assert_eq!(map, original);
+}
+
+#[test]
+#[should_panic]
+fn invalid_inertia_panics() {
+    terrain().erode_hydraulic(ErosionParams { inertia: 1.5, ..ErosionParams::default() });
+}
+
+/// Returns the largest difference between a value and one of the neighbours
+/// along the `x` and `y` axes.
+fn steepest_slope(map: &NoiseMap) -> f64 {
+    let mut steepest: f64 = 0.0;
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            if x + 1 < map.width() {
+                steepest = steepest.max((map.get_value(x, y) - map.get_value(x + 1, y)).abs());
+            }
+            if y + 1 < map.height() {
+                steepest = steepest.max((map.get_value(x, y) - map.get_value(x, y + 1)).abs());
+            }
+        }
+    }
+    steepest
+}
+
+#[test]
+fn thermal_spreads_spike_to_neighbours() {
+    let mut map = NoiseMap::new(9, 9);
+    map.set_value(4, 4, 10.0);
+    map.erode_thermal(0.5, 1);
+    assert!(map.get_value(4, 4) < 10.0);
+    for &(x, y) in &[(3, 4), (5, 4), (4, 3), (4, 5)] {
+        assert!(map.get_value(x, y) > 0.0);
+        assert_eq!(map.get_value(x, y), map.get_value(5, 4));
+    }
+    assert_eq!(map.get_value(3, 3), 0.0);
+    assert!((total(&map) - 10.0).abs() < 1e-12);
+}
+
+#[test]
+fn thermal_converges_to_talus_angle() {
+    let talus_angle: f64 = 0.4;
+    let mut map = NoiseMap::new(9, 9);
+    map.set_value(4, 4, 10.0);
+    map.erode_thermal(talus_angle, 10000);
+    assert!(steepest_slope(&map) <= talus_angle.tan() + 1e-9,
+            "steepest slope {} after erosion", steepest_slope(&map));
+    assert!((total(&map) - 10.0).abs() < 1e-9);
+
+    let mut map = NoiseMap::from_module(&Perlin::new(), (0.0, 0.0), (0.1, 0.1), 16, 16);
+    let before = total(&map);
+    map.erode_thermal(0.05, 2000);
+    assert!(steepest_slope(&map) <= 0.05f64.tan() + 1e-9,
+            "steepest slope {} after erosion", steepest_slope(&map));
+    assert!((total(&map) - before).abs() < 1e-9);
+}
+
+#[test]
+#[should_panic]
+fn invalid_talus_angle_panics() {
+    terrain().erode_thermal(-0.1, 1);
+}
@@ -0,0 +1,109 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+//! Checks that the output values of the noise modules do not depend on which
+//! optional features are enabled.
+//!
+//! The `simd` feature replaces the implementation of some noise modules, but
+//! must not change their output values; the tolerance is zero.  Each test
+//! compares a noise module against a straightforward scalar reference
+//! calculation built from the functions in `noisegen`, so running the tests
+//! both with and without the feature checks both implementations against the
+//! same reference.
+
+extern crate noise;
+
+use noise::module::{Module, Perlin};
+use noise::noisegen::{gradient_coherent_noise3d, make_i32_range, NoiseQuality};
+
+/// Calculates the output value of a `Perlin` noise module one octave at a time.
+fn reference_perlin(perlin: &Perlin, x: f64, y: f64, z: f64) -> f64 {
+    let mut value = 0.0;
+    let mut cur_persistence = 1.0;
+    let mut x = x * perlin.frequency();
+    let mut y = y * perlin.frequency();
+    let mut z = z * perlin.frequency();
+
+    for cur_octave in 0..perlin.octave_count() {
+        let seed = perlin.seed() + cur_octave;
+        let signal = gradient_coherent_noise3d(make_i32_range(x), make_i32_range(y),
+                                               make_i32_range(z), seed, perlin.quality());
+        value += signal * cur_persistence;
+
+        x *= perlin.lacunarity();
+        y *= perlin.lacunarity();
+        z *= perlin.lacunarity();
+        cur_persistence *= perlin.persistence();
+    }
+
+    value
+}
+
+/// Returns the input values of a grid of sample points, including negative and
+/// very large coordinates.
+fn grid() -> Vec<(f64, f64, f64)> {
+    let mut points = Vec::new();
+    for k in 0..4 {
+        for j in 0..8 {
+            for i in 0..8 {
+                points.push((i as f64 * 0.731 - 2.9, j as f64 * 1.37 - 5.3, k as f64 * 0.419));
+            }
+        }
+    }
+    points.push((1.0e9, -3.0e9, 2.5e9));
+    points
+}
+
+#[test]
+fn perlin_matches_reference() {
+    let qualities = [NoiseQuality::Fast, NoiseQuality::Standard, NoiseQuality::Best];
+    // Octave counts on either side of the four-octave batches of the `simd`
+    // implementation.
+    for octave_count in 1..10 {
+        for &quality in &qualities {
+            let mut perlin = Perlin::new();
+            perlin.set_octave_count(octave_count);
+            perlin.set_quality(quality);
+            perlin.set_seed(1234);
+            perlin.set_persistence(0.55);
+            for &(x, y, z) in &grid() {
+                assert_eq!(perlin.get_value(x, y, z), reference_perlin(&perlin, x, y, z),
+                           "octave_count = {}, quality = {:?}, input = ({}, {}, {})",
+                           octave_count, quality, x, y, z);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+#[test]
+fn gradient_coherent_noise3d_x4_matches_scalar() {
+    use noise::noisegen::gradient_coherent_noise3d_x4;
+
+    let points = grid();
+    for chunk in points.chunks(4).filter(|chunk| chunk.len() == 4) {
+        let x = [chunk[0].0, chunk[1].0, chunk[2].0, chunk[3].0];
+        let y = [chunk[0].1, chunk[1].1, chunk[2].1, chunk[3].1];
+        let z = [chunk[0].2, chunk[1].2, chunk[2].2, chunk[3].2];
+        let seed = [0, 1, -7, 1 << 20];
+        let values = gradient_coherent_noise3d_x4(x, y, z, seed, NoiseQuality::Standard);
+        for i in 0..4 {
+            let expected = gradient_coherent_noise3d(x[i], y[i], z[i], seed[i],
+                                                     NoiseQuality::Standard);
+            assert_eq!(values[i], expected);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+mod common;
+
+use common::boxed_constant;
+use noise::module::{fold_constants, Abs, Add, BoxedModule, Constant, Min, Module, Multiply,
+                    Perlin, ScaleBias};
+
+/// (2 * 3 + min(1, -4)) * 0.5 + 0.25, which is 1.25.
+fn constant_subtree() -> BoxedModule {
+    let product: BoxedModule = Box::new(Multiply::new(boxed_constant(2.0), boxed_constant(3.0)));
+    let min: BoxedModule = Box::new(Min::new(boxed_constant(1.0), boxed_constant(-4.0)));
+    let sum: BoxedModule = Box::new(Add::new(product, min));
+    let mut scale_bias = ScaleBias::new(sum);
+    scale_bias.set_scale(0.5);
+    scale_bias.set_bias(0.25);
+    Box::new(scale_bias)
+}
+
+/// perlin + constant subtree.
+fn mixed_tree() -> BoxedModule {
+    let perlin: BoxedModule = Box::new(Perlin::new());
+    Box::new(Add::new(perlin, constant_subtree()))
+}
+
+#[test]
+fn folds_constant_subtree() {
+    let folded = fold_constants(constant_subtree());
+    let constant = folded.as_any().downcast_ref::<Constant>().unwrap();
+    assert_eq!(constant.const_value(), 1.25);
+}
+
+#[test]
+fn folds_clamped_scale_bias() {
+    let mut scale_bias = ScaleBias::new(boxed_constant(0.5));
+    scale_bias.set_scale(2.0);
+    scale_bias.set_bias(0.8);
+    scale_bias.set_output_clamp(Some((-1.0, 1.0)));
+    let tree: BoxedModule = Box::new(scale_bias);
+    assert_eq!(tree.get_value(0.0, 0.0, 0.0), 1.0);
+    let folded = fold_constants(tree);
+    let constant = folded.as_any().downcast_ref::<Constant>().unwrap();
+    assert_eq!(constant.const_value(), 1.0);
+}
+
+#[test]
+fn leaves_generator_subtree_intact() {
+    let folded = fold_constants(mixed_tree());
+    let add = folded.as_any().downcast_ref::<Add<BoxedModule, BoxedModule>>().unwrap();
+    assert_eq!(add.module1().config_hash(), Perlin::new().config_hash());
+    let constant = add.module2().as_any().downcast_ref::<Constant>().unwrap();
+    assert_eq!(constant.const_value(), 1.25);
+
+    // Subtrees beneath other noise modules are not walked.
+    let tree: BoxedModule = Box::new(Abs::new(constant_subtree()));
+    let hash = tree.config_hash();
+    assert_eq!(fold_constants(tree).config_hash(), hash);
+}
+
+#[test]
+fn output_unchanged() {
+    let tree = mixed_tree();
+    let folded = fold_constants(mixed_tree());
+    for i in 0..100 {
+        let (x, y, z) = (i as f64 * 0.173 - 8.0, i as f64 * 0.071, i as f64 * -0.029);
+        assert_eq!(folded.get_value(x, y, z), tree.get_value(x, y, z));
+    }
+}
@@ -0,0 +1,385 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Locks the output values of the generator noise modules.
+//!
+//! Each generator, in a few configurations and at a few seeds, is sampled at a
+//! fixed set of input values, and the output values are compared against
+//! values recorded from a known-good build.  See "Output stability" in the
+//! README for when these values may change.  After an intended change, print
+//! the new table with
+//!
+//! ```text
+//! cargo test --test golden -- --ignored --nocapture
+//! ```
+//!
+//! and replace `GOLDEN` with it.
+//!
+//! The tolerance is tiny rather than zero only so that last-bit differences in
+//! the platform's `sin`, `exp` and similar functions do not fail the tests.
+//! It is relative to the expected value where that is larger than one.
+
+#![cfg(not(feature = "old-noise-version"))]
+
+extern crate noise;
+
+use noise::module::{Axis, Billow, BoxedModule, Checkerboard, Constant, Cylinders, Falloff,
+                    Module, Perlin, PointField, RadialGradient, RidgedMulti, Sine, Spheres,
+                    Voronoi};
+use noise::noisegen::{Dimensions, NoiseQuality};
+
+const TOLERANCE: f64 = 1e-12;
+
+const SEEDS: [i32; 3] = [0, 1234, -77];
+
+const POINTS: [(f64, f64, f64); 6] = [
+    (0.0, 0.0, 0.0),
+    (0.3, -1.7, 2.45),
+    (-12.75, 4.1, -0.6),
+    (105.3, 88.8, -71.2),
+    (1000000.25, -350000.5, 7.75),
+    (-0.001, 0.5, 0.999),
+];
+
+/// Returns the configurations of the generators that have a seed.
+fn seeded_cases(seed: i32) -> Vec<(String, BoxedModule)> {
+    let mut cases: Vec<(&str, BoxedModule)> = Vec::new();
+
+    let mut perlin = Perlin::new();
+    perlin.set_seed(seed);
+    cases.push(("perlin", Box::new(perlin.clone())));
+    let mut m = perlin.clone();
+    m.set_quality(NoiseQuality::Fast);
+    m.set_octave_count(3);
+    cases.push(("perlin/fast", Box::new(m)));
+    let mut m = perlin.clone();
+    m.set_quality(NoiseQuality::Best);
+    m.set_frequency(2.5);
+    m.set_lacunarity(1.9);
+    m.set_persistence(0.6);
+    cases.push(("perlin/best", Box::new(m)));
+    let mut m = perlin.clone();
+    m.set_dimensions(Dimensions::Dim2);
+    cases.push(("perlin/2d", Box::new(m)));
+    let mut m = perlin.clone();
+    m.set_octave_rotation(true);
+    m.set_octave_offset(true);
+    cases.push(("perlin/rotation+offset", Box::new(m)));
+
+    let mut billow = Billow::new();
+    billow.set_seed(seed);
+    cases.push(("billow", Box::new(billow.clone())));
+    let mut m = billow.clone();
+    m.set_normalize(true);
+    m.set_dimensions(Dimensions::Dim2);
+    cases.push(("billow/normalize+2d", Box::new(m)));
+
+    let mut ridged = RidgedMulti::new();
+    ridged.set_seed(seed);
+    cases.push(("ridged_multi", Box::new(ridged.clone())));
+    let mut m = ridged.clone();
+    m.set_normalize(true);
+    m.set_dimensions(Dimensions::Dim2);
+    cases.push(("ridged_multi/normalize+2d", Box::new(m)));
+
+    let mut voronoi = Voronoi::new();
+    voronoi.set_seed(seed);
+    cases.push(("voronoi", Box::new(voronoi.clone())));
+    let mut m = voronoi.clone();
+    m.enable_distance(true);
+    m.set_distance_falloff(Falloff::Quadratic);
+    m.set_jitter(0.7);
+    cases.push(("voronoi/distance", Box::new(m)));
+    let mut m = voronoi.clone();
+    m.set_fast(true);
+    m.set_frequency(3.0);
+    cases.push(("voronoi/fast", Box::new(m)));
+    let mut m = voronoi.clone();
+    m.set_smooth(true);
+    m.enable_distance(true);
+    m.set_smooth_k(0.4);
+    cases.push(("voronoi/smooth", Box::new(m)));
+
+    cases.into_iter().map(|(name, module)| (format!("{}/seed={}", name, seed), module)).collect()
+}
+
+/// Returns the configurations of every generator.
+fn cases() -> Vec<(String, BoxedModule)> {
+    let mut cases = Vec::new();
+    for &seed in &SEEDS {
+        cases.extend(seeded_cases(seed));
+    }
+
+    let mut constant = Constant::new();
+    constant.set_const_value(0.625);
+    cases.push(("constant".to_string(), Box::new(constant) as BoxedModule));
+    cases.push(("checkerboard".to_string(), Box::new(Checkerboard)));
+    let mut cylinders = Cylinders::new();
+    cylinders.set_frequency(1.5);
+    cylinders.set_offset(0.2);
+    cases.push(("cylinders".to_string(), Box::new(cylinders)));
+    let mut spheres = Spheres::new();
+    spheres.set_frequency(0.75);
+    spheres.set_signed_distance(true);
+    cases.push(("spheres".to_string(), Box::new(spheres)));
+    let mut sine = Sine::new();
+    sine.set_frequency(0.3);
+    sine.set_phase(0.5);
+    sine.set_axis(Axis::Y);
+    cases.push(("sine".to_string(), Box::new(sine)));
+    let mut radial_gradient = RadialGradient::new();
+    radial_gradient.set_center(1.0, -2.0, 0.5);
+    radial_gradient.set_radius(20.0);
+    radial_gradient.set_falloff(Falloff::Smoothstep);
+    cases.push(("radial_gradient".to_string(), Box::new(radial_gradient)));
+    let mut point_field = PointField::new();
+    point_field.add_point(0.0, 0.0, 0.0);
+    point_field.add_point(-12.0, 4.0, 0.0);
+    point_field.set_radius(3.0);
+    point_field.set_falloff(Falloff::Smoothstep);
+    cases.push(("point_field".to_string(), Box::new(point_field)));
+
+    cases
+}
+
+fn sample(module: &dyn Module) -> Vec<f64> {
+    POINTS.iter().map(|&(x, y, z)| module.get_value(x, y, z)).collect()
+}
+
+#[test]
+fn generators_match_golden_values() {
+    let cases = cases();
+    let names: Vec<&str> = cases.iter().map(|(name, _)| name.as_str()).collect();
+    let golden_names: Vec<&str> = GOLDEN.iter().map(|&(name, _)| name).collect();
+    assert_eq!(names, golden_names, "the cases do not match the golden table");
+
+    let mut mismatches = Vec::new();
+    for ((name, module), (_, expected)) in cases.iter().zip(GOLDEN) {
+        for (i, (actual, &expected)) in sample(&**module).into_iter().zip(expected).enumerate() {
+            let matches = (actual - expected).abs() <= TOLERANCE * expected.abs().max(1.0);
+            if !matches {
+                mismatches.push(format!("{} at {:?}: expected {:?}, got {:?}",
+                                        name, POINTS[i], expected, actual));
+            }
+        }
+    }
+    assert!(mismatches.is_empty(), "output values drifted:\n{}", mismatches.join("\n"));
+}
+
+/// Prints the golden table for the current build.
+#[test]
+#[ignore]
+fn print_golden_values() {
+    println!("const GOLDEN: &[(&str, [f64; {}])] = &[", POINTS.len());
+    for (name, module) in cases() {
+        println!("    ({:?}, [", name);
+        let values: Vec<String> = sample(&*module).iter().map(|value| format!("{:?}", value))
+            .collect();
+        for line in values.chunks(3) {
+            println!("        {},", line.join(", "));
+        }
+        println!("    ]),");
+    }
+    println!("];");
+}
+
+const GOLDEN: &[(&str, [f64; 6])] = &[
+    ("perlin/seed=0", [
+        0.0, -0.420344574574508, 0.13316569097795478,
+        -1.1182608999569956, -0.413079370703125, -0.20089624558177666,
+    ]),
+    ("perlin/fast/seed=0", [
+        0.0, -0.44031411259984016, 0.030307590596000078,
+        -1.0914451982246314, -0.4514325085, -0.1977064907114759,
+    ]),
+    ("perlin/best/seed=0", [
+        0.0, 0.687302606657447, 0.6705313965273364,
+        0.43117758592684124, 0.7772300849656185, 0.07768698506986939,
+    ]),
+    ("perlin/2d/seed=0", [
+        0.0, -0.036988387661376215, -0.4083559710866282,
+        -0.12753038760855195, -0.6352449365876424, -0.3311739206604332,
+    ]),
+    ("perlin/rotation+offset/seed=0", [
+        -0.17113379037734225, -0.5038681124667116, -0.1801840871070955,
+        0.36456254541157956, -0.3276581969128748, 0.9349789283184734,
+    ]),
+    ("billow/seed=0", [
+        -1.46875, 0.3517157389520651, -0.44656003961793045,
+        1.2421741163955957, -0.6425912585937499, -1.0627148945647429,
+    ]),
+    ("billow/normalize+2d/seed=0", [
+        -0.5, -0.05210080907690673, -0.06298139881044995,
+        -0.015230623941685617, 0.14532818954935095, -0.16147512867932357,
+    ]),
+    ("ridged_multi/seed=0", [
+        1.4609375, -0.6799938537729532, 0.40896324582428867,
+        -0.9269645686869163, 0.5685143535606605, 1.0023992764759289,
+    ]),
+    ("ridged_multi/normalize+2d/seed=0", [
+        1.0, 0.19943466149775313, 0.15681495475141993,
+        0.1273334984766561, -0.23227712745324647, 0.38365655662357967,
+    ]),
+    ("voronoi/seed=0", [
+        0.37672762479633093, -0.9360209768638015, 0.5796209489926696,
+        0.7535699093714356, 0.573128524236381, -0.2817909838631749,
+    ]),
+    ("voronoi/distance/seed=0", [
+        0.6544909087308941, -1.6901957488113797, -0.2946912259990978,
+        1.1936248280495374, 0.57529035914083, -1.2668580838288235,
+    ]),
+    ("voronoi/fast/seed=0", [
+        0.37672762479633093, 0.8396361591294408, -0.5108049036934972,
+        -0.8745378600433469, 0.2125166179612279, 0.778416377492249,
+    ]),
+    ("voronoi/smooth/seed=0", [
+        0.006178375816364268, -0.8615251811999318, 0.34570218990829726,
+        0.679483184309633, 0.4828684706677889, -0.8939314291752158,
+    ]),
+    ("perlin/seed=1234", [
+        0.0, -0.6437580698559562, 0.0728747365060773,
+        0.21200702271110683, 0.7644004213779296, -0.6084589711850436,
+    ]),
+    ("perlin/fast/seed=1234", [
+        0.0, -0.6040335342228399, 0.07197363889191999,
+        0.4211285335385657, 0.5983075361875001, -0.6074015043458648,
+    ]),
+    ("perlin/best/seed=1234", [
+        0.0, -0.5898134850238761, -0.286435750491806,
+        0.47800583939797614, 0.37702467214287944, 0.6833768719999223,
+    ]),
+    ("perlin/2d/seed=1234", [
+        0.0, -0.05612171297980308, -0.030132824059590328,
+        0.2852282204171958, 0.2539641052020843, 0.2507879564385486,
+    ]),
+    ("perlin/rotation+offset/seed=1234", [
+        -0.3202429146646519, 0.631309363641415, 0.8860438628338038,
+        0.22961852585415293, 0.2733070346131299, 0.025972240170354387,
+    ]),
+    ("billow/seed=1234", [
+        -1.46875, -0.18123386028808786, -0.6028967765900475,
+        0.22497047875824183, 0.06005084275585926, -0.2185350154442237,
+    ]),
+    ("billow/normalize+2d/seed=1234", [
+        -0.5, -0.25334473712793437, 0.018478601596727817,
+        0.51006927047654, -0.1254830931651707, -0.24009308067346813,
+    ]),
+    ("ridged_multi/seed=1234", [
+        1.4609375, 0.2196502972388097, 0.45214653180765607,
+        -0.34050573757525404, -0.3734479015537707, -0.19123052822272002,
+    ]),
+    ("ridged_multi/normalize+2d/seed=1234", [
+        1.0, 0.5425961673521704, 0.03267168641496743,
+        -0.742872175340983, 0.3356318057368535, 0.5438663105284574,
+    ]),
+    ("voronoi/seed=1234", [
+        0.0886096628382802, -0.9360209768638015, -0.7589958691969514,
+        0.7289909413084388, 0.573128524236381, -0.9750016564503312,
+    ]),
+    ("voronoi/distance/seed=1234", [
+        -0.16536219788445417, -1.385539843580884, 1.3552526225826211,
+        0.9397751190027375, 0.6451085783359034, -1.4577872795892035,
+    ]),
+    ("voronoi/fast/seed=1234", [
+        0.0886096628382802, -0.7338162111118436, -0.5788337616249919,
+        -0.542489162646234, 0.7709597097709775, -0.3487995648756623,
+    ]),
+    ("voronoi/smooth/seed=1234", [
+        -0.02507774406613092, -1.273805354347358, -0.038659346562482044,
+        0.3084700703890576, 0.252723155723905, -1.523878202818313,
+    ]),
+    ("perlin/seed=-77", [
+        0.0, 0.11744738825637674, 0.555601398398356,
+        0.2949575517518369, -0.45482975179931645, -0.10298026538377765,
+    ]),
+    ("perlin/fast/seed=-77", [
+        0.0, 0.06279926819628001, 0.46301687090679966,
+        0.33032925364160093, -0.45761011065625007, -0.10385662237244887,
+    ]),
+    ("perlin/best/seed=-77", [
+        0.0, 0.15567520473239085, -0.45916331211915357,
+        -0.5281406900284358, 0.3708863895513235, 0.3151076967004364,
+    ]),
+    ("perlin/2d/seed=-77", [
+        0.0, -0.5092773092049891, -0.35340645323256087,
+        0.3985866365597632, -0.5971171458090743, -0.1358154597179535,
+    ]),
+    ("perlin/rotation+offset/seed=-77", [
+        0.06612472402875147, 0.2076661293330893, 0.39365394572459,
+        0.0062720099551026864, -0.02787511405508642, 0.13867218014830587,
+    ]),
+    ("billow/seed=-77", [
+        -1.46875, 0.25886251341159744, -0.2464855600261533,
+        -0.09817297246683565, -0.559090496401367, -1.2359255694421762,
+    ]),
+    ("billow/normalize+2d/seed=-77", [
+        -0.5, 0.17747646657373561, 0.0566205205941816,
+        -0.06772138573500497, 0.10659519574255172, -0.3546143463544491,
+    ]),
+    ("ridged_multi/seed=-77", [
+        1.4609375, -0.37921019747331874, 0.10240817545698588,
+        -0.10462992243022062, 0.38421430109616383, 1.184377725915871,
+    ]),
+    ("ridged_multi/normalize+2d/seed=-77", [
+        1.0, -0.2642667671460327, -0.046964885623452135,
+        0.1965351167895213, -0.1950414089913085, 0.7283116667681786,
+    ]),
+    ("voronoi/seed=-77", [
+        -0.06378129217773676, 0.10697379242628813, 0.9837503200396895,
+        -0.2522633755579591, -0.961147197522223, -0.9750016564503312,
+    ]),
+    ("voronoi/distance/seed=-77", [
+        0.1192145496207544, 0.2063139225478856, 0.5920271231399694,
+        -0.3296170680186181, 1.080143862345848, -1.1572215133340114,
+    ]),
+    ("voronoi/fast/seed=-77", [
+        0.559146617539227, 0.5683314492926002, 0.21845379378646612,
+        -0.8745378600433469, 0.34921475406736135, -0.3487995648756623,
+    ]),
+    ("voronoi/smooth/seed=-77", [
+        -0.515179238759293, -0.1664955705682803, 0.7156572588924703,
+        -0.614085910216367, -0.897368942578899, -1.3237160460073745,
+    ]),
+    ("constant", [
+        0.625, 0.625, 0.625,
+        0.625, 0.625, 0.625,
+    ]),
+    ("checkerboard", [
+        1.0, 1.0, 1.0,
+        -1.0, 1.0, -1.0,
+    ]),
+    ("cylinders", [
+        0.20000000000000018, -0.9902059433630193, 0.7846590382173133,
+        -0.8736392455163013, 0.29981981217861176, -0.19400300300225126,
+    ]),
+    ("spheres", [
+        0.0, 0.33041524746247514, 0.0731016886999285,
+        0.3912902397752494, 0.07285573938861489, -0.21619323468858406,
+    ]),
+    ("sine", [
+        0.479425538604203, -0.42337563682715246, 0.9307505142752348,
+        -0.9817863251701385, -0.4281799453013902, 0.9917784677003418,
+    ]),
+    ("radial_gradient", [
+        0.9272646305996295, 0.9388497550926623, -0.696732480997923,
+        -1.0, -1.0, 0.8977568260894696,
+    ]),
+    ("point_field", [
+        0.0, 0.9999971634322211, 0.2441312356240386,
+        1.0, 1.0, 0.31272710537860415,
+    ]),
+];
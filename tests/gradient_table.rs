@@ -0,0 +1,130 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Perlin};
+use noise::noisegen::{gradient_coherent_noise3d, gradient_coherent_noise3d_custom,
+                      gradient_coherent_noise3d_table, gradient_coherent_noise3d_with_gradient,
+                      gradient_coherent_noise3d_with_gradient_custom, GradientTable,
+                      NoiseQuality, PermTable};
+
+fn axis_gradients() -> GradientTable {
+    GradientTable::new(&[[1.0, 0.0, 0.0], [-1.0, 0.0, 0.0],
+                         [0.0, 1.0, 0.0], [0.0, -1.0, 0.0],
+                         [0.0, 0.0, 1.0], [0.0, 0.0, -1.0]])
+}
+
+/// Counts the unit segments along the `x` axis, out of `count`, in which the
+/// noise is zero at the centre of the segment.
+fn flat_segments<F: Fn(f64, f64, f64) -> f64>(noise: F, count: i32) -> usize {
+    (0..count).filter(|&i| noise(i as f64 + 0.5, 0.0, 0.0) == 0.0).count()
+}
+
+#[test]
+fn standard_table_matches_default_noise() {
+    let standard = GradientTable::standard();
+    let perm_table = PermTable::new(5);
+    for i in 0..200 {
+        let (x, y, z) = (i as f64 * 0.37 - 20.0, i as f64 * 0.13, i as f64 * -0.29);
+        for &quality in &[NoiseQuality::Fast, NoiseQuality::Standard, NoiseQuality::Best] {
+            assert_eq!(gradient_coherent_noise3d_custom(x, y, z, &standard, None, 7, quality),
+                       gradient_coherent_noise3d(x, y, z, 7, quality));
+            assert_eq!(gradient_coherent_noise3d_custom(x, y, z, &standard, Some(&perm_table), 7,
+                                                        quality),
+                       gradient_coherent_noise3d_table(x, y, z, &perm_table, 7, quality));
+            assert_eq!(gradient_coherent_noise3d_with_gradient_custom(x, y, z, &standard, None, 7,
+                                                                      quality),
+                       gradient_coherent_noise3d_with_gradient(x, y, z, 7, quality));
+        }
+    }
+
+    let mut perlin = Perlin::new();
+    perlin.set_gradient_table(Some(standard));
+    assert_eq!(perlin.get_value(0.3, 0.5, 0.7), Perlin::new().get_value(0.3, 0.5, 0.7));
+}
+
+#[test]
+fn axis_gradients_alter_character() {
+    // Along a lattice line parallel to the `x` axis, only gradient vectors
+    // along the `x` axis contribute to the noise, so with axis-aligned
+    // gradient vectors the noise is exactly zero wherever neither end of the
+    // segment has one; about (4 / 6)^2 of the segments.  With the standard
+    // gradient vectors the noise is almost never zero.
+    //
+    // The hash of older versions of libnoise is correlated along the `x` axis,
+    // so the gradient vectors of neighbouring lattice points are not
+    // independent and the fraction of flat segments is only roughly 4 / 9.
+    let axes = axis_gradients();
+    let quality = NoiseQuality::Standard;
+    let count = 10000;
+    let axis_flat = flat_segments(|x, y, z| {
+        gradient_coherent_noise3d_custom(x, y, z, &axes, None, 0, quality)
+    }, count);
+    let standard_flat = flat_segments(|x, y, z| gradient_coherent_noise3d(x, y, z, 0, quality),
+                                      count);
+    assert_flat_fraction(axis_flat, count);
+    assert_eq!(standard_flat, 0);
+
+    // The same holds for a single octave of Perlin noise.
+    let mut perlin = Perlin::new();
+    perlin.set_octave_count(1);
+    perlin.set_gradient_table(Some(axes));
+    let perlin_flat = flat_segments(|x, y, z| perlin.get_value(x, y, z), count);
+    assert_flat_fraction(perlin_flat, count);
+}
+
+/// Asserts that about 4 / 9 of `count` segments are flat.
+fn assert_flat_fraction(flat: usize, count: i32) {
+    #[cfg(not(feature = "old-noise-version"))]
+    let tolerance = 0.03;
+    #[cfg(feature = "old-noise-version")]
+    let tolerance = 0.1;
+    assert!((flat as f64 / count as f64 - 4.0 / 9.0).abs() < tolerance, "{}", flat);
+}
+
+#[test]
+fn custom_gradients_keep_analytic_gradient_and_bounds() {
+    let mut perlin = Perlin::new();
+    perlin.set_gradient_table(Some(GradientTable::new(&[[2.0, 0.0, 0.0], [0.0, -1.0, 0.0],
+                                                        [0.0, 0.0, 1.0], [0.6, 0.8, 0.0]])));
+    let bound = perlin.max_gradient_estimate().unwrap();
+    assert!(bound > 1.9 * Perlin::new().max_gradient_estimate().unwrap());
+
+    let h = 1e-6;
+    for i in 0..50 {
+        let (x, y, z) = (i as f64 * 0.31 - 7.0, i as f64 * 0.17 + 0.05, i as f64 * -0.23 + 0.1);
+        let (value, gradient) = perlin.get_value_and_gradient(x, y, z);
+        assert_eq!(value, perlin.get_value(x, y, z));
+        let numeric = [(perlin.get_value(x + h, y, z) - perlin.get_value(x - h, y, z)) / (2.0 * h),
+                       (perlin.get_value(x, y + h, z) - perlin.get_value(x, y - h, z)) / (2.0 * h),
+                       (perlin.get_value(x, y, z + h) - perlin.get_value(x, y, z - h)) / (2.0 * h)];
+        for axis in 0..3 {
+            assert!((gradient[axis] - numeric[axis]).abs() < 1e-3 * (1.0 + numeric[axis].abs()));
+            assert!(gradient[axis].abs() <= bound);
+        }
+    }
+
+    // The gradient vectors are part of the configuration.
+    assert!(perlin.config_hash() != Perlin::new().config_hash());
+}
+
+#[test]
+#[should_panic]
+fn empty_table_panics() {
+    GradientTable::new(&[]);
+}
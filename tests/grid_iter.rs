@@ -0,0 +1,45 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::Perlin;
+use noise::noise_map::NoiseMap;
+use noise::sampling::grid_iter;
+
+#[test]
+fn matches_noise_map() {
+    let perlin = Perlin::new();
+    let map = NoiseMap::from_module(&perlin, (-1.5, 0.25), (0.1, 0.3), 13, 7);
+    let samples: Vec<((usize, usize), f64)> =
+        grid_iter(&perlin, (-1.5, 0.25), (0.1, 0.3), (13, 7)).collect();
+    assert_eq!(samples.len(), 13 * 7);
+    for (i, &((ix, iy), value)) in samples.iter().enumerate() {
+        assert_eq!((ix, iy), (i % 13, i / 13));
+        assert_eq!(value, map.get_value(ix, iy));
+    }
+}
+
+#[test]
+fn reports_exact_size() {
+    let perlin = Perlin::new();
+    let mut iter = grid_iter(&perlin, (0.0, 0.0), (1.0, 1.0), (4, 3));
+    assert_eq!(iter.len(), 12);
+    iter.next();
+    assert_eq!(iter.len(), 11);
+    assert_eq!(grid_iter(&perlin, (0.0, 0.0), (1.0, 1.0), (0, 5)).count(), 0);
+}
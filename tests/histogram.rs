@@ -0,0 +1,58 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::analysis::histogram;
+use noise::module::{Constant, Perlin};
+
+#[test]
+fn constant_fills_one_bin() {
+    let mut constant = Constant::new();
+    constant.set_const_value(0.3);
+    let bounds = ((-4.0, -4.0), (4.0, 4.0));
+
+    let counts = histogram(&constant, bounds, (10, 20), 5, None);
+    assert_eq!(counts, vec![200, 0, 0, 0, 0]);
+
+    let counts = histogram(&constant, bounds, (10, 20), 5, Some((-1.0, 1.0)));
+    assert_eq!(counts, vec![0, 0, 0, 200, 0]);
+}
+
+#[test]
+fn observed_range_counts_every_sample() {
+    let bounds = ((0.0, 0.0), (8.0, 8.0));
+    let counts = histogram(&Perlin::new(), bounds, (32, 32), 16, None);
+    assert_eq!(counts.iter().sum::<u64>(), 32 * 32);
+    // The smallest and largest output values fall into the end bins.
+    assert!(counts[0] > 0);
+    assert!(counts[15] > 0);
+}
+
+#[test]
+fn values_outside_the_range_are_not_counted() {
+    let mut constant = Constant::new();
+    constant.set_const_value(2.0);
+    let counts = histogram(&constant, ((0.0, 0.0), (1.0, 1.0)), (4, 4), 4, Some((-1.0, 1.0)));
+    assert_eq!(counts, vec![0; 4]);
+}
+
+#[test]
+#[should_panic]
+fn zero_bins_panics() {
+    histogram(&Perlin::new(), ((0.0, 0.0), (1.0, 1.0)), (4, 4), 0, None);
+}
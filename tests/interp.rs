@@ -0,0 +1,77 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Blend, FnModule, Interp, Module, Select};
+
+const H: f64 = 0.01;
+
+/// Returns the second difference of the output value along the `x` axis at
+/// `x`, with a step of `H`.
+fn second_difference<M: Module>(module: &M, x: f64) -> f64 {
+    module.get_value(x - H, 0.0, 0.0) - 2.0 * module.get_value(x, 0.0, 0.0)
+        + module.get_value(x + H, 0.0, 0.0)
+}
+
+/// Returns the magnitude of the second difference at the start of a
+/// transition for each easing curve, in the order linear, cubic, quintic.
+fn second_differences<M: Module, F: Fn(Interp) -> M>(make_module: F, x: f64) -> Vec<f64> {
+    [Interp::Linear, Interp::Cubic, Interp::Quintic].iter()
+        .map(|&interp| second_difference(&make_module(interp), x).abs())
+        .collect()
+}
+
+/// Checks that each easing curve bends the output value at least ten times
+/// less sharply than the previous one.
+fn assert_smoother(differences: &[f64]) {
+    assert!(differences[1] * 10.0 < differences[0], "{:?}", differences);
+    assert!(differences[2] * 10.0 < differences[1], "{:?}", differences);
+}
+
+#[test]
+fn blend_second_differences() {
+    let make_blend = |interp| {
+        let mut blend = Blend::new(FnModule::new(|_: f64, _: f64, _: f64| 0.0),
+                                   FnModule::new(|_: f64, _: f64, _: f64| 1.0),
+                                   FnModule::new(|x: f64, _: f64, _: f64| x));
+        // The weight is only clamped with a custom control range.
+        blend.set_control_range(0.0, 2.0);
+        blend.set_interp(interp);
+        blend
+    };
+    // The transition starts at a control value of 0.
+    assert_smoother(&second_differences(make_blend, 0.0));
+    // The linear transition does not bend away from its ends.
+    assert!(second_difference(&make_blend(Interp::Linear), 0.3).abs() < 1e-12);
+}
+
+#[test]
+fn select_second_differences() {
+    let make_select = |interp| {
+        let mut select = Select::new(FnModule::new(|_: f64, _: f64, _: f64| 0.0),
+                                     FnModule::new(|_: f64, _: f64, _: f64| 1.0),
+                                     FnModule::new(|x: f64, _: f64, _: f64| x));
+        select.set_bounds(-0.5, 0.5);
+        select.set_edge_falloff(0.25);
+        select.set_interp(interp);
+        select
+    };
+    // The transitions start at control values of -0.75 and +0.25.
+    assert_smoother(&second_differences(make_select, -0.75));
+    assert_smoother(&second_differences(make_select, 0.25));
+}
@@ -0,0 +1,108 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Checks that the estimated bounds on the gradient are never exceeded by the
+//! slopes between nearby sample points.
+
+extern crate noise;
+
+use noise::module::{Abs, BoxedModule, Constant, Module, Perlin};
+use noise::noisegen::NoiseQuality;
+
+/// Returns the steepest slope found between neighbouring points of a sample
+/// grid, along each axis and along the diagonals.
+fn steepest_slope<M: Module>(module: &M, step: f64) -> f64 {
+    let offsets = [(step, 0.0, 0.0), (0.0, step, 0.0), (0.0, 0.0, step), (step, step, step),
+                   (step, -step, 0.0)];
+    let mut steepest: f64 = 0.0;
+    for k in 0..8 {
+        for j in 0..24 {
+            for i in 0..24 {
+                let (x, y, z) = (i as f64 * 0.173 - 2.0, j as f64 * 0.151 - 1.5, k as f64 * 0.37);
+                let value = module.get_value(x, y, z);
+                for &(dx, dy, dz) in &offsets {
+                    let dist = (dx * dx + dy * dy + dz * dz).sqrt();
+                    let slope = (module.get_value(x + dx, y + dy, z + dz) - value).abs() / dist;
+                    steepest = steepest.max(slope);
+                }
+            }
+        }
+    }
+    steepest
+}
+
+fn assert_bounded<M: Module>(module: &M) {
+    let bound = module.max_gradient_estimate().unwrap();
+    let steepest = steepest_slope(module, 1e-4);
+    assert!(steepest > 0.0);
+    assert!(steepest <= bound, "steepest slope {} exceeds bound {}", steepest, bound);
+}
+
+#[test]
+fn constant_has_zero_gradient() {
+    let mut constant = Constant::new();
+    constant.set_const_value(0.75);
+    assert_eq!(constant.max_gradient_estimate(), Some(0.0));
+    assert_eq!(steepest_slope(&constant, 1e-4), 0.0);
+}
+
+#[test]
+fn perlin_default() {
+    assert_bounded(&Perlin::new());
+}
+
+#[test]
+fn perlin_qualities() {
+    for &quality in &[NoiseQuality::Fast, NoiseQuality::Standard, NoiseQuality::Best] {
+        let mut perlin = Perlin::new();
+        perlin.set_quality(quality);
+        perlin.set_octave_count(1);
+        assert_bounded(&perlin);
+    }
+}
+
+#[test]
+fn perlin_parameters() {
+    let mut perlin = Perlin::new();
+    perlin.set_frequency(-3.0);
+    perlin.set_lacunarity(2.5);
+    perlin.set_persistence(0.7);
+    perlin.set_octave_count_f(3.5);
+    assert_bounded(&perlin);
+
+    let bound = perlin.max_gradient_estimate().unwrap();
+    perlin.set_normalize(true);
+    assert!(perlin.max_gradient_estimate().unwrap() < bound);
+    assert_bounded(&perlin);
+
+    perlin.set_octave_rotation(true);
+    assert_bounded(&perlin);
+}
+
+#[test]
+fn perlin_bound_grows_with_frequency() {
+    let mut perlin = Perlin::new();
+    let bound = perlin.max_gradient_estimate().unwrap();
+    perlin.set_frequency(2.0);
+    assert!((perlin.max_gradient_estimate().unwrap() - 2.0 * bound).abs() < 1e-9 * bound);
+}
+
+#[test]
+fn unknown_bound() {
+    let boxed: BoxedModule = Box::new(Abs::new(Perlin::new()));
+    assert_eq!(boxed.max_gradient_estimate(), None);
+}
@@ -0,0 +1,57 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Mirror, Module, Perlin};
+
+const POINTS: [(f64, f64, f64); 4] = [(0.3, 0.5, 0.7), (1.7, -2.25, 0.1), (-4.5, 3.3, -1.2),
+                                      (10.5, -4.25, 3.0)];
+
+#[test]
+fn mirror_x_is_symmetric() {
+    let mut mirror = Mirror::new(Perlin::new());
+    mirror.set_mirror_x(true);
+    for &(x, y, z) in &POINTS {
+        assert_eq!(mirror.get_value(-x, y, z), mirror.get_value(x, y, z));
+    }
+}
+
+#[test]
+fn mirrored_axes_use_positive_half() {
+    let perlin = Perlin::new();
+    let mut mirror = Mirror::new(perlin.clone());
+    mirror.set_mirror_y(true);
+    mirror.set_mirror_z(true);
+    for &(x, y, z) in &POINTS {
+        let x = -x.abs();
+        assert_eq!(mirror.get_value(x, -y, -z), perlin.get_value(x, y.abs(), z.abs()));
+        assert_eq!(mirror.get_value(x, y, z), mirror.get_value(x, -y, -z));
+    }
+}
+
+#[test]
+fn disabled_by_default() {
+    let perlin = Perlin::new();
+    let mirror = Mirror::new(perlin.clone());
+    for &(x, y, z) in &POINTS {
+        assert_eq!(mirror.get_value(-x, -y, -z), perlin.get_value(-x, -y, -z));
+    }
+    // Perlin noise is not symmetric, so the test above would notice mirroring.
+    let (x, y, z) = POINTS[0];
+    assert!(perlin.get_value(-x, y, z) != perlin.get_value(x, y, z));
+}
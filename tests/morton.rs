@@ -0,0 +1,93 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::FnModule;
+use noise::sampling::{morton_decode, morton_encode, sample_morton, MORTON_MAX_BITS};
+
+/// Reference Morton encoding, interleaving the bits one at a time.
+fn reference_encode(i: u32, j: u32, k: u32) -> u64 {
+    let mut index = 0;
+    for bit in 0..MORTON_MAX_BITS {
+        index |= ((i as u64 >> bit) & 1) << (3 * bit);
+        index |= ((j as u64 >> bit) & 1) << (3 * bit + 1);
+        index |= ((k as u64 >> bit) & 1) << (3 * bit + 2);
+    }
+    index
+}
+
+/// Reference Morton decoding, gathering the bits one at a time.
+fn reference_decode(index: u64) -> (u32, u32, u32) {
+    let (mut i, mut j, mut k) = (0, 0, 0);
+    for bit in 0..MORTON_MAX_BITS {
+        i |= ((index >> (3 * bit)) & 1) << bit;
+        j |= ((index >> (3 * bit + 1)) & 1) << bit;
+        k |= ((index >> (3 * bit + 2)) & 1) << bit;
+    }
+    (i as u32, j as u32, k as u32)
+}
+
+#[test]
+fn decode_matches_reference() {
+    for index in 0..4096 {
+        assert_eq!(morton_decode(index), reference_decode(index));
+    }
+    let mut index: u64 = 0x123456789abcdef;
+    for _ in 0..1000 {
+        index = index.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let index = index >> 1;
+        assert_eq!(morton_decode(index), reference_decode(index));
+    }
+}
+
+#[test]
+fn encode_matches_reference() {
+    let max = (1 << MORTON_MAX_BITS) - 1;
+    for &(i, j, k) in &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (0, 0, 1), (5, 3, 7), (max, 0, max),
+                        (max, max, max), (123456, 654321, 1048576)] {
+        let index = morton_encode(i, j, k);
+        assert_eq!(index, reference_encode(i, j, k));
+        assert_eq!(morton_decode(index), (i, j, k));
+    }
+}
+
+#[test]
+fn samples_in_morton_order() {
+    // Encode the input value in the output value, to check where each sample
+    // was taken.
+    let position = FnModule::new(|x: f64, y: f64, z: f64| x + 100.0 * y + 10000.0 * z);
+    let (origin, step) = ((1.0, 2.0, 3.0), (1.0, 2.0, 4.0));
+    let bits = 3;
+
+    let samples: Vec<(u64, f64)> = sample_morton(&position, origin, step, bits).collect();
+    assert_eq!(samples.len(), 1 << (3 * bits));
+    for (n, &(index, value)) in samples.iter().enumerate() {
+        assert_eq!(index, n as u64);
+        let (i, j, k) = reference_decode(index);
+        let (x, y, z) = (origin.0 + i as f64 * step.0, origin.1 + j as f64 * step.1,
+                         origin.2 + k as f64 * step.2);
+        assert_eq!(value, x + 100.0 * y + 10000.0 * z);
+    }
+}
+
+#[test]
+#[should_panic]
+fn too_many_bits_panics() {
+    let position = FnModule::new(|x: f64, _y: f64, _z: f64| x);
+    sample_morton(&position, (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), MORTON_MAX_BITS + 1);
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+mod common;
+
+use common::boxed_constant;
+use noise::module::{FnModule, Interp, Module, MultiSelect};
+
+/// A control module whose output value is the `x` coordinate of the input
+/// value.
+type XControl = FnModule<fn(f64, f64, f64) -> f64>;
+
+fn x_control() -> XControl {
+    fn x_coord(x: f64, _y: f64, _z: f64) -> f64 {
+        x
+    }
+    FnModule::new(x_coord as fn(f64, f64, f64) -> f64)
+}
+
+/// Bands meeting at -0.5, 0.0 and 0.5, outputting 10, 20, 30 and 40.
+fn four_bands() -> MultiSelect<XControl> {
+    let mut select = MultiSelect::new(x_control());
+    // Added out of order; the bands are sorted by their upper bounds.
+    select.add_band(0.5, boxed_constant(30.0));
+    select.add_band(-0.5, boxed_constant(10.0));
+    select.add_band(1.0, boxed_constant(40.0));
+    select.add_band(0.0, boxed_constant(20.0));
+    select
+}
+
+#[test]
+fn no_bands_outputs_zero() {
+    let select = MultiSelect::new(x_control());
+    assert_eq!(select.get_value(0.3, 0.0, 0.0), 0.0);
+    assert_eq!(select.source_count(), 1);
+}
+
+#[test]
+fn band_boundaries() {
+    let select = four_bands();
+    let bounds: Vec<f64> = select.bands().iter().map(|band| band.0).collect();
+    assert_eq!(bounds, vec![-0.5, 0.0, 0.5, 1.0]);
+
+    assert_eq!(select.get_value(-100.0, 0.0, 0.0), 10.0);
+    assert_eq!(select.get_value(-0.500001, 0.0, 0.0), 10.0);
+    // A control value equal to an upper bound falls into the band above.
+    assert_eq!(select.get_value(-0.5, 0.0, 0.0), 20.0);
+    assert_eq!(select.get_value(-0.000001, 0.0, 0.0), 20.0);
+    assert_eq!(select.get_value(0.0, 0.0, 0.0), 30.0);
+    assert_eq!(select.get_value(0.5, 0.0, 0.0), 40.0);
+    // The last band extends upwards without limit.
+    assert_eq!(select.get_value(1.0, 0.0, 0.0), 40.0);
+    assert_eq!(select.get_value(100.0, 0.0, 0.0), 40.0);
+}
+
+#[test]
+fn falloff_blends_adjacent_bands() {
+    let mut select = four_bands();
+    select.set_edge_falloff(0.1);
+    select.set_interp(Interp::Linear);
+    assert_eq!(select.edge_falloff(), 0.1);
+
+    // Outside the transitions, the bands are unchanged.
+    assert_eq!(select.get_value(-0.65, 0.0, 0.0), 10.0);
+    assert_eq!(select.get_value(-0.25, 0.0, 0.0), 20.0);
+    assert_eq!(select.get_value(0.75, 0.0, 0.0), 40.0);
+
+    // Halfway through a transition, the two bands are mixed equally.
+    for &(boundary, expected) in &[(-0.5, 15.0), (0.0, 25.0), (0.5, 35.0)] {
+        assert!((select.get_value(boundary, 0.0, 0.0) - expected).abs() < 1e-9);
+    }
+    assert!((select.get_value(-0.45, 0.0, 0.0) - 17.5).abs() < 1e-9);
+    assert!((select.get_value(0.05, 0.0, 0.0) - 27.5).abs() < 1e-9);
+
+    // The output value is continuous across each transition.
+    for i in 0..2000 {
+        let x = -1.0 + i as f64 * 0.001;
+        let step = select.get_value(x + 0.001, 0.0, 0.0) - select.get_value(x, 0.0, 0.0);
+        assert!(step.abs() <= 10.0 * 0.001 / 0.2 + 1e-9, "x = {}", x);
+    }
+}
+
+#[test]
+fn falloff_is_clamped_to_narrowest_band() {
+    let mut select = four_bands();
+    select.set_edge_falloff(1.0);
+    assert_eq!(select.edge_falloff(), 0.25);
+
+    select.add_band(0.1, boxed_constant(50.0));
+    assert!((select.edge_falloff() - 0.05).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic]
+fn duplicate_bound_panics() {
+    let mut select = four_bands();
+    select.add_band(0.5, boxed_constant(0.0));
+}
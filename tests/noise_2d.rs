@@ -0,0 +1,132 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Billow, Module, Perlin, RidgedMulti};
+use noise::noisegen::{gradient_coherent_noise2d, gradient_coherent_noise2d_max_gradient,
+                      gradient_coherent_noise2d_with_gradient, gradient_coherent_noise3d,
+                      Dimensions, NoiseQuality};
+
+const QUALITIES: [NoiseQuality; 3] = [NoiseQuality::Fast, NoiseQuality::Standard,
+                                      NoiseQuality::Best];
+
+/// Returns sample points spread over several lattice cells, including
+/// negative coordinates.
+fn points() -> Vec<(f64, f64)> {
+    (0..2000).map(|i| (i as f64 * 0.0731 - 60.0, (i as f64 * 0.619).sin() * 40.0)).collect()
+}
+
+#[test]
+fn zero_at_integer_points() {
+    for &quality in &QUALITIES {
+        for x in -3..4 {
+            for y in -3..4 {
+                let value = gradient_coherent_noise2d(x as f64, y as f64, 7, quality);
+                assert_eq!(value, 0.0);
+            }
+        }
+    }
+}
+
+#[test]
+fn stays_in_range_and_varies() {
+    for &quality in &QUALITIES {
+        let values: Vec<f64> = points().iter()
+            .map(|&(x, y)| gradient_coherent_noise2d(x, y, 3, quality))
+            .collect();
+        assert!(values.iter().all(|value| value.abs() <= 1.0));
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let rms = (values.iter().map(|value| value * value).sum::<f64>()
+                   / values.len() as f64).sqrt();
+        assert!(mean.abs() < 0.05);
+        assert!(rms > 0.2);
+    }
+}
+
+#[test]
+fn seed_changes_output() {
+    let differs = points().iter().any(|&(x, y)| {
+        gradient_coherent_noise2d(x, y, 0, NoiseQuality::Standard)
+            != gradient_coherent_noise2d(x, y, 1, NoiseQuality::Standard)
+    });
+    assert!(differs);
+}
+
+#[test]
+fn differs_from_3d_slice() {
+    let differs = points().iter().any(|&(x, y)| {
+        gradient_coherent_noise2d(x, y, 0, NoiseQuality::Standard)
+            != gradient_coherent_noise3d(x, y, 0.0, 0, NoiseQuality::Standard)
+    });
+    assert!(differs);
+}
+
+#[test]
+fn gradient_matches_finite_differences() {
+    let h = 1e-6;
+    for &quality in &[NoiseQuality::Standard, NoiseQuality::Best] {
+        let bound = gradient_coherent_noise2d_max_gradient(quality);
+        for &(x, y) in &points() {
+            let (value, gradient) = gradient_coherent_noise2d_with_gradient(x, y, 5, quality);
+            assert_eq!(value, gradient_coherent_noise2d(x, y, 5, quality));
+            let dx = (gradient_coherent_noise2d(x + h, y, 5, quality)
+                      - gradient_coherent_noise2d(x - h, y, 5, quality)) / (2.0 * h);
+            let dy = (gradient_coherent_noise2d(x, y + h, 5, quality)
+                      - gradient_coherent_noise2d(x, y - h, 5, quality)) / (2.0 * h);
+            assert!((gradient[0] - dx).abs() < 1e-5);
+            assert!((gradient[1] - dy).abs() < 1e-5);
+            assert!(gradient[0].hypot(gradient[1]) <= bound);
+        }
+    }
+}
+
+#[test]
+fn generators_ignore_z_in_2d_mode() {
+    let mut perlin = Perlin::new();
+    perlin.set_dimensions(Dimensions::Dim2);
+    let mut rotated = Perlin::new();
+    rotated.set_dimensions(Dimensions::Dim2);
+    rotated.set_octave_rotation(true);
+    let mut billow = Billow::new();
+    billow.set_dimensions(Dimensions::Dim2);
+    let mut ridged = RidgedMulti::new();
+    ridged.set_dimensions(Dimensions::Dim2);
+    let modules: [&dyn Module; 4] = [&perlin, &rotated, &billow, &ridged];
+    for module in modules.iter() {
+        for &(x, y) in points().iter().take(200) {
+            assert_eq!(module.get_value(x, y, 0.0), module.get_value(x, y, 12.5));
+        }
+    }
+    assert!(perlin.config_hash() != Perlin::new().config_hash());
+}
+
+#[test]
+fn perlin_2d_gradient_ignores_z() {
+    let mut perlin = Perlin::new();
+    perlin.set_dimensions(Dimensions::Dim2);
+    perlin.set_octave_rotation(true);
+    let h = 1e-6;
+    for &(x, y) in points().iter().take(200) {
+        let (value, gradient) = perlin.get_value_and_gradient(x * 0.1, y * 0.1, 3.0);
+        assert_eq!(value, perlin.get_value(x * 0.1, y * 0.1, 3.0));
+        assert_eq!(gradient[2], 0.0);
+        let dx = (perlin.get_value(x * 0.1 + h, y * 0.1, 3.0)
+                  - perlin.get_value(x * 0.1 - h, y * 0.1, 3.0)) / (2.0 * h);
+        assert!((gradient[0] - dx).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::noise_map::NoiseMap;
+
+fn map_from(width: usize, height: usize, values: &[f64]) -> NoiseMap {
+    let mut map = NoiseMap::new(width, height);
+    map.values_mut().copy_from_slice(values);
+    map
+}
+
+#[test]
+fn blend_with_applies_function_element_wise() {
+    let mut base = map_from(2, 2, &[1.0, 2.0, 3.0, 4.0]);
+    let detail = map_from(2, 2, &[10.0, 20.0, 30.0, 40.0]);
+    base.blend_with(&detail, |a, b| a - b * 0.5);
+    assert_eq!(base.values(), &[-4.0, -8.0, -12.0, -16.0]);
+}
+
+#[test]
+fn add() {
+    let mut base = map_from(3, 1, &[1.0, -2.0, 0.5]);
+    base.add(&map_from(3, 1, &[0.5, 0.5, -0.5]));
+    assert_eq!(base.values(), &[1.5, -1.5, 0.0]);
+}
+
+#[test]
+fn multiply() {
+    let mut base = map_from(1, 3, &[1.0, -2.0, 0.5]);
+    base.multiply(&map_from(1, 3, &[2.0, 0.5, -4.0]));
+    assert_eq!(base.values(), &[2.0, -1.0, -2.0]);
+}
+
+#[test]
+fn max_with() {
+    let mut base = map_from(2, 2, &[1.0, -2.0, 0.5, 3.0]);
+    base.max_with(&map_from(2, 2, &[0.0, -1.0, 0.75, 2.0]));
+    assert_eq!(base.values(), &[1.0, -1.0, 0.75, 3.0]);
+}
+
+#[test]
+#[should_panic(expected = "Cannot blend a 2x3 map with a 3x2 map!")]
+fn mismatched_dimensions_panic() {
+    let mut base = NoiseMap::new(2, 3);
+    base.add(&NoiseMap::new(3, 2));
+}
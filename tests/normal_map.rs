@@ -0,0 +1,81 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::FnModule;
+use noise::noise_map::NoiseMap;
+use noise::render::{render_normal_map, render_normal_map_eps};
+
+/// Decodes a pixel of a normal map into the slopes of the surface.
+fn decode_slopes(pixel: [u8; 3]) -> (f64, f64) {
+    let component = |channel: u8| channel as f64 / 255.0 * 2.0 - 1.0;
+    let (nx, ny, nz) = (component(pixel[0]), component(pixel[1]), component(pixel[2]));
+    (-nx / nz, -ny / nz)
+}
+
+fn assert_slopes(pixel: [u8; 3], dx: f64, dy: f64) {
+    let (decoded_dx, decoded_dy) = decode_slopes(pixel);
+    assert!((decoded_dx - dx).abs() < 0.02, "{:?}: dx = {}, expected {}", pixel, decoded_dx, dx);
+    assert!((decoded_dy - dy).abs() < 0.02, "{:?}: dy = {}, expected {}", pixel, decoded_dy, dy);
+}
+
+#[test]
+fn plane_slope_in_world_units() {
+    let plane = FnModule::new(|x: f64, y: f64, _z: f64| 0.5 * x - 0.25 * y);
+    for &step in &[0.1, 0.25, 2.0] {
+        let map = NoiseMap::from_module(&plane, (-1.0, 3.0), (step, step), 9, 7);
+        let pixels = render_normal_map(&map, (step, step));
+        assert_eq!(pixels.len(), 9 * 7);
+        for &pixel in &pixels {
+            assert_eq!(pixel, [72, 155, 239]);
+            assert_slopes(pixel, 0.5, -0.25);
+        }
+    }
+}
+
+#[test]
+fn flat_map() {
+    let map = NoiseMap::new(4, 3);
+    assert!(render_normal_map(&map, (1.0, 1.0)).iter().all(|&pixel| pixel == [128, 128, 255]));
+}
+
+#[test]
+fn curved_surface_with_epsilon() {
+    // The slope of x^2 + y^3 is (2x, 3y^2), which the coarse map smooths but
+    // sampling close to each point follows.
+    let surface = FnModule::new(|x: f64, y: f64, _z: f64| x * x + y * y * y);
+    let (origin, step) = ((-0.5, -0.5), (0.5, 0.5));
+    let pixels = render_normal_map_eps(&surface, origin, step, 3, 3, 1e-4);
+    for j in 0..3 {
+        for i in 0..3 {
+            let (x, y) = (origin.0 + i as f64 * step.0, origin.1 + j as f64 * step.1);
+            assert_slopes(pixels[j * 3 + i], 2.0 * x, 3.0 * y * y);
+        }
+    }
+
+    let map = NoiseMap::from_module(&surface, origin, step, 3, 3);
+    let coarse = render_normal_map(&map, step);
+    // At the centre, the map's central difference of y^3 is 0.25 rather than 0.
+    assert_slopes(coarse[4], 0.0, 0.25);
+}
+
+#[test]
+#[should_panic]
+fn zero_world_step_panics() {
+    render_normal_map(&NoiseMap::new(2, 2), (1.0, 0.0));
+}
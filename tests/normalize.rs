@@ -0,0 +1,63 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{FnModule, Module, Normalize};
+
+/// Noise module that outputs the `x` coordinate of the input value.
+type XModule = FnModule<fn(f64, f64, f64) -> f64>;
+
+fn identity() -> Normalize<XModule> {
+    fn x(x: f64, _y: f64, _z: f64) -> f64 {
+        x
+    }
+    let mut normalize = Normalize::new(XModule::new(x));
+    normalize.set_source_range(2.0, 6.0);
+    normalize
+}
+
+#[test]
+fn clamps_and_remaps_to_default_range() {
+    let normalize = identity();
+    assert_eq!(normalize.get_value(-10.0, 0.0, 0.0), -1.0);
+    assert_eq!(normalize.get_value(2.0, 0.0, 0.0), -1.0);
+    assert_eq!(normalize.get_value(3.0, 0.0, 0.0), -0.5);
+    assert_eq!(normalize.get_value(4.0, 0.0, 0.0), 0.0);
+    assert_eq!(normalize.get_value(6.0, 0.0, 0.0), 1.0);
+    assert_eq!(normalize.get_value(10.0, 0.0, 0.0), 1.0);
+}
+
+#[test]
+fn remaps_to_output_range() {
+    let mut normalize = identity();
+    normalize.set_output_range(0.0, 255.0);
+    assert_eq!(normalize.get_value(1.0, 0.0, 0.0), 0.0);
+    assert_eq!(normalize.get_value(5.0, 0.0, 0.0), 191.25);
+    assert_eq!(normalize.get_value(7.0, 0.0, 0.0), 255.0);
+
+    normalize.set_output_range(1.0, -1.0);
+    assert_eq!(normalize.get_value(1.0, 0.0, 0.0), 1.0);
+    assert_eq!(normalize.get_value(3.0, 0.0, 0.0), 0.5);
+    assert_eq!(normalize.get_value(7.0, 0.0, 0.0), -1.0);
+}
+
+#[test]
+#[should_panic]
+fn empty_source_range_panics() {
+    identity().set_source_range(1.0, 1.0);
+}
@@ -0,0 +1,88 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Perlin};
+
+fn points() -> Vec<(f64, f64, f64)> {
+    (0..500).map(|i| (i as f64 * 0.173 - 40.0, i as f64 * 0.031, (i as f64 * 0.7).cos())).collect()
+}
+
+#[test]
+fn disabled_by_default() {
+    assert!(!Perlin::new().is_octave_offset());
+}
+
+#[test]
+fn changes_output_deterministically() {
+    let plain = Perlin::new();
+    let mut offset = Perlin::new();
+    offset.set_octave_offset(true);
+    let mut other = Perlin::new();
+    other.set_octave_offset(true);
+    let mut differs = false;
+    for &(x, y, z) in &points() {
+        let value = offset.get_value(x, y, z);
+        assert_eq!(value, other.get_value(x, y, z));
+        differs |= value != plain.get_value(x, y, z);
+    }
+    assert!(differs);
+    assert!(offset.config_hash() != plain.config_hash());
+    assert_eq!(offset.config_hash(), other.config_hash());
+}
+
+#[test]
+fn origin_is_no_longer_zero() {
+    // Without offsets every octave is zero at the origin, where the lattice
+    // points of all the octaves coincide.
+    let mut offset = Perlin::new();
+    assert_eq!(offset.get_value(0.0, 0.0, 0.0), 0.0);
+    offset.set_octave_offset(true);
+    assert!(offset.get_value(0.0, 0.0, 0.0) != 0.0);
+}
+
+#[test]
+fn offsets_depend_on_seed() {
+    let mut a = Perlin::new();
+    a.set_octave_offset(true);
+    let mut b = a.clone();
+    b.set_seed(1);
+    let mut shifted = Perlin::new();
+    shifted.set_seed(1);
+    // Changing the seed changes both the noise and the offsets, so the output
+    // differs from changing the seed alone.
+    let differs = points().iter().any(|&(x, y, z)| {
+        b.get_value(x, y, z) != shifted.get_value(x, y, z)
+            && b.get_value(x, y, z) != a.get_value(x, y, z)
+    });
+    assert!(differs);
+}
+
+#[test]
+fn gradient_matches_finite_differences() {
+    let mut perlin = Perlin::new();
+    perlin.set_octave_offset(true);
+    let h = 1e-6;
+    for &(x, y, z) in points().iter().take(100) {
+        let (value, gradient) = perlin.get_value_and_gradient(x * 0.1, y, z);
+        assert_eq!(value, perlin.get_value(x * 0.1, y, z));
+        let dz = (perlin.get_value(x * 0.1, y, z + h) - perlin.get_value(x * 0.1, y, z - h))
+                 / (2.0 * h);
+        assert!((gradient[2] - dz).abs() < 1e-4);
+    }
+}
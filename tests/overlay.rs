@@ -0,0 +1,76 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+mod common;
+
+use common::constant;
+use noise::layers::{BlendMode, LayerStack};
+use noise::module::{Module, Overlay, Perlin};
+
+fn overlay(base: f64, detail: f64) -> f64 {
+    Overlay::new(constant(base), constant(detail)).get_value(0.0, 0.0, 0.0)
+}
+
+#[test]
+fn formula_at_known_inputs() {
+    // Dark base: 2ab, with a = 0.25 and b = 0.75, gives 0.375.
+    assert_eq!(overlay(-0.5, 0.5), -0.25);
+    // Light base: 1 - 2(1 - a)(1 - b), with a = 0.75 and b = 0.25, gives 0.625.
+    assert_eq!(overlay(0.5, -0.5), 0.25);
+    // A mid-grey detail leaves the base unchanged.
+    for &base in &[-1.0, -0.5, 0.0, 0.5, 1.0] {
+        assert_eq!(overlay(base, 0.0), base);
+    }
+    // Black and white bases are kept.
+    for &detail in &[-1.0, -0.3, 0.4, 1.0] {
+        assert_eq!(overlay(-1.0, detail), -1.0);
+        assert_eq!(overlay(1.0, detail), 1.0);
+    }
+    // The two halves of the formula meet at a mid-grey base.
+    assert!((overlay(-1e-12, 0.6) - overlay(0.0, 0.6)).abs() < 1e-9);
+}
+
+#[test]
+fn matches_layer_blend_mode() {
+    let mut detail = Perlin::new();
+    detail.set_seed(3);
+    let overlay = Overlay::new(Perlin::new(), detail.clone());
+
+    let mut stack = LayerStack::new();
+    stack.add_layer(Box::new(Perlin::new()), BlendMode::Add, 1.0);
+    stack.add_layer(Box::new(detail.clone()), BlendMode::Overlay, 1.0);
+    let graph = stack.build();
+    assert!(graph.as_any().is::<Overlay<Box<dyn Module>, Box<dyn Module>>>());
+
+    for i in 0..32 {
+        let (x, y, z) = (i as f64 * 0.21, i as f64 * 0.13, 0.5);
+        let base = Perlin::new().get_value(x, y, z);
+        let value = overlay.get_value(x, y, z);
+        assert_eq!(value, BlendMode::Overlay.blend(base, detail.get_value(x, y, z)));
+        assert_eq!(value, graph.get_value(x, y, z));
+    }
+}
+
+#[test]
+fn clone_matches() {
+    let overlay = Overlay::new(Perlin::new(), constant(0.25));
+    let clone = overlay.clone();
+    assert_eq!(clone.config_hash(), overlay.config_hash());
+    assert_eq!(clone.get_value(0.3, 0.5, 0.7), overlay.get_value(0.3, 0.5, 0.7));
+}
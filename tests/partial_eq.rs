@@ -0,0 +1,71 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Add, Cache, Module, Perlin, RidgedMulti, ScaleBias, Voronoi};
+
+#[test]
+fn identical_perlins_are_equal() {
+    let mut a = Perlin::new();
+    a.set_frequency(2.5);
+    a.set_seed(42);
+    let mut b = Perlin::new();
+    b.set_seed(42);
+    b.set_frequency(2.5);
+    assert!(a == b);
+
+    b.set_persistence(0.25);
+    assert!(a != b);
+    b.set_persistence(a.persistence());
+    assert!(a == b);
+    b.set_octave_count(3);
+    assert!(a != b);
+}
+
+#[test]
+fn ridged_multi_compares_spectral_weights() {
+    let mut a = RidgedMulti::new();
+    let b = RidgedMulti::new();
+    assert!(a == b);
+
+    // Changing the lacunarity recalculates the spectral weights, and changing
+    // it back restores them exactly.
+    a.set_lacunarity(3.0);
+    assert!(a != b);
+    a.set_lacunarity(b.lacunarity());
+    assert!(a == b);
+}
+
+#[test]
+fn combinators_compare_sources() {
+    let mut seeded = Perlin::new();
+    seeded.set_seed(7);
+    let a = Add::new(Perlin::new(), Voronoi::new());
+    assert!(a == Add::new(Perlin::new(), Voronoi::new()));
+    assert!(a != Add::new(seeded.clone(), Voronoi::new()));
+
+    let mut scale_bias = ScaleBias::new(Perlin::new());
+    scale_bias.set_bias(0.5);
+    assert!(scale_bias != ScaleBias::new(Perlin::new()));
+    assert!(ScaleBias::new(seeded.clone()) != ScaleBias::new(Perlin::new()));
+
+    // Caches compare their source modules only, not their cached values.
+    let cache = Cache::new(Perlin::new());
+    cache.get_value(0.5, 0.25, 0.75);
+    assert!(cache == Cache::new(Perlin::new()));
+}
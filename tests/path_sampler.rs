@@ -0,0 +1,97 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Perlin};
+use noise::sampling::PathSampler;
+
+fn distance(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+    ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2) + (b.2 - a.2).powi(2)).sqrt()
+}
+
+fn sample_points(waypoints: &[(f64, f64, f64)], step: f64) -> Vec<(f64, f64, f64)> {
+    PathSampler::new(&Perlin::new(), waypoints, step).map(|(point, _)| point).collect()
+}
+
+#[test]
+fn samples_output_values() {
+    let perlin = Perlin::new();
+    let waypoints = [(0.3, 0.2, 0.1), (2.9, -1.4, 0.6)];
+    for (point, value) in PathSampler::new(&perlin, &waypoints, 0.25) {
+        assert_eq!(value, perlin.get_value(point.0, point.1, point.2));
+    }
+}
+
+#[test]
+fn steps_are_spaced_along_polyline() {
+    // The corners are not multiples of the step, so steps cross them.
+    let waypoints = [(0.0, 0.0, 0.0), (0.7, 0.0, 0.0), (0.7, 1.3, 0.0), (0.7, 1.3, -2.3)];
+    let step = 0.3;
+    let points = sample_points(&waypoints, step);
+    // The polyline is 4.3 long, so 14 steps are taken, then the final waypoint.
+    assert_eq!(points.len(), 16);
+    assert_eq!(points[0], waypoints[0]);
+    // Measured along the polyline, each sample point is one step further.
+    let arclength = |p: (f64, f64, f64)| p.0 + p.1 - p.2;
+    for (i, &point) in points[..15].iter().enumerate() {
+        assert!((arclength(point) - i as f64 * step).abs() < 1e-12, "{:?}", point);
+    }
+    // The step crossing the first corner is shorter in a straight line.
+    assert!(distance(points[2], points[3]) < step);
+    assert!((distance(points[3], points[4]) - step).abs() < 1e-12);
+}
+
+#[test]
+fn final_waypoint_is_sampled() {
+    // A multiple of the step: the last step lands on the final waypoint, which
+    // is only sampled once, despite rounding errors adding up the steps.
+    let points = sample_points(&[(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)], 0.1);
+    assert_eq!(points.len(), 11);
+    assert!(distance(points[10], (1.0, 0.0, 0.0)) < 1e-12);
+
+    // Not a multiple of the step: the last step is shorter.
+    let points = sample_points(&[(0.0, 0.0, 0.0), (0.0, 1.1, 0.0)], 0.5);
+    assert_eq!(points, [(0.0, 0.0, 0.0), (0.0, 0.5, 0.0), (0.0, 1.0, 0.0), (0.0, 1.1, 0.0)]);
+}
+
+#[test]
+fn zero_length_segments_are_skipped() {
+    let waypoints = [(0.0, 0.0, 0.0), (0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 0.0, 0.0),
+                     (1.0, 0.0, 0.0), (1.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+    let points = sample_points(&waypoints, 0.5);
+    assert_eq!(points, [(0.0, 0.0, 0.0), (0.5, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 0.5, 0.0),
+                        (1.0, 1.0, 0.0)]);
+}
+
+#[test]
+fn polyline_without_length_samples_once() {
+    let perlin = Perlin::new();
+    let waypoints = [(0.3, 0.4, 0.5), (0.3, 0.4, 0.5)];
+    let samples: Vec<_> = PathSampler::new(&perlin, &waypoints, 0.5).collect();
+    assert_eq!(samples, [((0.3, 0.4, 0.5), perlin.get_value(0.3, 0.4, 0.5))]);
+    assert!(!samples[0].1.is_nan());
+
+    assert_eq!(sample_points(&[(0.3, 0.4, 0.5)], 0.5), [(0.3, 0.4, 0.5)]);
+    assert!(sample_points(&[], 0.5).is_empty());
+}
+
+#[test]
+#[should_panic]
+fn zero_step_panics() {
+    sample_points(&[(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)], 0.0);
+}
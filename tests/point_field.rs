@@ -0,0 +1,65 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Falloff, Module, PointField};
+
+fn two_point_field() -> PointField {
+    let mut field = PointField::new();
+    field.add_point(-1.0, 0.0, 0.0);
+    field.add_point(1.0, 0.0, 0.0);
+    field
+}
+
+#[test]
+fn midpoint_distance() {
+    let field = two_point_field();
+    assert_eq!(field.get_value(0.0, 0.0, 0.0), 1.0);
+    assert_eq!(field.get_value(-1.0, 0.0, 0.0), 0.0);
+    assert_eq!(field.get_value(1.0, 0.0, 0.0), 0.0);
+}
+
+#[test]
+fn nearest_point_is_used() {
+    let field = two_point_field();
+    assert_eq!(field.get_value(0.5, 0.0, 0.0), 0.5);
+    assert_eq!(field.get_value(-1.0, 3.0, 4.0), 5.0);
+}
+
+#[test]
+fn radius_and_falloff() {
+    let mut field = two_point_field();
+    field.set_radius(2.0);
+    assert_eq!(field.get_value(0.0, 0.0, 0.0), 0.5);
+    field.set_falloff(Falloff::Quadratic);
+    assert_eq!(field.get_value(0.0, 0.0, 0.0), 0.25);
+    field.set_invert(true);
+    assert_eq!(field.get_value(0.0, 0.0, 0.0), -0.25);
+}
+
+#[test]
+#[should_panic]
+fn empty_field_panics() {
+    PointField::new().get_value(0.0, 0.0, 0.0);
+}
+
+#[test]
+#[should_panic]
+fn zero_radius_panics() {
+    PointField::new().set_radius(0.0);
+}
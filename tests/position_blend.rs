@@ -0,0 +1,54 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+mod common;
+
+use common::constant;
+use noise::module::{Axis, Module, PositionBlend};
+
+#[test]
+fn blend_follows_position() {
+    let mut blend = PositionBlend::new(constant(-2.0), constant(6.0));
+    blend.set_axis(Axis::Y);
+    blend.set_range(10.0, 14.0);
+
+    assert_eq!(blend.get_value(0.0, 5.0, 0.0), -2.0);
+    assert_eq!(blend.get_value(0.0, 10.0, 0.0), -2.0);
+    assert_eq!(blend.get_value(0.0, 11.0, 0.0), 0.0);
+    assert_eq!(blend.get_value(0.0, 12.0, 0.0), 2.0);
+    assert_eq!(blend.get_value(0.0, 13.0, 0.0), 4.0);
+    assert_eq!(blend.get_value(0.0, 14.0, 0.0), 6.0);
+    assert_eq!(blend.get_value(0.0, 20.0, 0.0), 6.0);
+
+    // The other coordinates do not affect the weight.
+    assert_eq!(blend.get_value(-7.0, 12.0, 3.0), 2.0);
+}
+
+#[test]
+fn default_range_follows_x() {
+    let blend = PositionBlend::new(constant(0.0), constant(1.0));
+    assert_eq!(blend.get_value(0.0, 5.0, 5.0), 0.5);
+    assert_eq!(blend.get_value(0.5, 0.0, 0.0), 0.75);
+}
+
+#[test]
+#[should_panic]
+fn empty_range_panics() {
+    PositionBlend::new(constant(0.0), constant(1.0)).set_range(1.0, 1.0);
+}
@@ -0,0 +1,56 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::analysis::power_spectrum_1d;
+use noise::module::{Constant, FnModule};
+use std::f64::consts::PI;
+
+fn peak_bin(spectrum: &[f64]) -> usize {
+    (0..spectrum.len()).max_by(|&a, &b| spectrum[a].partial_cmp(&spectrum[b]).unwrap()).unwrap()
+}
+
+#[test]
+fn sine_peaks_at_its_frequency() {
+    // Eight cycles per unit, sampled 256 times over two units along `y`.
+    let sine = FnModule::new(|_x: f64, y: f64, _z: f64| (2.0 * PI * 8.0 * y).sin());
+    let spectrum = power_spectrum_1d(&sine, (0.0, 0.0, 0.0), (0.0, 2.0 / 256.0, 0.0), 256);
+    assert_eq!(spectrum.len(), 129);
+    assert_eq!(peak_bin(&spectrum), 16);
+
+    // All of the power is in the peak: (n / 2)^2 for a unit sine.
+    assert!((spectrum[16] - 128.0 * 128.0).abs() < 1e-6);
+    let leaked: f64 =
+        spectrum.iter().enumerate().filter(|&(k, _)| k != 16).map(|(_, &p)| p).sum();
+    assert!(leaked < 1e-12, "leaked power {}", leaked);
+}
+
+#[test]
+fn constant_only_has_dc_power() {
+    let mut constant = Constant::new();
+    constant.set_const_value(0.5);
+    let spectrum = power_spectrum_1d(&constant, (1.0, 2.0, 3.0), (0.1, 0.2, 0.3), 64);
+    assert!((spectrum[0] - 32.0 * 32.0).abs() < 1e-9);
+    assert!(spectrum[1..].iter().all(|&p| p < 1e-20));
+}
+
+#[test]
+#[should_panic]
+fn non_power_of_two_panics() {
+    power_spectrum_1d(&Constant::new(), (0.0, 0.0, 0.0), (1.0, 0.0, 0.0), 100);
+}
@@ -0,0 +1,66 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Falloff, Module, RadialGradient};
+
+const FALLOFFS: [Falloff; 4] =
+    [Falloff::Linear, Falloff::Quadratic, Falloff::Exponential, Falloff::Smoothstep];
+
+fn gradient(falloff: Falloff) -> RadialGradient {
+    let mut gradient = RadialGradient::new();
+    gradient.set_center(1.0, -2.0, 0.5);
+    gradient.set_radius(4.0);
+    gradient.set_falloff(falloff);
+    gradient
+}
+
+#[test]
+fn one_at_center() {
+    for &falloff in &FALLOFFS {
+        assert_eq!(gradient(falloff).get_value(1.0, -2.0, 0.5), 1.0);
+    }
+}
+
+#[test]
+fn minus_one_at_and_beyond_radius() {
+    for &falloff in &FALLOFFS {
+        let gradient = gradient(falloff);
+        assert!((gradient.get_value(5.0, -2.0, 0.5) + 1.0).abs() < 1e-12);
+        assert!((gradient.get_value(1.0, -2.0, -3.5) + 1.0).abs() < 1e-12);
+        assert_eq!(gradient.get_value(1.0, 10.0, 0.5), -1.0);
+        assert_eq!(gradient.get_value(-100.0, 50.0, 20.0), -1.0);
+    }
+}
+
+#[test]
+fn midpoint_under_each_falloff() {
+    let expected = [(Falloff::Linear, 0.0),
+                    (Falloff::Quadratic, 0.5),
+                    (Falloff::Exponential, 1.0 - 2.0 * (0.5f64.exp() - 1.0) / (1f64.exp() - 1.0)),
+                    (Falloff::Smoothstep, 0.0)];
+    for &(falloff, value) in &expected {
+        let actual = gradient(falloff).get_value(1.0, 0.0, 0.5);
+        assert!((actual - value).abs() < 1e-12, "{:?}: expected {}, got {}", falloff, value,
+                actual);
+    }
+
+    // Smoothstep differs from linear away from the midpoint.
+    let quarter = gradient(Falloff::Smoothstep).get_value(2.0, -2.0, 0.5);
+    assert!((quarter - (1.0 - 2.0 * 0.15625)).abs() < 1e-12);
+}
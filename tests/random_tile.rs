@@ -0,0 +1,90 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{FnModule, Module, Perlin, RandomTile};
+
+#[test]
+fn orientations_are_deterministic_and_vary() {
+    let tiles = RandomTile::new(Perlin::new());
+    let other = RandomTile::new(Perlin::new());
+    let mut seen = [false; 8];
+    for tile_y in -8..8 {
+        for tile_x in -8..8 {
+            let orientation = tiles.tile_orientation(tile_x, tile_y);
+            assert_eq!(orientation, other.tile_orientation(tile_x, tile_y));
+            let (quarter_turns, mirrored) = orientation;
+            assert!(quarter_turns < 4);
+            seen[quarter_turns as usize + if mirrored { 4 } else { 0 }] = true;
+        }
+    }
+    assert!(seen.iter().all(|&seen| seen));
+
+    let mut reseeded = RandomTile::new(Perlin::new());
+    reseeded.set_seed(1);
+    let differs = (0..64).any(|i| {
+        reseeded.tile_orientation(i, 0) != tiles.tile_orientation(i, 0)
+    });
+    assert!(differs);
+}
+
+#[test]
+fn tiles_sample_transformed_base_tile() {
+    // The source module outputs a distinct value for each point of the base
+    // tile, so the point it was sampled at can be recovered.
+    let source = FnModule::new(|x: f64, y: f64, _z: f64| x * 1000.0 + y);
+    let mut tiles = RandomTile::new(source);
+    tiles.set_period(4.0);
+    for tile_y in -3..3 {
+        for tile_x in -3..3 {
+            // The offset (1, 0.5) from the center of the tile, turned and
+            // mirrored into the base tile.
+            let (quarter_turns, mirrored) = tiles.tile_orientation(tile_x, tile_y);
+            let (mut dx, mut dy) = (1.0, if mirrored { -0.5 } else { 0.5 });
+            for _ in 0..quarter_turns {
+                let turned = (-dy, dx);
+                dx = turned.0;
+                dy = turned.1;
+            }
+            let x = tile_x as f64 * 4.0 + 3.0;
+            let y = tile_y as f64 * 4.0 + 2.5;
+            assert_eq!(tiles.get_value(x, y, 0.0), (2.0 + dx) * 1000.0 + 2.0 + dy);
+        }
+    }
+}
+
+#[test]
+fn symmetric_content_is_continuous() {
+    // A radially symmetric tile looks the same in every orientation.
+    let source = FnModule::new(|x: f64, y: f64, _z: f64| {
+        ((x - 0.5).powi(2) + (y - 0.5).powi(2)).sqrt()
+    });
+    let tiles = RandomTile::new(source);
+    for i in 0..200 {
+        let x = i as f64 * 0.0731 - 7.0;
+        let y = i as f64 * 0.0417 - 4.0;
+        let expected = ((x - x.floor() - 0.5).powi(2) + (y - y.floor() - 0.5).powi(2)).sqrt();
+        assert!((tiles.get_value(x, y, 0.0) - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+#[should_panic]
+fn zero_period_panics() {
+    RandomTile::new(Perlin::new()).set_period(0.0);
+}
@@ -0,0 +1,58 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::Perlin;
+use noise::noise_map::NoiseMap;
+use noise::writer::{read_raw_f32, read_raw_f64, write_raw_f32, write_raw_f64};
+use std::io::ErrorKind;
+
+fn header(magic: &[u8; 4], width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = magic.to_vec();
+    bytes.extend_from_slice(&width.to_le_bytes());
+    bytes.extend_from_slice(&height.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn huge_header_without_data() {
+    // The size of the values in bytes overflows.
+    let bytes = header(b"NMF4", u32::MAX, u32::MAX);
+    assert_eq!(read_raw_f32(&mut &bytes[..]).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    // A map of 8 GiB, which must not be allocated before the data arrives.
+    let mut bytes = header(b"NMF8", 1 << 20, 1 << 10);
+    bytes.extend_from_slice(&[0; 64]);
+    assert_eq!(read_raw_f64(&mut &bytes[..]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn truncated_data() {
+    let map = NoiseMap::from_module(&Perlin::new(), (0.0, 0.0), (0.1, 0.1), 5, 3);
+    let mut bytes = Vec::new();
+    write_raw_f32(&mut bytes, &map).unwrap();
+    bytes.pop();
+    assert_eq!(read_raw_f32(&mut &bytes[..]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+
+    let mut bytes = Vec::new();
+    write_raw_f64(&mut bytes, &map).unwrap();
+    let len = bytes.len();
+    assert_eq!(read_raw_f64(&mut &bytes[..len - 8]).unwrap_err().kind(),
+               ErrorKind::UnexpectedEof);
+    assert_eq!(read_raw_f64(&mut &bytes[..]).unwrap(), map);
+}
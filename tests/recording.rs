@@ -0,0 +1,61 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{replay, BoxedModule, Module, Perlin, Recording, Voronoi};
+
+#[test]
+fn records_inputs_in_order() {
+    let recording = Recording::new(Perlin::new());
+    let inputs = [(0.5, 1.5, -2.0), (3.25, 0.0, 0.0), (0.5, 1.5, -2.0), (-7.0, 8.0, 9.5)];
+    let values: Vec<f64> = inputs.iter().map(|&(x, y, z)| recording.get_value(x, y, z)).collect();
+
+    assert_eq!(recording.recorded_inputs(), inputs.to_vec());
+    assert_eq!(replay(&Perlin::new(), &recording.recorded_inputs()), values);
+}
+
+#[test]
+fn records_rows() {
+    let recording = Recording::new(Voronoi::new());
+    let mut row = [0.0; 4];
+    recording.get_value_row(1.0, 2.0, 3.0, 0.5, &mut row);
+    recording.get_value(-1.0, 0.0, 0.0);
+
+    let inputs = recording.recorded_inputs();
+    assert_eq!(inputs, vec![(1.0, 2.0, 3.0), (1.5, 2.0, 3.0), (2.0, 2.0, 3.0), (2.5, 2.0, 3.0),
+                            (-1.0, 0.0, 0.0)]);
+    assert_eq!(replay(&Voronoi::new(), &inputs[..4]), row.to_vec());
+}
+
+#[test]
+fn records_through_a_graph() {
+    let graph: BoxedModule = Box::new(Recording::new(Perlin::new()));
+    graph.get_value(0.1, 0.2, 0.3);
+    let recording = graph.as_any().downcast_ref::<Recording<Perlin>>().unwrap();
+    assert_eq!(recording.recorded_inputs(), vec![(0.1, 0.2, 0.3)]);
+}
+
+#[test]
+fn clear_discards_inputs() {
+    let recording = Recording::new(Perlin::new());
+    recording.get_value(1.0, 2.0, 3.0);
+    recording.clear();
+    assert!(recording.recorded_inputs().is_empty());
+    recording.get_value(4.0, 5.0, 6.0);
+    assert_eq!(recording.recorded_inputs(), vec![(4.0, 5.0, 6.0)]);
+}
@@ -0,0 +1,91 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{reseed_all, split_seed, Add, Billow, BoxedModule, Module, Perlin,
+                    RidgedMulti, Select, Turbulence, Voronoi};
+use std::sync::Arc;
+
+fn graph() -> BoxedModule {
+    let terrain: BoxedModule = Box::new(Add::new(Billow::new(), RidgedMulti::new()));
+    Box::new(Select::new(Turbulence::new(Voronoi::new()), terrain, Perlin::new()))
+}
+
+/// Returns the seeds of the seeded noise modules in the graph, in the order
+/// they are visited.
+fn seeds(module: &mut dyn Module) -> Vec<i32> {
+    let mut found = Vec::new();
+    if let Some(seeded) = module.as_seeded_mut() {
+        found.push(seeded.seed());
+    }
+    for source in module.sources_mut() {
+        found.extend(seeds(source));
+    }
+    found
+}
+
+fn samples(module: &dyn Module) -> Vec<f64> {
+    (0..64).map(|i| module.get_value(i as f64 * 0.37, i as f64 * 0.11, 0.5)).collect()
+}
+
+#[test]
+fn reseeding_changes_output() {
+    let original = graph();
+    let mut reseeded = graph();
+    reseed_all(&mut *reseeded, 99);
+    assert!(samples(&*reseeded) != samples(&*original));
+    assert!(reseeded.config_hash() != original.config_hash());
+}
+
+#[test]
+fn same_base_seed_reproduces_result() {
+    let mut graph1 = graph();
+    let mut graph2 = graph();
+    reseed_all(&mut *graph1, 99);
+    reseed_all(&mut *graph2, 99);
+    assert_eq!(samples(&*graph1), samples(&*graph2));
+    assert_eq!(graph1.config_hash(), graph2.config_hash());
+
+    let mut graph3 = graph();
+    reseed_all(&mut *graph3, 100);
+    assert!(samples(&*graph1) != samples(&*graph3));
+}
+
+#[test]
+fn every_seeded_module_gets_a_distinct_derived_seed() {
+    let mut graph = graph();
+    reseed_all(&mut *graph, 7);
+    // Turbulence, Voronoi, Billow, RidgedMulti and Perlin, in depth-first
+    // order.
+    let expected: Vec<i32> = (0..5).map(|i| split_seed(7, i)).collect();
+    assert_eq!(seeds(&mut *graph), expected);
+}
+
+#[test]
+fn shared_modules_are_left_unchanged() {
+    let shared = Arc::new(Perlin::new());
+    let mut graph: BoxedModule = Box::new(Add::new(shared.clone(), Perlin::new()));
+    reseed_all(&mut *graph, 7);
+    assert_eq!(seeds(&mut *graph), vec![split_seed(7, 0)]);
+    assert_eq!(shared.seed(), Perlin::new().seed());
+
+    // Once the graph holds the only pointer, it is reseeded.
+    drop(shared);
+    reseed_all(&mut *graph, 7);
+    assert_eq!(seeds(&mut *graph), vec![split_seed(7, 0), split_seed(7, 1)]);
+}
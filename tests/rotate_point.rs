@@ -0,0 +1,54 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Constant, RotatePoint};
+use std::f64;
+
+#[test]
+#[should_panic]
+fn nan_axis_panics() {
+    RotatePoint::from_axis_angle(Constant::new(), [f64::NAN, 1.0, 0.0], 30.0);
+}
+
+#[test]
+#[should_panic]
+fn infinite_axis_panics() {
+    RotatePoint::from_axis_angle(Constant::new(), [f64::INFINITY, 0.0, 0.0], 30.0);
+}
+
+#[test]
+#[should_panic]
+fn nan_angle_panics() {
+    RotatePoint::from_axis_angle(Constant::new(), [0.0, 0.0, 1.0], f64::NAN);
+}
+
+#[test]
+#[should_panic]
+fn infinite_matrix_element_panics() {
+    let mut rotate = RotatePoint::new(Constant::new());
+    rotate.set_matrix([[1.0, 0.0, 0.0], [0.0, f64::NEG_INFINITY, 0.0], [0.0, 0.0, 1.0]]);
+}
+
+#[test]
+fn finite_matrix_accepted() {
+    let matrix = [[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 2.0]];
+    let mut rotate = RotatePoint::new(Constant::new());
+    rotate.set_matrix(matrix);
+    assert_eq!(rotate.matrix(), matrix);
+}
@@ -0,0 +1,82 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+//! Checks that sampling a noise module over a row or grid calculates each
+//! input value from its index, rather than by repeatedly adding the step, so
+//! that no rounding error accumulates over long rows.
+
+extern crate noise;
+
+use noise::module::{Module, Voronoi};
+use noise::noise_map::NoiseMap;
+use noise::sampling::sample_slab;
+
+/// Noise module that outputs the `x` coordinate of the input value.
+struct XCoord;
+
+impl Module for XCoord {
+    fn get_value(&self, x: f64, _y: f64, _z: f64) -> f64 {
+        x
+    }
+}
+
+const START: f64 = -12.3;
+const STEP: f64 = 0.001;
+const LEN: usize = 100000;
+
+#[test]
+fn running_sum_drifts() {
+    // Make sure the other tests would notice a running sum.
+    let mut x = START;
+    let mut drifted = false;
+    for i in 0..LEN {
+        drifted |= x != START + i as f64 * STEP;
+        x += STEP;
+    }
+    assert!(drifted);
+}
+
+#[test]
+fn get_value_row() {
+    let mut row = vec![0.0; LEN];
+    XCoord.get_value_row(START, 0.0, 0.0, STEP, &mut row);
+    for (i, &value) in row.iter().enumerate() {
+        assert_eq!(value, START + i as f64 * STEP);
+    }
+
+    // Overridden implementations must give the same output values.
+    let voronoi = Voronoi::new();
+    voronoi.get_value_row(START, 0.5, 0.5, STEP, &mut row);
+    for (i, &value) in row.iter().enumerate() {
+        assert_eq!(value, voronoi.get_value(START + i as f64 * STEP, 0.5, 0.5));
+    }
+}
+
+#[test]
+fn noise_map_from_module() {
+    let map = NoiseMap::from_module(&XCoord, (START, 0.0), (STEP, STEP), LEN, 1);
+    for i in 0..LEN {
+        assert_eq!(map.get_value(i, 0), START + i as f64 * STEP);
+    }
+}
+
+#[test]
+fn sample_slab_x() {
+    let slab = sample_slab(&XCoord, (START, 0.0, 0.0), (STEP, STEP, STEP), (LEN, 1, 1));
+    for (i, &value) in slab.iter().enumerate() {
+        assert_eq!(value, (START + i as f64 * STEP) as f32);
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Perlin, ScaleBias};
+
+#[test]
+fn output_clamp_caps_the_output() {
+    let mut scale_bias = ScaleBias::new(Perlin::new());
+    scale_bias.set_scale(8.0);
+    scale_bias.set_bias(0.5);
+    let mut clamped = scale_bias.clone();
+    clamped.set_output_clamp(Some((-1.0, 1.0)));
+
+    let mut exceeded = false;
+    for i in 0..500 {
+        let (x, y) = (i as f64 * 0.173, i as f64 * 0.061);
+        let value = scale_bias.get_value(x, y, 0.5);
+        let clamped_value = clamped.get_value(x, y, 0.5);
+        exceeded |= value.abs() > 1.0;
+        assert_eq!(clamped_value, value.clamp(-1.0, 1.0));
+    }
+    assert!(exceeded);
+}
+
+#[test]
+fn output_clamp_defaults_to_none() {
+    let scale_bias = ScaleBias::new(Perlin::new());
+    assert_eq!(scale_bias.output_clamp(), None);
+}
+
+#[test]
+#[should_panic]
+fn inverted_output_clamp_panics() {
+    ScaleBias::new(Perlin::new()).set_output_clamp(Some((1.0, -1.0)));
+}
@@ -0,0 +1,84 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Perlin, SeamlessPlane};
+use noise::sampling::is_seamless;
+
+/// Largest difference between the output values a small distance either side
+/// of the seams along the `x` and `y` axes.
+fn seam_jump<M: Module>(module: &M, period: (f64, f64), delta: f64) -> f64 {
+    let mut worst: f64 = 0.0;
+    for i in 0..64 {
+        let a = i as f64 / 64.0;
+        let (x, y) = (a * period.0, a * period.1);
+        worst = worst.max((module.get_value(period.0 - delta, y, 0.5)
+                           - module.get_value(period.0 + delta, y, 0.5)).abs());
+        worst = worst.max((module.get_value(x, -delta, 0.5)
+                           - module.get_value(x, delta, 0.5)).abs());
+    }
+    worst
+}
+
+#[test]
+fn continuous_across_seam() {
+    let delta = 1e-6;
+    for &seam_blend in &[1.0, 0.5, 0.1] {
+        let mut plane = SeamlessPlane::new(Perlin::new());
+        plane.set_period(3.0, 2.0);
+        plane.set_seam_blend(seam_blend);
+        // The steepest slope grows as the blended strip narrows.
+        let jump = seam_jump(&plane, (3.0, 2.0), delta);
+        assert!(jump < 100.0 * delta / seam_blend, "jump {} with seam blend {}", jump,
+                seam_blend);
+        assert!(is_seamless(&plane, 3.0, 2.0, 0.5, 1e-12, 64).is_ok());
+    }
+    assert!(is_seamless(&Perlin::new(), 3.0, 2.0, 0.5, 1e-12, 64).is_err());
+}
+
+#[test]
+fn default_matches_libnoise() {
+    let perlin = Perlin::new();
+    let mut plane = SeamlessPlane::new(perlin.clone());
+    plane.set_period(2.0, 4.0);
+    for i in 0..50 {
+        let (x, y, z) = (i as f64 / 32.0, i as f64 / 16.0, 0.3);
+        let (x_blend, y_blend) = (1.0 - x / 2.0, 1.0 - y / 4.0);
+        let lerp = |n0: f64, n1: f64, a: f64| (1.0 - a) * n0 + a * n1;
+        let z0 = lerp(perlin.get_value(x, y, z), perlin.get_value(x + 2.0, y, z), x_blend);
+        let z1 = lerp(perlin.get_value(x, y + 4.0, z), perlin.get_value(x + 2.0, y + 4.0, z),
+                      x_blend);
+        assert_eq!(plane.get_value(x, y, z), lerp(z0, z1, y_blend));
+        assert_eq!(plane.get_value(x - 6.0, y + 8.0, z), plane.get_value(x, y, z));
+    }
+}
+
+#[test]
+fn narrow_blend_leaves_rest_of_tile_unchanged() {
+    let perlin = Perlin::new();
+    let mut plane = SeamlessPlane::new(perlin.clone());
+    plane.set_seam_blend(0.25);
+    assert_eq!(plane.get_value(0.5, 0.75, 0.1), perlin.get_value(0.5, 0.75, 0.1));
+    assert!(plane.get_value(0.1, 0.75, 0.1) != perlin.get_value(0.1, 0.75, 0.1));
+}
+
+#[test]
+#[should_panic]
+fn zero_seam_blend_panics() {
+    SeamlessPlane::new(Perlin::new()).set_seam_blend(0.0);
+}
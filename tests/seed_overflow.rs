@@ -0,0 +1,134 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Checks that the generators accept seeds at the extremes of the `i32` range,
+//! where the seeds derived for each octave or coordinate overflow.  The tests
+//! are run with overflow checks in debug builds, so any unchecked arithmetic
+//! panics.
+
+extern crate noise;
+
+use noise::module::{Billow, Module, Perlin, RidgedMulti, Turbulence, Voronoi};
+use noise::presets::{self, CaveParams, HillParams, MountainParams};
+
+const SEEDS: [i32; 2] = [i32::MAX, i32::MIN];
+
+fn sample<M: Module>(module: &M) {
+    for i in 0..16 {
+        let value = module.get_value(i as f64 * 0.37, 0.5, 0.25);
+        assert!(!value.is_nan());
+    }
+}
+
+#[test]
+fn perlin() {
+    for &seed in &SEEDS {
+        let mut perlin = Perlin::new();
+        perlin.set_seed(seed);
+        perlin.set_octave_count_f(noise::module::PERLIN_MAX_OCTAVE as f64);
+        sample(&perlin);
+        perlin.get_value_and_gradient(0.3, 0.5, 0.7);
+        perlin.dominant_octave(0.3, 0.5, 0.7);
+        perlin.octave_contributions(0.3, 0.5, 0.7);
+    }
+}
+
+#[test]
+fn billow() {
+    for &seed in &SEEDS {
+        let mut billow = Billow::new();
+        billow.set_seed(seed);
+        billow.set_octave_count(noise::module::BILLOW_MAX_OCTAVE);
+        sample(&billow);
+    }
+}
+
+#[test]
+fn ridged_multi() {
+    for &seed in &SEEDS {
+        let mut ridged_multi = RidgedMulti::new();
+        ridged_multi.set_seed(seed);
+        ridged_multi.set_octave_count(noise::module::RIDGED_MAX_OCTAVE);
+        sample(&ridged_multi);
+    }
+}
+
+#[test]
+fn turbulence() {
+    for &seed in &SEEDS {
+        let mut turbulence = Turbulence::new(Perlin::new());
+        turbulence.set_seed(seed);
+        sample(&turbulence);
+    }
+}
+
+#[test]
+fn voronoi() {
+    for &seed in &SEEDS {
+        let mut voronoi = Voronoi::new();
+        voronoi.set_seed(seed);
+        sample(&voronoi);
+        voronoi.cell_attributes(0.3, 0.5, 0.7, 4);
+    }
+}
+
+#[test]
+fn presets() {
+    for &seed in &SEEDS {
+        sample(&presets::mountainous_terrain(seed, &MountainParams::default()));
+        sample(&presets::rolling_hills(seed, &HillParams::default()));
+        sample(&presets::caves(seed, &CaveParams::default()));
+    }
+}
+
+#[test]
+fn sign_bit_is_masked() {
+    fn assert_same<M: Module>(module: &M, other: &M) {
+        for i in 0..16 {
+            let (x, y, z) = (i as f64 * 0.37, 0.5, 0.25);
+            assert_eq!(module.get_value(x, y, z), other.get_value(x, y, z));
+        }
+    }
+
+    for &seed in &[0, 42, i32::MAX] {
+        let other = seed ^ i32::MIN;
+
+        let (mut perlin, mut perlin_other) = (Perlin::new(), Perlin::new());
+        perlin.set_seed(seed);
+        perlin_other.set_seed(other);
+        assert_same(&perlin, &perlin_other);
+        perlin.set_octave_offset(true);
+        perlin_other.set_octave_offset(true);
+        assert_same(&perlin, &perlin_other);
+
+        let (mut billow, mut billow_other) = (Billow::new(), Billow::new());
+        billow.set_seed(seed);
+        billow_other.set_seed(other);
+        assert_same(&billow, &billow_other);
+
+        let (mut ridged_multi, mut ridged_multi_other) = (RidgedMulti::new(), RidgedMulti::new());
+        ridged_multi.set_seed(seed);
+        ridged_multi_other.set_seed(other);
+        assert_same(&ridged_multi, &ridged_multi_other);
+
+        let mut turbulence = Turbulence::new(Perlin::new());
+        let mut turbulence_other = Turbulence::new(Perlin::new());
+        turbulence.set_seed(seed);
+        turbulence_other.set_seed(other);
+        assert_same(&turbulence, &turbulence_other);
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Checks that Perlin noise with consecutive 64-bit seeds is uncorrelated,
+//! unlike with consecutive `i32` seeds, whose octaves overlap.
+
+extern crate noise;
+
+use noise::module::{Module, Perlin};
+
+/// Samples the module at points scattered through a box, scaled by `scale`.
+fn samples(perlin: &Perlin, scale: f64) -> Vec<f64> {
+    (0..4096).map(|i| {
+        let t = i as f64;
+        perlin.get_value(scale * (t * 0.731 % 40.0), scale * (t * 0.377 % 50.0),
+                         scale * (t * 0.113 % 30.0))
+    }).collect()
+}
+
+/// Returns the Pearson correlation coefficient of two equally long series.
+fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len() as f64;
+    let mean_a = a.iter().sum::<f64>() / n;
+    let mean_b = b.iter().sum::<f64>() / n;
+    let (mut sum_ab, mut sum_aa, mut sum_bb) = (0.0, 0.0, 0.0);
+    for (a, b) in a.iter().zip(b) {
+        sum_ab += (a - mean_a) * (b - mean_b);
+        sum_aa += (a - mean_a) * (a - mean_a);
+        sum_bb += (b - mean_b) * (b - mean_b);
+    }
+    sum_ab / (sum_aa * sum_bb).sqrt()
+}
+
+/// Returns the correlation between the noise with one seed, sampled at half
+/// scale, and the noise with the next seed.  With `i32` seeds, every octave of
+/// the second noise is an octave of the first at this scale.
+fn octave_lag_correlation(first: &Perlin, second: &Perlin) -> f64 {
+    correlation(&samples(first, 0.5), &samples(second, 1.0))
+}
+
+#[test]
+fn adjacent_seeds_are_uncorrelated() {
+    for seed in 0..4 {
+        let (mut a, mut b) = (Perlin::new(), Perlin::new());
+        a.set_seed(seed);
+        b.set_seed(seed + 1);
+        let additive = octave_lag_correlation(&a, &b);
+
+        a.set_seed_u64(seed as u64);
+        b.set_seed_u64(seed as u64 + 1);
+        let hashed = octave_lag_correlation(&a, &b);
+
+        assert!(additive > 0.3, "additive correlation {}", additive);
+        assert!(hashed.abs() < 0.1, "hashed correlation {}", hashed);
+        assert!(correlation(&samples(&a, 1.0), &samples(&b, 1.0)).abs() < 0.1);
+    }
+}
+
+#[test]
+fn seed_u64_is_configuration() {
+    let mut perlin = Perlin::new();
+    perlin.set_seed_u64(7);
+    assert_eq!(perlin.seed_u64(), Some(7));
+    let hash = perlin.config_hash();
+
+    let mut other = Perlin::new();
+    other.set_seed_u64(8);
+    assert!(other.config_hash() != hash);
+    other.set_seed_u64(7);
+    assert_eq!(other.config_hash(), hash);
+    assert_eq!(other.get_value(0.3, 0.5, 0.7), perlin.get_value(0.3, 0.5, 0.7));
+
+    // Setting an `i32` seed goes back to adding the index of each octave.
+    other.set_seed(0);
+    assert_eq!(other.seed_u64(), None);
+    assert_eq!(other.get_value(0.3, 0.5, 0.7), Perlin::new().get_value(0.3, 0.5, 0.7));
+}
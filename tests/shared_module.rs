@@ -0,0 +1,61 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Blend, Module, Perlin, SharedModule, Voronoi};
+use noise::noise_map::NoiseMap;
+use std::sync::Arc;
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+#[test]
+fn branches_share_one_module() {
+    let perlin = Arc::new(Perlin::new());
+    let blend = Blend::new(perlin.clone(), perlin.clone(), Voronoi::new());
+    assert!(Arc::ptr_eq(blend.module1(), blend.module2()));
+    assert_eq!(Arc::strong_count(&perlin), 3);
+
+    for i in 0..100 {
+        let (x, y, z) = (i as f64 * 0.37, i as f64 * -0.11, 0.5);
+        let value = perlin.get_value(x, y, z);
+        assert_eq!(blend.module1().get_value(x, y, z), value);
+        assert_eq!(blend.module2().get_value(x, y, z), value);
+        // Blending a value with itself gives back the same value.
+        assert!((blend.get_value(x, y, z) - value).abs() < 1e-12);
+    }
+    assert_eq!(blend.config_hash(),
+               Blend::new(Perlin::new(), Perlin::new(), Voronoi::new()).config_hash());
+}
+
+#[test]
+fn shared_graphs_can_be_sampled_from_threads() {
+    let perlin = Arc::new(Perlin::new());
+    assert_send_sync(&perlin);
+    let shared: SharedModule = perlin.clone();
+    assert_send_sync(&shared);
+
+    let expected = NoiseMap::from_module(&*perlin, (0.0, 0.0), (0.1, 0.1), 16, 16);
+    let handles: Vec<_> = (0..4).map(|_| {
+        let shared = shared.clone();
+        thread::spawn(move || NoiseMap::from_module(&shared, (0.0, 0.0), (0.1, 0.1), 16, 16))
+    }).collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+}
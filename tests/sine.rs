@@ -0,0 +1,56 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Axis, Module, Sine};
+use std::f64::consts::FRAC_PI_2;
+
+const EPSILON: f64 = 1e-12;
+
+#[test]
+fn quarter_period_values() {
+    let mut sine = Sine::new();
+    sine.set_frequency(2.0);
+    // The period is 0.5, so a quarter period is 0.125.
+    let expected = [0.0, 1.0, 0.0, -1.0, 0.0];
+    for (i, &value) in expected.iter().enumerate() {
+        let x = i as f64 * 0.125;
+        assert!((sine.get_value(x, 0.3, -0.7) - value).abs() < EPSILON);
+    }
+}
+
+#[test]
+fn phase_shifts_the_wave() {
+    let mut sine = Sine::new();
+    sine.set_phase(FRAC_PI_2);
+    assert!((sine.get_value(0.0, 0.0, 0.0) - 1.0).abs() < EPSILON);
+    assert!((sine.get_value(0.25, 0.0, 0.0)).abs() < EPSILON);
+    assert!((sine.get_value(0.5, 0.0, 0.0) + 1.0).abs() < EPSILON);
+}
+
+#[test]
+fn axis_selects_the_coordinate() {
+    let mut sine = Sine::new();
+    for &(axis, point) in &[(Axis::X, [0.25, 0.0, 0.0]),
+                            (Axis::Y, [0.0, 0.25, 0.0]),
+                            (Axis::Z, [0.0, 0.0, 0.25])] {
+        sine.set_axis(axis);
+        assert!((sine.get_value(point[0], point[1], point[2]) - 1.0).abs() < EPSILON);
+        assert!((sine.get_value(0.0, 0.0, 0.0)).abs() < EPSILON);
+    }
+}
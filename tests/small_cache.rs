@@ -0,0 +1,113 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+extern crate noise;
+
+use noise::module::{Module, Perlin, SmallCache, SMALL_CACHE_CAPACITY};
+use std::cell::Cell;
+
+/// Noise module that counts how many times its output value is calculated.
+struct Counting {
+    perlin: Perlin,
+    count: Cell<usize>,
+}
+
+impl Counting {
+    fn new() -> Counting {
+        Counting {
+            perlin: Perlin::new(),
+            count: Cell::new(0),
+        }
+    }
+}
+
+impl Module for Counting {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.count.set(self.count.get() + 1);
+        self.perlin.get_value(x, y, z)
+    }
+}
+
+/// Calculates the normals of a height map by forward differences, sampling
+/// each point and its neighbours along `x` and `y`.
+fn normal_map_pass<M: Module>(module: &M, width: usize, height: usize) -> Vec<[f64; 3]> {
+    let step = 0.05;
+    let mut normals = Vec::with_capacity(width * height);
+    for j in 0..height {
+        for i in 0..width {
+            // Calculate each coordinate from its index, so that a neighbour
+            // has exactly the same coordinates as the point it is next to.
+            let (x0, x1) = (i as f64 * step, (i + 1) as f64 * step);
+            let (y0, y1) = (j as f64 * step, (j + 1) as f64 * step);
+            let value = module.get_value(x0, y0, 0.0);
+            let dx = (module.get_value(x1, y0, 0.0) - value) / step;
+            let dy = (module.get_value(x0, y1, 0.0) - value) / step;
+            let len = (dx * dx + dy * dy + 1.0).sqrt();
+            normals.push([-dx / len, -dy / len, 1.0 / len]);
+        }
+    }
+    normals
+}
+
+#[test]
+fn normal_map_pass_reuses_neighbours() {
+    let (width, height) = (32, 16);
+
+    let uncached = Counting::new();
+    let expected = normal_map_pass(&uncached, width, height);
+    assert_eq!(uncached.count.get(), 3 * width * height);
+
+    let cached = SmallCache::new(Counting::new());
+    assert_eq!(normal_map_pass(&cached, width, height), expected);
+    // Each point along a row is the neighbour of the point before it.
+    assert_eq!(cached.module().count.get(), 2 * width * height + height);
+}
+
+#[test]
+fn least_recently_used_is_forgotten() {
+    let cache = SmallCache::new(Counting::new());
+    for i in 0..SMALL_CACHE_CAPACITY {
+        cache.get_value(i as f64, 0.0, 0.0);
+    }
+    // Using the first entry again keeps it; the second is now the oldest.
+    cache.get_value(0.0, 0.0, 0.0);
+    assert_eq!(cache.module().count.get(), SMALL_CACHE_CAPACITY);
+
+    cache.get_value(100.0, 0.0, 0.0);
+    cache.get_value(0.0, 0.0, 0.0);
+    assert_eq!(cache.module().count.get(), SMALL_CACHE_CAPACITY + 1);
+    cache.get_value(1.0, 0.0, 0.0);
+    assert_eq!(cache.module().count.get(), SMALL_CACHE_CAPACITY + 2);
+}
+
+#[test]
+fn coordinates_compared_bit_for_bit() {
+    let cache = SmallCache::new(Counting::new());
+    cache.get_value(0.0, 0.5, 0.25);
+    cache.get_value(-0.0, 0.5, 0.25);
+    cache.get_value(0.1 + 0.2, 0.5, 0.25);
+    cache.get_value(0.3, 0.5, 0.25);
+    assert_eq!(cache.module().count.get(), 4);
+}
+
+#[test]
+fn changing_module_invalidates() {
+    let mut cache = SmallCache::new(Counting::new());
+    let value = cache.get_value(0.3, 0.5, 0.7);
+    cache.module_mut().perlin.set_seed(1);
+    assert!(cache.get_value(0.3, 0.5, 0.7) != value);
+    assert_eq!(cache.module().count.get(), 2);
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Abs, Add, Blend, BoxedModule, Cache, Displace, Max, Module, Perlin,
+                    ScaleBias, Select, SmallCache, Sum, Turbulence, Voronoi};
+
+fn perlin(seed: i32) -> Perlin {
+    let mut perlin = Perlin::new();
+    perlin.set_seed(seed);
+    perlin
+}
+
+/// Checks that `sources_mut()` and `source()` return the same source modules
+/// as `sources()`, in the same order.
+fn assert_same_sources(module: &mut dyn Module) {
+    let hashes: Vec<u64> =
+        module.sources().into_iter().map(|source| source.config_hash()).collect();
+    let hashes_mut: Vec<u64> =
+        module.sources_mut().into_iter().map(|source| source.config_hash()).collect();
+    assert_eq!(hashes_mut, hashes);
+    for (i, &hash) in hashes.iter().enumerate() {
+        assert_eq!(module.source(i).unwrap().config_hash(), hash);
+    }
+    assert!(module.source(hashes.len()).is_none());
+}
+
+#[test]
+fn same_order_as_sources() {
+    let mut sum = Sum::new();
+    sum.push(Box::new(perlin(1)));
+    sum.push(Box::new(Voronoi::new()));
+    let modules: Vec<BoxedModule> = vec![
+        Box::new(Add::new(perlin(1), perlin(2))),
+        Box::new(Max::new(Voronoi::new(), perlin(2))),
+        Box::new(Select::new(perlin(1), perlin(2), perlin(3))),
+        Box::new(Blend::new(perlin(1), perlin(2), perlin(3))),
+        Box::new(Displace::new(perlin(1), perlin(2), perlin(3), perlin(4))),
+        Box::new(Turbulence::new(perlin(1))),
+        Box::new(Abs::new(perlin(1))),
+        Box::new(sum),
+    ];
+    for mut module in modules {
+        assert_eq!(module.sources_mut().len(), module.source_count());
+        assert_same_sources(&mut *module);
+    }
+}
+
+#[test]
+fn mutate_child_through_accessor() {
+    let mut graph = ScaleBias::new(Add::new(perlin(1), Abs::new(perlin(2))));
+    graph.set_scale(0.5);
+    {
+        let mut sources = graph.sources_mut();
+        let mut add_sources = sources[0].sources_mut();
+        add_sources[1].sources_mut()[0].as_seeded_mut().unwrap().set_seed(20);
+    }
+    assert_eq!(graph.module().module2().module().seed(), 20);
+
+    let mut expected = ScaleBias::new(Add::new(perlin(1), Abs::new(perlin(20))));
+    expected.set_scale(0.5);
+    assert_eq!(graph.config_hash(), expected.config_hash());
+    assert_eq!(graph.get_value(0.3, 0.5, 0.7), expected.get_value(0.3, 0.5, 0.7));
+}
+
+#[test]
+fn caches_are_invalidated() {
+    let mut cache = Cache::new(perlin(1));
+    let mut small_cache = SmallCache::new(perlin(1));
+    let before = (cache.get_value(0.3, 0.5, 0.7), small_cache.get_value(0.3, 0.5, 0.7));
+
+    cache.sources_mut()[0].as_seeded_mut().unwrap().set_seed(2);
+    small_cache.sources_mut()[0].as_seeded_mut().unwrap().set_seed(2);
+    let expected = perlin(2).get_value(0.3, 0.5, 0.7);
+    assert!(expected != before.0);
+    assert_eq!(cache.get_value(0.3, 0.5, 0.7), expected);
+    assert_eq!(small_cache.get_value(0.3, 0.5, 0.7), expected);
+}
@@ -0,0 +1,57 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Perlin};
+use noise::noise_map::NoiseMap;
+
+#[test]
+fn full_longitude_range_wraps_around() {
+    let perlin = Perlin::new();
+    let map = NoiseMap::from_sphere(&perlin, 64, 32, (-90.0, 90.0), (-180.0, 180.0));
+    for y in 0..map.height() {
+        assert_eq!(map.get_value(0, y), map.get_value(map.width() - 1, y));
+    }
+}
+
+#[test]
+fn pole_rows_are_constant() {
+    let perlin = Perlin::new();
+    let map = NoiseMap::from_sphere(&perlin, 64, 32, (-90.0, 90.0), (-180.0, 180.0));
+    let last = map.height() - 1;
+    for x in 1..map.width() {
+        assert_eq!(map.get_value(x, 0), map.get_value(0, 0));
+        assert_eq!(map.get_value(x, last), map.get_value(0, last));
+    }
+    assert!(map.get_value(1, 1) != map.get_value(map.width() / 2, 1));
+}
+
+#[test]
+fn samples_the_unit_sphere() {
+    let perlin = Perlin::new();
+    let map = NoiseMap::from_sphere(&perlin, 3, 3, (-45.0, 45.0), (0.0, 90.0));
+    // Latitude 0, longitude 45.
+    let c = 0.5f64.sqrt();
+    assert!((map.get_value(1, 1) - perlin.get_value(c, 0.0, c)).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic]
+fn latitude_out_of_range_panics() {
+    NoiseMap::from_sphere(&Perlin::new(), 4, 4, (-100.0, 90.0), (-180.0, 180.0));
+}
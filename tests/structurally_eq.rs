@@ -0,0 +1,65 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{structurally_eq, Abs, Add, BoxedModule, Constant, Max, Perlin, ScaleBias,
+                    Select, SharedModule, Voronoi};
+use std::sync::Arc;
+
+fn perlin(seed: i32) -> Perlin {
+    let mut perlin = Perlin::new();
+    perlin.set_seed(seed);
+    perlin
+}
+
+fn tree(seed: i32, bias: f64) -> BoxedModule {
+    let mut scale_bias = ScaleBias::new(Box::new(perlin(seed)) as BoxedModule);
+    scale_bias.set_bias(bias);
+    Box::new(Select::new(Box::new(scale_bias) as BoxedModule,
+                         Box::new(Abs::new(Box::new(Voronoi::new()) as BoxedModule)) as BoxedModule,
+                         Box::new(perlin(seed + 1)) as BoxedModule))
+}
+
+#[test]
+fn equal_trees() {
+    assert!(structurally_eq(&*tree(1, 0.5), &*tree(1, 0.5)));
+
+    // How the source modules are held makes no difference.
+    let owned = Add::new(perlin(3), Abs::new(Voronoi::new()));
+    let shared: SharedModule = Arc::new(perlin(3));
+    let boxed: BoxedModule = Box::new(Abs::new(Voronoi::new()));
+    assert!(structurally_eq(&owned, &Add::new(shared, boxed)));
+}
+
+#[test]
+fn unequal_trees() {
+    // A parameter deep in the tree differs.
+    assert!(!structurally_eq(&*tree(1, 0.5), &*tree(1, 0.25)));
+    assert!(!structurally_eq(&*tree(1, 0.5), &*tree(2, 0.5)));
+
+    // The same source modules in a different order.
+    let a = Add::new(perlin(1), perlin(2));
+    let b = Add::new(perlin(2), perlin(1));
+    assert!(!structurally_eq(&a, &b));
+
+    // The same source modules combined by a different type.
+    assert!(!structurally_eq(&a, &Max::new(perlin(1), perlin(2))));
+
+    // A different number of levels.
+    assert!(!structurally_eq(&Constant::new(), &Abs::new(Constant::new())));
+}
@@ -0,0 +1,71 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Perlin, Terrace};
+
+fn terrace() -> Terrace<Perlin> {
+    let mut terrace = Terrace::new(Perlin::new());
+    terrace.make_control_points(3);
+    terrace
+}
+
+#[test]
+fn map_value_squares_alpha() {
+    let terrace = terrace();
+    assert_eq!(terrace.control_points(), [-1.0, 0.0, 1.0]);
+    assert_eq!(terrace.map_value(0.5), 0.25);
+    assert_eq!(terrace.map_value(-0.5), -0.75);
+    for &control_point in terrace.control_points() {
+        assert_eq!(terrace.map_value(control_point), control_point);
+    }
+}
+
+#[test]
+fn map_value_inverted() {
+    let mut terrace = terrace();
+    terrace.set_invert_terraces(true);
+    assert_eq!(terrace.map_value(0.5), 0.75);
+    assert_eq!(terrace.map_value(-0.5), -0.25);
+    for &control_point in terrace.control_points() {
+        assert_eq!(terrace.map_value(control_point), control_point);
+    }
+}
+
+#[test]
+fn map_value_clamps_outside_control_points() {
+    for &invert in &[false, true] {
+        let mut terrace = terrace();
+        terrace.set_invert_terraces(invert);
+        assert_eq!(terrace.map_value(-1.5), -1.0);
+        assert_eq!(terrace.map_value(-100.0), -1.0);
+        assert_eq!(terrace.map_value(1.5), 1.0);
+        assert_eq!(terrace.map_value(100.0), 1.0);
+    }
+}
+
+#[test]
+fn get_value_uses_map_value() {
+    let perlin = Perlin::new();
+    let mut terrace = terrace();
+    terrace.set_invert_terraces(true);
+    for i in 0..100 {
+        let (x, y, z) = (i as f64 * 0.173, i as f64 * 0.071, 0.5);
+        assert_eq!(terrace.get_value(x, y, z), terrace.map_value(perlin.get_value(x, y, z)));
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+//! Checks the regression testing helpers, which are only available with the
+//! `testing` feature.
+
+#![cfg(feature = "testing")]
+
+extern crate noise;
+
+mod common;
+
+use common::constant;
+use noise::module::{Module, Perlin};
+use noise::testing::assert_module_eq;
+use std::f64;
+
+#[test]
+fn matching_samples_pass() {
+    let perlin = Perlin::new();
+    let samples: Vec<_> = (0..20).map(|i| {
+        let point = (i as f64 * 0.173, i as f64 * -0.071, 0.5);
+        (point, perlin.get_value(point.0, point.1, point.2))
+    }).collect();
+    assert_module_eq(&perlin, &samples, 0.0);
+
+    // Differences up to the tolerance, and NaN against NaN, match.
+    assert_module_eq(&constant(0.5), &[((0.0, 0.0, 0.0), 0.5), ((1.0, 2.0, 3.0), 0.505)], 0.01);
+    assert_module_eq(&constant(f64::NAN), &[((0.0, 0.0, 0.0), f64::NAN)], 0.0);
+}
+
+#[test]
+#[should_panic(expected = "differs at 1 of 2 samples")]
+fn difference_beyond_tolerance_panics() {
+    assert_module_eq(&constant(0.5), &[((0.0, 0.0, 0.0), 0.5), ((1.0, 2.0, 3.0), 0.52)], 0.01);
+}
+
+#[test]
+#[should_panic]
+fn nan_against_number_panics() {
+    assert_module_eq(&constant(f64::NAN), &[((0.0, 0.0, 0.0), 0.0)], 1.0);
+}
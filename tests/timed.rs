@@ -0,0 +1,61 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+extern crate noise;
+
+use noise::module::{Module, Perlin, Timed};
+use std::thread;
+use std::time::Duration;
+
+/// Noise module that sleeps for a millisecond before outputting zero.
+struct Slow;
+
+impl Module for Slow {
+    fn get_value(&self, _x: f64, _y: f64, _z: f64) -> f64 {
+        thread::sleep(Duration::from_millis(1));
+        0.0
+    }
+}
+
+#[test]
+fn time_accumulates_across_calls() {
+    let timed = Timed::new(Slow);
+    assert_eq!(timed.total_time(), Duration::new(0, 0));
+
+    timed.get_value(0.0, 0.0, 0.0);
+    let after_one = timed.total_time();
+    assert!(after_one >= Duration::from_millis(1));
+
+    for _ in 0..4 {
+        timed.get_value(0.0, 0.0, 0.0);
+    }
+    assert!(timed.total_time() >= after_one + Duration::from_millis(4));
+
+    let mut row = [1.0; 3];
+    timed.get_value_row(0.0, 0.0, 0.0, 0.1, &mut row);
+    assert_eq!(row, [0.0; 3]);
+    assert!(timed.total_time() >= after_one + Duration::from_millis(7));
+
+    timed.reset();
+    assert_eq!(timed.total_time(), Duration::new(0, 0));
+}
+
+#[test]
+fn output_is_unchanged() {
+    let perlin = Perlin::new();
+    let timed = Timed::new(perlin.clone());
+    assert_eq!(timed.get_value(0.3, 0.5, 0.7), perlin.get_value(0.3, 0.5, 0.7));
+}
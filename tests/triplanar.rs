@@ -0,0 +1,68 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{FnModule, Module, Perlin};
+use noise::sampling::triplanar_sample;
+
+#[test]
+fn axis_aligned_normal_selects_plane() {
+    let perlin = Perlin::new();
+    let pos = [0.31, -1.27, 2.45];
+    for &sharpness in &[0.5, 1.0, 8.0] {
+        assert_eq!(triplanar_sample(&perlin, pos, [1.0, 0.0, 0.0], sharpness),
+                   perlin.get_value(pos[1], pos[2], 0.0));
+        assert_eq!(triplanar_sample(&perlin, pos, [0.0, -3.0, 0.0], sharpness),
+                   perlin.get_value(pos[0], pos[2], 0.0));
+        assert_eq!(triplanar_sample(&perlin, pos, [0.0, 0.0, 0.5], sharpness),
+                   perlin.get_value(pos[0], pos[1], 0.0));
+    }
+}
+
+#[test]
+fn weights_follow_normal() {
+    // Each plane outputs a distinct constant, so the result is the weighted
+    // average of the three constants.
+    let planes = FnModule::new(|x: f64, _y: f64, _z: f64| x);
+    let pos = [2.0, 3.0, 5.0];
+    let value = triplanar_sample(&planes, pos, [1.0, 1.0, 2.0], 1.0);
+    assert!((value - (3.0 + 2.0 + 2.0 * 2.0) / 4.0).abs() < 1e-12);
+    let value = triplanar_sample(&planes, pos, [1.0, 1.0, 2.0], 2.0);
+    assert!((value - (3.0 + 2.0 + 4.0 * 2.0) / 6.0).abs() < 1e-12);
+    let value = triplanar_sample(&planes, pos, [1.0, 0.0, 2.0], 0.0);
+    assert!((value - (3.0 + 2.0 + 2.0) / 3.0).abs() < 1e-12);
+}
+
+#[test]
+fn large_sharpness_does_not_underflow() {
+    let perlin = Perlin::new();
+    let value = triplanar_sample(&perlin, [0.1, 0.2, 0.3], [1e-200, 0.0, 2e-200], 1000.0);
+    assert_eq!(value, perlin.get_value(0.1, 0.2, 0.0));
+}
+
+#[test]
+#[should_panic]
+fn zero_normal_panics() {
+    triplanar_sample(&Perlin::new(), [0.0; 3], [0.0; 3], 1.0);
+}
+
+#[test]
+#[should_panic]
+fn negative_sharpness_panics() {
+    triplanar_sample(&Perlin::new(), [0.0; 3], [0.0, 0.0, 1.0], -1.0);
+}
@@ -0,0 +1,57 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+extern crate noise;
+
+use noise::module::{Module, Turbulence};
+use std::cell::Cell;
+
+/// Noise module that records the last input value it was given.
+struct Recorder {
+    last_input: Cell<(f64, f64, f64)>,
+}
+
+impl Module for Recorder {
+    fn get_value(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.last_input.set((x, y, z));
+        0.0
+    }
+}
+
+#[test]
+fn matches_displacement_used_by_get_value() {
+    let mut turbulence = Turbulence::new(Recorder { last_input: Cell::new((0.0, 0.0, 0.0)) });
+    turbulence.set_frequency(2.0);
+    turbulence.set_power(0.75);
+    turbulence.set_seed(17);
+
+    for i in 0..100 {
+        let (x, y, z) = (i as f64 * 0.173 - 4.0, i as f64 * -0.091, i as f64 * 0.037 + 1.0);
+        let (dx, dy, dz) = turbulence.displacement_at(x, y, z);
+        turbulence.get_value(x, y, z);
+        assert_eq!(turbulence.module().last_input.get(), (x + dx, y + dy, z + dz));
+    }
+}
+
+#[test]
+fn scales_with_power() {
+    let mut turbulence = Turbulence::new(Recorder { last_input: Cell::new((0.0, 0.0, 0.0)) });
+    turbulence.set_power(1.0);
+    let (dx, dy, dz) = turbulence.displacement_at(0.3, 0.5, 0.7);
+    assert!(dx != 0.0 && dy != 0.0 && dz != 0.0);
+    turbulence.set_power(0.5);
+    assert_eq!(turbulence.displacement_at(0.3, 0.5, 0.7), (dx * 0.5, dy * 0.5, dz * 0.5));
+}
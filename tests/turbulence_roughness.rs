@@ -0,0 +1,54 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Perlin, Turbulence, DEFAULT_TURBULENCE_ROUGHNESS, PERLIN_MAX_OCTAVE};
+
+#[test]
+fn accepts_boundary_roughness() {
+    let mut turbulence = Turbulence::new(Perlin::new());
+    assert_eq!(turbulence.try_set_roughness(1), Ok(()));
+    assert_eq!(turbulence.roughness(), 1);
+    assert_eq!(turbulence.try_set_roughness(PERLIN_MAX_OCTAVE), Ok(()));
+    assert_eq!(turbulence.roughness(), PERLIN_MAX_OCTAVE);
+    turbulence.set_roughness(1);
+    assert_eq!(turbulence.roughness(), 1);
+    turbulence.set_roughness(PERLIN_MAX_OCTAVE);
+    assert_eq!(turbulence.roughness(), PERLIN_MAX_OCTAVE);
+}
+
+#[test]
+fn rejects_out_of_range_roughness() {
+    let mut turbulence = Turbulence::new(Perlin::new());
+    for &roughness in &[0, PERLIN_MAX_OCTAVE + 1, -1, i32::MIN, i32::MAX] {
+        assert_eq!(turbulence.try_set_roughness(roughness), Err("roughness must be in [1, 30]"));
+        assert_eq!(turbulence.roughness(), DEFAULT_TURBULENCE_ROUGHNESS);
+    }
+}
+
+#[test]
+#[should_panic(expected = "roughness must be in [1, 30]")]
+fn zero_roughness_panics() {
+    Turbulence::new(Perlin::new()).set_roughness(0);
+}
+
+#[test]
+#[should_panic(expected = "roughness must be in [1, 30]")]
+fn too_large_roughness_panics() {
+    Turbulence::new(Perlin::new()).set_roughness(PERLIN_MAX_OCTAVE + 1);
+}
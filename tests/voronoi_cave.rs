@@ -0,0 +1,72 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Falloff, Voronoi};
+
+/// Returns a `Voronoi` noise module with a seed point on every lattice point.
+fn regular_voronoi() -> Voronoi {
+    let mut voronoi = Voronoi::new();
+    voronoi.set_jitter(0.0);
+    voronoi
+}
+
+#[test]
+fn open_within_threshold() {
+    let voronoi = regular_voronoi();
+    // 0.1 from the seed point at (3, -2, 5).
+    assert!(voronoi.cave_value(3.1, -2.0, 5.0, 0.2));
+    assert!(!voronoi.cave_value(3.1, -2.0, 5.0, 0.05));
+    // About 0.69 from the seed point at the origin.
+    assert!(voronoi.cave_value(0.4, 0.4, 0.4, 0.75));
+    assert!(!voronoi.cave_value(0.4, 0.4, 0.4, 0.5));
+    // Exactly on a seed point, only a zero threshold is closed.
+    assert!(voronoi.cave_value(1.0, 1.0, 1.0, 0.01));
+    assert!(!voronoi.cave_value(1.0, 1.0, 1.0, 0.0));
+}
+
+#[test]
+fn threshold_scales_with_frequency() {
+    let mut voronoi = regular_voronoi();
+    voronoi.set_frequency(2.0);
+    // (0.05, 0, 0) lies 0.1 from the seed point at the origin.
+    assert!(voronoi.cave_value(0.05, 0.0, 0.0, 0.2));
+    assert!(!voronoi.cave_value(0.05, 0.0, 0.0, 0.05));
+}
+
+#[test]
+fn distance_settings_do_not_matter() {
+    let plain = Voronoi::new();
+    let mut styled = Voronoi::new();
+    styled.enable_distance(true);
+    styled.set_distance_falloff(Falloff::Quadratic);
+    styled.set_smooth_k(0.5);
+    for i in 0..200 {
+        let (x, y, z) = (i as f64 * 0.173, i as f64 * -0.071, 0.25);
+        for &threshold in &[0.1, 0.3, 0.6] {
+            assert_eq!(styled.cave_value(x, y, z, threshold),
+                       plain.cave_value(x, y, z, threshold));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn negative_threshold_panics() {
+    regular_voronoi().cave_value(0.0, 0.0, 0.0, -0.5);
+}
@@ -0,0 +1,65 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Falloff, Module, Voronoi, DEFAULT_VORONOI_DISTANCE_FALLOFF};
+use std::f64::consts::E;
+
+const DISTANCES: [f64; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+#[test]
+fn falloff_shapes_at_fixed_distances() {
+    for &dist in &DISTANCES {
+        assert_eq!(Falloff::Linear.apply(dist), dist);
+        assert_eq!(Falloff::Quadratic.apply(dist), dist * dist);
+        assert!((Falloff::Exponential.apply(dist) - (dist.exp() - 1.0) / (E - 1.0)).abs() < 1e-12);
+    }
+    // Every curve maps the ends of the range onto themselves, and in between
+    // the quadratic curve stays lowest and the linear curve highest.
+    for &falloff in &[Falloff::Linear, Falloff::Quadratic, Falloff::Exponential] {
+        assert_eq!(falloff.apply(0.0), 0.0);
+        assert!((falloff.apply(1.0) - 1.0).abs() < 1e-12);
+    }
+    for &dist in &DISTANCES[1..4] {
+        assert!(Falloff::Quadratic.apply(dist) < Falloff::Exponential.apply(dist));
+        assert!(Falloff::Exponential.apply(dist) < Falloff::Linear.apply(dist));
+    }
+}
+
+#[test]
+fn voronoi_applies_falloff_to_distance() {
+    let voronoi = |falloff| {
+        let mut voronoi = Voronoi::new();
+        voronoi.enable_distance(true);
+        voronoi.set_displacement(0.0);
+        voronoi.set_distance_falloff(falloff);
+        voronoi
+    };
+    assert_eq!(DEFAULT_VORONOI_DISTANCE_FALLOFF, Falloff::Linear);
+    let (linear, quadratic, exponential) =
+        (voronoi(Falloff::Linear), voronoi(Falloff::Quadratic), voronoi(Falloff::Exponential));
+    for i in 0..100 {
+        let (x, y, z) = (i as f64 * 0.173, i as f64 * 0.071, 0.5);
+        // With no displacement, the output value is the curve minus one.
+        let dist = linear.get_value(x, y, z) + 1.0;
+        let expected = Falloff::Quadratic.apply(dist) - 1.0;
+        assert!((quadratic.get_value(x, y, z) - expected).abs() < 1e-12);
+        let expected = Falloff::Exponential.apply(dist) - 1.0;
+        assert!((exponential.get_value(x, y, z) - expected).abs() < 1e-12);
+    }
+}
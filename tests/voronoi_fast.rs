@@ -0,0 +1,49 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Voronoi};
+
+#[test]
+fn fast_mode_only_narrows_the_search() {
+    // Without jitter every seed point lies inside its own unit cube, so the
+    // smaller search of the fast mode always finds the nearest one, and the
+    // output value must match the default mode exactly.
+    let mut voronoi = Voronoi::new();
+    voronoi.set_jitter(0.0);
+    voronoi.enable_distance(true);
+    let mut fast = voronoi.clone();
+    fast.set_fast(true);
+    for i in 0..1000 {
+        let (x, y, z) = (i as f64 * 0.173 - 50.0, i as f64 * 0.071 - 20.0, i as f64 * -0.029);
+        assert_eq!(fast.get_value(x, y, z), voronoi.get_value(x, y, z));
+    }
+}
+
+#[test]
+fn fast_mode_mostly_matches() {
+    let voronoi = Voronoi::new();
+    let mut fast = voronoi.clone();
+    fast.set_fast(true);
+    let count = 10000;
+    let same = (0..count).filter(|&i| {
+        let (x, y, z) = (i as f64 * 0.0173, i as f64 * 0.0071, 0.5);
+        fast.get_value(x, y, z) == voronoi.get_value(x, y, z)
+    }).count();
+    assert!(same as f64 > 0.75 * count as f64, "{}", same);
+}
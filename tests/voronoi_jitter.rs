@@ -0,0 +1,75 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Voronoi};
+
+fn regular_voronoi() -> Voronoi {
+    let mut voronoi = Voronoi::new();
+    voronoi.set_jitter(0.0);
+    voronoi.enable_distance(true);
+    voronoi.set_displacement(0.0);
+    voronoi
+}
+
+#[test]
+fn no_jitter_places_seed_points_on_lattice_points() {
+    let voronoi = regular_voronoi();
+    // With the distance enabled and no displacement, the output value is -1.0
+    // exactly at a seed point.
+    for &(x, y, z) in &[(0, 0, 0), (3, -2, 5), (-7, 11, -1), (100, 40, -60)] {
+        let value = voronoi.get_value(x as f64, y as f64, z as f64);
+        assert_eq!(value, -1.0);
+    }
+}
+
+#[test]
+fn no_jitter_produces_uniform_cells() {
+    let voronoi = regular_voronoi();
+    for i in 0..50 {
+        let (x, y, z) = (i as f64 * 0.173, i as f64 * 0.311, i as f64 * 0.097);
+        let value = voronoi.get_value(x, y, z);
+        // Each cell is a unit cube, so the output repeats every unit.
+        for &(dx, dy, dz) in &[(1.0, 0.0, 0.0), (0.0, -3.0, 0.0), (0.0, 0.0, 7.0)] {
+            assert!((voronoi.get_value(x + dx, y + dy, z + dz) - value).abs() < 1e-9);
+        }
+        // The cell attributes identify the nearest lattice point.
+        let attributes = voronoi.cell_attributes(x, y, z, 2);
+        let point = (x.round(), y.round(), z.round());
+        assert_eq!(attributes, voronoi.cell_attributes(point.0, point.1, point.2, 2));
+    }
+}
+
+#[test]
+fn full_jitter_is_default() {
+    let default = Voronoi::new();
+    let mut voronoi = Voronoi::new();
+    voronoi.set_jitter(1.0);
+    assert_eq!(default.jitter(), 1.0);
+    assert_eq!(voronoi.config_hash(), default.config_hash());
+    for i in 0..50 {
+        let (x, y, z) = (i as f64 * 0.173, i as f64 * -0.311, i as f64 * 0.097);
+        assert_eq!(voronoi.get_value(x, y, z), default.get_value(x, y, z));
+    }
+}
+
+#[test]
+#[should_panic]
+fn jitter_out_of_range_panics() {
+    Voronoi::new().set_jitter(1.5);
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Module, Voronoi, VoronoiCache, DEFAULT_VORONOI_SMOOTHNESS};
+
+/// Returns pairs of input values a tiny step apart along a line, one on
+/// either side of each border between cells that the line crosses.
+fn borders(voronoi: &Voronoi) -> Vec<(f64, f64)> {
+    let value = |x: f64| voronoi.get_value(x, 0.3, 0.7);
+    let mut borders = Vec::new();
+    for i in 0..400 {
+        let (mut a, mut b) = (i as f64 * 0.01, (i + 1) as f64 * 0.01);
+        if value(a) == value(b) {
+            continue;
+        }
+        // Bisect to find the border.
+        while b - a > 1e-6 {
+            let mid = 0.5 * (a + b);
+            if value(mid) == value(a) {
+                a = mid;
+            } else {
+                b = mid;
+            }
+        }
+        if (value(a) - value(b)).abs() > 0.1 {
+            borders.push((a, b));
+        }
+    }
+    borders
+}
+
+#[test]
+fn smooth_output_is_continuous_across_borders() {
+    let hard = Voronoi::new();
+    let borders = borders(&hard);
+    assert!(!borders.is_empty());
+
+    let mut smooth = hard.clone();
+    smooth.set_smooth(true);
+    assert_eq!(smooth.smoothness(), DEFAULT_VORONOI_SMOOTHNESS);
+    for &(a, b) in &borders {
+        let jump = (smooth.get_value(a, 0.3, 0.7) - smooth.get_value(b, 0.3, 0.7)).abs();
+        assert!(jump < 1e-3, "smooth output jumps by {} at x = {}", jump, a);
+    }
+}
+
+#[test]
+fn approaches_cell_value_near_seed_point() {
+    let hard = Voronoi::new();
+    let mut smooth = hard.clone();
+    smooth.set_smooth(true);
+    smooth.set_smoothness(0.001);
+    // Away from the borders, a narrow blend leaves the cell values unchanged.
+    let mut close = 0;
+    for i in 0..1000 {
+        let x = i as f64 * 0.0037;
+        if (smooth.get_value(x, 0.3, 0.7) - hard.get_value(x, 0.3, 0.7)).abs() < 1e-9 {
+            close += 1;
+        }
+    }
+    assert!(close > 900);
+}
+
+#[test]
+fn row_and_cache_match_get_value() {
+    let mut smooth = Voronoi::new();
+    smooth.set_smooth(true);
+    smooth.set_smoothness(0.3);
+    let mut row = vec![0.0; 64];
+    smooth.get_value_row(-1.3, 0.4, 2.2, 0.071, &mut row);
+    let cache = VoronoiCache::new(smooth.clone(), (-2.0, 0.0, 2.0), (4.0, 1.0, 3.0));
+    for (i, &value) in row.iter().enumerate() {
+        let x = -1.3 + i as f64 * 0.071;
+        assert_eq!(value, smooth.get_value(x, 0.4, 2.2));
+        assert_eq!(cache.get_value(x, 0.4, 2.2), value);
+    }
+    assert!(smooth.config_hash() != Voronoi::new().config_hash());
+}
+
+#[test]
+#[should_panic]
+fn zero_smoothness_panics() {
+    Voronoi::new().set_smoothness(0.0);
+}
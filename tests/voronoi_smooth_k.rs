@@ -0,0 +1,82 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+use noise::module::{Falloff, Module, Voronoi};
+
+fn points() -> Vec<(f64, f64, f64)> {
+    (0..400).map(|i| {
+        let i = i as f64;
+        (i * 0.137 - 20.0, i * 0.071 + 3.0, i * -0.053)
+    }).collect()
+}
+
+#[test]
+fn zero_k_matches_nearest_distance() {
+    for &smooth in &[false, true] {
+        let mut hard = Voronoi::new();
+        hard.enable_distance(true);
+        hard.set_distance_falloff(Falloff::Quadratic);
+        hard.set_smooth(smooth);
+        let mut zero = hard.clone();
+        zero.set_smooth_k(0.5);
+        zero.set_smooth_k(0.0);
+
+        for &(x, y, z) in &points() {
+            assert_eq!(zero.get_value(x, y, z), hard.get_value(x, y, z));
+        }
+    }
+}
+
+#[test]
+fn smooth_min_lowers_the_distance() {
+    let mut hard = Voronoi::new();
+    hard.enable_distance(true);
+    hard.set_displacement(0.0);
+    let mut smooth = hard.clone();
+    smooth.set_smooth_k(0.5);
+
+    let mut lowered = false;
+    for &(x, y, z) in &points() {
+        let (hard_value, smooth_value) = (hard.get_value(x, y, z), smooth.get_value(x, y, z));
+        assert!(smooth_value <= hard_value);
+        // The distance never falls below zero.
+        assert!(smooth_value >= -1.0);
+        lowered |= smooth_value < hard_value;
+    }
+    assert!(lowered);
+}
+
+#[test]
+fn row_matches_get_value() {
+    let mut voronoi = Voronoi::new();
+    voronoi.enable_distance(true);
+    voronoi.set_smooth_k(0.3);
+
+    let mut row = vec![0.0; 64];
+    voronoi.get_value_row(-2.0, 0.7, 1.3, 0.09, &mut row);
+    for (i, &value) in row.iter().enumerate() {
+        assert_eq!(value, voronoi.get_value(-2.0 + i as f64 * 0.09, 0.7, 1.3));
+    }
+}
+
+#[test]
+#[should_panic]
+fn negative_k_panics() {
+    Voronoi::new().set_smooth_k(-0.1);
+}
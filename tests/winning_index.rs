@@ -0,0 +1,96 @@
+// Copyright (C) 2016 Matthew Nicholls
+//
+// This library is free software; you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation; either version 2.1 of the License, or (at
+// your option) any later version.
+//
+// This library is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License (COPYING.txt) for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with this library; if not, write to the Free Software Foundation,
+// Inc., 59 Temple Place, Suite 330, Boston, MA  02111-1307  USA
+
+
+extern crate noise;
+
+mod common;
+
+use common::boxed_constant;
+use noise::module::{Max, MaxN, Min, MinN, Module, Perlin};
+use std::f64;
+
+#[test]
+fn known_constant_wins() {
+    let mut max_n = MaxN::new();
+    let mut min_n = MinN::new();
+    for &value in &[0.25, -0.5, 0.75] {
+        max_n.push(boxed_constant(value));
+        min_n.push(boxed_constant(value));
+    }
+    assert_eq!(max_n.winning_index(1.0, 2.0, 3.0), 2);
+    assert_eq!(max_n.get_value(1.0, 2.0, 3.0), 0.75);
+    assert_eq!(min_n.winning_index(1.0, 2.0, 3.0), 1);
+    assert_eq!(min_n.get_value(1.0, 2.0, 3.0), -0.5);
+}
+
+#[test]
+fn ties_and_nan_are_resolved_consistently() {
+    let mut max_n = MaxN::new();
+    max_n.push(boxed_constant(f64::NAN));
+    max_n.push(boxed_constant(0.5));
+    max_n.push(boxed_constant(0.5));
+    assert_eq!(max_n.winning_index(0.0, 0.0, 0.0), 1);
+    assert_eq!(max_n.get_value(0.0, 0.0, 0.0), 0.5);
+
+    let mut all_nan = MinN::new();
+    all_nan.push(boxed_constant(f64::NAN));
+    all_nan.push(boxed_constant(f64::NAN));
+    assert_eq!(all_nan.winning_index(0.0, 0.0, 0.0), 0);
+    assert!(all_nan.get_value(0.0, 0.0, 0.0).is_nan());
+}
+
+#[test]
+fn matches_nested_modules_and_winning_source() {
+    let perlins: Vec<Perlin> = (0..3).map(|seed| {
+        let mut perlin = Perlin::new();
+        perlin.set_seed(seed);
+        perlin
+    }).collect();
+    let nested_max = Max::new(Max::new(perlins[0].clone(), perlins[1].clone()), perlins[2].clone());
+    let nested_min = Min::new(Min::new(perlins[0].clone(), perlins[1].clone()), perlins[2].clone());
+    let (mut max_n, mut min_n) = (MaxN::new(), MinN::new());
+    for perlin in &perlins {
+        max_n.push(Box::new(perlin.clone()));
+        min_n.push(Box::new(perlin.clone()));
+    }
+
+    let mut winners = [0; 3];
+    for i in 0..200 {
+        let (x, y, z) = (i as f64 * 0.173, i as f64 * -0.091, 0.4);
+        assert_eq!(max_n.get_value(x, y, z), nested_max.get_value(x, y, z));
+        assert_eq!(min_n.get_value(x, y, z), nested_min.get_value(x, y, z));
+
+        let index = max_n.winning_index(x, y, z);
+        assert_eq!(perlins[index].get_value(x, y, z), max_n.get_value(x, y, z));
+        let index = min_n.winning_index(x, y, z);
+        assert_eq!(perlins[index].get_value(x, y, z), min_n.get_value(x, y, z));
+        winners[index] += 1;
+    }
+    assert!(winners.iter().all(|&count| count > 0));
+}
+
+#[test]
+fn empty_outputs_identity() {
+    assert_eq!(MaxN::new().get_value(0.0, 0.0, 0.0), f64::NEG_INFINITY);
+    assert_eq!(MinN::new().get_value(0.0, 0.0, 0.0), f64::INFINITY);
+}
+
+#[test]
+#[should_panic]
+fn empty_winning_index_panics() {
+    MaxN::new().winning_index(0.0, 0.0, 0.0);
+}